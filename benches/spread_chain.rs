@@ -0,0 +1,88 @@
+//! Validation of item-like documents against a chain of spread structs,
+//! with and without reusing the expanded spreads
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use serde_json::{json, Value};
+use voxel_rsmcdoc::validator::{DatapackValidator, ValidationOptions};
+
+/// Root struct spreading a 4-level chain, declared alongside other structs like the
+/// components of vanilla items
+fn schema() -> String {
+    let mut schema = String::from(r#"
+dispatch minecraft:resource[item_like] to struct ItemLike {
+    id: string,
+    ...ItemData,
+}
+
+struct ItemData {
+    count?: int @ 1..99,
+    max_stack_size?: int @ 1..99,
+    max_damage?: int @ 1..,
+    damage?: int @ 0..,
+    unbreakable?: struct { show_in_tooltip?: boolean },
+    ...ItemDisplay,
+}
+
+struct ItemDisplay {
+    name?: string,
+    item_name?: string,
+    lore?: [string] @ 0..256,
+    hide_tooltip?: struct {},
+    hide_additional_tooltip?: struct {},
+    ...ItemAppearance,
+}
+
+struct ItemAppearance {
+    model?: string,
+    glint?: boolean,
+    dyed_color?: int,
+    map_color?: int,
+    custom_model_data?: int,
+    #[since="1.21"]
+    ...ItemRarity,
+}
+
+struct ItemRarity {
+    rarity?: ("common" | "uncommon" | "rare" | "epic"),
+    repair_cost?: int @ 0..,
+    enchantable?: struct { value: int @ 1.. },
+    #[until="1.21"]
+    creative_slot_lock?: struct {},
+}
+"#);
+    for index in 0..24 {
+        schema.push_str(&format!("\nstruct Component{} {{\n    value: int,\n    label?: string,\n    nested?: struct {{ flag: boolean }},\n}}\n", index));
+    }
+    schema
+}
+
+fn documents() -> Vec<Value> {
+    (0..500)
+        .map(|index| json!({
+            "id": format!("item_{}", index),
+            "count": index % 64 + 1,
+            "name": "Item",
+            "rarity": "rare",
+        }))
+        .collect()
+}
+
+fn spread_chain(c: &mut Criterion) {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("item.mcdoc".to_string(), schema()).unwrap();
+    let documents = documents();
+
+    let mut group = c.benchmark_group("spread_chain");
+    for (name, memoize_spreads) in [("memoized", true), ("expanded", false)] {
+        let options = ValidationOptions { memoize_spreads, ..Default::default() };
+        group.bench_function(name, |b| b.iter(|| {
+            for document in &documents {
+                assert!(validator.validate_json_with_options(document, "item_like", Some("1.21"), &options).is_valid);
+            }
+        }));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, spread_chain);
+criterion_main!(benches);
@@ -1,5 +1,38 @@
 import init, { DatapackValidator as WasmValidator } from '../voxel_rsmcdoc.js';
 
+/**
+ * Options de validation, toutes facultatives
+ */
+export interface ValidationOptions {
+  nodeBudget?: number;
+  errorBudget?: number;
+  strictFields?: boolean;
+  checkTagContents?: boolean;
+  keepDuplicateDependencies?: boolean;
+  /** false: validation structurelle et extraction des dépendances sans vérifier les registries */
+  checkRegistries?: boolean;
+  /** namespace des identifiants écrits sans namespace (défaut: "minecraft") */
+  defaultNamespace?: string;
+  /** profondeur maximale d'imbrication des objets et tableaux validés (défaut: 128) */
+  maxDepth?: number;
+  /** fichier d'où vient le JSON, reporté dans `file` des erreurs et `sourceFile` des dépendances */
+  sourceFile?: string;
+  /** rapporte en warnings les erreurs souvent bénignes des packs existants (voir `ValidationOptions::lenient_legacy`) */
+  lenient?: boolean;
+  /** sévérité par code d'erreur, appliquée après `lenient` (ex: `{ unknownField: "warning" }`) */
+  severityOverrides?: Record<string, 'error' | 'warning' | 'info'>;
+}
+
+/**
+ * Erreur d'un fichier MCDOC non chargé par `initLenient`
+ */
+export interface McDocDiagnostic {
+  message: string;
+  errorType: string;
+  line?: number;
+  column?: number;
+}
+
 /**
  * DatapackValidator avec API moderne et auto-initialisation
  */
@@ -8,6 +41,7 @@ export class DatapackValidator {
 
   /**
    * Factory method - initialise automatiquement WASM et validator
+   * (un fichier MCDOC invalide lève une Error dont `errors` donne line, column, columnUtf16 (colonne des éditeurs), offset et endOffset)
    */
   static async init(
     registries: Record<string, any>,
@@ -19,11 +53,54 @@ export class DatapackValidator {
     return new DatapackValidator(wasm);
   }
 
+  /**
+   * Comme `init`, mais charge tous les fichiers MCDOC valides au lieu d'échouer au premier invalide.
+   * `diagnostics` donne les erreurs de chaque fichier non chargé, par nom de fichier.
+   */
+  static async initLenient(
+    registries: Record<string, any>,
+    mcdocFiles: Record<string, string>,
+    version: string
+  ): Promise<{ validator: DatapackValidator; diagnostics: Record<string, McDocDiagnostic[]> }> {
+    await init();
+    const { validator, diagnostics } = WasmValidator.init_lenient(registries, mcdocFiles, version);
+    return { validator: new DatapackValidator(validator), diagnostics };
+  }
+
   /**
    * Valide un JSON contre un type de ressource
+   * (options: `{ checkRegistries: false }` pour extraire les dépendances sans registries)
+   */
+  validate(json: any, resourceType: string, version?: string, options?: ValidationOptions) {
+    return this.wasm.validate(json, resourceType, version, options);
+  }
+
+  /**
+   * Valide un JSON donné en texte (évite la double conversion JSON.parse -> serde)
+   */
+  validateStr(jsonText: string, resourceType: string, version?: string, options?: ValidationOptions) {
+    return this.wasm.validate_str(jsonText, resourceType, version, options);
+  }
+
+  /**
+   * Valide un fragment JSON contre un type nommé (ex: "util::Ingredient")
+   */
+  validateType(json: any, typeName: string, version?: string) {
+    return this.wasm.validateType(json, typeName, version);
+  }
+
+  /**
+   * Valide un objet "type + contenu" contre un registre dispatché (ex: "recipe_serializer")
+   */
+  validateDispatched(json: any, dispatchRegistry: string, version?: string) {
+    return this.wasm.validate_dispatched(json, dispatchRegistry, version);
+  }
+
+  /**
+   * Valide un fichier dont le type de ressource est déduit du chemin (ex: "data/mypack/recipe/sword.json")
    */
-  validate(json: any, resourceType: string, version?: string) {
-    return this.wasm.validate(json, resourceType, version);
+  validateFile(path: string, json: any, version?: string) {
+    return this.wasm.validate_file(path, json, version);
   }
 
   /**
@@ -32,6 +109,55 @@ export class DatapackValidator {
   analyzeDatapack(files: Record<string, any>) {
     return this.wasm.analyze_datapack(files);
   }
+
+  /**
+   * Analyse un datapack dont les fichiers sont donnés en texte
+   */
+  analyzeDatapackStrs(files: Record<string, string>) {
+    return this.wasm.analyze_datapack_strs(files);
+  }
+
+  /**
+   * Graphe des dépendances d'un datapack, avec les références manquantes et les fichiers inutilisés
+   */
+  dependencyGraph(files: Record<string, any>) {
+    return this.wasm.dependency_graph(files);
+  }
+
+  /**
+   * Clés dispatchées sur un registre (ex: "minecraft:recipe_serializer"), pour l'autocomplétion
+   */
+  dispatchKeys(registry: string): string[] {
+    return this.wasm.dispatch_keys(registry);
+  }
+
+  /**
+   * Pseudo-clés (`%unknown`, `%none`) dispatchées sur un registre
+   */
+  dispatchFallbacks(registry: string): string[] {
+    return this.wasm.dispatch_fallbacks(registry);
+  }
+
+  /**
+   * Décrit les schémas chargés (ou un seul), pour générer de la documentation
+   */
+  describeSchemas(filename?: string) {
+    return this.wasm.describe_schemas(filename);
+  }
+
+  /**
+   * JSON Schema (draft-07) d'un type de ressource, à donner à Monaco
+   */
+  toJsonSchema(resourceType: string): Record<string, unknown> {
+    return this.wasm.to_json_schema(resourceType);
+  }
+
+  /**
+   * Registres ayant au moins un dispatch
+   */
+  registriesWithDispatch(): string[] {
+    return this.wasm.registries_with_dispatch();
+  }
 }
 
 // Re-exports
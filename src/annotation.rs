@@ -0,0 +1,216 @@
+//! Parsing of annotation contents like `#[id(registry="item", tags="allowed")]`
+//!
+//! The lexer captures a whole annotation as one token, this module splits it into
+//! a name and structured data. Commas, `=` and brackets inside quoted strings or
+//! nested `[...]`/`(...)` don't split values.
+
+use crate::lexer::unescape;
+use crate::parser::{AnnotationData, AnnotationValue};
+use rustc_hash::FxHashMap;
+use std::borrow::Cow;
+
+/// Parse an annotation token such as `#[since="1.20"]` into its name and data
+pub fn parse_annotation(text: &str) -> (&str, AnnotationData<'_>) {
+    let body = text.strip_prefix("#[").unwrap_or(text);
+    let body = body.strip_suffix(']').unwrap_or(body).trim();
+
+    let Some(split) = body.find(['=', '(']) else {
+        return (body, AnnotationData::Empty);
+    };
+    let name = body[..split].trim();
+
+    if body[split..].starts_with('(') {
+        // #[name(key=value, ...)]
+        return (name, AnnotationData::Complex(parse_arguments(strip_delimiters(&body[split..], '(', ')'))));
+    }
+
+    // #[name=value], #[name=(key=value, ...)] or #[name=[a, b]]
+    let value = body[split + 1..].trim();
+    let data = if value.starts_with('(') {
+        AnnotationData::Complex(parse_arguments(strip_delimiters(value, '(', ')')))
+    } else if value.starts_with('[') {
+        AnnotationData::List(parse_list(strip_delimiters(value, '[', ']')))
+    } else {
+        AnnotationData::Simple(unquote(value))
+    };
+    (name, data)
+}
+
+/// Parse `key=value` arguments, ignoring entries without a key
+fn parse_arguments(text: &str) -> FxHashMap<&str, AnnotationValue<'_>> {
+    split_top_level(text, ',')
+        .into_iter()
+        .filter_map(|argument| {
+            let eq_pos = find_top_level(argument, '=')?;
+            Some((argument[..eq_pos].trim(), parse_value(&argument[eq_pos + 1..])))
+        })
+        .collect()
+}
+
+fn parse_list(text: &str) -> Vec<AnnotationValue<'_>> {
+    split_top_level(text, ',')
+        .into_iter()
+        .filter(|item| !item.trim().is_empty())
+        .map(parse_value)
+        .collect()
+}
+
+fn parse_value(text: &str) -> AnnotationValue<'_> {
+    let text = text.trim();
+    if is_quoted(text) {
+        return AnnotationValue::String(unquote(text));
+    }
+    if text.starts_with('[') {
+        return AnnotationValue::List(parse_list(strip_delimiters(text, '[', ']')));
+    }
+    match text {
+        "true" => AnnotationValue::Boolean(true),
+        "false" => AnnotationValue::Boolean(false),
+        _ => match text.parse::<f64>() {
+            Ok(number) => AnnotationValue::Number(number),
+            Err(_) => AnnotationValue::String(Cow::Borrowed(text)),
+        },
+    }
+}
+
+fn is_quoted(text: &str) -> bool {
+    text.len() >= 2 && text.starts_with('"') && text.ends_with('"')
+}
+
+/// Content of a quoted string with its escape sequences decoded. The lexer already
+/// rejected invalid escapes, one left here is kept as written.
+fn unquote(text: &str) -> Cow<'_, str> {
+    if is_quoted(text) {
+        let content = &text[1..text.len() - 1];
+        unescape(content).unwrap_or(Cow::Borrowed(content))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+fn strip_delimiters(text: &str, open: char, close: char) -> &str {
+    let text = text.strip_prefix(open).unwrap_or(text);
+    text.strip_suffix(close).unwrap_or(text)
+}
+
+/// Split on `separator` outside of quoted strings and nested brackets
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+    while let Some(index) = find_top_level(rest, separator) {
+        parts.push(&rest[..index]);
+        rest = &rest[index + separator.len_utf8()..];
+    }
+    parts.push(rest);
+    parts
+}
+
+/// Byte offset of the first `target` outside of quoted strings and nested brackets
+fn find_top_level(text: &str, target: char) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (index, c) in text.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match c {
+            _ if c == target && depth == 0 => return Some(index),
+            '"' => in_string = true,
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Whether an `#[id]` value may or must be a tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdTags {
+    /// Only entries are accepted
+    #[default]
+    None,
+    /// `tags="allowed"`, entries or `#`-prefixed tags
+    Allowed,
+    /// `tags="required"`, only `#`-prefixed tags
+    Required,
+    /// `tags="implicit"`, always a tag written without `#`
+    Implicit,
+}
+
+/// Arguments of an `#[id]` annotation, like `#[id(registry="texture", path="entity/")]`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct IdAnnotation<'input> {
+    pub registry: Option<&'input str>,
+    pub tags: IdTags,
+    /// Prefix applied to the path of the value before registry lookup
+    pub path: Option<&'input str>,
+    /// The value defines a new resource instead of referencing one
+    pub definition: bool,
+    /// `empty="allowed"`, an empty string is accepted
+    pub empty_allowed: bool,
+}
+
+impl<'a> IdAnnotation<'a> {
+    /// Read the `#[id]` arguments, `#[id="item"]` only names the registry.
+    /// A `tag/` registry like `tag/item` stands for the tags of `item`, implicit
+    /// unless `tags` says otherwise.
+    pub fn from_data(data: &'a AnnotationData<'_>) -> Self {
+        let map = match data {
+            AnnotationData::Simple(registry) => return Self::default().with_registry(registry),
+            AnnotationData::Complex(map) => map,
+            _ => return Self::default(),
+        };
+        let string = |key: &str| map.get(key).and_then(AnnotationValue::as_str);
+
+        let id = Self {
+            registry: None,
+            tags: match string("tags") {
+                Some("allowed") => IdTags::Allowed,
+                Some("required") => IdTags::Required,
+                Some("implicit") => IdTags::Implicit,
+                _ => IdTags::None,
+            },
+            path: string("path"),
+            definition: matches!(map.get("definition"), Some(AnnotationValue::Boolean(true))) || string("definition") == Some("true"),
+            empty_allowed: string("empty") == Some("allowed"),
+        };
+        match string("registry") {
+            Some(registry) => id.with_registry(registry),
+            None => id,
+        }
+    }
+
+    fn with_registry(mut self, registry: &'a str) -> Self {
+        match registry.strip_prefix("tag/") {
+            Some(tagged) => {
+                self.registry = Some(tagged);
+                if self.tags == IdTags::None {
+                    self.tags = IdTags::Implicit;
+                }
+            }
+            None => self.registry = Some(registry),
+        }
+        self
+    }
+
+    /// Apply the `path` prefix to a resource location, keeping its namespace
+    pub fn apply_path(&self, resource_location: &str) -> String {
+        let Some(prefix) = self.path else {
+            return resource_location.to_string();
+        };
+        match resource_location.split_once(':') {
+            Some((namespace, path)) => format!("{}:{}{}", namespace, prefix, path),
+            None => format!("{}{}", prefix, resource_location),
+        }
+    }
+}
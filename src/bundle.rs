@@ -0,0 +1,77 @@
+//! Precompiled schema bundles
+//!
+//! A bundle holds parsed MCDOC files as owned ASTs, so they can be parsed once
+//! offline with `build_schema_bundle` and loaded at startup with
+//! `DatapackValidator::load_schema_bundle`, which only deserializes.
+//!
+//! Layout: the `MAGIC` bytes, the format version as a little-endian `u16`, then the
+//! files as CBOR. The version changes whenever the AST does, older bundles are rejected.
+
+use crate::error::ParseError;
+use crate::owned::McDocFileOwned;
+use serde::{Deserialize, Serialize};
+
+/// First bytes of every bundle
+pub const MAGIC: &[u8; 8] = b"RSMCDOC\0";
+
+/// Version of the bundle format, bumped on any change to the owned AST
+pub const BUNDLE_VERSION: u16 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct SchemaBundle {
+    files: Vec<(String, McDocFileOwned)>,
+}
+
+/// Parse MCDOC files given as (filename, source) into a bundle.
+/// Parse errors are reported for every file, in the context of its filename.
+pub fn build_schema_bundle(files: &[(String, String)]) -> Result<Vec<u8>, Vec<ParseError>> {
+    let mut parsed = Vec::with_capacity(files.len());
+    let mut errors = Vec::new();
+    for (filename, source) in files {
+        match crate::parse_mcdoc_owned(source) {
+            Ok(file) => parsed.push((filename.clone(), file)),
+            Err(file_errors) => errors.extend(file_errors.into_iter().map(|error| ParseError::Context {
+                message: error.to_string(),
+                context: filename.clone(),
+                pos: error.position(),
+            })),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let mut bytes = Vec::with_capacity(MAGIC.len() + 2);
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&BUNDLE_VERSION.to_le_bytes());
+    ciborium::into_writer(&SchemaBundle { files: parsed }, &mut bytes)
+        .map_err(|e| vec![bundle_error(format!("Failed to encode schema bundle: {}", e))])?;
+    Ok(bytes)
+}
+
+/// Files of a bundle, after checking its header
+pub(crate) fn read_schema_bundle(bytes: &[u8]) -> Result<Vec<(String, McDocFileOwned)>, ParseError> {
+    let payload = bytes.strip_prefix(MAGIC.as_slice())
+        .ok_or_else(|| bundle_error("Not a schema bundle"))?;
+    let (version, payload) = payload.split_first_chunk::<2>()
+        .ok_or_else(|| bundle_error("Truncated schema bundle header"))?;
+    let version = u16::from_le_bytes(*version);
+    if version != BUNDLE_VERSION {
+        return Err(bundle_error(format!(
+            "Unsupported schema bundle version {}, expected {}: rebuild the bundle with this version of the library",
+            version, BUNDLE_VERSION
+        )));
+    }
+
+    let bundle: SchemaBundle = ciborium::from_reader(payload)
+        .map_err(|e| bundle_error(format!("Invalid schema bundle: {}", e)))?;
+    Ok(bundle.files)
+}
+
+fn bundle_error(message: impl Into<String>) -> ParseError {
+    ParseError::Context {
+        message: message.into(),
+        context: "schema bundle".to_string(),
+        pos: None,
+    }
+}
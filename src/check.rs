@@ -0,0 +1,172 @@
+//! Semantic checks of a parsed MCDOC file
+//!
+//! The parser accepts schemas that are well formed but can't mean what they say:
+//! an enum with two variants of the same name or value, an `enum(int)` with string
+//! values, or a range like `@ 5..1`. `check_mcdoc` reports them as
+//! `ParseError::Validation`, the path naming the declaration and the fields leading
+//! to the faulty type, like `Recipe.pattern`.
+
+use crate::error::ParseError;
+use crate::lexer::Position;
+use crate::parser::{
+    ArrayConstraints, Declaration, EnumVariant, LiteralValue, McDocFile, StructMember, TypeConstraints,
+    TypeExpression,
+};
+use crate::printer;
+
+/// Problems of every declaration of a file, in source order
+pub fn check_mcdoc(file: &McDocFile) -> Vec<ParseError> {
+    let mut checker = Checker { errors: Vec::new() };
+    for declaration in &file.declarations {
+        match declaration {
+            Declaration::Struct(declaration) => {
+                checker.check_members(declaration.name, &declaration.members);
+            }
+            Declaration::Enum(declaration) => {
+                checker.check_enum(declaration.name, declaration.base_type, &declaration.variants);
+            }
+            Declaration::Type(declaration) => {
+                checker.check_type(declaration.name, &declaration.type_expr, declaration.position);
+            }
+            Declaration::Dispatch(declaration) => {
+                let keys: Vec<String> = declaration.targets.iter().map(printer::dispatch_target).collect();
+                let path = format!(
+                    "{}:{}[{}]",
+                    declaration.source.namespace,
+                    declaration.source.registry,
+                    keys.join(",")
+                );
+                checker.check_type(&path, &declaration.target_type, declaration.position);
+            }
+        }
+    }
+    checker.errors
+}
+
+struct Checker {
+    errors: Vec<ParseError>,
+}
+
+impl Checker {
+    fn report(&mut self, message: String, path: &str, position: Position) {
+        self.errors.push(ParseError::validation_at(message, path, position.into()));
+    }
+
+    fn check_members(&mut self, path: &str, members: &[StructMember]) {
+        for member in members {
+            match member {
+                StructMember::Field(field) => {
+                    self.check_type(&format!("{}.{}", path, field.name), &field.field_type, field.position);
+                }
+                StructMember::DynamicField(field) => {
+                    let path = format!("{}[]", path);
+                    self.check_type(&path, &field.key_type, field.position);
+                    self.check_type(&path, &field.value_type, field.position);
+                }
+                StructMember::Spread(spread) => {
+                    if let Some(target) = &spread.target {
+                        self.check_type(path, target, spread.position);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Constraints carry no position, they are reported at the field or declaration
+    /// owning the type
+    fn check_type(&mut self, path: &str, type_expr: &TypeExpression, position: Position) {
+        match type_expr {
+            TypeExpression::Array { element_type, constraints } => {
+                if let Some(constraints) = constraints {
+                    self.check_array_constraints(path, constraints, position);
+                }
+                self.check_type(path, element_type, position);
+            }
+            TypeExpression::Union(types) => {
+                for type_expr in types {
+                    self.check_type(path, type_expr, position);
+                }
+            }
+            TypeExpression::Struct { members, .. } => self.check_members(path, members),
+            TypeExpression::Generic { type_args, .. } => {
+                for type_arg in type_args {
+                    self.check_type(path, type_arg, position);
+                }
+            }
+            TypeExpression::Spread(spread) => {
+                if let Some(target) = &spread.target {
+                    self.check_type(path, target, spread.position);
+                }
+            }
+            TypeExpression::Enum { name, base_type, variants } => {
+                let path = match name {
+                    Some(name) => format!("{}.{}", path, name),
+                    None => path.to_string(),
+                };
+                self.check_enum(&path, *base_type, variants);
+            }
+            TypeExpression::Annotated { type_expr, .. } => self.check_type(path, type_expr, position),
+            TypeExpression::Constrained { base_type, constraints } => {
+                self.check_type_constraints(path, constraints, position);
+                self.check_type(path, base_type, position);
+            }
+            TypeExpression::Simple(_) | TypeExpression::Reference(_) | TypeExpression::Literal(_) => {}
+        }
+    }
+
+    /// Variant names and values are unique, and values match the base type
+    fn check_enum(&mut self, path: &str, base_type: Option<&str>, variants: &[EnumVariant]) {
+        for (index, variant) in variants.iter().enumerate() {
+            let variant_path = format!("{}.{}", path, variant.name);
+            let earlier = &variants[..index];
+            if earlier.iter().any(|other| other.name == variant.name) {
+                self.report(format!("Duplicate enum variant '{}'", variant.name), &variant_path, variant.position);
+            }
+
+            let Some(value) = &variant.value else { continue };
+            if let Some(other) = earlier.iter().find(|other| other.value.as_ref() == Some(value)) {
+                self.report(
+                    format!("Enum variant '{}' has the same value {} as '{}'", variant.name, printer::literal(value), other.name),
+                    &variant_path,
+                    variant.position,
+                );
+            }
+            if let Some(base_type) = base_type {
+                if !value_matches_base_type(value, base_type) {
+                    self.report(
+                        format!("Value {} of enum variant '{}' doesn't match enum({})", printer::literal(value), variant.name, base_type),
+                        &variant_path,
+                        variant.position,
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_array_constraints(&mut self, path: &str, constraints: &ArrayConstraints, position: Position) {
+        if let (Some(min), Some(max)) = (constraints.min, constraints.max) {
+            if min > max {
+                self.report(format!("Array length range {}..{} has a minimum above its maximum", min, max), path, position);
+            }
+        }
+    }
+
+    fn check_type_constraints(&mut self, path: &str, constraints: &TypeConstraints, position: Position) {
+        if let (Some(min), Some(max)) = (constraints.min, constraints.max) {
+            if min > max {
+                self.report(format!("Range {}..{} has a minimum above its maximum", min, max), path, position);
+            }
+        }
+    }
+}
+
+/// Whether an enum variant value fits `enum(base_type)`. Unknown base types accept anything.
+fn value_matches_base_type(value: &LiteralValue, base_type: &str) -> bool {
+    match (base_type, value) {
+        ("string", LiteralValue::String(_)) => true,
+        ("byte" | "short" | "int" | "long", LiteralValue::Number(number)) => number.fract() == 0.0,
+        ("float" | "double", LiteralValue::Number(_)) => true,
+        ("string" | "byte" | "short" | "int" | "long" | "float" | "double", _) => false,
+        _ => true,
+    }
+}
@@ -0,0 +1,81 @@
+//! Human readable rendering of MCDOC errors with source excerpts
+//!
+//! Output follows rustc's layout:
+//!
+//! ```text
+//! error: Expected ':' after field name, found Comma
+//!  --> item.mcdoc:3:19
+//!   |
+//! 3 |     count: int int,
+//!   |                   ^
+//! ```
+
+use crate::error::{ParseError, SourcePos};
+
+/// Display width of a tab in source excerpts
+const TAB_WIDTH: usize = 4;
+
+/// Render errors of a source file, separated by blank lines
+pub fn render(errors: &[ParseError], source: &str, filename: &str) -> String {
+    errors.iter()
+        .map(|error| render_error(error, source, filename))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_error(error: &ParseError, source: &str, filename: &str) -> String {
+    let mut output = format!("error: {}\n", message(error));
+    let Some(start) = error.position() else {
+        output.push_str(&format!(" --> {}\n", filename));
+        return output;
+    };
+
+    let lines: Vec<&str> = source.lines().collect();
+    // Positions past the end of the source point after its last character
+    let (line_number, line) = match lines.get(start.line.saturating_sub(1) as usize) {
+        Some(line) => (start.line.max(1), *line),
+        None => (lines.len().max(1) as u32, lines.last().copied().unwrap_or("")),
+    };
+    let line_length = line.chars().count();
+    let start_column = if line_number == start.line {
+        (start.column.max(1) as usize).min(line_length + 1)
+    } else {
+        line_length + 1
+    };
+    let end_column = match error.end_position() {
+        Some(SourcePos { line, column, .. }) if line == line_number => (column as usize).min(line_length + 1),
+        Some(SourcePos { line, .. }) if line > line_number => line_length + 1,
+        _ => start_column + 1,
+    };
+
+    let gutter = " ".repeat(line_number.to_string().len());
+    let offset = display_width(line.chars().take(start_column - 1));
+    let width = display_width(line.chars().skip(start_column - 1).take(end_column.saturating_sub(start_column))).max(1);
+
+    output.push_str(&format!("{}--> {}:{}:{}\n", gutter, filename, start.line, start.column));
+    output.push_str(&format!("{} |\n", gutter));
+    output.push_str(&format!("{} | {}\n", line_number, expand_tabs(line)));
+    output.push_str(&format!("{} | {}{}\n", gutter, " ".repeat(offset), "^".repeat(width)));
+    output
+}
+
+/// Error message without the position that `Display` appends
+fn message(error: &ParseError) -> String {
+    match error {
+        ParseError::Lexer { message, .. } => message.clone(),
+        // `expected` is either what was expected or a whole "Expected ..." sentence
+        ParseError::Syntax { expected, found, .. } if expected.starts_with("Expected") => format!("{}, found {}", expected, found),
+        ParseError::Syntax { expected, found, .. } => format!("expected {}, found {}", expected, found),
+        ParseError::Validation { message, .. } => message.clone(),
+        ParseError::Context { message, context, .. } => format!("{} in {}", message, context),
+        _ => error.to_string(),
+    }
+}
+
+fn display_width(chars: impl Iterator<Item = char>) -> usize {
+    chars.map(|c| if c == '\t' { TAB_WIDTH } else { 1 }).sum()
+}
+
+fn expand_tabs(line: &str) -> String {
+    line.replace('\t', &" ".repeat(TAB_WIDTH))
+}
@@ -2,17 +2,26 @@
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::ops::Range;
 
 /// Position in the source code
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SourcePos {
     pub line: u32,
+    /// Column in characters
     pub column: u32,
+    /// Column in UTF-16 code units, for editors and LSP
+    #[serde(default)]
+    pub column_utf16: u32,
+    /// Byte offset from the start of the source
+    #[serde(default)]
+    pub offset: usize,
 }
 
 impl SourcePos {
-    pub fn new(line: u32, column: u32) -> Self {
-        Self { line, column }
+    /// Position on a line without characters outside of the BMP, where both columns agree
+    pub fn new(line: u32, column: u32, offset: usize) -> Self {
+        Self { line, column, column_utf16: column, offset }
     }
 }
 
@@ -28,6 +37,8 @@ pub enum ParseError {
         expected: String, 
         found: String, 
         pos: SourcePos,
+        /// Position right after the offending token, when known
+        end: Option<SourcePos>,
     },
     
     Resolution { 
@@ -47,7 +58,10 @@ pub enum ParseError {
         pos: Option<SourcePos>,
     },
     
-    InvalidResourceId(String),
+    InvalidResourceId {
+        id: String,
+        reason: String,
+    },
     
     ModuleNotFound {
         module: String,
@@ -73,7 +87,80 @@ pub enum ErrorType {
     Context,
     InvalidResourceId,
     ModuleNotFound,
-    CircularDependency
+    CircularDependency,
+    UnknownField,
+}
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
+    /// A note about something that needs no fix
+    Info,
+}
+
+/// Specific reason of a validation error, for handling errors without parsing messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ErrorCode {
+    /// A required field is absent
+    MissingField,
+    /// Several fields of an `#[exclusive]` group are present, or none of a required one
+    ExclusiveFields,
+    /// A key that no struct member declares (strict fields)
+    UnknownField,
+    /// A field that doesn't exist in the requested version
+    UnavailableField,
+    /// The JSON value doesn't have the expected type
+    TypeMismatch,
+    /// A number, string length or array length outside of its range
+    OutOfRange,
+    /// A value that doesn't match the expected literal or enum values
+    LiteralMismatch,
+    /// No branch of a union matched
+    UnionNoMatch,
+    /// A resource location missing from its registry
+    RegistryMiss,
+    /// A reference to a registry that isn't loaded
+    UnknownRegistry,
+    /// A malformed resource location, or a tag where none is allowed
+    InvalidResourceLocation,
+    /// A tag marker `#` after the start of a resource location, like `minecraft:#logs`
+    MisplacedTagMarker,
+    /// A resource location with an empty namespace, like `:stone` or `#:logs`
+    EmptyNamespace,
+    /// A namespace written twice, like `minecraft:minecraft:stone`
+    RepeatedNamespace,
+    /// A string that doesn't match the format of its annotation, like `#[uuid]`
+    InvalidFormat,
+    /// A tag that can't be expanded or contains unknown entries
+    InvalidTag,
+    /// A dispatcher key no schema is registered for
+    UnknownDispatchKey,
+    /// An invalid block state or other attached registry property
+    InvalidProperty,
+    /// A type reference without declaration or with the wrong number of arguments
+    UnresolvedType,
+    /// No schema is known for the resource type
+    SchemaNotFound,
+    /// The document isn't well-formed JSON
+    InvalidJson,
+    /// Use of a deprecated element (warnings)
+    Deprecated,
+    /// Malformed MCDOC source
+    Syntax,
+    /// A schema with the same content as a loaded one, loaded as its alias (info)
+    DuplicateSchema,
+    /// A value nested deeper than `ValidationOptions::max_depth`, or a generic alias
+    /// instantiating itself without end
+    MaxDepthExceeded,
+    /// A type that refers back to itself without going into the value, like `type A = B; type B = A`
+    TypeCycle,
+    #[default]
+    Other,
 }
 
 impl fmt::Display for ParseError {
@@ -82,7 +169,7 @@ impl fmt::Display for ParseError {
             ParseError::Lexer { message, pos } => {
                 write!(f, "{} at {}:{}", message, pos.line, pos.column)
             }
-            ParseError::Syntax { expected, found, pos } => {
+            ParseError::Syntax { expected, found, pos, .. } => {
                 write!(f, "Expected '{}', found '{}' at {}:{}", expected, found, pos.line, pos.column)
             }
             ParseError::Resolution { message, path } => {
@@ -103,8 +190,8 @@ impl fmt::Display for ParseError {
                     None => write!(f, "{} in {}", message, context),
                 }
             }
-            ParseError::InvalidResourceId(id) => {
-                write!(f, "Invalid resource identifier: '{}'", id)
+            ParseError::InvalidResourceId { id, reason } => {
+                write!(f, "Invalid resource identifier '{}': {}", id, reason)
             }
             ParseError::ModuleNotFound { module, from } => {
                 write!(f, "Module not found: {} from {}", module, from)
@@ -128,7 +215,8 @@ impl ParseError {
         Self::Syntax { 
             expected: expected.into(), 
             found: found.into(), 
-            pos 
+            pos,
+            end: None,
         }
     }
     
@@ -160,12 +248,21 @@ impl ParseError {
             ParseError::Resolution { .. } => ErrorType::Resolution,
             ParseError::Validation { .. } => ErrorType::Validation,
             ParseError::Context { .. } => ErrorType::Context,
-            ParseError::InvalidResourceId(_) => ErrorType::InvalidResourceId,
+            ParseError::InvalidResourceId { .. } => ErrorType::InvalidResourceId,
             ParseError::ModuleNotFound { .. } => ErrorType::ModuleNotFound,
             ParseError::CircularDependency { .. } => ErrorType::CircularDependency,
         }
     }
     
+    /// Error code of this error when reported on a document
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ParseError::Lexer { .. } | ParseError::Syntax { .. } => ErrorCode::Syntax,
+            ParseError::InvalidResourceId { .. } => ErrorCode::InvalidResourceLocation,
+            _ => ErrorCode::Other,
+        }
+    }
+    
     /// Get the position if available
     pub fn position(&self) -> Option<SourcePos> {
         match self {
@@ -174,9 +271,24 @@ impl ParseError {
             ParseError::Validation { pos, .. } |
             ParseError::Context { pos, .. } => *pos,
             ParseError::Resolution { .. } |
-            ParseError::InvalidResourceId(_) |
+            ParseError::InvalidResourceId { .. } |
             ParseError::ModuleNotFound { .. } |
             ParseError::CircularDependency { .. } => None,
         }
     }
+    
+    /// Get the position right after the offending source, if known
+    pub fn end_position(&self) -> Option<SourcePos> {
+        match self {
+            ParseError::Syntax { end, .. } => *end,
+            _ => None,
+        }
+    }
+
+    /// Byte range of the offending source, empty when only its start is known
+    pub fn byte_range(&self) -> Option<Range<usize>> {
+        let start = self.position()?.offset;
+        let end = self.end_position().map_or(start, |end| end.offset.max(start));
+        Some(start..end)
+    }
 } 
\ No newline at end of file
@@ -0,0 +1,181 @@
+//! String formats given by annotations like `#[uuid]`, `#[color="hex_rgb"]` or `#[regex_pattern]`
+
+use rustc_hash::FxHashMap;
+
+/// Check of a string against a format, given the annotation value (`hex_rgb` for
+/// `#[color="hex_rgb"]`). Returns why the string doesn't conform.
+pub type FormatCheck = fn(value: &str, argument: Option<&str>) -> Result<(), String>;
+
+/// Format checks by annotation name
+///
+/// Strings annotated with a registered name are checked during validation, other
+/// annotations are ignored. Built-in checks can be replaced with `register`.
+#[derive(Debug, Clone)]
+pub struct StringFormats {
+    checks: FxHashMap<String, FormatCheck>,
+}
+
+impl StringFormats {
+    /// Create the registry with the `uuid`, `color` and `regex_pattern` checks
+    pub fn new() -> Self {
+        let mut formats = Self { checks: FxHashMap::default() };
+        formats.register("uuid", check_uuid);
+        formats.register("color", check_color);
+        formats.register("regex_pattern", check_regex_pattern);
+        formats
+    }
+
+    /// Register the check of an annotation, replacing any previous one
+    pub fn register(&mut self, annotation: impl Into<String>, check: FormatCheck) {
+        self.checks.insert(annotation.into(), check);
+    }
+
+    /// Remove the check of an annotation, returns whether there was one
+    pub fn unregister(&mut self, annotation: &str) -> bool {
+        self.checks.remove(annotation).is_some()
+    }
+
+    /// Check registered for an annotation name
+    pub fn get(&self, annotation: &str) -> Option<FormatCheck> {
+        self.checks.get(annotation).copied()
+    }
+}
+
+impl Default for StringFormats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Dashed UUID like `f81d4fae-7dec-11d0-a765-00a0c91e6bf6`
+fn check_uuid(value: &str, _argument: Option<&str>) -> Result<(), String> {
+    let groups: Vec<&str> = value.split('-').collect();
+    let well_formed = groups.len() == 5
+        && groups.iter().zip([8, 4, 4, 4, 12]).all(|(group, length)| {
+            group.len() == length && group.chars().all(|c| c.is_ascii_hexdigit())
+        });
+    if well_formed {
+        Ok(())
+    } else {
+        Err("expected hexadecimal digits grouped as 8-4-4-4-12".to_string())
+    }
+}
+
+/// `#[color="hex_rgb"]` strings are `#` followed by 6 hex digits, other color
+/// kinds are numbers or lists and have no string format
+fn check_color(value: &str, argument: Option<&str>) -> Result<(), String> {
+    if argument != Some("hex_rgb") {
+        return Ok(());
+    }
+    match value.strip_prefix('#') {
+        Some(digits) if digits.len() == 6 && digits.chars().all(|c| c.is_ascii_hexdigit()) => Ok(()),
+        _ => Err("expected '#' followed by 6 hexadecimal digits".to_string()),
+    }
+}
+
+/// Structural check of a Java regular expression: escapes, character classes, groups
+/// and quantifiers. Doesn't verify the meaning of escapes or group modifiers.
+fn check_regex_pattern(value: &str, _argument: Option<&str>) -> Result<(), String> {
+    let chars: Vec<char> = value.chars().collect();
+    let mut open_groups = 0usize;
+    // Whether the previous element can be repeated by a quantifier
+    let mut repeatable = false;
+    let mut index = 0;
+
+    while index < chars.len() {
+        match chars[index] {
+            '\\' => {
+                if index + 1 == chars.len() {
+                    return Err("trailing backslash".to_string());
+                }
+                index += 1;
+                repeatable = true;
+            }
+            '[' => {
+                index = class_end(&chars, index).ok_or("unclosed character class")?;
+                repeatable = true;
+            }
+            '(' => {
+                open_groups += 1;
+                // `(?:`, `(?=`, ... start with a modifier, not a quantifier
+                if chars.get(index + 1) == Some(&'?') {
+                    index += 1;
+                }
+                repeatable = false;
+            }
+            ')' => {
+                open_groups = open_groups.checked_sub(1).ok_or("unmatched ')'")?;
+                repeatable = true;
+            }
+            '|' => repeatable = false,
+            '*' | '+' | '?' => {
+                if !repeatable {
+                    return Err(format!("nothing to repeat before '{}' at index {}", chars[index], index));
+                }
+                // Lazy `*?` and possessive `*+` forms
+                if matches!(chars.get(index + 1), Some('?' | '+')) {
+                    index += 1;
+                }
+                repeatable = false;
+            }
+            '{' => {
+                if let Some((end, min, max)) = repetition(&chars, index) {
+                    if !repeatable {
+                        return Err(format!("nothing to repeat before '{{' at index {}", index));
+                    }
+                    if max.is_some_and(|max| max < min) {
+                        return Err(format!("invalid repetition range at index {}", index));
+                    }
+                    index = end;
+                    repeatable = false;
+                } else {
+                    repeatable = true;
+                }
+            }
+            _ => repeatable = true,
+        }
+        index += 1;
+    }
+
+    if open_groups > 0 {
+        return Err("unclosed group".to_string());
+    }
+    Ok(())
+}
+
+/// Index of the `]` closing the character class opened at `start`, nested classes included
+fn class_end(chars: &[char], start: usize) -> Option<usize> {
+    let mut index = start + 1;
+    if chars.get(index) == Some(&'^') {
+        index += 1;
+    }
+    // A `]` right after the opening bracket is a literal
+    if chars.get(index) == Some(&']') {
+        index += 1;
+    }
+    while index < chars.len() {
+        match chars[index] {
+            '\\' => index += 1,
+            '[' => index = class_end(chars, index)?,
+            ']' => return Some(index),
+            _ => {}
+        }
+        index += 1;
+    }
+    None
+}
+
+/// `{n}`, `{n,}` or `{n,m}` at `start`, as the index of the closing brace and the bounds
+fn repetition(chars: &[char], start: usize) -> Option<(usize, u32, Option<u32>)> {
+    let end = start + chars[start..].iter().position(|&c| c == '}')?;
+    let body: String = chars[start + 1..end].iter().collect();
+    let (min, max) = match body.split_once(',') {
+        Some((min, "")) => (min.parse().ok()?, None),
+        Some((min, max)) => (min.parse().ok()?, Some(max.parse().ok()?)),
+        None => {
+            let count = body.parse().ok()?;
+            (count, Some(count))
+        }
+    };
+    Some((end, min, max))
+}
@@ -0,0 +1,120 @@
+//! Dependency graph of a datapack, from the files to the resources they reference
+//!
+//! Built from per-file validation results with `DatapackValidator::dependency_graph`.
+//! Each edge records whether the loaded registries list its resource, so the graph
+//! answers pack-level questions on its own: which references nothing provides, which
+//! of the pack's own resources nothing references, and which files use a resource.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Resource or tag of a registry, like `minecraft:stone` in `item`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceNode {
+    /// Registry without the `minecraft:` namespace, like `item` or `worldgen/biome`
+    pub registry: String,
+    /// Namespaced id, without the `#` of tags
+    pub id: String,
+    pub is_tag: bool,
+}
+
+impl ResourceNode {
+    pub fn new(registry: &str, id: &str, is_tag: bool) -> Self {
+        let registry = registry.strip_prefix("minecraft:").unwrap_or(registry);
+        let id = id.strip_prefix('#').unwrap_or(id);
+        Self {
+            registry: registry.to_string(),
+            id: if id.contains(':') { id.to_string() } else { format!("minecraft:{}", id) },
+            is_tag,
+        }
+    }
+}
+
+/// File of the datapack with the resource it defines, when its path gives one
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileNode {
+    pub file_path: String,
+    pub resource: Option<ResourceNode>,
+}
+
+/// Reference from a file to a resource
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyEdge {
+    pub file_path: String,
+    pub resource: ResourceNode,
+    /// A loaded registry lists the resource, the pack's own files aside
+    pub in_registries: bool,
+}
+
+/// Files of a datapack and the resources they reference
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyGraph {
+    pub files: Vec<FileNode>,
+    pub edges: Vec<DependencyEdge>,
+}
+
+impl DependencyGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file, `resource` being the one it defines
+    pub fn add_file(&mut self, file_path: &str, resource: Option<ResourceNode>) {
+        self.files.push(FileNode { file_path: file_path.to_string(), resource });
+    }
+
+    /// Add a reference from `file_path` to `resource`
+    pub fn add_dependency(&mut self, file_path: &str, resource: ResourceNode, in_registries: bool) {
+        self.edges.push(DependencyEdge { file_path: file_path.to_string(), resource, in_registries });
+    }
+
+    /// Whether a file of the pack defines `resource`
+    pub fn provides(&self, resource: &ResourceNode) -> bool {
+        self.files.iter().any(|file| file.resource.as_ref() == Some(resource))
+    }
+
+    /// References to resources neither listed by the registries nor defined by the pack,
+    /// sorted by file then resource
+    pub fn missing_references(&self) -> Vec<&DependencyEdge> {
+        let provided: BTreeSet<&ResourceNode> = self.files.iter().filter_map(|file| file.resource.as_ref()).collect();
+        let mut missing: Vec<&DependencyEdge> = self.edges.iter()
+            .filter(|edge| !edge.in_registries && !provided.contains(&edge.resource))
+            .collect();
+        missing.sort_by(|a, b| (&a.file_path, &a.resource).cmp(&(&b.file_path, &b.resource)));
+        missing
+    }
+
+    /// Files defining a resource no file of the pack references, sorted by path
+    pub fn unused_local_resources(&self) -> Vec<&FileNode> {
+        let referenced: BTreeSet<&ResourceNode> = self.edges.iter().map(|edge| &edge.resource).collect();
+        let mut unused: Vec<&FileNode> = self.files.iter()
+            .filter(|file| file.resource.as_ref().is_some_and(|resource| !referenced.contains(resource)))
+            .collect();
+        unused.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+        unused
+    }
+
+    /// Files referencing `id` in any registry, sorted. `#` selects tags, and an id
+    /// without namespace is in `minecraft`.
+    pub fn dependents_of(&self, id: &str) -> Vec<&str> {
+        let wanted = ResourceNode::new("", id, id.starts_with('#'));
+        let dependents: BTreeSet<&str> = self.edges.iter()
+            .filter(|edge| edge.resource.id == wanted.id && edge.resource.is_tag == wanted.is_tag)
+            .map(|edge| edge.file_path.as_str())
+            .collect();
+        dependents.into_iter().collect()
+    }
+
+    /// Resources `file_path` references, sorted
+    pub fn dependencies_of(&self, file_path: &str) -> Vec<&ResourceNode> {
+        let dependencies: BTreeSet<&ResourceNode> = self.edges.iter()
+            .filter(|edge| edge.file_path == file_path)
+            .map(|edge| &edge.resource)
+            .collect();
+        dependencies.into_iter().collect()
+    }
+}
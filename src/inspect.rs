@@ -0,0 +1,217 @@
+//! Owned descriptions of loaded schemas, for documentation and editor tooling
+//!
+//! Types are rendered back to MCDOC syntax, nested struct and enum bodies elided:
+//!
+//! ```text
+//! [string] @ 1..3
+//! (string | struct {...})
+//! #[id="item"] string
+//! ```
+
+use crate::parser::{Annotation, Declaration, DispatchTarget, EnumVariant, McDocFile, StructMember, TypeExpression};
+use crate::printer;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Declarations of a loaded schema
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaDescription {
+    pub filename: String,
+    pub declarations: Vec<DeclarationDescription>,
+}
+
+impl SchemaDescription {
+    pub fn new(filename: &str, schema: &McDocFile) -> Self {
+        Self {
+            filename: filename.to_string(),
+            declarations: schema.declarations.iter().map(DeclarationDescription::new).collect(),
+        }
+    }
+
+    /// The description as a JSON value, in the shape it's serialized to
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("schema descriptions only hold strings, numbers and lists")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum DeclarationDescription {
+    Struct(StructDescription),
+    Enum(EnumDescription),
+    Type(TypeAliasDescription),
+    Dispatch(DispatchDescription),
+}
+
+impl DeclarationDescription {
+    fn new(declaration: &Declaration) -> Self {
+        match declaration {
+            Declaration::Struct(struct_decl) => DeclarationDescription::Struct(StructDescription {
+                name: struct_decl.name.to_string(),
+                docs: owned_docs(&struct_decl.docs),
+                annotations: render_annotations(&struct_decl.annotations),
+                members: describe_members(&struct_decl.members),
+            }),
+            Declaration::Enum(enum_decl) => DeclarationDescription::Enum(EnumDescription {
+                name: enum_decl.name.to_string(),
+                base_type: enum_decl.base_type.map(str::to_string),
+                docs: owned_docs(&enum_decl.docs),
+                annotations: render_annotations(&enum_decl.annotations),
+                variants: enum_decl.variants.iter().map(VariantDescription::new).collect(),
+            }),
+            Declaration::Type(type_decl) => DeclarationDescription::Type(TypeAliasDescription {
+                name: type_decl.name.to_string(),
+                type_params: type_decl.type_params.iter().map(|param| param.to_string()).collect(),
+                type_name: type_decl.type_expr.to_string(),
+                docs: owned_docs(&type_decl.docs),
+                annotations: render_annotations(&type_decl.annotations),
+            }),
+            Declaration::Dispatch(dispatch) => DeclarationDescription::Dispatch(DispatchDescription {
+                registry: format!("{}:{}", dispatch.source.namespace, dispatch.source.registry),
+                keys: dispatch.targets.iter().map(|target| match target {
+                    DispatchTarget::Specific(key) => key.to_string(),
+                    target => printer::dispatch_target(target),
+                }).collect(),
+                type_name: dispatch.target_type.to_string(),
+                docs: owned_docs(&dispatch.docs),
+                annotations: render_annotations(&dispatch.annotations),
+                members: match &dispatch.target_type {
+                    TypeExpression::Struct { members, .. } => describe_members(members),
+                    _ => Vec::new(),
+                },
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructDescription {
+    pub name: String,
+    pub docs: Vec<String>,
+    pub annotations: Vec<String>,
+    pub members: Vec<MemberDescription>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnumDescription {
+    pub name: String,
+    /// `string`, `int`, ... from `enum(string)`
+    pub base_type: Option<String>,
+    pub docs: Vec<String>,
+    pub annotations: Vec<String>,
+    pub variants: Vec<VariantDescription>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariantDescription {
+    pub name: String,
+    /// Value in MCDOC syntax, like `"blocks"` or `3`
+    pub value: Option<String>,
+    pub docs: Vec<String>,
+}
+
+impl VariantDescription {
+    fn new(variant: &EnumVariant) -> Self {
+        Self {
+            name: variant.name.to_string(),
+            value: variant.value.as_ref().map(printer::literal),
+            docs: owned_docs(&variant.docs),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeAliasDescription {
+    pub name: String,
+    pub type_params: Vec<String>,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub docs: Vec<String>,
+    pub annotations: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DispatchDescription {
+    /// Dispatch source like `minecraft:resource`
+    pub registry: String,
+    /// Keys without quotes, `%unknown` and `%none` included
+    pub keys: Vec<String>,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub docs: Vec<String>,
+    pub annotations: Vec<String>,
+    /// Members of a dispatched struct, empty for other types
+    pub members: Vec<MemberDescription>,
+}
+
+/// Struct member with its type in MCDOC syntax
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum MemberDescription {
+    Field {
+        name: String,
+        #[serde(rename = "type")]
+        type_name: String,
+        optional: bool,
+        docs: Vec<String>,
+        annotations: Vec<String>,
+    },
+    /// `[key]: type`
+    #[serde(rename_all = "camelCase")]
+    DynamicField {
+        key_type: String,
+        #[serde(rename = "type")]
+        type_name: String,
+        optional: bool,
+        annotations: Vec<String>,
+    },
+    /// `...type`
+    Spread {
+        #[serde(rename = "type")]
+        type_name: String,
+    },
+}
+
+fn describe_members(members: &[StructMember]) -> Vec<MemberDescription> {
+    members.iter()
+        .map(|member| match member {
+            StructMember::Field(field) => MemberDescription::Field {
+                name: field.name.to_string(),
+                type_name: field.field_type.to_string(),
+                optional: field.optional,
+                docs: owned_docs(&field.docs),
+                annotations: render_annotations(&field.annotations),
+            },
+            StructMember::DynamicField(dynamic_field) => MemberDescription::DynamicField {
+                key_type: dynamic_field.key_type.to_string(),
+                type_name: dynamic_field.value_type.to_string(),
+                optional: dynamic_field.optional,
+                annotations: render_annotations(&dynamic_field.annotations),
+            },
+            StructMember::Spread(spread) => MemberDescription::Spread {
+                type_name: printer::spread_summary(spread),
+            },
+        })
+        .collect()
+}
+
+fn owned_docs(docs: &[&str]) -> Vec<String> {
+    docs.iter().map(|line| line.to_string()).collect()
+}
+
+fn render_annotations(annotations: &[Annotation]) -> Vec<String> {
+    annotations.iter().map(printer::annotation).collect()
+}
+
+/// MCDOC syntax of the type, struct and enum bodies written `{...}`
+impl fmt::Display for TypeExpression<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&printer::type_summary(self))
+    }
+}
@@ -0,0 +1,370 @@
+//! Export of loaded schemas as draft-07 JSON Schema, for editors like Monaco
+//!
+//! The export is looser than validation: version annotations are ignored, names
+//! that don't resolve accept any value, and spreads chosen by a discriminator
+//! become `if`/`then` pairs on the discriminator field. Resource locations keep
+//! their registry in an `x-registry` extension.
+
+use crate::annotation::IdAnnotation;
+use crate::parser::{
+    Annotation, DynamicReferenceType, EnumVariant, ImportPath, LiteralValue, SpreadExpression, StructMember, TypeConstraints,
+    TypeExpression,
+};
+use crate::types::is_builtin_type;
+use crate::validator::DatapackValidator;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+const DRAFT_07: &str = "http://json-schema.org/draft-07/schema#";
+
+/// Maximum nesting of generic instantiations, recursive generic aliases accept anything past it
+const MAX_GENERIC_DEPTH: usize = 32;
+
+/// Converts types of one validator, collecting named declarations under `definitions`
+pub(crate) struct JsonSchemaExporter<'v, 'input> {
+    validator: &'v DatapackValidator<'input>,
+    definitions: Map<String, Value>,
+    /// Definition names given to declarations, by declaring schema and name
+    names: HashMap<(String, String), String>,
+    generic_depth: usize,
+}
+
+impl<'v, 'input> JsonSchemaExporter<'v, 'input> {
+    pub(crate) fn new(validator: &'v DatapackValidator<'input>) -> Self {
+        Self { validator, definitions: Map::new(), names: HashMap::new(), generic_depth: 0 }
+    }
+
+    /// Schema document for `type_expr` with the names in it bound in `module`
+    pub(crate) fn document(mut self, module: &str, type_expr: &TypeExpression<'input>) -> Value {
+        let root = self.type_schema(type_expr, module);
+        let mut document = Map::new();
+        document.insert("$schema".to_string(), json!(DRAFT_07));
+        match root {
+            Value::Object(root) if !root.contains_key("$ref") => document.extend(root),
+            root => {
+                document.insert("allOf".to_string(), json!([root]));
+            }
+        }
+        if !self.definitions.is_empty() {
+            document.insert("definitions".to_string(), Value::Object(self.definitions));
+        }
+        Value::Object(document)
+    }
+
+    /// Document accepting any value
+    pub(crate) fn any() -> Value {
+        json!({ "$schema": DRAFT_07 })
+    }
+
+    fn type_schema(&mut self, type_expr: &TypeExpression<'input>, module: &str) -> Value {
+        match type_expr {
+            TypeExpression::Simple(type_name) => match builtin_schema(type_name) {
+                Some(schema) => schema,
+                None if is_builtin_type(type_name) => json!({}),
+                None => self.named_schema(type_name, module),
+            },
+            TypeExpression::Struct { members, .. } => self.struct_schema(members, module),
+            TypeExpression::Array { element_type, constraints } => {
+                let mut schema = json!({ "type": "array", "items": self.type_schema(element_type, module) });
+                if let Some(constraints) = constraints {
+                    if let Some(min) = constraints.min {
+                        schema["minItems"] = json!(if constraints.min_exclusive { min + 1 } else { min });
+                    }
+                    if let Some(max) = constraints.max {
+                        schema["maxItems"] = json!(if constraints.max_exclusive { max.saturating_sub(1) } else { max });
+                    }
+                }
+                schema
+            }
+            TypeExpression::Union(types) => match types.as_slice() {
+                [single] => self.type_schema(single, module),
+                types => json!({ "anyOf": types.iter().map(|branch| self.type_schema(branch, module)).collect::<Vec<_>>() }),
+            },
+            TypeExpression::Generic { name, type_args } => self.generic_schema(name, type_args, module),
+            TypeExpression::Enum { base_type, variants, .. } => enum_schema(*base_type, variants),
+            TypeExpression::Annotated { annotations, type_expr } => {
+                let schema = self.type_schema(type_expr, module);
+                with_annotations(schema, annotations)
+            }
+            TypeExpression::Constrained { base_type, constraints } => {
+                let schema = self.type_schema(base_type, module);
+                with_constraints(schema, constraints)
+            }
+            TypeExpression::Literal(literal_value) => json!({ "const": literal(literal_value) }),
+            TypeExpression::Reference(ImportPath::Absolute(segments)) if segments.len() == 3 => {
+                self.dispatch_schema(&format!("{}:{}", segments[0], segments[1]), segments[2])
+            }
+            // Attached registries and other references aren't resolved by validation either
+            TypeExpression::Reference(_) | TypeExpression::Spread(_) => json!({}),
+        }
+    }
+
+    /// `$ref` to the declaration `type_name` names, anything when it doesn't resolve
+    fn named_schema(&mut self, type_name: &str, module: &str) -> Value {
+        let validator = self.validator;
+        let Some((scope, name)) = validator.scoped_name(Some(module), type_name) else {
+            return json!({});
+        };
+
+        if let Some((filename, enum_decl)) = validator.find_enum(scope, name) {
+            self.definition(filename, name, |_| enum_schema(enum_decl.base_type, &enum_decl.variants))
+        } else if let Some((filename, members)) = validator.find_struct(scope, name) {
+            self.definition(filename, name, |exporter| exporter.struct_schema(members, filename))
+        } else if let Some((filename, type_decl)) = validator.find_type_alias(scope, name).filter(|(_, decl)| decl.type_params.is_empty()) {
+            self.definition(filename, name, |exporter| exporter.type_schema(&type_decl.type_expr, filename))
+        } else {
+            json!({})
+        }
+    }
+
+    /// Generic alias instantiated inline, its arguments differing between uses
+    fn generic_schema(&mut self, name: &str, type_args: &[TypeExpression<'input>], module: &str) -> Value {
+        let validator = self.validator;
+        let found = validator.scoped_name(Some(module), name)
+            .and_then(|(scope, name)| validator.find_type_alias(scope, name))
+            .filter(|(_, decl)| decl.type_params.len() == type_args.len());
+        let Some((filename, type_decl)) = found.filter(|_| self.generic_depth < MAX_GENERIC_DEPTH) else {
+            return json!({});
+        };
+
+        let instantiated = DatapackValidator::substitute_type_params(&type_decl.type_expr, &type_decl.type_params, type_args);
+        self.generic_depth += 1;
+        let schema = self.type_schema(&instantiated, filename);
+        self.generic_depth -= 1;
+        schema
+    }
+
+    /// `$ref` to the type dispatched for `key` in `registry`
+    fn dispatch_schema(&mut self, registry: &str, key: &str) -> Value {
+        let Some((filename, target)) = self.validator.find_dispatch_target(registry, Some(key)) else {
+            return json!({});
+        };
+        match target {
+            TypeExpression::Simple(type_name) if !is_builtin_type(type_name) => self.named_schema(type_name, filename),
+            TypeExpression::Struct { name: Some(name), members } => {
+                self.definition(filename, name, |exporter| exporter.struct_schema(members, filename))
+            }
+            _ => {
+                let name = format!("{}[{}]", registry, key);
+                self.definition(filename, &name, |exporter| exporter.type_schema(target, filename))
+            }
+        }
+    }
+
+    /// `$ref` to the definition of `name` declared in `filename`, built on first use.
+    /// The name is reserved before building so recursive types refer to themselves.
+    fn definition(&mut self, filename: &str, name: &str, build: impl FnOnce(&mut Self) -> Value) -> Value {
+        let key = (filename.to_string(), name.to_string());
+        if let Some(definition) = self.names.get(&key) {
+            return reference(definition);
+        }
+
+        let base = definition_name(name);
+        let mut definition = base.clone();
+        let mut suffix = 2;
+        while self.definitions.contains_key(&definition) {
+            definition = format!("{}_{}", base, suffix);
+            suffix += 1;
+        }
+        self.names.insert(key, definition.clone());
+        self.definitions.insert(definition.clone(), json!({}));
+
+        let schema = build(self);
+        self.definitions.insert(definition.clone(), schema);
+        reference(&definition)
+    }
+
+    fn struct_schema(&mut self, members: &[StructMember<'input>], module: &str) -> Value {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        let mut dynamic_values = Vec::new();
+        let mut dynamic_keys = Vec::new();
+        let mut spreads = Vec::new();
+
+        for member in members {
+            match member {
+                StructMember::Field(field) => {
+                    let mut schema = with_annotations(self.type_schema(&field.field_type, module), &field.annotations);
+                    if !field.docs.is_empty() {
+                        schema = extend(schema, [("description", json!(field.docs.join("\n")))]);
+                    }
+                    properties.insert(field.name.to_string(), schema);
+                    if !field.optional {
+                        required.push(json!(field.name));
+                    }
+                }
+                StructMember::DynamicField(dynamic_field) => {
+                    dynamic_keys.push(self.type_schema(&dynamic_field.key_type, module));
+                    let value = self.type_schema(&dynamic_field.value_type, module);
+                    dynamic_values.push(with_annotations(value, &dynamic_field.annotations));
+                }
+                StructMember::Spread(spread) => spreads.extend(self.spread_schema(spread, module)),
+            }
+        }
+
+        let mut schema = Map::new();
+        schema.insert("type".to_string(), json!("object"));
+        schema.insert("properties".to_string(), Value::Object(properties));
+        if !required.is_empty() {
+            schema.insert("required".to_string(), Value::Array(required));
+        }
+        if !dynamic_values.is_empty() {
+            schema.insert("additionalProperties".to_string(), any_of(dynamic_values));
+            let keys = any_of(dynamic_keys);
+            if keys != json!({ "type": "string" }) {
+                schema.insert("propertyNames".to_string(), keys);
+            }
+        }
+        if !spreads.is_empty() {
+            schema.insert("allOf".to_string(), Value::Array(spreads));
+        }
+        Value::Object(schema)
+    }
+
+    /// Schemas the object must also match for a spread: the spread struct, or for
+    /// `...registry[[field]]` the dispatched type of each key of the field
+    fn spread_schema(&mut self, spread: &SpreadExpression<'input>, module: &str) -> Vec<Value> {
+        if let Some(target) = &spread.target {
+            return vec![self.type_schema(target, module)];
+        }
+        let Some(DynamicReferenceType::Field(field)) = spread.dynamic_key.as_ref().map(|key| &key.reference) else {
+            return Vec::new();
+        };
+        if spread.namespace.is_empty() || spread.registry.is_empty() {
+            return Vec::new();
+        }
+
+        let registry = format!("{}:{}", spread.namespace, spread.registry);
+        let mut keys = self.validator.dispatcher_keys(&registry);
+        keys.sort_unstable();
+        keys.dedup();
+        keys.into_iter()
+            .map(|key| {
+                // Keys in the default namespace may be written with or without it
+                let spellings = match key.contains(':') {
+                    true => json!([key]),
+                    false => json!([key, format!("minecraft:{}", key)]),
+                };
+                let mut properties = Map::new();
+                properties.insert(field.to_string(), json!({ "enum": spellings }));
+                json!({
+                    "if": { "properties": properties, "required": [field] },
+                    "then": self.dispatch_schema(&registry, key),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Schema of a built-in type, `None` for names of declarations and for `any`
+fn builtin_schema(type_name: &str) -> Option<Value> {
+    let json_type = match type_name {
+        "string" => "string",
+        "boolean" => "boolean",
+        "byte" | "short" | "int" | "long" => "integer",
+        "float" | "double" => "number",
+        _ => return None,
+    };
+    Some(json!({ "type": json_type }))
+}
+
+fn enum_schema(base_type: Option<&str>, variants: &[EnumVariant]) -> Value {
+    let values: Vec<Value> = variants.iter()
+        .map(|variant| match &variant.value {
+            Some(value) => literal(value),
+            None => json!(variant.name),
+        })
+        .collect();
+    let schema = json!({ "enum": values });
+    match base_type.and_then(builtin_schema) {
+        Some(base) => extend(base, [("enum", schema["enum"].clone())]),
+        None => schema,
+    }
+}
+
+/// Registry of `#[id]` as `x-registry`, field docs aside other annotations have no JSON Schema counterpart
+fn with_annotations(schema: Value, annotations: &[Annotation]) -> Value {
+    let registry = annotations.iter()
+        .find(|annotation| annotation.name == "id")
+        .and_then(|annotation| IdAnnotation::from_data(&annotation.data).registry);
+    match registry {
+        Some(registry) => extend(schema, [("x-registry", json!(registry))]),
+        None => schema,
+    }
+}
+
+/// Range of a number, or of the length of a string
+fn with_constraints(schema: Value, constraints: &TypeConstraints) -> Value {
+    let mut keywords = Vec::new();
+    if schema["type"] == "string" {
+        if let Some(min) = constraints.min {
+            let min = if constraints.min_exclusive { min.floor() + 1.0 } else { min.ceil() };
+            keywords.push(("minLength", number(min.max(0.0))));
+        }
+        if let Some(max) = constraints.max {
+            let max = if constraints.max_exclusive { max.ceil() - 1.0 } else { max.floor() };
+            keywords.push(("maxLength", number(max.max(0.0))));
+        }
+    } else {
+        if let Some(min) = constraints.min {
+            keywords.push((if constraints.min_exclusive { "exclusiveMinimum" } else { "minimum" }, number(min)));
+        }
+        if let Some(max) = constraints.max {
+            keywords.push((if constraints.max_exclusive { "exclusiveMaximum" } else { "maximum" }, number(max)));
+        }
+    }
+    extend(schema, keywords)
+}
+
+/// `schema` with more keywords. Keywords beside a `$ref` are ignored in draft-07,
+/// so a reference is wrapped in `allOf` instead.
+fn extend<'k>(schema: Value, keywords: impl IntoIterator<Item = (&'k str, Value)>) -> Value {
+    let mut schema = match schema {
+        Value::Object(object) if !object.contains_key("$ref") => object,
+        schema => {
+            let mut wrapper = Map::new();
+            wrapper.insert("allOf".to_string(), json!([schema]));
+            wrapper
+        }
+    };
+    for (keyword, value) in keywords {
+        schema.insert(keyword.to_string(), value);
+    }
+    Value::Object(schema)
+}
+
+fn any_of(mut schemas: Vec<Value>) -> Value {
+    if schemas.len() == 1 {
+        schemas.remove(0)
+    } else {
+        json!({ "anyOf": schemas })
+    }
+}
+
+fn reference(definition: &str) -> Value {
+    json!({ "$ref": format!("#/definitions/{}", definition) })
+}
+
+/// Definition name usable in a `$ref` without escaping
+fn definition_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.') { c } else { '_' })
+        .collect()
+}
+
+fn literal(literal_value: &LiteralValue) -> Value {
+    match literal_value {
+        LiteralValue::String(s) => json!(s),
+        LiteralValue::Number(n) => number(*n),
+        LiteralValue::Boolean(b) => json!(b),
+    }
+}
+
+/// Integral numbers written without a fraction
+fn number(value: f64) -> Value {
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        json!(value as i64)
+    } else {
+        json!(value)
+    }
+}
@@ -1,13 +1,15 @@
 //! MCDOC Lexer with zero-copy parsing
 
-use crate::error::ParseError;
+use crate::error::{ParseError, SourcePos};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 /// MCDOC Token with zero-copy reference to the source
 #[derive(Debug, Clone, PartialEq)]
 pub enum Token<'input> {
     Identifier(&'input str),
-    String(&'input str),
+    /// Content of a quoted string with its escapes decoded, borrowed when it has none
+    String(Cow<'input, str>),
     Number(f64),
     True,
     False,
@@ -35,6 +37,12 @@ pub enum Token<'input> {
     Dot,
     DotDotDot,
     DotDot,
+    /// `..<`, range excluding its maximum
+    DotDotLess,
+    /// `<..`, range excluding its minimum
+    LessDotDot,
+    /// `<..<`, range excluding both bounds
+    LessDotDotLess,
     Percent,
     Equal,
     Equals,
@@ -43,6 +51,8 @@ pub enum Token<'input> {
     Annotation(&'input str),
     LineComment(&'input str),
     BlockComment(&'input str),
+    /// `/// text`, the text after the slashes and one optional space
+    DocComment(&'input str),
     Eof,
     Newline,
     Whitespace,
@@ -52,15 +62,68 @@ pub enum Token<'input> {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct Position {
     pub line: u32,
+    /// Column in characters, starting at 1
     pub column: u32,
+    /// Column in UTF-16 code units like editors and LSP count them, starting at 1.
+    /// Characters outside of the BMP, like emoji, take two.
+    #[serde(default)]
+    pub column_utf16: u32,
     pub offset: usize,
 }
 
+impl Position {
+    /// Position right after `ch`, `ch` being at this position
+    fn after(mut self, ch: char) -> Self {
+        self.offset += ch.len_utf8();
+        if ch == '\n' {
+            self.line += 1;
+            self.column = 1;
+            self.column_utf16 = 1;
+        } else {
+            self.column += 1;
+            self.column_utf16 += ch.len_utf16() as u32;
+        }
+        self
+    }
+}
+
+impl From<Position> for SourcePos {
+    fn from(position: Position) -> Self {
+        SourcePos { column_utf16: position.column_utf16, ..SourcePos::new(position.line, position.column, position.offset) }
+    }
+}
+
+/// Range of the source from `start` to `end`, `end` excluded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    pub fn new(start: Position, end: Position) -> Self {
+        Self { start, end }
+    }
+
+    /// Source text covered by the span, empty when it's outside of `source`
+    pub fn slice<'a>(&self, source: &'a str) -> &'a str {
+        source.get(self.start.offset..self.end.offset).unwrap_or("")
+    }
+}
+
 /// Token with position in the source
 #[derive(Debug, Clone, PartialEq)]
 pub struct TokenWithPos<'input> {
     pub token: Token<'input>,
     pub position: Position,
+    /// Position right after the token
+    pub end: Position,
+}
+
+impl TokenWithPos<'_> {
+    pub fn span(&self) -> Span {
+        Span::new(self.position, self.end)
+    }
 }
 
 /// MCDOC Lexer with zero-copy
@@ -82,7 +145,7 @@ impl<'input> Lexer<'input> {
         Self {
             input,
             chars,
-            current_pos: Position { line: 1, column: 1, offset: 0 },
+            current_pos: Position { line: 1, column: 1, column_utf16: 1, offset: 0 },
             current_char,
             peek_char,
         }
@@ -91,14 +154,7 @@ impl<'input> Lexer<'input> {
     /// Advance one character
     fn advance(&mut self) {
         if let Some(ch) = self.current_char {
-            self.current_pos.offset += ch.len_utf8();
-            
-            if ch == '\n' {
-                self.current_pos.line += 1;
-                self.current_pos.column = 1;
-            } else {
-                self.current_pos.column += 1;
-            }
+            self.current_pos = self.current_pos.after(ch);
         }
         
         self.current_char = self.peek_char;
@@ -110,6 +166,14 @@ impl<'input> Lexer<'input> {
         self.peek_char
     }
     
+    /// Look at the character after the peeked one
+    fn peek_second(&self) -> Option<char> {
+        let peek_len = self.peek_char.map_or(0, char::len_utf8);
+        self.input.get(self.current_pos.offset + self.current_char.map_or(0, char::len_utf8) + peek_len..)?
+            .chars()
+            .next()
+    }
+    
     /// Skip whitespace and comments
     fn skip_whitespace_and_comments(&mut self) -> Result<(), ParseError> {
         while let Some(ch) = self.current_char {
@@ -118,7 +182,8 @@ impl<'input> Lexer<'input> {
                 '\n' => {
                     break;
                 }
-                '/' if self.peek() == Some('/') => {
+                // Doc comments (`///`) are emitted as tokens
+                '/' if self.peek() == Some('/') && self.peek_second() != Some('/') => {
                     while self.current_char.is_some() && self.current_char != Some('\n') {
                         self.advance();
                     }
@@ -145,7 +210,7 @@ impl<'input> Lexer<'input> {
                     if depth > 0 {
                         return Err(ParseError::lexer(
                             "Unterminated block comment", 
-                            crate::error::SourcePos::new(self.current_pos.line, self.current_pos.column)
+                            self.current_pos.into()
                         ));
                     }
                 }
@@ -212,23 +277,23 @@ impl<'input> Lexer<'input> {
         number_str.parse().map_err(|_| {
             ParseError::lexer(
                 format!("Invalid number format: {}", number_str),
-                crate::error::SourcePos::new(self.current_pos.line, self.current_pos.column)
+                self.current_pos.into()
             )
         })
     }
     
-    /// Read a string literal
-    fn read_string(&mut self) -> Result<&'input str, ParseError> {
+    /// Read a string literal, decoding its escapes
+    fn read_string(&mut self) -> Result<Cow<'input, str>, ParseError> {
         let quote_char = self.current_char.unwrap();
         self.advance();
         
-        let start_offset = self.current_pos.offset;
+        let start = self.current_pos;
         
         while let Some(ch) = self.current_char {
             if ch == quote_char {
-                let string_content = &self.input[start_offset..self.current_pos.offset];
+                let string_content = &self.input[start.offset..self.current_pos.offset];
                 self.advance();
-                return Ok(string_content);
+                return unescape(string_content).map_err(|error| Self::escape_error(start, string_content, error));
             } else if ch == '\\' {
                 self.advance();
                 if self.current_char.is_some() {
@@ -241,7 +306,7 @@ impl<'input> Lexer<'input> {
         
         Err(ParseError::lexer(
             "Unterminated string literal",
-            crate::error::SourcePos::new(self.current_pos.line, self.current_pos.column)
+            self.current_pos.into()
         ))
     }
     
@@ -253,17 +318,31 @@ impl<'input> Lexer<'input> {
         if self.current_char != Some('[') {
             return Err(ParseError::lexer(
                 "Expected '[' after '#' in annotation",
-                crate::error::SourcePos::new(self.current_pos.line, self.current_pos.column)
+                self.current_pos.into()
             ));
         }
         
         self.advance();
         
+        // Brackets inside quoted values like #[match_regex="^[a-z]"] don't count
         let mut bracket_depth = 1;
+        let mut string_start: Option<Position> = None;
         while bracket_depth > 0 && self.current_char.is_some() {
+            let in_string = string_start.is_some();
             match self.current_char {
-                Some('[') => bracket_depth += 1,
-                Some(']') => bracket_depth -= 1,
+                Some('\\') if in_string => self.advance(),
+                // Values are decoded when the annotation is parsed, check their escapes now
+                Some('"') => match string_start.take() {
+                    Some(start) => {
+                        let value = &self.input[start.offset..self.current_pos.offset];
+                        unescape(value).map_err(|error| Self::escape_error(start, value, error))?;
+                    }
+                    None => {
+                        string_start = Some(self.current_pos.after('"'));
+                    }
+                },
+                Some('[') if !in_string => bracket_depth += 1,
+                Some(']') if !in_string => bracket_depth -= 1,
                 _ => {}
             }
             self.advance();
@@ -272,15 +351,35 @@ impl<'input> Lexer<'input> {
         if bracket_depth > 0 {
             return Err(ParseError::lexer(
                 "Unterminated annotation",
-                crate::error::SourcePos::new(self.current_pos.line, self.current_pos.column)
+                self.current_pos.into()
             ));
         }
         
         Ok(&self.input[start_offset..self.current_pos.offset])
     }
     
+    /// Lexer error for an invalid escape at `offset` in the string `content` starting at `start`
+    fn escape_error(start: Position, content: &str, error: EscapeError) -> ParseError {
+        let position = content[..error.offset].chars().fold(start, Position::after);
+        ParseError::lexer(error.message, position.into())
+    }
+
+    /// Read a `///` doc comment up to the end of the line
+    fn read_doc_comment(&mut self) -> &'input str {
+        for _ in 0..3 {
+            self.advance();
+        }
+        let start_offset = self.current_pos.offset;
+        while self.current_char.is_some() && self.current_char != Some('\n') {
+            self.advance();
+        }
+
+        let text = self.input[start_offset..self.current_pos.offset].trim_end();
+        text.strip_prefix(' ').unwrap_or(text)
+    }
+    
     /// Determine the token type for an identifier
-    fn identifier_to_token(ident: &str) -> Token {
+    fn identifier_to_token(ident: &str) -> Token<'_> {
         match ident {
             "use" => Token::Use,
             "struct" => Token::Struct,
@@ -304,6 +403,7 @@ impl<'input> Lexer<'input> {
         let token = match self.current_char {
             None => Token::Eof,
             Some('\n') => { self.advance(); Token::Newline }
+            Some('/') if self.peek() == Some('/') => Token::DocComment(self.read_doc_comment()),
             Some('(') => { self.advance(); Token::LeftParen }
             Some(')') => { self.advance(); Token::RightParen }
             Some('{') => { self.advance(); Token::LeftBrace }
@@ -317,6 +417,18 @@ impl<'input> Lexer<'input> {
             Some('@') => { self.advance(); Token::At }
             Some('%') => { self.advance(); Token::Percent }
             Some('=') => { self.advance(); Token::Equal }
+            Some('<') if self.peek() == Some('.') && self.peek_second() == Some('.') => {
+                // `<..` or `<..<`
+                self.advance();
+                self.advance();
+                self.advance();
+                if self.current_char == Some('<') {
+                    self.advance();
+                    Token::LessDotDotLess
+                } else {
+                    Token::LessDotDot
+                }
+            }
             Some('<') => { self.advance(); Token::Less }
             Some('>') => { self.advance(); Token::Greater }
             Some(':') => {
@@ -335,6 +447,9 @@ impl<'input> Lexer<'input> {
                     if self.current_char == Some('.') {
                         self.advance();
                         Token::DotDotDot
+                    } else if self.current_char == Some('<') {
+                        self.advance();
+                        Token::DotDotLess
                     } else {
                         Token::DotDot
                     }
@@ -349,15 +464,20 @@ impl<'input> Lexer<'input> {
                 Token::String(self.read_string()?)
             }
             Some('-') => {
-                // Handle negative numbers: -42, -3.14, -.5
-                if self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.') {
+                // Handle negative numbers: -42, -3.14, -.5 (but not "-..")
+                let starts_number = match self.peek() {
+                    Some(c) if c.is_ascii_digit() => true,
+                    Some('.') => self.peek_second().is_some_and(|c| c.is_ascii_digit()),
+                    _ => false,
+                };
+                if starts_number {
                     self.advance(); // consume '-'
                     let number = self.read_number()?;
                     Token::Number(-number)
                 } else {
                     return Err(ParseError::lexer(
                         format!("Unexpected character: '{}'", '-'),
-                        crate::error::SourcePos::new(pos.line, pos.column)
+                        pos.into()
                     ));
                 }
             }
@@ -371,12 +491,12 @@ impl<'input> Lexer<'input> {
             Some(ch) => {
                 return Err(ParseError::lexer(
                     format!("Unexpected character: '{}'", ch),
-                    crate::error::SourcePos::new(pos.line, pos.column)
+                    pos.into()
                 ));
             }
         };
         
-        Ok(TokenWithPos { token, position: pos })
+        Ok(TokenWithPos { token, position: pos, end: self.current_pos })
     }
     
     /// Tokenize the entire file
@@ -395,4 +515,87 @@ impl<'input> Lexer<'input> {
         
         Ok(tokens)
     }
-} 
\ No newline at end of file
+}
+
+/// Escape sequence that can't be decoded, at a byte offset of the string content
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscapeError {
+    pub offset: usize,
+    pub message: String,
+}
+
+/// Decode the JSON-style escapes of a string's content: `\"`, `\'`, `\\`, `\/`, `\b`,
+/// `\f`, `\n`, `\r`, `\t`, `\u0041` (surrogate pairs included) and `\u{1F600}`.
+/// The content is borrowed as is when it has no escape.
+pub fn unescape(content: &str) -> Result<Cow<'_, str>, EscapeError> {
+    let Some(first_escape) = content.find('\\') else {
+        return Ok(Cow::Borrowed(content));
+    };
+
+    let mut decoded = String::with_capacity(content.len());
+    decoded.push_str(&content[..first_escape]);
+    let mut rest = &content[first_escape..];
+    while let Some(escape) = rest.find('\\') {
+        decoded.push_str(&rest[..escape]);
+        let offset = content.len() - rest.len() + escape;
+        let error = |message: String| EscapeError { offset, message };
+        let sequence = &rest[escape + 1..];
+
+        let (ch, length) = match sequence.chars().next() {
+            Some('"') => ('"', 1),
+            Some('\'') => ('\'', 1),
+            Some('\\') => ('\\', 1),
+            Some('/') => ('/', 1),
+            Some('b') => ('\u{8}', 1),
+            Some('f') => ('\u{c}', 1),
+            Some('n') => ('\n', 1),
+            Some('r') => ('\r', 1),
+            Some('t') => ('\t', 1),
+            Some('u') => decode_unicode_escape(&sequence[1..])
+                .map(|(ch, length)| (ch, length + 1))
+                .map_err(|reason| error(format!("Invalid unicode escape '\\{}': {}", escape_text(sequence), reason)))?,
+            Some(other) => return Err(error(format!("Invalid escape sequence '\\{}'", other))),
+            None => return Err(error("Unterminated escape sequence".to_string())),
+        };
+        decoded.push(ch);
+        rest = &sequence[length..];
+    }
+    decoded.push_str(rest);
+    Ok(Cow::Owned(decoded))
+}
+
+/// Character of a `\u` escape from the text after the `u`, with the length of that text
+fn decode_unicode_escape(text: &str) -> Result<(char, usize), &'static str> {
+    if let Some(braced) = text.strip_prefix('{') {
+        let end = braced.find('}').ok_or("missing '}'")?;
+        let digits = &braced[..end];
+        if digits.is_empty() || digits.len() > 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err("expected 1 to 6 hex digits");
+        }
+        let code = u32::from_str_radix(digits, 16).map_err(|_| "expected 1 to 6 hex digits")?;
+        return char::from_u32(code).map(|ch| (ch, end + 2)).ok_or("not a unicode scalar value");
+    }
+
+    let high = hex4(text).ok_or("expected 4 hex digits")?;
+    if !(0xD800..0xDC00).contains(&high) {
+        return char::from_u32(high).map(|ch| (ch, 4)).ok_or("lone low surrogate");
+    }
+    // A high surrogate must be followed by the low one of its pair
+    let low = text[4..].strip_prefix("\\u").and_then(hex4).filter(|low| (0xDC00..0xE000).contains(low)).ok_or("lone high surrogate")?;
+    let code = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+    char::from_u32(code).map(|ch| (ch, 10)).ok_or("not a unicode scalar value")
+}
+
+fn hex4(text: &str) -> Option<u32> {
+    let digits = text.get(..4)?;
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(digits, 16).ok()
+}
+
+/// The start of an escape for error messages, `u` and what follows up to 6 characters
+fn escape_text(sequence: &str) -> &str {
+    let end = sequence.char_indices().nth(7).map_or(sequence.len(), |(index, _)| index);
+    &sequence[..end]
+}
@@ -1,27 +1,44 @@
 //! Voxel RSMCDOC - MCDOC Parser in Rust
 
+pub mod annotation;
+pub mod check;
+pub mod diagnostics;
 pub mod lexer;
+pub mod owned;
 pub mod parser;
 pub mod error;
+pub mod format;
+pub mod graph;
+pub mod inspect;
+mod json_schema;
+pub mod printer;
 pub mod types;
 pub mod registry;
+pub mod resource_type;
+pub mod schema;
 pub mod validator;
+pub mod version;
+
+#[cfg(feature = "bundle")]
+pub mod bundle;
 
 #[cfg(feature = "wasm")]
 pub mod wasm;
 
 // Main re-exports for compatibility
-pub use error::{ParseError, SourcePos, ErrorType};
+pub use error::{ParseError, SourcePos, ErrorType, ErrorCode, Severity};
 pub use parser::{Parser, McDocFile, Declaration, StructDeclaration, FieldDeclaration, TypeExpression}; 
-pub use lexer::{Lexer, Token, TokenWithPos, Position};
+pub use lexer::{Lexer, Token, TokenWithPos, Position, Span};
 pub use types::*;
 pub use registry::Registry;
-pub use validator::DatapackValidator;
+pub use resource_type::ResourceTypeResolver;
+pub use validator::{DatapackValidator, ValidationOptions};
+pub use owned::{parse_mcdoc_owned, McDocFileOwned};
 
 use std::fmt;
 
 /// Main entry point to parse an MCDOC file
-pub fn parse_mcdoc(input: &str) -> Result<McDocFile, Vec<ParseError>> {
+pub fn parse_mcdoc(input: &str) -> Result<McDocFile<'_>, Vec<ParseError>> {
     let mut lexer = Lexer::new(input);
     let tokens = lexer.tokenize().map_err(|e| vec![e])?;
     
@@ -29,6 +46,19 @@ pub fn parse_mcdoc(input: &str) -> Result<McDocFile, Vec<ParseError>> {
     parser.parse()
 }
 
+/// Parse an MCDOC file as far as possible, returning the declarations that parsed
+/// along with every error. A lexer error leaves the file empty.
+pub fn parse_mcdoc_lenient(input: &str) -> (McDocFile<'_>, Vec<ParseError>) {
+    let mut lexer = Lexer::new(input);
+    let tokens = match lexer.tokenize() {
+        Ok(tokens) => tokens,
+        Err(e) => return (McDocFile { imports: Vec::new(), declarations: Vec::new() }, vec![e]),
+    };
+    
+    let mut parser = Parser::new(tokens);
+    parser.parse_lenient()
+}
+
 /// Resource identifier for Minecraft resources (e.g., "minecraft:diamond_sword")
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ResourceId {
@@ -42,31 +72,51 @@ impl ResourceId {
         Self::parse_with_default_namespace(input, None)
     }
     
-    /// Parse with optional default namespace
+    /// Parse with optional default namespace.
+    ///
+    /// The namespace may only contain `[a-z0-9_.-]` and the path `[a-z0-9_./-]`,
+    /// the path can't be empty. Without a namespace and a default one, the namespace is empty.
     pub fn parse_with_default_namespace(input: &str, default_namespace: Option<&str>) -> Result<Self, ParseError> {
-        let parts: Vec<&str> = input.split(':').collect();
+        let invalid = |reason: String| ParseError::InvalidResourceId { id: input.to_string(), reason };
+        let (namespace, path) = match input.split_once(':') {
+            Some((namespace, path)) => (namespace, path),
+            None => (default_namespace.unwrap_or(""), input),
+        };
         
-        match parts.as_slice() {
-            [namespace, path] => Ok(ResourceId {
-                namespace: namespace.to_string(),
-                path: path.to_string(),
-            }),
-            [path] => {
-                match default_namespace {
-                    Some(ns) => Ok(ResourceId {
-                        namespace: ns.to_string(),
-                        path: path.to_string(),
-                    }),
-                    None => Ok(ResourceId {
-                        namespace: String::new(),
-                        path: path.to_string(),
-                    }),
-                }
-            },
-            _ => Err(ParseError::InvalidResourceId(input.to_string())),
+        if path.contains(':') {
+            return Err(invalid("more than one ':' separator".to_string()));
+        }
+        if input.contains(':') && namespace.is_empty() {
+            return Err(invalid("empty namespace".to_string()));
+        }
+        if path.is_empty() {
+            return Err(invalid("empty path".to_string()));
         }
+        if let Some(c) = namespace.chars().find(|&c| !Self::is_namespace_char(c)) {
+            return Err(invalid(format!("invalid character '{}' in namespace '{}', expected [a-z0-9_.-]", c, namespace)));
         }
+        if let Some(c) = path.chars().find(|&c| !Self::is_path_char(c)) {
+            return Err(invalid(format!("invalid character '{}' in path '{}', expected [a-z0-9_./-]", c, path)));
+        }
+        
+        Ok(ResourceId {
+            namespace: namespace.to_string(),
+            path: path.to_string(),
+        })
+    }
+
+    /// Whether a string is a valid resource identifier, or a tag written `#namespace:path`
+    pub fn is_valid(input: &str) -> bool {
+        Self::parse(input.strip_prefix('#').unwrap_or(input)).is_ok()
+    }
+
+    fn is_namespace_char(c: char) -> bool {
+        matches!(c, 'a'..='z' | '0'..='9' | '_' | '.' | '-')
+    }
 
+    fn is_path_char(c: char) -> bool {
+        Self::is_namespace_char(c) || c == '/'
+    }
 }
 
 impl fmt::Display for ResourceId {
@@ -75,10 +125,29 @@ impl fmt::Display for ResourceId {
     }
 }
 
-/// Registry dependency for dynamic loading
+/// Registry dependency for dynamic loading, found by the heuristic pre-scan
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RegistryDependency {
     pub registry: String,
     pub identifier: String,
     pub is_tag: bool,
+    /// Path in the source JSON (e.g., "result.id", "ingredients[1]")
+    pub source_path: String,
+    /// JSON Pointer to the value (e.g., "/result/id", "/ingredients/1")
+    pub pointer: String,
+}
+
+impl From<RegistryDependency> for McDocDependency {
+    fn from(dependency: RegistryDependency) -> Self {
+        McDocDependency {
+            resource_location: dependency.identifier.into(),
+            registry_type: dependency.registry.into(),
+            source_path: dependency.source_path,
+            pointer: dependency.pointer,
+            source_file: None,
+            is_tag: dependency.is_tag,
+            dependency_kind: DependencyKind::Resource,
+            count: 1,
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,870 @@
+//! Owned mirror of the MCDOC AST
+//!
+//! The parser's AST borrows from the source text, so it can't outlive it. These
+//! types hold `String`s instead, with the same structure, and can be serialized:
+//! parse the schemas once, store the result, and load it into a validator later
+//! without the sources. `to_borrowed` gives back the AST the validator works on.
+
+use crate::error::ParseError;
+use crate::lexer::Position;
+use crate::parser::{
+    Annotation, AnnotationData, AnnotationValue, ArrayConstraints, Declaration, DispatchDeclaration,
+    DispatchSource, DispatchTarget, DynamicFieldDeclaration, DynamicReference, DynamicReferenceType,
+    EnumDeclaration, EnumVariant, FieldDeclaration, ImportPath, ImportStatement, LiteralValue, McDocFile,
+    PathSegment, SpreadExpression, StructDeclaration, StructMember, TypeConstraints, TypeDeclaration,
+    TypeExpression,
+};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+/// Parse an MCDOC file into an AST that doesn't borrow from `input`
+pub fn parse_mcdoc_owned(input: &str) -> Result<McDocFileOwned, Vec<ParseError>> {
+    crate::parse_mcdoc(input).map(McDocFileOwned::from)
+}
+
+fn owned_strs(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+fn borrowed_strs(values: &[String]) -> Vec<&str> {
+    values.iter().map(String::as_str).collect()
+}
+
+/// Owned `McDocFile`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McDocFileOwned {
+    pub imports: Vec<ImportStatementOwned>,
+    pub declarations: Vec<DeclarationOwned>,
+}
+
+impl McDocFileOwned {
+    /// The AST borrowing from this one
+    pub fn to_borrowed(&self) -> McDocFile<'_> {
+        McDocFile {
+            imports: self.imports.iter().map(ImportStatementOwned::to_borrowed).collect(),
+            declarations: self.declarations.iter().map(DeclarationOwned::to_borrowed).collect(),
+        }
+    }
+}
+
+impl From<&McDocFile<'_>> for McDocFileOwned {
+    fn from(file: &McDocFile<'_>) -> Self {
+        Self {
+            imports: file.imports.iter().map(ImportStatementOwned::from).collect(),
+            declarations: file.declarations.iter().map(DeclarationOwned::from).collect(),
+        }
+    }
+}
+
+impl From<McDocFile<'_>> for McDocFileOwned {
+    fn from(file: McDocFile<'_>) -> Self {
+        Self::from(&file)
+    }
+}
+
+/// Owned `ImportStatement`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportStatementOwned {
+    pub path: ImportPathOwned,
+    pub alias: Option<String>,
+    pub position: Position,
+}
+
+impl ImportStatementOwned {
+    pub fn to_borrowed(&self) -> ImportStatement<'_> {
+        ImportStatement {
+            path: self.path.to_borrowed(),
+            alias: self.alias.as_deref(),
+            position: self.position,
+        }
+    }
+}
+
+impl From<&ImportStatement<'_>> for ImportStatementOwned {
+    fn from(import: &ImportStatement<'_>) -> Self {
+        Self {
+            path: ImportPathOwned::from(&import.path),
+            alias: import.alias.map(str::to_string),
+            position: import.position,
+        }
+    }
+}
+
+/// Owned `ImportPath`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ImportPathOwned {
+    Absolute(Vec<String>),
+    Relative {
+        supers: usize,
+        segments: Vec<String>,
+    },
+}
+
+impl ImportPathOwned {
+    pub fn to_borrowed(&self) -> ImportPath<'_> {
+        match self {
+            ImportPathOwned::Absolute(segments) => ImportPath::Absolute(borrowed_strs(segments)),
+            ImportPathOwned::Relative { supers, segments } => ImportPath::Relative { supers: *supers, segments: borrowed_strs(segments) },
+        }
+    }
+}
+
+impl From<&ImportPath<'_>> for ImportPathOwned {
+    fn from(path: &ImportPath<'_>) -> Self {
+        match path {
+            ImportPath::Absolute(segments) => ImportPathOwned::Absolute(owned_strs(segments)),
+            ImportPath::Relative { supers, segments } => ImportPathOwned::Relative { supers: *supers, segments: owned_strs(segments) },
+        }
+    }
+}
+
+/// Owned `Declaration`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DeclarationOwned {
+    Struct(StructDeclarationOwned),
+    Enum(EnumDeclarationOwned),
+    Type(TypeDeclarationOwned),
+    Dispatch(DispatchDeclarationOwned),
+}
+
+impl DeclarationOwned {
+    pub fn to_borrowed(&self) -> Declaration<'_> {
+        match self {
+            DeclarationOwned::Struct(declaration) => Declaration::Struct(declaration.to_borrowed()),
+            DeclarationOwned::Enum(declaration) => Declaration::Enum(declaration.to_borrowed()),
+            DeclarationOwned::Type(declaration) => Declaration::Type(declaration.to_borrowed()),
+            DeclarationOwned::Dispatch(declaration) => Declaration::Dispatch(declaration.to_borrowed()),
+        }
+    }
+}
+
+impl From<&Declaration<'_>> for DeclarationOwned {
+    fn from(declaration: &Declaration<'_>) -> Self {
+        match declaration {
+            Declaration::Struct(declaration) => DeclarationOwned::Struct(declaration.into()),
+            Declaration::Enum(declaration) => DeclarationOwned::Enum(declaration.into()),
+            Declaration::Type(declaration) => DeclarationOwned::Type(declaration.into()),
+            Declaration::Dispatch(declaration) => DeclarationOwned::Dispatch(declaration.into()),
+        }
+    }
+}
+
+/// Owned `Annotation`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnnotationOwned {
+    pub name: String,
+    pub data: AnnotationDataOwned,
+    pub position: Position,
+    pub end: Position,
+}
+
+impl AnnotationOwned {
+    pub fn to_borrowed(&self) -> Annotation<'_> {
+        Annotation {
+            name: &self.name,
+            data: self.data.to_borrowed(),
+            position: self.position,
+            end: self.end,
+        }
+    }
+}
+
+impl From<&Annotation<'_>> for AnnotationOwned {
+    fn from(annotation: &Annotation<'_>) -> Self {
+        Self {
+            name: annotation.name.to_string(),
+            data: AnnotationDataOwned::from(&annotation.data),
+            position: annotation.position,
+            end: annotation.end,
+        }
+    }
+}
+
+fn owned_annotations(annotations: &[Annotation<'_>]) -> Vec<AnnotationOwned> {
+    annotations.iter().map(AnnotationOwned::from).collect()
+}
+
+fn borrowed_annotations(annotations: &[AnnotationOwned]) -> Vec<Annotation<'_>> {
+    annotations.iter().map(AnnotationOwned::to_borrowed).collect()
+}
+
+/// Owned `AnnotationData`, complex arguments are sorted by key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AnnotationDataOwned {
+    Simple(String),
+    Complex(BTreeMap<String, AnnotationValueOwned>),
+    List(Vec<AnnotationValueOwned>),
+    Empty,
+}
+
+impl AnnotationDataOwned {
+    pub fn to_borrowed(&self) -> AnnotationData<'_> {
+        match self {
+            AnnotationDataOwned::Simple(value) => AnnotationData::Simple(Cow::Borrowed(value)),
+            AnnotationDataOwned::Complex(map) => AnnotationData::Complex(
+                map.iter().map(|(key, value)| (key.as_str(), value.to_borrowed())).collect(),
+            ),
+            AnnotationDataOwned::List(values) => AnnotationData::List(values.iter().map(AnnotationValueOwned::to_borrowed).collect()),
+            AnnotationDataOwned::Empty => AnnotationData::Empty,
+        }
+    }
+}
+
+impl From<&AnnotationData<'_>> for AnnotationDataOwned {
+    fn from(data: &AnnotationData<'_>) -> Self {
+        match data {
+            AnnotationData::Simple(value) => AnnotationDataOwned::Simple(value.to_string()),
+            AnnotationData::Complex(map) => AnnotationDataOwned::Complex(
+                map.iter().map(|(key, value)| (key.to_string(), value.into())).collect(),
+            ),
+            AnnotationData::List(values) => AnnotationDataOwned::List(values.iter().map(AnnotationValueOwned::from).collect()),
+            AnnotationData::Empty => AnnotationDataOwned::Empty,
+        }
+    }
+}
+
+/// Owned `AnnotationValue`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AnnotationValueOwned {
+    String(String),
+    Number(f64),
+    Boolean(bool),
+    List(Vec<AnnotationValueOwned>),
+}
+
+impl AnnotationValueOwned {
+    pub fn to_borrowed(&self) -> AnnotationValue<'_> {
+        match self {
+            AnnotationValueOwned::String(value) => AnnotationValue::String(Cow::Borrowed(value)),
+            AnnotationValueOwned::Number(number) => AnnotationValue::Number(*number),
+            AnnotationValueOwned::Boolean(value) => AnnotationValue::Boolean(*value),
+            AnnotationValueOwned::List(values) => AnnotationValue::List(values.iter().map(Self::to_borrowed).collect()),
+        }
+    }
+}
+
+impl From<&AnnotationValue<'_>> for AnnotationValueOwned {
+    fn from(value: &AnnotationValue<'_>) -> Self {
+        match value {
+            AnnotationValue::String(value) => AnnotationValueOwned::String(value.to_string()),
+            AnnotationValue::Number(number) => AnnotationValueOwned::Number(*number),
+            AnnotationValue::Boolean(value) => AnnotationValueOwned::Boolean(*value),
+            AnnotationValue::List(values) => AnnotationValueOwned::List(values.iter().map(Self::from).collect()),
+        }
+    }
+}
+
+/// Owned `StructDeclaration`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructDeclarationOwned {
+    pub name: String,
+    pub members: Vec<StructMemberOwned>,
+    pub annotations: Vec<AnnotationOwned>,
+    pub docs: Vec<String>,
+    pub position: Position,
+    pub end: Position,
+}
+
+impl StructDeclarationOwned {
+    pub fn to_borrowed(&self) -> StructDeclaration<'_> {
+        StructDeclaration {
+            name: &self.name,
+            members: borrowed_members(&self.members),
+            annotations: borrowed_annotations(&self.annotations),
+            docs: borrowed_strs(&self.docs),
+            position: self.position,
+            end: self.end,
+        }
+    }
+}
+
+impl From<&StructDeclaration<'_>> for StructDeclarationOwned {
+    fn from(declaration: &StructDeclaration<'_>) -> Self {
+        Self {
+            name: declaration.name.to_string(),
+            members: owned_members(&declaration.members),
+            annotations: owned_annotations(&declaration.annotations),
+            docs: owned_strs(&declaration.docs),
+            position: declaration.position,
+            end: declaration.end,
+        }
+    }
+}
+
+/// Owned `FieldDeclaration`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldDeclarationOwned {
+    pub name: String,
+    pub field_type: TypeExpressionOwned,
+    pub optional: bool,
+    pub annotations: Vec<AnnotationOwned>,
+    pub docs: Vec<String>,
+    pub position: Position,
+    pub end: Position,
+}
+
+impl FieldDeclarationOwned {
+    pub fn to_borrowed(&self) -> FieldDeclaration<'_> {
+        FieldDeclaration {
+            name: &self.name,
+            field_type: self.field_type.to_borrowed(),
+            optional: self.optional,
+            annotations: borrowed_annotations(&self.annotations),
+            docs: borrowed_strs(&self.docs),
+            position: self.position,
+            end: self.end,
+        }
+    }
+}
+
+impl From<&FieldDeclaration<'_>> for FieldDeclarationOwned {
+    fn from(field: &FieldDeclaration<'_>) -> Self {
+        Self {
+            name: field.name.to_string(),
+            field_type: TypeExpressionOwned::from(&field.field_type),
+            optional: field.optional,
+            annotations: owned_annotations(&field.annotations),
+            docs: owned_strs(&field.docs),
+            position: field.position,
+            end: field.end,
+        }
+    }
+}
+
+/// Owned `StructMember`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum StructMemberOwned {
+    Field(FieldDeclarationOwned),
+    DynamicField(DynamicFieldDeclarationOwned),
+    Spread(SpreadExpressionOwned),
+}
+
+impl StructMemberOwned {
+    pub fn to_borrowed(&self) -> StructMember<'_> {
+        match self {
+            StructMemberOwned::Field(field) => StructMember::Field(field.to_borrowed()),
+            StructMemberOwned::DynamicField(field) => StructMember::DynamicField(field.to_borrowed()),
+            StructMemberOwned::Spread(spread) => StructMember::Spread(spread.to_borrowed()),
+        }
+    }
+}
+
+impl From<&StructMember<'_>> for StructMemberOwned {
+    fn from(member: &StructMember<'_>) -> Self {
+        match member {
+            StructMember::Field(field) => StructMemberOwned::Field(field.into()),
+            StructMember::DynamicField(field) => StructMemberOwned::DynamicField(field.into()),
+            StructMember::Spread(spread) => StructMemberOwned::Spread(spread.into()),
+        }
+    }
+}
+
+fn owned_members(members: &[StructMember<'_>]) -> Vec<StructMemberOwned> {
+    members.iter().map(StructMemberOwned::from).collect()
+}
+
+fn borrowed_members(members: &[StructMemberOwned]) -> Vec<StructMember<'_>> {
+    members.iter().map(StructMemberOwned::to_borrowed).collect()
+}
+
+/// Owned `DynamicFieldDeclaration`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DynamicFieldDeclarationOwned {
+    pub key_type: TypeExpressionOwned,
+    pub value_type: TypeExpressionOwned,
+    pub optional: bool,
+    pub annotations: Vec<AnnotationOwned>,
+    pub position: Position,
+}
+
+impl DynamicFieldDeclarationOwned {
+    pub fn to_borrowed(&self) -> DynamicFieldDeclaration<'_> {
+        DynamicFieldDeclaration {
+            key_type: self.key_type.to_borrowed(),
+            value_type: self.value_type.to_borrowed(),
+            optional: self.optional,
+            annotations: borrowed_annotations(&self.annotations),
+            position: self.position,
+        }
+    }
+}
+
+impl From<&DynamicFieldDeclaration<'_>> for DynamicFieldDeclarationOwned {
+    fn from(field: &DynamicFieldDeclaration<'_>) -> Self {
+        Self {
+            key_type: TypeExpressionOwned::from(&field.key_type),
+            value_type: TypeExpressionOwned::from(&field.value_type),
+            optional: field.optional,
+            annotations: owned_annotations(&field.annotations),
+            position: field.position,
+        }
+    }
+}
+
+/// Owned `EnumDeclaration`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnumDeclarationOwned {
+    pub name: String,
+    pub base_type: Option<String>,
+    pub variants: Vec<EnumVariantOwned>,
+    pub annotations: Vec<AnnotationOwned>,
+    pub docs: Vec<String>,
+    pub position: Position,
+}
+
+impl EnumDeclarationOwned {
+    pub fn to_borrowed(&self) -> EnumDeclaration<'_> {
+        EnumDeclaration {
+            name: &self.name,
+            base_type: self.base_type.as_deref(),
+            variants: borrowed_variants(&self.variants),
+            annotations: borrowed_annotations(&self.annotations),
+            docs: borrowed_strs(&self.docs),
+            position: self.position,
+        }
+    }
+}
+
+impl From<&EnumDeclaration<'_>> for EnumDeclarationOwned {
+    fn from(declaration: &EnumDeclaration<'_>) -> Self {
+        Self {
+            name: declaration.name.to_string(),
+            base_type: declaration.base_type.map(str::to_string),
+            variants: owned_variants(&declaration.variants),
+            annotations: owned_annotations(&declaration.annotations),
+            docs: owned_strs(&declaration.docs),
+            position: declaration.position,
+        }
+    }
+}
+
+/// Owned `EnumVariant`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnumVariantOwned {
+    pub name: String,
+    pub value: Option<LiteralValueOwned>,
+    pub annotations: Vec<AnnotationOwned>,
+    pub docs: Vec<String>,
+    pub position: Position,
+}
+
+impl EnumVariantOwned {
+    pub fn to_borrowed(&self) -> EnumVariant<'_> {
+        EnumVariant {
+            name: &self.name,
+            value: self.value.as_ref().map(LiteralValueOwned::to_borrowed),
+            annotations: borrowed_annotations(&self.annotations),
+            docs: borrowed_strs(&self.docs),
+            position: self.position,
+        }
+    }
+}
+
+impl From<&EnumVariant<'_>> for EnumVariantOwned {
+    fn from(variant: &EnumVariant<'_>) -> Self {
+        Self {
+            name: variant.name.to_string(),
+            value: variant.value.as_ref().map(LiteralValueOwned::from),
+            annotations: owned_annotations(&variant.annotations),
+            docs: owned_strs(&variant.docs),
+            position: variant.position,
+        }
+    }
+}
+
+fn owned_variants(variants: &[EnumVariant<'_>]) -> Vec<EnumVariantOwned> {
+    variants.iter().map(EnumVariantOwned::from).collect()
+}
+
+fn borrowed_variants(variants: &[EnumVariantOwned]) -> Vec<EnumVariant<'_>> {
+    variants.iter().map(EnumVariantOwned::to_borrowed).collect()
+}
+
+/// Owned `TypeDeclaration`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeDeclarationOwned {
+    pub name: String,
+    pub type_params: Vec<String>,
+    pub type_expr: TypeExpressionOwned,
+    pub annotations: Vec<AnnotationOwned>,
+    pub docs: Vec<String>,
+    pub position: Position,
+}
+
+impl TypeDeclarationOwned {
+    pub fn to_borrowed(&self) -> TypeDeclaration<'_> {
+        TypeDeclaration {
+            name: &self.name,
+            type_params: borrowed_strs(&self.type_params),
+            type_expr: self.type_expr.to_borrowed(),
+            annotations: borrowed_annotations(&self.annotations),
+            docs: borrowed_strs(&self.docs),
+            position: self.position,
+        }
+    }
+}
+
+impl From<&TypeDeclaration<'_>> for TypeDeclarationOwned {
+    fn from(declaration: &TypeDeclaration<'_>) -> Self {
+        Self {
+            name: declaration.name.to_string(),
+            type_params: owned_strs(&declaration.type_params),
+            type_expr: TypeExpressionOwned::from(&declaration.type_expr),
+            annotations: owned_annotations(&declaration.annotations),
+            docs: owned_strs(&declaration.docs),
+            position: declaration.position,
+        }
+    }
+}
+
+/// Owned `DispatchDeclaration`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DispatchDeclarationOwned {
+    pub source: DispatchSourceOwned,
+    pub targets: Vec<DispatchTargetOwned>,
+    pub target_type: TypeExpressionOwned,
+    pub annotations: Vec<AnnotationOwned>,
+    pub docs: Vec<String>,
+    pub position: Position,
+}
+
+impl DispatchDeclarationOwned {
+    pub fn to_borrowed(&self) -> DispatchDeclaration<'_> {
+        DispatchDeclaration {
+            source: self.source.to_borrowed(),
+            targets: self.targets.iter().map(DispatchTargetOwned::to_borrowed).collect(),
+            target_type: self.target_type.to_borrowed(),
+            annotations: borrowed_annotations(&self.annotations),
+            docs: borrowed_strs(&self.docs),
+            position: self.position,
+        }
+    }
+}
+
+impl From<&DispatchDeclaration<'_>> for DispatchDeclarationOwned {
+    fn from(declaration: &DispatchDeclaration<'_>) -> Self {
+        Self {
+            source: DispatchSourceOwned::from(&declaration.source),
+            targets: declaration.targets.iter().map(DispatchTargetOwned::from).collect(),
+            target_type: TypeExpressionOwned::from(&declaration.target_type),
+            annotations: owned_annotations(&declaration.annotations),
+            docs: owned_strs(&declaration.docs),
+            position: declaration.position,
+        }
+    }
+}
+
+/// Owned `DispatchSource`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DispatchSourceOwned {
+    pub namespace: String,
+    pub registry: String,
+    pub position: Position,
+}
+
+impl DispatchSourceOwned {
+    pub fn to_borrowed(&self) -> DispatchSource<'_> {
+        DispatchSource {
+            namespace: &self.namespace,
+            registry: &self.registry,
+            position: self.position,
+        }
+    }
+}
+
+impl From<&DispatchSource<'_>> for DispatchSourceOwned {
+    fn from(source: &DispatchSource<'_>) -> Self {
+        Self {
+            namespace: source.namespace.to_string(),
+            registry: source.registry.to_string(),
+            position: source.position,
+        }
+    }
+}
+
+/// Owned `DispatchTarget`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DispatchTargetOwned {
+    Specific(String),
+    Unknown,
+    None,
+    Special(String),
+}
+
+impl DispatchTargetOwned {
+    pub fn to_borrowed(&self) -> DispatchTarget<'_> {
+        match self {
+            DispatchTargetOwned::Specific(key) => DispatchTarget::Specific(key),
+            DispatchTargetOwned::Unknown => DispatchTarget::Unknown,
+            DispatchTargetOwned::None => DispatchTarget::None,
+            DispatchTargetOwned::Special(name) => DispatchTarget::Special(name),
+        }
+    }
+}
+
+impl From<&DispatchTarget<'_>> for DispatchTargetOwned {
+    fn from(target: &DispatchTarget<'_>) -> Self {
+        match target {
+            DispatchTarget::Specific(key) => DispatchTargetOwned::Specific(key.to_string()),
+            DispatchTarget::Unknown => DispatchTargetOwned::Unknown,
+            DispatchTarget::None => DispatchTargetOwned::None,
+            DispatchTarget::Special(name) => DispatchTargetOwned::Special(name.to_string()),
+        }
+    }
+}
+
+/// Owned `TypeExpression`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TypeExpressionOwned {
+    Simple(String),
+    Array {
+        element_type: Box<TypeExpressionOwned>,
+        constraints: Option<ArrayConstraints>,
+    },
+    Union(Vec<TypeExpressionOwned>),
+    Struct {
+        name: Option<String>,
+        members: Vec<StructMemberOwned>,
+    },
+    Generic {
+        name: String,
+        type_args: Vec<TypeExpressionOwned>,
+    },
+    Reference(ImportPathOwned),
+    Spread(SpreadExpressionOwned),
+    Literal(LiteralValueOwned),
+    Enum {
+        name: Option<String>,
+        base_type: Option<String>,
+        variants: Vec<EnumVariantOwned>,
+    },
+    Annotated {
+        annotations: Vec<AnnotationOwned>,
+        type_expr: Box<TypeExpressionOwned>,
+    },
+    Constrained {
+        base_type: Box<TypeExpressionOwned>,
+        constraints: TypeConstraints,
+    },
+}
+
+impl TypeExpressionOwned {
+    pub fn to_borrowed(&self) -> TypeExpression<'_> {
+        match self {
+            TypeExpressionOwned::Simple(name) => TypeExpression::Simple(name),
+            TypeExpressionOwned::Array { element_type, constraints } => TypeExpression::Array {
+                element_type: Box::new(element_type.to_borrowed()),
+                constraints: constraints.clone(),
+            },
+            TypeExpressionOwned::Union(types) => TypeExpression::Union(types.iter().map(Self::to_borrowed).collect()),
+            TypeExpressionOwned::Struct { name, members } => TypeExpression::Struct {
+                name: name.as_deref(),
+                members: borrowed_members(members),
+            },
+            TypeExpressionOwned::Generic { name, type_args } => TypeExpression::Generic {
+                name,
+                type_args: type_args.iter().map(Self::to_borrowed).collect(),
+            },
+            TypeExpressionOwned::Reference(path) => TypeExpression::Reference(path.to_borrowed()),
+            TypeExpressionOwned::Spread(spread) => TypeExpression::Spread(spread.to_borrowed()),
+            TypeExpressionOwned::Literal(value) => TypeExpression::Literal(value.to_borrowed()),
+            TypeExpressionOwned::Enum { name, base_type, variants } => TypeExpression::Enum {
+                name: name.as_deref(),
+                base_type: base_type.as_deref(),
+                variants: borrowed_variants(variants),
+            },
+            TypeExpressionOwned::Annotated { annotations, type_expr } => TypeExpression::Annotated {
+                annotations: borrowed_annotations(annotations),
+                type_expr: Box::new(type_expr.to_borrowed()),
+            },
+            TypeExpressionOwned::Constrained { base_type, constraints } => TypeExpression::Constrained {
+                base_type: Box::new(base_type.to_borrowed()),
+                constraints: constraints.clone(),
+            },
+        }
+    }
+}
+
+impl From<&TypeExpression<'_>> for TypeExpressionOwned {
+    fn from(type_expr: &TypeExpression<'_>) -> Self {
+        match type_expr {
+            TypeExpression::Simple(name) => TypeExpressionOwned::Simple(name.to_string()),
+            TypeExpression::Array { element_type, constraints } => TypeExpressionOwned::Array {
+                element_type: Box::new(element_type.as_ref().into()),
+                constraints: constraints.clone(),
+            },
+            TypeExpression::Union(types) => TypeExpressionOwned::Union(types.iter().map(Self::from).collect()),
+            TypeExpression::Struct { name, members } => TypeExpressionOwned::Struct {
+                name: name.map(str::to_string),
+                members: owned_members(members),
+            },
+            TypeExpression::Generic { name, type_args } => TypeExpressionOwned::Generic {
+                name: name.to_string(),
+                type_args: type_args.iter().map(Self::from).collect(),
+            },
+            TypeExpression::Reference(path) => TypeExpressionOwned::Reference(path.into()),
+            TypeExpression::Spread(spread) => TypeExpressionOwned::Spread(spread.into()),
+            TypeExpression::Literal(value) => TypeExpressionOwned::Literal(value.into()),
+            TypeExpression::Enum { name, base_type, variants } => TypeExpressionOwned::Enum {
+                name: name.map(str::to_string),
+                base_type: base_type.map(str::to_string),
+                variants: owned_variants(variants),
+            },
+            TypeExpression::Annotated { annotations, type_expr } => TypeExpressionOwned::Annotated {
+                annotations: owned_annotations(annotations),
+                type_expr: Box::new(type_expr.as_ref().into()),
+            },
+            TypeExpression::Constrained { base_type, constraints } => TypeExpressionOwned::Constrained {
+                base_type: Box::new(base_type.as_ref().into()),
+                constraints: constraints.clone(),
+            },
+        }
+    }
+}
+
+/// Owned `SpreadExpression`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpreadExpressionOwned {
+    pub namespace: String,
+    pub registry: String,
+    pub dynamic_key: Option<DynamicReferenceOwned>,
+    pub target: Option<Box<TypeExpressionOwned>>,
+    pub annotations: Vec<AnnotationOwned>,
+    pub position: Position,
+}
+
+impl SpreadExpressionOwned {
+    pub fn to_borrowed(&self) -> SpreadExpression<'_> {
+        SpreadExpression {
+            namespace: &self.namespace,
+            registry: &self.registry,
+            dynamic_key: self.dynamic_key.as_ref().map(DynamicReferenceOwned::to_borrowed),
+            target: self.target.as_ref().map(|target| Box::new(target.to_borrowed())),
+            annotations: borrowed_annotations(&self.annotations),
+            position: self.position,
+        }
+    }
+}
+
+impl From<&SpreadExpression<'_>> for SpreadExpressionOwned {
+    fn from(spread: &SpreadExpression<'_>) -> Self {
+        Self {
+            namespace: spread.namespace.to_string(),
+            registry: spread.registry.to_string(),
+            dynamic_key: spread.dynamic_key.as_ref().map(DynamicReferenceOwned::from),
+            target: spread.target.as_ref().map(|target| Box::new(target.as_ref().into())),
+            annotations: owned_annotations(&spread.annotations),
+            position: spread.position,
+        }
+    }
+}
+
+/// Owned `DynamicReference`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DynamicReferenceOwned {
+    pub reference: DynamicReferenceTypeOwned,
+    pub position: Position,
+}
+
+impl DynamicReferenceOwned {
+    pub fn to_borrowed(&self) -> DynamicReference<'_> {
+        DynamicReference {
+            reference: self.reference.to_borrowed(),
+            position: self.position,
+        }
+    }
+}
+
+impl From<&DynamicReference<'_>> for DynamicReferenceOwned {
+    fn from(reference: &DynamicReference<'_>) -> Self {
+        Self {
+            reference: DynamicReferenceTypeOwned::from(&reference.reference),
+            position: reference.position,
+        }
+    }
+}
+
+/// Owned `DynamicReferenceType`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DynamicReferenceTypeOwned {
+    Field(String),
+    SpecialKey(String),
+    Path(Vec<PathSegmentOwned>),
+}
+
+impl DynamicReferenceTypeOwned {
+    pub fn to_borrowed(&self) -> DynamicReferenceType<'_> {
+        match self {
+            DynamicReferenceTypeOwned::Field(field) => DynamicReferenceType::Field(field),
+            DynamicReferenceTypeOwned::SpecialKey(key) => DynamicReferenceType::SpecialKey(key),
+            DynamicReferenceTypeOwned::Path(segments) => DynamicReferenceType::Path(segments.iter().map(PathSegmentOwned::to_borrowed).collect()),
+        }
+    }
+}
+
+impl From<&DynamicReferenceType<'_>> for DynamicReferenceTypeOwned {
+    fn from(reference: &DynamicReferenceType<'_>) -> Self {
+        match reference {
+            DynamicReferenceType::Field(field) => DynamicReferenceTypeOwned::Field(field.to_string()),
+            DynamicReferenceType::SpecialKey(key) => DynamicReferenceTypeOwned::SpecialKey(key.to_string()),
+            DynamicReferenceType::Path(segments) => DynamicReferenceTypeOwned::Path(segments.iter().map(PathSegmentOwned::from).collect()),
+        }
+    }
+}
+
+/// Owned `PathSegment`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PathSegmentOwned {
+    Field(String),
+    Parent,
+    Key,
+}
+
+impl PathSegmentOwned {
+    pub fn to_borrowed(&self) -> PathSegment<'_> {
+        match self {
+            PathSegmentOwned::Field(field) => PathSegment::Field(field),
+            PathSegmentOwned::Parent => PathSegment::Parent,
+            PathSegmentOwned::Key => PathSegment::Key,
+        }
+    }
+}
+
+impl From<&PathSegment<'_>> for PathSegmentOwned {
+    fn from(segment: &PathSegment<'_>) -> Self {
+        match segment {
+            PathSegment::Field(field) => PathSegmentOwned::Field(field.to_string()),
+            PathSegment::Parent => PathSegmentOwned::Parent,
+            PathSegment::Key => PathSegmentOwned::Key,
+        }
+    }
+}
+
+/// Owned `LiteralValue`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LiteralValueOwned {
+    String(String),
+    Number(f64),
+    Boolean(bool),
+}
+
+impl LiteralValueOwned {
+    pub fn to_borrowed(&self) -> LiteralValue<'_> {
+        match self {
+            LiteralValueOwned::String(value) => LiteralValue::String(Cow::Borrowed(value)),
+            LiteralValueOwned::Number(number) => LiteralValue::Number(*number),
+            LiteralValueOwned::Boolean(value) => LiteralValue::Boolean(*value),
+        }
+    }
+}
+
+impl From<&LiteralValue<'_>> for LiteralValueOwned {
+    fn from(value: &LiteralValue<'_>) -> Self {
+        match value {
+            LiteralValue::String(value) => LiteralValueOwned::String(value.to_string()),
+            LiteralValue::Number(number) => LiteralValueOwned::Number(*number),
+            LiteralValue::Boolean(value) => LiteralValueOwned::Boolean(*value),
+        }
+    }
+}
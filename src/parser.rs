@@ -1,8 +1,10 @@
 //! Parser MCDOC unifié
 
-use crate::error::{ParseError, SourcePos};
-use crate::lexer::{Token, TokenWithPos, Position};
+use crate::error::ParseError;
+use crate::lexer::{Lexer, Token, TokenWithPos, Position, Span};
 use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 // ================================
 // AST ESSENTIAL STRUCTURES
@@ -19,14 +21,48 @@ pub struct McDocFile<'input> {
 #[derive(Debug, Clone, PartialEq)]
 pub struct ImportStatement<'input> {
     pub path: ImportPath<'input>,
+    /// Local name given with `as`, like `D` in `use a::b::C as D`
+    pub alias: Option<&'input str>,
     pub position: Position,
 }
 
+impl<'input> ImportStatement<'input> {
+    /// Name the import binds in its module, the alias or the last path segment
+    pub fn local_name(&self) -> Option<&'input str> {
+        self.alias.or_else(|| self.path.segments().last().copied())
+    }
+}
+
 /// Import path
 #[derive(Debug, Clone, PartialEq)]
 pub enum ImportPath<'input> {
     Absolute(Vec<&'input str>),
-    Relative(Vec<&'input str>),
+    /// Path after `supers` leading `super::` hops
+    Relative {
+        supers: usize,
+        segments: Vec<&'input str>,
+    },
+}
+
+/// Names of an import group with their optional alias
+type ImportGroup<'input> = Vec<(&'input str, Option<&'input str>)>;
+
+impl<'input> ImportPath<'input> {
+    /// Segments after the leading `::` or `super::` hops
+    pub fn segments(&self) -> &[&'input str] {
+        match self {
+            ImportPath::Absolute(segments) | ImportPath::Relative { segments, .. } => segments,
+        }
+    }
+
+    /// The same path with `segment` appended
+    fn join(&self, segment: &'input str) -> Self {
+        let mut path = self.clone();
+        match &mut path {
+            ImportPath::Absolute(segments) | ImportPath::Relative { segments, .. } => segments.push(segment),
+        }
+        path
+    }
 }
 
 /// Top-level declarations
@@ -44,22 +80,51 @@ pub struct Annotation<'input> {
     pub name: &'input str,
     pub data: AnnotationData<'input>,
     pub position: Position,
+    pub end: Position,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AnnotationData<'input> {
-    Simple(&'input str),
-    Complex(FxHashMap<&'input str, &'input str>),
+    /// `#[name=value]`, the value without its quotes and with its escapes decoded
+    Simple(Cow<'input, str>),
+    /// `#[name(key=value, ...)]`
+    Complex(FxHashMap<&'input str, AnnotationValue<'input>>),
+    /// `#[name=[value, ...]]`
+    List(Vec<AnnotationValue<'input>>),
     Empty,
 }
 
+/// Value inside complex or list annotations
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnnotationValue<'input> {
+    /// Quoted or bare string, quotes removed and escapes decoded
+    String(Cow<'input, str>),
+    Number(f64),
+    Boolean(bool),
+    List(Vec<AnnotationValue<'input>>),
+}
+
+impl<'input> AnnotationValue<'input> {
+    /// The string value, if this is a string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AnnotationValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
 /// Struct declaration
 #[derive(Debug, Clone, PartialEq)]
 pub struct StructDeclaration<'input> {
     pub name: &'input str,
     pub members: Vec<StructMember<'input>>,
     pub annotations: Vec<Annotation<'input>>,
+    /// Text of the `///` doc comments written before it
+    pub docs: Vec<&'input str>,
     pub position: Position,
+    /// Position right after the closing `}`
+    pub end: Position,
 }
 
 /// Field declaration
@@ -68,8 +133,32 @@ pub struct FieldDeclaration<'input> {
     pub name: &'input str,
     pub field_type: TypeExpression<'input>,
     pub optional: bool,
+    /// Annotations before the field name, like `#[since="1.20"]`. Those written after
+    /// the colon belong to the type, an `Annotated` field type.
     pub annotations: Vec<Annotation<'input>>,
+    /// Text of the `///` doc comments written before it
+    pub docs: Vec<&'input str>,
     pub position: Position,
+    /// Position right after the field type, before any `,`
+    pub end: Position,
+}
+
+impl Annotation<'_> {
+    pub fn span(&self) -> Span {
+        Span::new(self.position, self.end)
+    }
+}
+
+impl StructDeclaration<'_> {
+    pub fn span(&self) -> Span {
+        Span::new(self.position, self.end)
+    }
+}
+
+impl FieldDeclaration<'_> {
+    pub fn span(&self) -> Span {
+        Span::new(self.position, self.end)
+    }
 }
 
 /// Struct member (either a field, dynamic field, or a spread)
@@ -97,6 +186,8 @@ pub struct EnumDeclaration<'input> {
     pub base_type: Option<&'input str>,
     pub variants: Vec<EnumVariant<'input>>,
     pub annotations: Vec<Annotation<'input>>,
+    /// Text of the `///` doc comments written before it
+    pub docs: Vec<&'input str>,
     pub position: Position,
 }
 
@@ -106,6 +197,8 @@ pub struct EnumVariant<'input> {
     pub name: &'input str,
     pub value: Option<LiteralValue<'input>>,
     pub annotations: Vec<Annotation<'input>>,
+    /// Text of the `///` doc comments written before it
+    pub docs: Vec<&'input str>,
     pub position: Position,
 }
 
@@ -116,6 +209,8 @@ pub struct TypeDeclaration<'input> {
     pub type_params: Vec<&'input str>, // Generic parameters like <C, T>
     pub type_expr: TypeExpression<'input>,
     pub annotations: Vec<Annotation<'input>>,
+    /// Text of the `///` doc comments written before it
+    pub docs: Vec<&'input str>,
     pub position: Position,
 }
 
@@ -126,22 +221,32 @@ pub struct DispatchDeclaration<'input> {
     pub targets: Vec<DispatchTarget<'input>>,
     pub target_type: TypeExpression<'input>,
     pub annotations: Vec<Annotation<'input>>,
+    /// Text of the `///` doc comments written before it
+    pub docs: Vec<&'input str>,
     pub position: Position,
 }
 
 /// Dispatch source
 #[derive(Debug, Clone, PartialEq)]
 pub struct DispatchSource<'input> {
+    /// Namespace before the colon (e.g. "minecraft" in `minecraft:loot_function`)
+    pub namespace: &'input str,
+    /// Registry path after the colon (e.g. "loot_function" in `minecraft:loot_function`)
     pub registry: &'input str,
-    pub key: Option<&'input str>,
     pub position: Position,
 }
 
-/// Dispatch target
+/// Dispatch target, one per key between the brackets
 #[derive(Debug, Clone, PartialEq)]
 pub enum DispatchTarget<'input> {
+    /// Identifier or string key (e.g. `cod_bucket`, `"worldgen/biome"`)
     Specific(&'input str),
+    /// `%unknown`, used when no specific key matches
     Unknown,
+    /// `%none`, used when the dispatch key is absent
+    None,
+    /// Any other `%` pattern, stored without the `%` (e.g. `key` for `%key`)
+    Special(&'input str),
 }
 
 /// Type expressions
@@ -153,7 +258,11 @@ pub enum TypeExpression<'input> {
         constraints: Option<ArrayConstraints>,
     },
     Union(Vec<TypeExpression<'input>>),
-    Struct(Vec<StructMember<'input>>),
+    /// Inline struct, named for `struct Foo { ... }`
+    Struct {
+        name: Option<&'input str>,
+        members: Vec<StructMember<'input>>,
+    },
     Generic {
         name: &'input str,
         type_args: Vec<TypeExpression<'input>>,
@@ -161,6 +270,17 @@ pub enum TypeExpression<'input> {
     Reference(ImportPath<'input>),
     Spread(SpreadExpression<'input>),
     Literal(LiteralValue<'input>),
+    /// Inline enum like `enum(string) { Blocks = "blocks" }`, the name is optional
+    Enum {
+        name: Option<&'input str>,
+        base_type: Option<&'input str>,
+        variants: Vec<EnumVariant<'input>>,
+    },
+    /// Type preceded by annotations, like a union branch "#[since="1.19.1"] ChatDecoration"
+    Annotated {
+        annotations: Vec<Annotation<'input>>,
+        type_expr: Box<TypeExpression<'input>>,
+    },
     /// Type with constraints like "float @ -80..80"
     Constrained {
         base_type: Box<TypeExpression<'input>>,
@@ -169,10 +289,14 @@ pub enum TypeExpression<'input> {
 }
 
 /// Array constraints
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ArrayConstraints {
     pub min: Option<u32>,
     pub max: Option<u32>,
+    /// `<..`, the length must be greater than `min`
+    pub min_exclusive: bool,
+    /// `..<`, the length must be less than `max`
+    pub max_exclusive: bool,
 }
 
 /// Spread expression
@@ -181,6 +305,9 @@ pub struct SpreadExpression<'input> {
     pub namespace: &'input str,
     pub registry: &'input str,
     pub dynamic_key: Option<DynamicReference<'input>>,
+    /// Spread type other than a registry dispatch, like `...struct { style: TextStyle }`,
+    /// `...Layer<T>` or `...super::ItemBase`
+    pub target: Option<Box<TypeExpression<'input>>>,
     pub annotations: Vec<Annotation<'input>>,
     pub position: Position,
 }
@@ -195,23 +322,50 @@ pub struct DynamicReference<'input> {
 /// Dynamic reference type
 #[derive(Debug, Clone, PartialEq)]
 pub enum DynamicReferenceType<'input> {
+    /// `[[type]]`, a field of the current object
     Field(&'input str),
+    /// `[[%key]]`, the key the current value is stored under
     SpecialKey(&'input str),
+    /// `[[%parent.type]]` or `[[..type]]`, a path through enclosing objects and nested fields
+    Path(Vec<PathSegment<'input>>),
+}
+
+/// Segment of a dynamic reference path
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathSegment<'input> {
+    /// A field name, bare or quoted
+    Field(&'input str),
+    /// `%parent` or `..`, the object enclosing the current one
+    Parent,
+    /// `%key`, the key the current value is stored under
+    Key,
 }
 
 /// Literal values
 #[derive(Debug, Clone, PartialEq)]
 pub enum LiteralValue<'input> {
-    String(&'input str),
+    String(Cow<'input, str>),
     Number(f64),
     Boolean(bool),
 }
 
 /// Type constraints (like @ -80..80)
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TypeConstraints {
     pub min: Option<f64>,
     pub max: Option<f64>,
+    /// `<..`, the value must be greater than `min`
+    pub min_exclusive: bool,
+    /// `..<`, the value must be less than `max`
+    pub max_exclusive: bool,
+}
+
+/// Bounds of a range constraint, shared by type and array constraints
+struct RangeBounds<T> {
+    min: Option<T>,
+    max: Option<T>,
+    min_exclusive: bool,
+    max_exclusive: bool,
 }
 
 // ================================
@@ -236,6 +390,17 @@ impl<'input> Parser<'input> {
 
     /// Full parse of the MCDOC file
     pub fn parse(&mut self) -> Result<McDocFile<'input>, Vec<ParseError>> {
+        let (file, errors) = self.parse_lenient();
+        if errors.is_empty() {
+            Ok(file)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Parse the MCDOC file as far as possible: the imports and declarations that
+    /// parsed are returned along with the errors of those that didn't
+    pub fn parse_lenient(&mut self) -> (McDocFile<'input>, Vec<ParseError>) {
         let mut imports = Vec::new();
         let mut declarations = Vec::new();
 
@@ -245,8 +410,8 @@ impl<'input> Parser<'input> {
             match self.current_token() {
                 Ok(token) => match &token.token {
                     Token::Use => match self.parse_import() {
-                        Ok(import) => {
-                            imports.push(import);
+                        Ok(statement_imports) => {
+                            imports.extend(statement_imports);
                             if self.check_token(Token::Semicolon) {
                                 self.advance();
                             }
@@ -276,14 +441,46 @@ impl<'input> Parser<'input> {
             self.skip_whitespace();
         }
 
-        if self.errors.is_empty() {
-            Ok(McDocFile {
-                imports,
-                declarations,
-            })
-        } else {
-            Err(std::mem::take(&mut self.errors))
+        (McDocFile { imports, declarations }, std::mem::take(&mut self.errors))
+    }
+
+    /// Parse the whole of `input` as one type expression, like `[float @ -80..80] @ 3`.
+    /// Anything but whitespace and comments after the type is an error.
+    pub fn parse_type_expression_complete(input: &'input str) -> Result<TypeExpression<'input>, Vec<ParseError>> {
+        let mut parser = Self::new(Lexer::new(input).tokenize().map_err(|e| vec![e])?);
+        parser.skip_whitespace();
+        let type_expr = parser.parse_type_expression().map_err(|e| vec![e])?;
+        parser.expect_end("type expression")?;
+        Ok(type_expr)
+    }
+
+    /// Parse the whole of `input` as one declaration, with its doc comments and annotations.
+    /// Anything but whitespace and comments after the declaration is an error.
+    pub fn parse_declaration_complete(input: &'input str) -> Result<Declaration<'input>, Vec<ParseError>> {
+        let mut parser = Self::new(Lexer::new(input).tokenize().map_err(|e| vec![e])?);
+        parser.skip_whitespace();
+        let declaration = match parser.parse_declaration().map_err(|e| vec![e])? {
+            Some(declaration) => declaration,
+            None => return Err(vec![parser.syntax_error("declaration", "EOF")]),
+        };
+        if parser.check_token_after_whitespace(Token::Semicolon) {
+            parser.advance();
         }
+        parser.expect_end("declaration")?;
+        Ok(declaration)
+    }
+
+    /// Error unless only whitespace and comments are left
+    fn expect_end(&mut self, context: &str) -> Result<(), Vec<ParseError>> {
+        self.skip_whitespace();
+        if self.is_at_end() {
+            return Ok(());
+        }
+        Err(vec![ParseError::Context {
+            message: "Unexpected trailing tokens".to_string(),
+            context: context.to_string(),
+            pos: Some(self.current_pos().into()),
+        }])
     }
 
     // ================================
@@ -297,12 +494,29 @@ impl<'input> Parser<'input> {
             .unwrap_or_default()
     }
 
+    /// End of the last consumed token that isn't whitespace or a comment
+    fn previous_end(&self) -> Position {
+        self.tokens[..self.current.min(self.tokens.len())]
+            .iter()
+            .rev()
+            .find(|t| !matches!(
+                t.token,
+                Token::Whitespace | Token::Newline | Token::LineComment(_) | Token::BlockComment(_) | Token::DocComment(_)
+            ))
+            .map(|t| t.end)
+            .unwrap_or_default()
+    }
+
     fn syntax_error(&self, expected: impl Into<String>, found: impl Into<String>) -> ParseError {
-        let pos = self.current_pos();
+        let span = self.tokens
+            .get(self.current)
+            .map(|t| t.span())
+            .unwrap_or_default();
         ParseError::Syntax {
             expected: expected.into(),
             found: found.into(),
-            pos: SourcePos { line: pos.line, column: pos.column }
+            pos: span.start.into(),
+            end: Some(span.end.into()),
         }
     }
 
@@ -310,7 +524,7 @@ impl<'input> Parser<'input> {
         while let Ok(token) = self.current_token() {
             if matches!(
                 token.token,
-                Token::Whitespace | Token::Newline | Token::LineComment(_) | Token::BlockComment(_)
+                Token::Whitespace | Token::Newline | Token::LineComment(_) | Token::BlockComment(_) | Token::DocComment(_)
             ) {
                 self.advance();
             } else {
@@ -319,6 +533,21 @@ impl<'input> Parser<'input> {
         }
     }
 
+    /// Doc comments directly before the current token, in source order.
+    /// Doc comments are skipped like whitespace, so they are found by looking back.
+    fn preceding_docs(&self) -> Vec<&'input str> {
+        let mut docs = Vec::new();
+        for token in self.tokens[..self.current.min(self.tokens.len())].iter().rev() {
+            match token.token {
+                Token::Whitespace | Token::Newline => {}
+                Token::DocComment(text) => docs.push(text),
+                _ => break,
+            }
+        }
+        docs.reverse();
+        docs
+    }
+
     fn current_token(&self) -> Result<&TokenWithPos<'input>, ParseError> {
         self.tokens
             .get(self.current)
@@ -333,6 +562,19 @@ impl<'input> Parser<'input> {
         std::mem::discriminant(&self.current_token().unwrap().token) == std::mem::discriminant(&token_type)
     }
 
+    /// Whether `token_type` comes next once whitespace and comments are skipped.
+    /// The whitespace is only consumed when the token is found.
+    fn check_token_after_whitespace(&mut self, token_type: Token) -> bool {
+        let before_whitespace = self.current;
+        self.skip_whitespace();
+        if self.check_token(token_type) {
+            true
+        } else {
+            self.current = before_whitespace;
+            false
+        }
+    }
+
     fn advance(&mut self) {
         if !self.is_at_end() {
             self.current += 1;
@@ -407,6 +649,26 @@ impl<'input> Parser<'input> {
         }
     }
 
+    /// Parse a field name, either an identifier or a quoted key like `"minecraft:custom_data"`
+    fn field_name(&mut self) -> Result<&'input str, ParseError> {
+        self.skip_whitespace();
+        if let Token::String(name) = &self.current_token()?.token {
+            let name = self.quoted_name(name)?;
+            self.advance();
+            return Ok(name);
+        }
+        self.current_identifier()
+    }
+
+    /// A quoted field name or dispatch key. Names borrow from the source, so they
+    /// can't contain escape sequences.
+    fn quoted_name(&self, name: &Cow<'input, str>) -> Result<&'input str, ParseError> {
+        match name {
+            Cow::Borrowed(name) => Ok(name),
+            Cow::Owned(name) => Err(self.syntax_error("name without escape sequences", format!("\"{}\"", name))),
+        }
+    }
+
     /// Parse special identifiers that can include patterns like %unknown, %key
     fn current_identifier_or_special(&mut self) -> Result<&'input str, ParseError> {
         self.skip_whitespace();
@@ -495,31 +757,48 @@ impl<'input> Parser<'input> {
     // MAIN PARSING LOGIC
     // ================================
 
-    pub fn parse_import(&mut self) -> Result<ImportStatement<'input>, ParseError> {
+    /// Parse a `use` statement. A group like `use a::b::{C, D as E}` gives one import per name.
+    pub fn parse_import(&mut self) -> Result<Vec<ImportStatement<'input>>, ParseError> {
         let pos = self.current_pos();
         self.consume(Token::Use, "Expected 'use'")?;
-        let path = self.parse_import_path()?;
-        Ok(ImportStatement {
-            path,
-            position: pos,
-        })
+        let (path, group) = self.parse_import_tree(true)?;
+        let Some(group) = group else {
+            let alias = self.parse_import_alias()?;
+            return Ok(vec![ImportStatement { path, alias, position: pos }]);
+        };
+        Ok(group.into_iter()
+            .map(|(name, alias)| ImportStatement { path: path.join(name), alias, position: pos })
+            .collect())
     }
 
     fn parse_import_path(&mut self) -> Result<ImportPath<'input>, ParseError> {
+        self.parse_import_tree(false).map(|(path, _)| path)
+    }
+
+    /// Import path, and the names of a trailing `::{A, B as C}` group when `allow_group`
+    /// (the path then stops before the group)
+    fn parse_import_tree(&mut self, allow_group: bool) -> Result<(ImportPath<'input>, Option<ImportGroup<'input>>), ParseError> {
         let mut segments = Vec::new();
-        let mut is_relative = false;
+        let mut group = None;
+        let mut supers = 0;
 
         self.skip_whitespace();
 
         if self.check_token(Token::DoubleColon) {
             self.advance(); // consume ::
-        } else if self.check_token(Token::Super) {
-            is_relative = true;
-            self.advance();
-            self.consume(Token::DoubleColon, "Expected '::' after 'super'")?;
+        } else {
+            while self.check_token(Token::Super) {
+                supers += 1;
+                self.advance();
+                self.consume(Token::DoubleColon, "Expected '::' after 'super'")?;
+            }
         }
 
         loop {
+            if allow_group && !segments.is_empty() && self.check_token_after_whitespace(Token::LeftBrace) {
+                group = Some(self.parse_import_group()?);
+                break;
+            }
             segments.push(self.current_identifier()?);
             
             if self.check_token(Token::DoubleColon) {
@@ -529,36 +808,70 @@ impl<'input> Parser<'input> {
             }
         }
 
-        if is_relative {
-            Ok(ImportPath::Relative(segments))
+        let path = if supers > 0 {
+            ImportPath::Relative { supers, segments }
         } else {
-            Ok(ImportPath::Absolute(segments))
+            ImportPath::Absolute(segments)
+        };
+        Ok((path, group))
+    }
+
+    /// Names of an import group `{A, B as C}`, a trailing comma is allowed
+    fn parse_import_group(&mut self) -> Result<ImportGroup<'input>, ParseError> {
+        self.consume(Token::LeftBrace, "Expected '{' in import group")?;
+        let mut names = Vec::new();
+        while !self.check_token_after_whitespace(Token::RightBrace) {
+            let name = self.current_identifier()?;
+            names.push((name, self.parse_import_alias()?));
+            if !self.check_token_after_whitespace(Token::Comma) {
+                break;
+            }
+            self.advance();
+        }
+        self.consume(Token::RightBrace, "Expected '}' after imported names")?;
+        Ok(names)
+    }
+
+    /// `as Name` after an imported path or group name
+    fn parse_import_alias(&mut self) -> Result<Option<&'input str>, ParseError> {
+        let before_whitespace = self.current;
+        self.skip_whitespace();
+        if matches!(self.current_token().map(|token| &token.token), Ok(Token::Identifier("as"))) {
+            self.advance();
+            return self.current_identifier().map(Some);
         }
+        self.current = before_whitespace;
+        Ok(None)
     }
 
     fn parse_declaration(&mut self) -> Result<Option<Declaration<'input>>, ParseError> {
+        let docs = self.preceding_docs();
         let annotations = self.parse_annotations()?;
-        let pos = self.current_pos();
 
         self.skip_whitespace();
         if self.is_at_end() {
             return Ok(None);
         }
+        let pos = self.current_pos();
 
         let token = self.current_token()?.token.clone();
         match token {
-            Token::Struct => Ok(Some(Declaration::Struct(
-                self.parse_struct_declaration(annotations, pos)?,
-            ))),
-            Token::Enum => Ok(Some(Declaration::Enum(
-                self.parse_enum_declaration(annotations, pos)?,
-            ))),
-            Token::Type => Ok(Some(Declaration::Type(
-                self.parse_type_declaration(annotations, pos)?,
-            ))),
-            Token::Dispatch => Ok(Some(Declaration::Dispatch(
-                self.parse_dispatch_declaration(annotations, pos)?,
-            ))),
+            Token::Struct => Ok(Some(Declaration::Struct(StructDeclaration {
+                docs,
+                ..self.parse_struct_declaration(annotations, pos)?
+            }))),
+            Token::Enum => Ok(Some(Declaration::Enum(EnumDeclaration {
+                docs,
+                ..self.parse_enum_declaration(annotations, pos)?
+            }))),
+            Token::Type => Ok(Some(Declaration::Type(TypeDeclaration {
+                docs,
+                ..self.parse_type_declaration(annotations, pos)?
+            }))),
+            Token::Dispatch => Ok(Some(Declaration::Dispatch(DispatchDeclaration {
+                docs,
+                ..self.parse_dispatch_declaration(annotations, pos)?
+            }))),
             _ => {
                 if annotations.is_empty() {
                     let found = format!("{:?}", self.current_token()?.token);
@@ -573,46 +886,33 @@ impl<'input> Parser<'input> {
         }
     }
 
+    /// Parse consecutive annotations, which may be separated by newlines and comments.
+    /// The cursor is left right after the last annotation.
     fn parse_annotations(&mut self) -> Result<Vec<Annotation<'input>>, ParseError> {
         let mut annotations = Vec::new();
         
-        while let Ok(token) = self.current_token() {
+        loop {
+            let before_whitespace = self.current;
+            self.skip_whitespace();
+            
+            let Ok(token) = self.current_token() else {
+                self.current = before_whitespace;
+                break;
+            };
             if let Token::Annotation(text) = token.token.clone() {
-                let pos = token.position;
+                let span = token.span();
                 self.advance();
                 
-                // Simple annotation parsing: #[name(key=value)] or #[name=value] or #[name]
-                let annotation_text = text.trim_start_matches("#[").trim_end_matches(']');
-                let (name, data) = if let Some(paren_pos) = annotation_text.find('(') {
-                // Complex: #[name(key=value)]
-                let name = annotation_text[..paren_pos].trim();
-                let params_text = annotation_text[paren_pos + 1..].trim_end_matches(')');
-                
-                let mut map = FxHashMap::default();
-                for param in params_text.split(',') {
-                    if let Some(eq_pos) = param.find('=') {
-                        let key = param[..eq_pos].trim();
-                        let value = param[eq_pos + 1..].trim_matches('"');
-                        map.insert(key, value);
-                    }
-                }
-                (name, AnnotationData::Complex(map))
-            } else if let Some(eq_pos) = annotation_text.find('=') {
-                // Simple: #[name=value]
-                let name = annotation_text[..eq_pos].trim();
-                let value = annotation_text[eq_pos + 1..].trim_matches('"');
-                (name, AnnotationData::Simple(value))
-            } else {
-                // Empty: #[name]
-                (annotation_text, AnnotationData::Empty)
-            };
+                let (name, data) = crate::annotation::parse_annotation(text);
                 
                 annotations.push(Annotation {
                     name,
                     data,
-                    position: pos,
+                    position: span.start,
+                    end: span.end,
                 });
             } else {
+                self.current = before_whitespace;
                 break;
             }
         }
@@ -641,12 +941,15 @@ impl<'input> Parser<'input> {
             name,
             members,
             annotations,
+            docs: Vec::new(), // Filled in by parse_declaration
             position: pos,
+            end: self.previous_end(),
         })
     }
 
     fn parse_struct_member(&mut self) -> Result<StructMember<'input>, ParseError> {
         self.skip_whitespace(); // Skip any whitespace before parsing
+        let docs = self.preceding_docs();
         
         // Parse annotations first (they can apply to both spreads and fields)
         let annotations = self.parse_annotations()?;
@@ -679,38 +982,24 @@ impl<'input> Parser<'input> {
                 }
                 
                 self.consume(Token::RightBrace, "Expected '}' to end struct body")?;
+                self.end_struct_member()?;
                 
-                // Skip any trailing comma
-                if self.check_token(Token::Comma) {
-                    self.advance();
-                }
-                
-                // Create a struct type expression and return it as a spread
-                // For now we treat spread structs as simple spreads
-                return Ok(StructMember::Spread(SpreadExpression {
+                // The inline struct's members are merged into the enclosing struct
+                Ok(StructMember::Spread(SpreadExpression {
                     namespace: "",  // No namespace for inline structs
                     registry: "",   // No registry for inline structs  
                     dynamic_key: None,
+                    target: Some(Box::new(TypeExpression::Struct { name: None, members })),
                     annotations,
                     position: self.current_pos(),
-                }));
+                }))
             } else {
-                // Smart parsing: detect different spread patterns
-                let (namespace, registry) = if self.check_token(Token::Super) || self.check_token(Token::DoubleColon) {
+                // Smart parsing: detect different spread patterns. Only the registry form
+                // uses namespace and registry, the others spread the type in `target`.
+                let (namespace, registry, target) = if self.check_token(Token::Super) || self.check_token(Token::DoubleColon) {
                     // Handle import path: super::ItemBase or ::absolute::path
-                    let namespace = if self.check_token(Token::Super) {
-                        self.advance(); // consume super
-                        self.consume(Token::DoubleColon, "Expected '::' after 'super'")?;
-                        "super"
-                    } else if self.check_token(Token::DoubleColon) {
-                        self.advance(); // consume ::
-                        ""
-                    } else {
-                        ""
-                    };
-                    
-                    let registry = self.current_identifier()?;
-                    (namespace, registry)
+                    let path = self.parse_import_path()?;
+                    ("", "", Some(TypeExpression::Reference(path)))
                 } else {
                     // Check if it's a namespace:registry pattern or generic type
                     let name = self.current_identifier()?;
@@ -719,52 +1008,33 @@ impl<'input> Parser<'input> {
                         // Pattern: minecraft:test_instance[[type]]
                         self.advance(); // consume :
                         let registry = self.current_identifier()?;
-                        (name, registry)
+                        (name, registry, None)
                     } else if self.check_token(Token::Less) {
-                        // Pattern: Layer<T> - parse as generic type
+                        // Pattern: Layer<T> - parse as generic type, keeping its arguments
                         self.current = self.current.saturating_sub(1); // Back up to reparse
                         let spread_type = self.parse_single_type()?;
-                        
-                        match spread_type {
-                            TypeExpression::Generic { name, .. } => (name, ""),
-                            TypeExpression::Simple(name) => (name, ""),
-                            _ => ("", "")
-                        }
+                        ("", "", Some(spread_type))
                     } else {
                         // Simple name
-                        (name, "")
+                        ("", "", Some(TypeExpression::Simple(name)))
                     }
                 };
                 
-                // Handle dynamic reference like [[type]] or [[%key]]
+                // Handle dynamic reference like [[type]] or [[%parent.type]]
                 let dynamic_key = if self.check_token(Token::LeftBracket) && 
                    self.tokens.get(self.current + 1).map(|t| &t.token) == Some(&Token::LeftBracket) {
-                    self.advance(); // consume first [
-                    self.advance(); // consume second [
-                    
-                    // Allow % patterns and identifiers in dynamic references
-                    let key = self.current_identifier_or_special()?;
-                    
-                    self.consume(Token::RightBracket, "Expected ']' in dynamic reference")?;
-                    self.consume(Token::RightBracket, "Expected ']]' in dynamic reference")?;
-                    
-                    Some(DynamicReference {
-                        reference: DynamicReferenceType::Field(key),
-                        position: self.current_pos(),
-                    })
+                    Some(self.parse_dynamic_reference()?)
                 } else {
                     None
                 };
                 
-                // Skip any trailing comma
-                if self.check_token(Token::Comma) {
-                    self.advance();
-                }
+                self.end_struct_member()?;
                 
                 Ok(StructMember::Spread(SpreadExpression {
                     namespace,
                     registry,
                     dynamic_key,
+                    target: target.map(Box::new),
                     annotations,
                     position: self.current_pos(),
                 }))
@@ -790,10 +1060,7 @@ impl<'input> Parser<'input> {
 
             // Parse value type
             let value_type = self.parse_type_expression()?;
-
-            if self.check_token(Token::Comma) {
-                self.advance();
-            }
+            self.end_struct_member()?;
 
             Ok(StructMember::DynamicField(DynamicFieldDeclaration {
                 key_type,
@@ -805,7 +1072,7 @@ impl<'input> Parser<'input> {
         } else {
             // Parse as regular field - but we already have annotations, so pass them
             let pos = self.current_pos();
-            let name = self.current_identifier()?;
+            let name = self.field_name()?;
             
             let optional = if self.check_token(Token::Question) {
                 self.advance();
@@ -816,34 +1083,67 @@ impl<'input> Parser<'input> {
 
             self.consume(Token::Colon, "Expected ':' after field name")?;
 
-            // Parse type annotations (like #[id(...)] before the type)
+            // Annotations after the colon, like `#[id="item"] string`, apply to the whole type
             let type_annotations = self.parse_annotations()?;
             
-            let field_type = self.parse_type_expression()?;
-
-            if self.check_token(Token::Comma) {
-                self.advance();
+            let mut field_type = self.parse_type_expression()?;
+            if !type_annotations.is_empty() {
+                field_type = TypeExpression::Annotated {
+                    annotations: type_annotations,
+                    type_expr: Box::new(field_type),
+                };
             }
-
-            // Combine field annotations and type annotations
-            let mut all_annotations = annotations;
-            all_annotations.extend(type_annotations);
+            let end = self.previous_end();
+            self.end_struct_member()?;
 
             Ok(StructMember::Field(FieldDeclaration {
                 name,
                 field_type,
                 optional,
-                annotations: all_annotations,
+                annotations,
+                docs,
                 position: pos,
+                end,
             }))
         }
     }
 
+    /// Members are separated by a comma or a line break, a trailing comma is allowed.
+    /// Another member on the same line is missing its comma, reported at the end of this one.
+    fn end_struct_member(&mut self) -> Result<(), ParseError> {
+        let next = self.tokens[self.current.min(self.tokens.len())..]
+            .iter()
+            .position(|t| !matches!(
+                t.token,
+                Token::Whitespace | Token::Newline | Token::LineComment(_) | Token::BlockComment(_) | Token::DocComment(_)
+            ))
+            .map(|offset| self.current + offset);
+        let Some(next_token) = next.and_then(|next| self.tokens.get(next)) else {
+            return Ok(());
+        };
+
+        let end = self.previous_end();
+        match &next_token.token {
+            Token::Comma => {
+                self.current = next.unwrap_or(self.current) + 1;
+                Ok(())
+            }
+            Token::RightBrace | Token::Eof => Ok(()),
+            _ if next_token.position.line > end.line => Ok(()),
+            token => Err(ParseError::Syntax {
+                expected: "Expected ',' after struct member".to_string(),
+                found: format!("{:?}", token),
+                pos: end.into(),
+                end: None,
+            }),
+        }
+    }
+
     #[allow(dead_code)]
     fn parse_field_declaration(&mut self) -> Result<FieldDeclaration<'input>, ParseError> {
         let field_annotations = self.parse_annotations()?;
         let pos = self.current_pos();
-        let name = self.current_identifier()?;
+        let name = self.field_name()?;
         
         let optional = if self.check_token(Token::Question) {
             self.advance();
@@ -858,6 +1158,7 @@ impl<'input> Parser<'input> {
         let type_annotations = self.parse_annotations()?;
         
         let field_type = self.parse_type_expression()?;
+        let end = self.previous_end();
 
         if self.check_token(Token::Comma) {
             self.advance();
@@ -872,19 +1173,81 @@ impl<'input> Parser<'input> {
             field_type,
             optional,
             annotations: all_annotations,
+            docs: Vec::new(),
             position: pos,
+            end,
         })
     }
 
+    /// Parse a dynamic reference like `[[type]]`, `[[%key]]`, `[[%parent.id]]` or `[[..type]]`.
+    /// A single field or `%key` keeps its dedicated variant, anything longer is a path.
+    fn parse_dynamic_reference(&mut self) -> Result<DynamicReference<'input>, ParseError> {
+        let position = self.current_pos();
+        self.consume(Token::LeftBracket, "Expected '[[' to start dynamic reference")?;
+        self.consume(Token::LeftBracket, "Expected '[[' to start dynamic reference")?;
+
+        let mut segments = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let segment = match self.current_token()?.token.clone() {
+                Token::DotDot => {
+                    // `..type` goes up one object, no separator follows
+                    self.advance();
+                    segments.push(PathSegment::Parent);
+                    continue;
+                }
+                Token::Percent => {
+                    self.advance();
+                    match self.current_identifier()? {
+                        "parent" => PathSegment::Parent,
+                        "key" => PathSegment::Key,
+                        other => return Err(self.syntax_error("'%parent' or '%key' in dynamic reference", format!("%{}", other))),
+                    }
+                }
+                Token::String(name) => {
+                    let name = self.quoted_name(&name)?;
+                    self.advance();
+                    PathSegment::Field(name)
+                }
+                _ => PathSegment::Field(self.current_identifier()?),
+            };
+            segments.push(segment);
+
+            if self.check_token(Token::Dot) {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+
+        self.consume(Token::RightBracket, "Expected ']' in dynamic reference")?;
+        self.consume(Token::RightBracket, "Expected ']]' in dynamic reference")?;
+
+        let reference = match segments.as_slice() {
+            [PathSegment::Field(name)] => DynamicReferenceType::Field(name),
+            [PathSegment::Key] => DynamicReferenceType::SpecialKey("key"),
+            _ => DynamicReferenceType::Path(segments),
+        };
+        Ok(DynamicReference { reference, position })
+    }
+
     pub fn parse_type_expression(&mut self) -> Result<TypeExpression<'input>, ParseError> {
+        // A union may start with a pipe when its branches are written one per line
+        if self.check_token_after_whitespace(Token::Pipe) {
+            self.advance();
+        }
+
         let mut type_expr = self.parse_single_type()?;
 
         // Check for constraints on simple types: int @ 1..10
         if self.check_token(Token::At) {
             self.advance(); // consume @
-            let _constraints = self.parse_array_constraints()?;
-            // For now, we ignore constraints on simple types and just return the type
-            // In a full implementation, we'd extend TypeExpression to support constraints
+            if let Some(constraints) = self.parse_type_constraints()? {
+                type_expr = TypeExpression::Constrained {
+                    base_type: Box::new(type_expr),
+                    constraints,
+                };
+            }
         }
 
         // Check for array type with optional constraints: [element_type] @ 1..10
@@ -906,8 +1269,8 @@ impl<'input> Parser<'input> {
             };
         }
 
-        // Check for union type
-        if self.check_token(Token::Pipe) {
+        // Check for union type, the pipe may be on the next line
+        if self.check_token_after_whitespace(Token::Pipe) {
             self.advance();
             let mut types = vec![type_expr];
 
@@ -920,8 +1283,7 @@ impl<'input> Parser<'input> {
                 }
                 
                 types.push(self.parse_single_type()?);
-                self.skip_whitespace();
-                if self.check_token(Token::Pipe) {
+                if self.check_token_after_whitespace(Token::Pipe) {
                     self.advance();
                 } else {
                     break;
@@ -934,74 +1296,62 @@ impl<'input> Parser<'input> {
         Ok(type_expr)
     }
 
-    /// Parse array constraints like 1..10, 5.., ..5, or just 5
+    /// Parse array size constraints like 1..10, 5.., ..5, 1..<5, or just 5
     fn parse_array_constraints(&mut self) -> Result<Option<ArrayConstraints>, ParseError> {
-        let token = self.current_token()?.token.clone();
-        
-        match token {
-            Token::Number(num) => {
-                self.advance();
-                
-                // Check if it's a range: 5..10 or 5..
-                if self.check_token(Token::DotDot) {
-                    self.advance(); // consume ..
-                    
-                    let max = if !self.is_at_end() {
-                        if let Ok(next_token) = self.current_token() {
-                            if let Token::Number(n) = &next_token.token {
-                                let num = *n;
-                                self.advance();
-                                Some(num as u32)
-                            } else {
-                                None // No max specified: 5..
-                            }
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    };
-                    
-                    Ok(Some(ArrayConstraints {
-                        min: Some(num as u32),
-                        max,
-                    }))
-                } else {
-                    // Just a single number: exactly this count
-                    Ok(Some(ArrayConstraints {
-                        min: Some(num as u32),
-                        max: Some(num as u32),
-                    }))
-                }
-            }
-            Token::DotDot => {
-                // Range starting from beginning: ..10
-                self.advance(); // consume ..
-                
-                if !self.is_at_end() {
-                    if let Ok(next_token) = self.current_token() {
-                        if let Token::Number(n) = &next_token.token {
-                            let num = *n;
-                            self.advance();
-                            Ok(Some(ArrayConstraints {
-                                min: None,
-                                max: Some(num as u32),
-                            }))
-                        } else {
-                            Err(self.syntax_error("number after '..'", format!("{:?}", next_token.token)))
-                        }
-                    } else {
-                        Err(self.syntax_error("number after '..'", "end of input"))
-                    }
-                } else {
-                    Err(self.syntax_error("number after '..'", "end of input"))
-                }
+        Ok(self.parse_range(Self::array_size)?.map(|range| ArrayConstraints {
+            min: range.min,
+            max: range.max,
+            min_exclusive: range.min_exclusive,
+            max_exclusive: range.max_exclusive,
+        }))
+    }
+
+    /// Parse a range like 1..10, 5.., ..5, 0..<1, 0<..1, 0<..<1 or just 5, reading each bound with `bound`
+    fn parse_range<T: Copy>(&mut self, bound: fn(&mut Self) -> Result<T, ParseError>) -> Result<Option<RangeBounds<T>>, ParseError> {
+        let min = match self.current_token()?.token.clone() {
+            Token::Number(_) => Some(bound(self)?),
+            Token::DotDot | Token::DotDotLess => None,
+            Token::LessDotDot | Token::LessDotDotLess => {
+                return Err(self.syntax_error("number before '<..'", format!("{:?}", self.current_token()?.token)));
             }
-            _ => {
-                // No valid constraint found
-                Ok(None)
+            // No valid constraint found
+            _ => return Ok(None),
+        };
+
+        let (operator, min_exclusive, max_exclusive) = match self.current_token().map(|t| &t.token) {
+            Ok(Token::DotDot) => ("..", false, false),
+            Ok(Token::DotDotLess) => ("..<", false, true),
+            Ok(Token::LessDotDot) => ("<..", true, false),
+            Ok(Token::LessDotDotLess) => ("<..<", true, true),
+            // Just a single number: exactly this value
+            _ => return Ok(Some(RangeBounds { min, max: min, min_exclusive: false, max_exclusive: false })),
+        };
+        self.advance(); // consume the range operator
+
+        let max = match self.current_token().map(|t| t.token.clone()).unwrap_or(Token::Eof) {
+            Token::Number(_) => Some(bound(self)?),
+            // `..5` needs its maximum, and so does an excluded one
+            token if min.is_none() || max_exclusive => {
+                let found = if self.is_at_end() { "end of input".to_string() } else { format!("{:?}", token) };
+                return Err(self.syntax_error(format!("number after '{}'", operator), found));
             }
+            _ => None, // No max specified: 5..
+        };
+
+        Ok(Some(RangeBounds { min, max, min_exclusive, max_exclusive }))
+    }
+
+    /// Consume the number of an array size constraint, sizes are whole and non-negative
+    fn array_size(&mut self) -> Result<u32, ParseError> {
+        let size = match self.current_token()?.token {
+            Token::Number(size) => size,
+            ref token => return Err(self.syntax_error("number", format!("{:?}", token))),
+        };
+        if size < 0.0 || size.fract() != 0.0 || size > u32::MAX as f64 {
+            return Err(self.syntax_error("array size constraint must be a non-negative integer", size.to_string()));
         }
+        self.advance();
+        Ok(size as u32)
     }
 
     pub fn parse_enum_declaration(
@@ -1009,16 +1359,42 @@ impl<'input> Parser<'input> {
         annotations: Vec<Annotation<'input>>,
         pos: Position,
     ) -> Result<EnumDeclaration<'input>, ParseError> {
+        let (base_type, name) = self.parse_enum_header()?;
+        let name = match name {
+            Some(name) => name,
+            None => return Err(self.syntax_error("enum name", format!("{:?}", self.current_token()?.token))),
+        };
+        let variants = self.parse_enum_body()?;
+
+        Ok(EnumDeclaration {
+            name,
+            base_type,
+            variants,
+            annotations,
+            docs: Vec::new(), // Filled in by parse_declaration
+            position: pos,
+        })
+    }
+
+    /// Parse `enum(string) Name`, `enum Name: string` or `enum(string)`, returning the base type and name
+    fn parse_enum_header(&mut self) -> Result<(Option<&'input str>, Option<&'input str>), ParseError> {
         self.consume(Token::Enum, "Expected 'enum'")?;
         
         // Support both syntaxes: enum(string) Test and enum Test: string
-        let (base_type, name) = if self.check_token(Token::LeftParen) {
-            // enum(string) Test
+        if self.check_token(Token::LeftParen) {
+            // enum(string) Test, the name is optional for inline enums
             self.advance();
             let bt = self.current_identifier()?;
             self.consume(Token::RightParen, "Expected ')' after enum base type")?;
-            let name = self.current_identifier()?;
-            (Some(bt), name)
+            let name = if self.check_token(Token::LeftBrace) {
+                None
+            } else {
+                Some(self.current_identifier()?)
+            };
+            Ok((Some(bt), name))
+        } else if self.check_token(Token::LeftBrace) {
+            // enum { ... }
+            Ok((None, None))
         } else {
             // enum Test: string or enum Test
             let name = self.current_identifier()?;
@@ -1028,13 +1404,17 @@ impl<'input> Parser<'input> {
             } else {
                 None
             };
-            (base_type, name)
-        };
-        
+            Ok((base_type, Some(name)))
+        }
+    }
+
+    /// Parse the `{ Name = value, ... }` variants of an enum
+    fn parse_enum_body(&mut self) -> Result<Vec<EnumVariant<'input>>, ParseError> {
         self.consume(Token::LeftBrace, "Expected '{' to start enum body")?;
         let mut variants = Vec::new();
         self.skip_whitespace();
         while !self.check_token(Token::RightBrace) && !self.is_at_end() {
+            let var_docs = self.preceding_docs();
             let var_annotations = self.parse_annotations()?;
             let var_pos = self.current_pos();
             let var_name = self.current_identifier()?;
@@ -1062,6 +1442,7 @@ impl<'input> Parser<'input> {
                 name: var_name,
                 value,
                 annotations: var_annotations,
+                docs: var_docs,
                 position: var_pos,
             });
 
@@ -1071,14 +1452,7 @@ impl<'input> Parser<'input> {
             self.skip_whitespace();
         }
         self.consume(Token::RightBrace, "Expected '}' to end enum body")?;
-
-        Ok(EnumDeclaration {
-            name,
-            base_type,
-            variants,
-            annotations,
-            position: pos,
-        })
+        Ok(variants)
     }
 
     pub fn parse_type_declaration(
@@ -1101,6 +1475,9 @@ impl<'input> Parser<'input> {
                 if self.check_token(Token::Comma) {
                     self.advance(); // consume comma
                     self.skip_whitespace(); // skip space after comma
+                    if self.check_token(Token::Greater) {
+                        break;
+                    }
                 } else {
                     break;
                 }
@@ -1121,10 +1498,35 @@ impl<'input> Parser<'input> {
             type_params,
             type_expr,
             annotations,
+            docs: Vec::new(), // Filled in by parse_declaration
             position: pos,
         })
     }
 
+    /// Parse one dispatch key: identifier, string literal, or %pattern
+    fn parse_dispatch_target(&mut self) -> Result<DispatchTarget<'input>, ParseError> {
+        let target = match &self.current_token()?.token {
+            Token::Identifier(name) => {
+                let result = DispatchTarget::Specific(name);
+                self.advance();
+                result
+            }
+            Token::String(value) => {
+                let result = DispatchTarget::Specific(self.quoted_name(value)?);
+                self.advance();
+                result
+            }
+            Token::Percent => match self.current_identifier_or_special()? {
+                "unknown" => DispatchTarget::Unknown,
+                "none" => DispatchTarget::None,
+                name => DispatchTarget::Special(name),
+            },
+            _ => return Err(self.syntax_error("identifier, string, or % pattern", format!("{:?}", self.current_token()?.token)))
+        };
+        self.skip_whitespace(); // Skip whitespace after the key
+        Ok(target)
+    }
+
     pub fn parse_dispatch_declaration(
         &mut self,
         annotations: Vec<Annotation<'input>>,
@@ -1133,57 +1535,28 @@ impl<'input> Parser<'input> {
         self.consume(Token::Dispatch, "Expected 'dispatch'")?;
         
         // Parse registry path (e.g., "minecraft:resource[test_recipe]")
-        let registry = self.current_identifier()?;
+        let namespace = self.current_identifier()?;
         self.consume(Token::Colon, "Expected ':'")?;
-        let _path = self.current_identifier()?;
+        let registry = self.current_identifier()?;
         
-        let key = if self.check_token(Token::LeftBracket) {
+        let mut targets = Vec::new();
+        if self.check_token(Token::LeftBracket) {
             self.advance();
             self.skip_whitespace(); // Skip whitespace after opening bracket
+            targets.push(self.parse_dispatch_target()?);
             
-            // Parse key name - can be identifier, string literal, or %pattern
-            let key_name = match &self.current_token()?.token {
-                Token::Identifier(name) => {
-                    let result = *name;
-                    self.advance();
-                    result
-                }
-                Token::String(value) => {
-                    let result = *value;
-                    self.advance();
-                    result
-                }
-                Token::Percent => {
-                    // Handle %unknown, %key patterns
-                    self.current_identifier_or_special()?
-                }
-                _ => return Err(self.syntax_error("identifier, string, or % pattern", format!("{:?}", self.current_token()?.token)))
-            };
-            
-            // Skip additional targets for now (multiple dispatch keys)
             while self.check_token(Token::Comma) {
                 self.advance();
                 self.skip_whitespace(); // Skip whitespace and newlines after comma
-                match &self.current_token()?.token {
-                    Token::Identifier(_) | Token::String(_) => {
-                        self.advance();
-                        self.skip_whitespace(); // Skip whitespace after identifier
-                    }
-                    Token::Percent => {
-                        // Handle % patterns in multiple targets
-                        self.current_identifier_or_special()?;
-                        self.skip_whitespace();
-                    }
-                    _ => return Err(self.syntax_error("identifier, string, or % pattern", format!("{:?}", self.current_token()?.token)))
+                if self.check_token(Token::RightBracket) {
+                    break;
                 }
+                targets.push(self.parse_dispatch_target()?);
             }
             
             self.skip_whitespace(); // Skip whitespace before closing bracket
             self.consume(Token::RightBracket, "Expected ']'")?;
-            Some(key_name)
-        } else {
-            None
-        };
+        }
 
         self.consume(Token::To, "Expected 'to'")?;
         
@@ -1192,13 +1565,14 @@ impl<'input> Parser<'input> {
 
         Ok(DispatchDeclaration {
             source: DispatchSource {
+                namespace,
                 registry,
-                key,
                 position: pos,
             },
-            targets: vec![], // TODO: parse targets
+            targets,
             target_type,
             annotations,
+            docs: Vec::new(), // Filled in by parse_declaration
             position: pos,
         })
     }
@@ -1207,11 +1581,23 @@ impl<'input> Parser<'input> {
         self.skip_whitespace();
         
         // Parse annotations before the type (for cases like #[regex_pattern] string)
-        let _type_annotations = self.parse_annotations()?;
+        let annotations = self.parse_annotations()?;
         
         // CRITICAL FIX: Skip whitespace/newlines after annotations
         self.skip_whitespace();
         
+        let type_expr = self.parse_unannotated_type()?;
+        if annotations.is_empty() {
+            Ok(type_expr)
+        } else {
+            Ok(TypeExpression::Annotated {
+                annotations,
+                type_expr: Box::new(type_expr),
+            })
+        }
+    }
+
+    fn parse_unannotated_type(&mut self) -> Result<TypeExpression<'input>, ParseError> {
         match &self.current_token()?.token {
             Token::Identifier(name) => {
                 let type_name = *name;
@@ -1222,25 +1608,16 @@ impl<'input> Parser<'input> {
                     self.advance(); // consume :
                     let registry = self.current_identifier()?;
                     
-                    // Check for dynamic reference: [[block]] or [[%key]]
+                    // Check for dynamic reference: [[block]], [[%key]] or [[%parent.id]]
                     if self.check_token(Token::LeftBracket) && 
                        self.tokens.get(self.current + 1).map(|t| &t.token) == Some(&Token::LeftBracket) {
-                        self.advance(); // consume first [
-                        self.advance(); // consume second [
-                        
-                        // Allow % patterns in dynamic references
-                        let key = self.current_identifier_or_special()?;
-                        
-                        self.consume(Token::RightBracket, "Expected ']' in dynamic reference")?;
-                        self.consume(Token::RightBracket, "Expected ']]' in dynamic reference")?;
+                        let dynamic_key = self.parse_dynamic_reference()?;
                         
                         Ok(TypeExpression::Spread(SpreadExpression {
                             namespace: type_name,
                             registry,
-                            dynamic_key: Some(DynamicReference {
-                                reference: DynamicReferenceType::Field(key),
-                                position: self.current_pos(),
-                            }),
+                            dynamic_key: Some(dynamic_key),
+                            target: None,
                             annotations: Vec::new(),
                             position: self.current_pos(),
                         }))
@@ -1263,11 +1640,16 @@ impl<'input> Parser<'input> {
                     self.advance(); // consume <
                     let mut type_args = Vec::new();
                     
+                    // Arguments are full type expressions: `Filterable<minecraft:text_component[[type]]>`,
+                    // `List<int @ 1..>`, and nested generics closed by `>>`
                     loop {
-                        type_args.push(self.parse_single_type()?);
+                        type_args.push(self.parse_type_expression()?);
                         
                         if self.check_token(Token::Comma) {
                             self.advance();
+                            if self.check_token_after_whitespace(Token::Greater) {
+                                break;
+                            }
                         } else {
                             break;
                         }
@@ -1316,6 +1698,7 @@ impl<'input> Parser<'input> {
                     namespace,
                     registry,
                     dynamic_key: None,
+                    target: None,
                     annotations: Vec::new(), // No annotations in type context
                     position: self.current_pos(),
                 }))
@@ -1361,7 +1744,7 @@ impl<'input> Parser<'input> {
                     match &token.token {
                         Token::Identifier(name) => {
                             // Named struct: struct TestRecipe { ... }
-                            let _struct_name = *name;
+                            let struct_name = *name;
                             self.advance(); // consume struct name
                             self.consume(Token::LeftBrace, "Expected '{' after struct name")?;
                             
@@ -1375,8 +1758,7 @@ impl<'input> Parser<'input> {
                             }
                             
                             self.consume(Token::RightBrace, "Expected '}' to end struct body")?;
-                            // For now, treat named struct same as anonymous struct
-                            Ok(TypeExpression::Struct(members))
+                            Ok(TypeExpression::Struct { name: Some(struct_name), members })
                         }
                         Token::LeftBrace => {
                             // Anonymous struct: struct { ... }
@@ -1392,7 +1774,7 @@ impl<'input> Parser<'input> {
                             }
                             
                             self.consume(Token::RightBrace, "Expected '}' to end struct body")?;
-                            Ok(TypeExpression::Struct(members))
+                            Ok(TypeExpression::Struct { name: None, members })
                         }
                         _ => Err(self.syntax_error("struct name or '{'", format!("{:?}", token.token)))
                     }
@@ -1400,6 +1782,12 @@ impl<'input> Parser<'input> {
                     Err(self.syntax_error("struct body", "end of input"))
                 }
             }
+            Token::Enum => {
+                // Inline enum: enum(string) Category { Blocks = "blocks" }, the name is optional
+                let (base_type, name) = self.parse_enum_header()?;
+                let variants = self.parse_enum_body()?;
+                Ok(TypeExpression::Enum { name, base_type, variants })
+            }
             Token::LeftParen => {
                 // Parenthesized type expression: (type1 | type2)
                 self.advance(); // consume (
@@ -1409,7 +1797,7 @@ impl<'input> Parser<'input> {
             }
             Token::String(s) => {
                 // String literal type constraint: #[id="test"] "literal_value"
-                let value = *s;
+                let value = s.clone();
                 self.advance();
                 Ok(TypeExpression::Literal(LiteralValue::String(value)))
             }
@@ -1433,73 +1821,24 @@ impl<'input> Parser<'input> {
         }
     }
 
-    /// Parse type constraints like @ -80..80, @ 5.., @ ..5, or @ 5
+    /// Parse type constraints like @ -80..80, @ 5.., @ ..5, @ 0..<1, or @ 5
     fn parse_type_constraints(&mut self) -> Result<Option<TypeConstraints>, ParseError> {
-        let token = self.current_token()?.token.clone();
-        
-        match token {
-            Token::Number(num) => {
+        Ok(self.parse_range(Self::number_bound)?.map(|range| TypeConstraints {
+            min: range.min,
+            max: range.max,
+            min_exclusive: range.min_exclusive,
+            max_exclusive: range.max_exclusive,
+        }))
+    }
+
+    /// Consume the number of a type constraint bound
+    fn number_bound(&mut self) -> Result<f64, ParseError> {
+        match self.current_token()?.token {
+            Token::Number(value) => {
                 self.advance();
-                
-                // Check if it's a range: -80..80 or 5..
-                if self.check_token(Token::DotDot) {
-                    self.advance(); // consume ..
-                    
-                                         let max = if !self.is_at_end() {
-                         if let Ok(next_token) = self.current_token() {
-                             if let Token::Number(n) = &next_token.token {
-                                 let num = *n;
-                                 self.advance();
-                                 Some(num)
-                             } else {
-                                 None // No max specified: 5..
-                             }
-                         } else {
-                             None
-                         }
-                     } else {
-                         None
-                     };
-                    
-                    Ok(Some(TypeConstraints {
-                        min: Some(num),
-                        max,
-                    }))
-                } else {
-                    // Just a single number: exactly this value
-                    Ok(Some(TypeConstraints {
-                        min: Some(num),
-                        max: Some(num),
-                    }))
-                }
-            }
-            Token::DotDot => {
-                // Range starting from beginning: ..80
-                self.advance(); // consume ..
-                
-                if !self.is_at_end() {
-                    if let Ok(next_token) = self.current_token() {
-                        if let Token::Number(n) = &next_token.token {
-                            let num = *n;
-                            self.advance();
-                            Ok(Some(TypeConstraints {
-                                min: None,
-                                max: Some(num),
-                            }))
-                        } else {
-                            Err(self.syntax_error("number after '..'", format!("{:?}", next_token.token)))
-                        }
-                    } else {
-                        Err(self.syntax_error("number after '..'", "end of input"))
-                    }
-                } else {
-                    Err(self.syntax_error("number after '..'", "end of input"))
-                }
-            }
-            _ => {
-                // No valid constraint found
-                Ok(None)
+                Ok(value)
             }
+            ref token => Err(self.syntax_error("number", format!("{:?}", token))),
         }
     }
 } 
\ No newline at end of file
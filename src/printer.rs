@@ -0,0 +1,514 @@
+//! Printing of parsed MCDOC back to source
+//!
+//! Output is canonical rather than faithful to the original layout: one member per
+//! line indented with tabs, annotations of declarations and members on their own
+//! lines, unions always parenthesized and comments other than `///` dropped.
+//! Parsing the output gives the same file, positions aside.
+
+use crate::parser::{
+    Annotation, AnnotationData, AnnotationValue, Declaration, DispatchTarget, DynamicReferenceType, EnumVariant,
+    ImportPath, LiteralValue, McDocFile, PathSegment, SpreadExpression, StructMember, TypeExpression,
+};
+use std::fmt::Display;
+
+/// Words lexed as keywords, accepted as field names but not as type names or dispatch keys
+const KEYWORDS: [&str; 9] = ["use", "struct", "enum", "type", "dispatch", "to", "super", "true", "false"];
+
+/// MCDOC source of a parsed file
+pub fn print(file: &McDocFile) -> String {
+    let mut printer = Printer::new(false);
+    printer.file(file);
+    printer.output
+}
+
+/// MCDOC syntax of a type on one line, struct and enum bodies written `{...}`
+pub fn type_summary(type_expr: &TypeExpression) -> String {
+    let mut printer = Printer::new(true);
+    printer.type_expression(type_expr);
+    printer.output
+}
+
+/// MCDOC syntax of one declaration, as `print` writes it
+pub(crate) fn declaration(declaration: &Declaration) -> String {
+    let mut printer = Printer::new(false);
+    printer.declaration(declaration);
+    printer.output
+}
+
+/// `#[name]`, `#[name="value"]`, `#[name(key="value")]` or `#[name=[...]]`
+pub(crate) fn annotation(annotation: &Annotation) -> String {
+    match &annotation.data {
+        AnnotationData::Empty => format!("#[{}]", annotation.name),
+        AnnotationData::Simple(value) => format!("#[{}={}]", annotation.name, quoted(value)),
+        AnnotationData::Complex(entries) => {
+            // Keys are sorted, the map doesn't keep the written order
+            let mut entries: Vec<_> = entries.iter().collect();
+            entries.sort_by_key(|(key, _)| **key);
+            let entries: Vec<String> = entries.into_iter()
+                .map(|(key, value)| format!("{}={}", key, annotation_value(value)))
+                .collect();
+            format!("#[{}({})]", annotation.name, entries.join(", "))
+        }
+        AnnotationData::List(values) => format!("#[{}={}]", annotation.name, annotation_list(values)),
+    }
+}
+
+pub(crate) fn literal(literal: &LiteralValue) -> String {
+    match literal {
+        LiteralValue::String(text) => quoted(text),
+        LiteralValue::Number(number) => number.to_string(),
+        LiteralValue::Boolean(value) => value.to_string(),
+    }
+}
+
+/// Dispatch key as written between brackets, `%unknown` for `DispatchTarget::Unknown`
+pub(crate) fn dispatch_target(target: &DispatchTarget) -> String {
+    match target {
+        DispatchTarget::Specific(key) => dispatch_key(key),
+        DispatchTarget::Unknown => "%unknown".to_string(),
+        DispatchTarget::None => "%none".to_string(),
+        DispatchTarget::Special(name) => format!("%{}", name),
+    }
+}
+
+/// Specific dispatch key, quoted unless it reads as an identifier
+pub(crate) fn dispatch_key(key: &str) -> String {
+    if is_identifier(key) {
+        key.to_string()
+    } else {
+        format!("\"{}\"", key)
+    }
+}
+
+/// What a spread member expands on one line, without the `...`
+pub(crate) fn spread_summary(spread: &SpreadExpression) -> String {
+    let mut printer = Printer::new(true);
+    printer.spread_target(spread);
+    printer.output
+}
+
+fn annotation_value(value: &AnnotationValue) -> String {
+    match value {
+        AnnotationValue::String(text) => quoted(text),
+        AnnotationValue::Number(number) => number.to_string(),
+        AnnotationValue::Boolean(value) => value.to_string(),
+        AnnotationValue::List(values) => annotation_list(values),
+    }
+}
+
+/// String between quotes, with the escapes the lexer decodes written back
+fn quoted(text: &str) -> String {
+    let mut output = String::with_capacity(text.len() + 2);
+    output.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            _ => output.push(ch),
+        }
+    }
+    output.push('"');
+    output
+}
+
+fn annotation_list(values: &[AnnotationValue]) -> String {
+    let values: Vec<String> = values.iter().map(annotation_value).collect();
+    format!("[{}]", values.join(", "))
+}
+
+fn import_path(path: &ImportPath) -> String {
+    match path {
+        ImportPath::Absolute(segments) => format!("::{}", segments.join("::")),
+        ImportPath::Relative { supers, segments } => format!("{}{}", "super::".repeat(*supers), segments.join("::")),
+    }
+}
+
+/// Lexed as an identifier rather than a keyword, number or punctuation
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars.next().is_some_and(|first| first.is_alphabetic() || first == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+        && !KEYWORDS.contains(&text)
+}
+
+/// Field name, quoted unless it reads as an identifier or keyword
+pub(crate) fn field_name(name: &str) -> String {
+    if is_identifier(name) || KEYWORDS.contains(&name) {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name)
+    }
+}
+
+/// `3`, `1..3`, `1<..`, `..<3`, ...
+pub(crate) fn range<T: PartialEq + Display>(min: Option<T>, max: Option<T>, min_exclusive: bool, max_exclusive: bool) -> String {
+    match (&min, &max) {
+        (Some(min), Some(max)) if min == max && !min_exclusive && !max_exclusive => min.to_string(),
+        _ => format!(
+            "{}{}..{}{}",
+            min.map(|min| min.to_string()).unwrap_or_default(),
+            if min_exclusive { "<" } else { "" },
+            if max_exclusive { "<" } else { "" },
+            max.map(|max| max.to_string()).unwrap_or_default(),
+        ),
+    }
+}
+
+struct Printer {
+    output: String,
+    indent: usize,
+    /// Write struct and enum bodies as `{...}`
+    elide_bodies: bool,
+}
+
+impl Printer {
+    fn new(elide_bodies: bool) -> Self {
+        Self { output: String::new(), indent: 0, elide_bodies }
+    }
+
+    fn write(&mut self, text: &str) {
+        self.output.push_str(text);
+    }
+
+    /// Start a line at the current indentation
+    fn line_start(&mut self) {
+        for _ in 0..self.indent {
+            self.output.push('\t');
+        }
+    }
+
+    fn file(&mut self, file: &McDocFile) {
+        for import in &file.imports {
+            self.write("use ");
+            self.write(&import_path(&import.path));
+            if let Some(alias) = import.alias {
+                self.write(" as ");
+                self.write(alias);
+            }
+            self.write("\n");
+        }
+
+        for (index, declaration) in file.declarations.iter().enumerate() {
+            if index > 0 || !file.imports.is_empty() {
+                self.write("\n");
+            }
+            self.declaration(declaration);
+            self.write("\n");
+        }
+    }
+
+    fn declaration(&mut self, declaration: &Declaration) {
+        match declaration {
+            Declaration::Struct(struct_decl) => {
+                self.prelude(&struct_decl.docs, &struct_decl.annotations);
+                self.write("struct ");
+                self.write(struct_decl.name);
+                self.write(" ");
+                self.struct_body(&struct_decl.members);
+            }
+            Declaration::Enum(enum_decl) => {
+                self.prelude(&enum_decl.docs, &enum_decl.annotations);
+                self.enum_header(Some(enum_decl.name), enum_decl.base_type);
+                self.enum_body(&enum_decl.variants);
+            }
+            Declaration::Type(type_decl) => {
+                self.prelude(&type_decl.docs, &type_decl.annotations);
+                self.write("type ");
+                self.write(type_decl.name);
+                if !type_decl.type_params.is_empty() {
+                    self.write("<");
+                    self.write(&type_decl.type_params.join(", "));
+                    self.write(">");
+                }
+                self.write(" = ");
+                self.type_expression(&type_decl.type_expr);
+            }
+            Declaration::Dispatch(dispatch) => {
+                self.prelude(&dispatch.docs, &dispatch.annotations);
+                self.write("dispatch ");
+                self.write(dispatch.source.namespace);
+                self.write(":");
+                self.write(dispatch.source.registry);
+                if !dispatch.targets.is_empty() {
+                    let keys: Vec<String> = dispatch.targets.iter().map(dispatch_target).collect();
+                    self.write("[");
+                    self.write(&keys.join(", "));
+                    self.write("]");
+                }
+                self.write(" to ");
+                self.type_expression(&dispatch.target_type);
+            }
+        }
+    }
+
+    /// Doc comments and annotations, each on its own line, then the start of the next line
+    fn prelude(&mut self, docs: &[&str], annotations: &[Annotation]) {
+        for doc in docs {
+            self.write("/// ");
+            self.write(doc);
+            self.write("\n");
+            self.line_start();
+        }
+        for written in annotations {
+            self.write(&annotation(written));
+            self.write("\n");
+            self.line_start();
+        }
+    }
+
+    fn struct_body(&mut self, members: &[StructMember]) {
+        if self.elide_bodies {
+            self.write("{...}");
+            return;
+        }
+        if members.is_empty() {
+            self.write("{}");
+            return;
+        }
+
+        self.write("{\n");
+        self.indent += 1;
+        for member in members {
+            self.line_start();
+            self.member(member);
+            self.write(",\n");
+        }
+        self.indent -= 1;
+        self.line_start();
+        self.write("}");
+    }
+
+    fn member(&mut self, member: &StructMember) {
+        match member {
+            StructMember::Field(field) => {
+                self.prelude(&field.docs, &field.annotations);
+                self.write(&field_name(field.name));
+                if field.optional {
+                    self.write("?");
+                }
+                self.write(": ");
+                self.type_expression(&field.field_type);
+            }
+            StructMember::DynamicField(dynamic_field) => {
+                self.prelude(&[], &dynamic_field.annotations);
+                self.write("[");
+                self.type_expression(&dynamic_field.key_type);
+                self.write("]");
+                if dynamic_field.optional {
+                    self.write("?");
+                }
+                self.write(": ");
+                self.type_expression(&dynamic_field.value_type);
+            }
+            StructMember::Spread(spread) => {
+                self.prelude(&[], &spread.annotations);
+                self.write("...");
+                self.spread_target(spread);
+            }
+        }
+    }
+
+    /// What a spread member expands, without the `...`
+    fn spread_target(&mut self, spread: &SpreadExpression) {
+        match spread.target.as_deref() {
+            // Spreads keep the import path they were written with
+            Some(TypeExpression::Reference(path)) => self.write(&import_path(path)),
+            Some(TypeExpression::Struct { name: None, members }) => {
+                self.write("struct ");
+                self.struct_body(members);
+            }
+            Some(target) => self.type_expression(target),
+            None => {
+                self.write(spread.namespace);
+                self.write(":");
+                self.write(spread.registry);
+            }
+        }
+        if let Some(dynamic_key) = &spread.dynamic_key {
+            self.dynamic_reference(&dynamic_key.reference);
+        }
+    }
+
+    fn dynamic_reference(&mut self, reference: &DynamicReferenceType) {
+        let path = match reference {
+            DynamicReferenceType::Field(field) => field_name(field),
+            DynamicReferenceType::SpecialKey(key) => format!("%{}", key),
+            DynamicReferenceType::Path(segments) => {
+                let segments: Vec<String> = segments.iter()
+                    .map(|segment| match segment {
+                        PathSegment::Field(field) => field_name(field),
+                        PathSegment::Parent => "%parent".to_string(),
+                        PathSegment::Key => "%key".to_string(),
+                    })
+                    .collect();
+                segments.join(".")
+            }
+        };
+        self.write("[[");
+        self.write(&path);
+        self.write("]]");
+    }
+
+    fn enum_header(&mut self, name: Option<&str>, base_type: Option<&str>) {
+        self.write("enum");
+        if let Some(base_type) = base_type {
+            self.write("(");
+            self.write(base_type);
+            self.write(")");
+        }
+        if let Some(name) = name {
+            self.write(" ");
+            self.write(name);
+        }
+        self.write(" ");
+    }
+
+    fn enum_body(&mut self, variants: &[EnumVariant]) {
+        if self.elide_bodies {
+            self.write("{...}");
+            return;
+        }
+        if variants.is_empty() {
+            self.write("{}");
+            return;
+        }
+
+        self.write("{\n");
+        self.indent += 1;
+        for variant in variants {
+            self.line_start();
+            self.prelude(&variant.docs, &variant.annotations);
+            self.write(variant.name);
+            if let Some(value) = &variant.value {
+                self.write(" = ");
+                self.write(&literal(value));
+            }
+            self.write(",\n");
+        }
+        self.indent -= 1;
+        self.line_start();
+        self.write("}");
+    }
+
+    fn parenthesized(&mut self, type_expr: &TypeExpression) {
+        self.write("(");
+        self.type_expression(type_expr);
+        self.write(")");
+    }
+
+    /// Type where only a single type parses (union branches, generic arguments, the
+    /// type under annotations or constraints), parenthesized when it's more than that
+    fn single_type(&mut self, type_expr: &TypeExpression) {
+        match type_expr {
+            TypeExpression::Constrained { .. } | TypeExpression::Annotated { .. } => self.parenthesized(type_expr),
+            _ => self.type_expression(type_expr),
+        }
+    }
+
+    fn type_expression(&mut self, type_expr: &TypeExpression) {
+        match type_expr {
+            TypeExpression::Simple(name) => self.write(name),
+            TypeExpression::Array { element_type, constraints } => {
+                self.write("[");
+                // `[int @ 1..3]` constrains the element, no parentheses needed
+                match element_type.as_ref() {
+                    TypeExpression::Constrained { base_type, constraints } => {
+                        self.constrained_base(base_type);
+                        self.write(" @ ");
+                        self.write(&range(constraints.min, constraints.max, constraints.min_exclusive, constraints.max_exclusive));
+                    }
+                    element_type => self.annotated_or_single(element_type),
+                }
+                self.write("]");
+                if let Some(constraints) = constraints {
+                    self.write(" @ ");
+                    self.write(&range(constraints.min, constraints.max, constraints.min_exclusive, constraints.max_exclusive));
+                }
+            }
+            TypeExpression::Union(types) => {
+                self.write("(");
+                for (index, branch) in types.iter().enumerate() {
+                    if index > 0 {
+                        self.write(" | ");
+                    }
+                    self.annotated_or_single(branch);
+                }
+                // A lone branch stays a union with its trailing pipe
+                if types.len() == 1 {
+                    self.write(" |");
+                }
+                self.write(")");
+            }
+            TypeExpression::Struct { name, members } => {
+                self.write("struct ");
+                if let Some(name) = name {
+                    self.write(name);
+                    self.write(" ");
+                }
+                self.struct_body(members);
+            }
+            TypeExpression::Generic { name, type_args } => {
+                self.write(name);
+                self.write("<");
+                for (index, type_arg) in type_args.iter().enumerate() {
+                    if index > 0 {
+                        self.write(", ");
+                    }
+                    self.annotated_or_single(type_arg);
+                }
+                self.write(">");
+            }
+            // Types parse `minecraft:block_entity[moving_piston]` as an absolute path of its parts
+            TypeExpression::Reference(ImportPath::Absolute(segments)) => match segments.as_slice() {
+                [namespace, registry, key] => self.write(&format!("{}:{}[{}]", namespace, registry, key)),
+                [namespace, registry] => self.write(&format!("{}:{}", namespace, registry)),
+                _ => self.write(&format!("::{}", segments.join("::"))),
+            },
+            TypeExpression::Reference(path) => self.write(&import_path(path)),
+            TypeExpression::Spread(spread) => {
+                // `...minecraft:item` or `minecraft:item[[type]]`, spreads in types have no target
+                if spread.dynamic_key.is_none() {
+                    self.write("...");
+                }
+                self.spread_target(spread);
+            }
+            TypeExpression::Literal(value) => self.write(&literal(value)),
+            TypeExpression::Enum { name, base_type, variants } => {
+                self.enum_header(*name, *base_type);
+                self.enum_body(variants);
+            }
+            TypeExpression::Annotated { annotations, type_expr } => {
+                for written in annotations {
+                    self.write(&annotation(written));
+                    self.write(" ");
+                }
+                self.single_type(type_expr);
+            }
+            TypeExpression::Constrained { base_type, constraints } => {
+                self.constrained_base(base_type);
+                self.write(" @ ");
+                self.write(&range(constraints.min, constraints.max, constraints.min_exclusive, constraints.max_exclusive));
+            }
+        }
+    }
+
+    /// Union branch or generic argument: annotations are part of a single type
+    fn annotated_or_single(&mut self, type_expr: &TypeExpression) {
+        match type_expr {
+            TypeExpression::Annotated { .. } => self.type_expression(type_expr),
+            _ => self.single_type(type_expr),
+        }
+    }
+
+    /// Type under a `@` constraint. An array would take the constraint as its size.
+    fn constrained_base(&mut self, base_type: &TypeExpression) {
+        match base_type {
+            TypeExpression::Array { .. } | TypeExpression::Constrained { .. } => self.parenthesized(base_type),
+            TypeExpression::Annotated { .. } => self.type_expression(base_type),
+            _ => self.single_type(base_type),
+        }
+    }
+}
@@ -1,9 +1,12 @@
 //! Minecraft registry management
 
-use crate::RegistryDependency;
+use crate::{RegistryDependency, ResourceId};
+use crate::types::JsonPath;
 use crate::error::ParseError;
+use crate::version::compare_versions;
 use rustc_hash::FxHashMap;
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 /// Minecraft registry with its entries
 #[derive(Debug, Clone)]
@@ -35,14 +38,58 @@ impl Registry {
         self.tags.contains_key(tag_name)
     }
     
-    /// Load from JSON (vanilla registries format)
+    /// Concrete entries of a tag, following nested `#tag` references.
+    /// Fails on unknown nested tags and on circular references.
+    pub fn resolve_tag(&self, tag: &str) -> Result<HashSet<String>, ParseError> {
+        let mut entries = HashSet::new();
+        let mut visiting = Vec::new();
+        self.collect_tag_entries(tag.strip_prefix('#').unwrap_or(tag), &mut visiting, &mut entries)?;
+        Ok(entries)
+    }
+    
+    fn collect_tag_entries(&self, tag: &str, visiting: &mut Vec<String>, entries: &mut HashSet<String>) -> Result<(), ParseError> {
+        if visiting.iter().any(|visited| visited == tag) {
+            let cycle: Vec<String> = visiting.iter().chain(std::iter::once(&tag.to_string()))
+                .map(|visited| format!("#{}", visited))
+                .collect();
+            return Err(ParseError::validation(format!("Circular tag reference: {}", cycle.join(" -> ")), format!("#{}", tag)));
+        }
+        let values = self.tags.get(tag)
+            .ok_or_else(|| ParseError::validation(format!("Unknown tag '#{}' in registry '{}'", tag, self.name), format!("#{}", tag)))?;
+        
+        visiting.push(tag.to_string());
+        for value in values {
+            match value.strip_prefix('#') {
+                Some(nested) => self.collect_tag_entries(nested, visiting, entries)?,
+                None => {
+                    entries.insert(value.clone());
+                }
+            }
+        }
+        visiting.pop();
+        Ok(())
+    }
+    
+    /// Load from JSON, accepting `{"entries": {...}, "tags": {...}}` (entries may also be
+    /// an array), a plain array of ids like `["minecraft:stone", ...]`, and the vanilla
+    /// `registries.json` shape `{"entries": {"minecraft:stone": {"protocol_id": 1}}, "protocol_id": 0}`
     pub fn from_json(name: String, version: String, json: &serde_json::Value) -> Result<Self, ParseError> {
         let mut registry = Registry::new(name, version);
         
-        if let Some(entries) = json.get("entries").and_then(|e| e.as_object()) {
-            for key in entries.keys() {
-                registry.entries.insert(key.clone());
-            }
+        let entries = match json {
+            serde_json::Value::Array(_) => json,
+            serde_json::Value::Object(object) => object.get("entries").unwrap_or(&serde_json::Value::Null),
+            _ => return Err(ParseError::validation(
+                format!("Registry '{}' must be an object or an array of ids", registry.name),
+                registry.name.as_str(),
+            )),
+        };
+        match entries {
+            serde_json::Value::Object(entries) => registry.entries.extend(entries.keys().cloned()),
+            serde_json::Value::Array(entries) => registry.entries.extend(
+                entries.iter().filter_map(|entry| entry.as_str().map(|id| id.to_string()))
+            ),
+            _ => {}
         }
         
         if let Some(tags) = json.get("tags").and_then(|t| t.as_object()) {
@@ -61,9 +108,68 @@ impl Registry {
     }
 }
 
+/// Data attached to the entries of a registry, like the block states of each block
+/// for `mcdoc:block_states`. Maps an entry to its properties and their allowed values.
+#[derive(Debug, Clone, Default)]
+pub struct AttachedRegistry {
+    pub entries: HashMap<String, HashMap<String, Vec<String>>>,
+}
+
+impl AttachedRegistry {
+    /// Load from JSON, each entry maps property names to their values:
+    /// `{ "minecraft:furnace": { "facing": ["north", "south"], "lit": ["true", "false"] } }`.
+    /// The `[properties, defaults]` pairs of the mcmeta block states summary are accepted too.
+    pub fn from_json(json: &serde_json::Value) -> Result<Self, ParseError> {
+        let entries = json.as_object()
+            .ok_or_else(|| ParseError::validation("Attached registry must be a JSON object", ""))?;
+
+        let mut registry = AttachedRegistry::default();
+        for (entry, data) in entries {
+            let properties = match data {
+                serde_json::Value::Array(pair) => pair.first().and_then(|first| first.as_object()),
+                _ => data.as_object(),
+            }
+            .ok_or_else(|| ParseError::validation(format!("Invalid properties for '{}'", entry), entry.as_str()))?;
+
+            let properties = properties.iter()
+                .map(|(name, values)| {
+                    let values = values.as_array()
+                        .map(|values| values.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+                        .unwrap_or_default();
+                    (name.clone(), values)
+                })
+                .collect();
+            registry.entries.insert(Self::normalize(entry), properties);
+        }
+        Ok(registry)
+    }
+
+    /// Properties of an entry, a missing namespace means `minecraft`
+    pub fn properties(&self, entry: &str) -> Option<&HashMap<String, Vec<String>>> {
+        self.entries.get(&Self::normalize(entry))
+    }
+
+    fn normalize(entry: &str) -> String {
+        if entry.contains(':') {
+            entry.to_string()
+        } else {
+            format!("minecraft:{}", entry)
+        }
+    }
+}
+
 /// Manager for all registries
+///
+/// Clones share the loaded registries, a registry is copied the first time a clone
+/// changes it, like when loading a tag file.
+#[derive(Clone)]
 pub struct RegistryManager {
-    registries: FxHashMap<String, Registry>,
+    /// Loaded versions of each registry, oldest first
+    registries: FxHashMap<String, Arc<Vec<Registry>>>,
+    /// Attached data by meta-registry name (e.g. "mcdoc:block_states")
+    attached_registries: FxHashMap<String, Arc<AttachedRegistry>>,
+    /// Resources provided by the datapack being validated, by registry
+    local_resources: FxHashMap<String, HashSet<String>>,
 }
 
 impl RegistryManager {
@@ -71,6 +177,8 @@ impl RegistryManager {
     pub fn new() -> Self {
         Self {
             registries: FxHashMap::default(),
+            attached_registries: FxHashMap::default(),
+            local_resources: FxHashMap::default(),
         }
     }
     
@@ -82,18 +190,57 @@ impl RegistryManager {
         json: &serde_json::Value,
     ) -> Result<(), ParseError> {
         let registry = Registry::from_json(name, version, json)?;
-        self.registries.insert(registry.name.clone(), registry);
+        let versions = Arc::make_mut(self.registries.entry(registry.name.clone()).or_default());
+        match versions.binary_search_by(|loaded| Self::order_versions(&loaded.version, &registry.version)) {
+            Ok(index) => versions[index] = registry,
+            Err(index) => versions.insert(index, registry),
+        }
         Ok(())
     }
+
+    /// Load every registry of a multi-registry document like `registries.json` or
+    /// `{"item": [...], "block": [...]}`. The `minecraft:` prefix of registry names is
+    /// dropped, so `minecraft:item` is loaded as `item`. Returns the number loaded.
+    pub fn load_registries_bundle(&mut self, version: &str, json: &serde_json::Value) -> Result<usize, ParseError> {
+        let registries = json.as_object()
+            .ok_or_else(|| ParseError::validation("Registries bundle must be a JSON object", ""))?;
+        for (name, registry) in registries {
+            let name = name.strip_prefix("minecraft:").unwrap_or(name);
+            self.load_registry_from_json(name.to_string(), version.to_string(), registry)?;
+        }
+        Ok(registries.len())
+    }
+
+    /// Release order of registry versions, unparsable parts are compared as text
+    fn order_versions(a: &str, b: &str) -> std::cmp::Ordering {
+        compare_versions(a, b).then_with(|| a.cmp(b))
+    }
+
+    /// A registry in the given version, or its latest loaded version when that
+    /// version isn't loaded or none is given
+    pub fn registry(&self, name: &str, version: Option<&str>) -> Option<&Registry> {
+        let versions = self.registries.get(name)?;
+        version
+            .and_then(|version| versions.iter().find(|registry| registry.version == version))
+            .or_else(|| versions.last())
+    }
+
+    /// Loaded versions of a registry, oldest first
+    pub fn registry_versions(&self, name: &str) -> Vec<&str> {
+        self.registries.get(name)
+            .map(|versions| versions.iter().map(|registry| registry.version.as_str()).collect())
+            .unwrap_or_default()
+    }
     
-    /// Validate a resource location in a registry
+    /// Validate a resource location in a registry, for a version (see `registry`)
     pub fn validate_resource_location(
         &self,
         registry_name: &str,
         resource_location: &str,
         is_tag: bool,
+        version: Option<&str>,
     ) -> Result<bool, ParseError> {
-        self.validate_resource_location_with_namespace(registry_name, resource_location, is_tag, None)
+        self.validate_resource_location_with_namespace(registry_name, resource_location, is_tag, version, None)
     }
     
     /// Validate a resource location with configurable namespace
@@ -102,9 +249,13 @@ impl RegistryManager {
         registry_name: &str,
         resource_location: &str,
         is_tag: bool,
+        version: Option<&str>,
         default_namespace: Option<&str>,
     ) -> Result<bool, ParseError> {
-        let registry = self.registries.get(registry_name)
+        if !is_tag && self.is_local_resource(registry_name, resource_location) {
+            return Ok(true);
+        }
+        let registry = self.registry(registry_name, version)
             .ok_or_else(|| ParseError::validation(
                 format!("Unknown registry '{}'", registry_name),
                 format!("Resource location: {}", resource_location)
@@ -117,60 +268,134 @@ impl RegistryManager {
                 resource_location
             };
             
-            Ok(registry.contains_tag(tag_name))
+            Ok(Self::contains_with_namespace(tag_name, default_namespace, |name| registry.contains_tag(name)))
         } else {
-            let found = registry.contains(resource_location);
-            
-            if let Some(namespace) = default_namespace {
-                let namespace_prefix = format!("{}:", namespace);
-                
-                if !found && resource_location.starts_with(&namespace_prefix) {
-                    let bare_name = &resource_location[namespace_prefix.len()..];
-                    Ok(registry.contains(bare_name))
-                } else if !found && !resource_location.contains(':') {
-                    let prefixed_name = format!("{}{}", namespace_prefix, resource_location);
-                    Ok(registry.contains(&prefixed_name))
-                } else {
-                    Ok(found)
-                }
+            Ok(Self::contains_with_namespace(resource_location, default_namespace, |name| registry.contains(name)))
+        }
+    }
+
+    /// Whether a loaded registry lists a resource or tag, local resources aside
+    pub fn registry_contains(
+        &self,
+        registry_name: &str,
+        resource_location: &str,
+        is_tag: bool,
+        version: Option<&str>,
+        default_namespace: Option<&str>,
+    ) -> bool {
+        let Some(registry) = self.registry(registry_name, version) else {
+            return false;
+        };
+        if is_tag {
+            let tag_name = resource_location.strip_prefix('#').unwrap_or(resource_location);
+            Self::contains_with_namespace(tag_name, default_namespace, |name| registry.contains_tag(name))
+        } else {
+            Self::contains_with_namespace(resource_location, default_namespace, |name| registry.contains(name))
+        }
+    }
+
+    /// Look a name up as written, then with the default namespace added or removed
+    fn contains_with_namespace(name: &str, default_namespace: Option<&str>, contains: impl Fn(&str) -> bool) -> bool {
+        if contains(name) {
+            return true;
+        }
+        let Some(namespace) = default_namespace else {
+            return false;
+        };
+        match name.split_once(':') {
+            Some((prefix, bare_name)) if prefix == namespace => contains(bare_name),
+            Some(_) => false,
+            None => contains(&format!("{}:{}", namespace, name)),
+        }
+    }
+    
+    /// Load a datapack tag file like `{"values": ["minecraft:stone", "#minecraft:logs"], "replace": false}`
+    /// into the tags of a loaded registry. Entries may also be written `{"id": ..., "required": false}`.
+    /// The values are merged into an existing tag unless `replace` is true, in every
+    /// loaded version of the registry.
+    pub fn load_tag_file(&mut self, registry: &str, tag_id: &str, json: &serde_json::Value) -> Result<(), ParseError> {
+        let tag_id = tag_id.strip_prefix('#').unwrap_or(tag_id);
+        let tag_id = if tag_id.contains(':') { tag_id.to_string() } else { format!("minecraft:{}", tag_id) };
+        let versions = self.registries.get_mut(registry)
+            .map(Arc::make_mut)
+            .ok_or_else(|| ParseError::validation(format!("Unknown registry '{}'", registry), format!("#{}", tag_id)))?;
+        
+        let values = json.get("values").and_then(|values| values.as_array())
+            .ok_or_else(|| ParseError::validation("Tag file must have a 'values' array", format!("#{}", tag_id)))?;
+        let entries = values.iter()
+            .map(|value| {
+                let id = match value {
+                    serde_json::Value::Object(entry) => entry.get("id").and_then(|id| id.as_str()),
+                    _ => value.as_str(),
+                };
+                id.map(|id| id.to_string())
+                    .ok_or_else(|| ParseError::validation(format!("Invalid tag entry {}", value), format!("#{}", tag_id)))
+            })
+            .collect::<Result<Vec<String>, ParseError>>()?;
+        
+        let replace = json.get("replace").and_then(|replace| replace.as_bool()).unwrap_or(false);
+        for target in versions {
+            let tag = target.tags.entry(tag_id.clone()).or_default();
+            if replace {
+                tag.clone_from(&entries);
             } else {
-                Ok(found)
+                for entry in &entries {
+                    if !tag.contains(entry) {
+                        tag.push(entry.clone());
+                    }
+                }
             }
         }
+        Ok(())
+    }
+    
+    /// Expand a tag of a registry to its concrete entries (see `Registry::resolve_tag`)
+    pub fn expand_tag(&self, registry_name: &str, tag: &str, version: Option<&str>) -> Result<HashSet<String>, ParseError> {
+        let registry = self.registry(registry_name, version)
+            .ok_or_else(|| ParseError::validation(
+                format!("Unknown registry '{}'", registry_name),
+                format!("Tag: {}", tag)
+            ))?;
+        registry.resolve_tag(tag)
     }
     
     /// Pre-scan a JSON to detect necessary registry types
     pub fn scan_required_registries(&self, json: &serde_json::Value) -> Vec<RegistryDependency> {
+        self.scan_required_registries_with_mapping(json, &HashMap::new())
+    }
+    
+    /// Pre-scan a JSON, resolving registry types from a path -> registry mapping
+    /// (see `create_registry_mapping_from_config`)
+    pub fn scan_required_registries_with_mapping(&self, json: &serde_json::Value, registry_mapping: &HashMap<String, String>) -> Vec<RegistryDependency> {
         let mut registries = Vec::new();
-        let empty_mapping = HashMap::new();
-        self.scan_json_simple(json, "", &mut registries, &empty_mapping);
+        self.scan_json_simple(json, &JsonPath::root(), &mut registries, registry_mapping);
         registries
     }
     
     /// Simplified JSON scan
-    fn scan_json_simple(&self, value: &serde_json::Value, path: &str, registries: &mut Vec<RegistryDependency>, registry_mapping: &HashMap<String, String>) {
+    fn scan_json_simple(&self, value: &serde_json::Value, path: &JsonPath, registries: &mut Vec<RegistryDependency>, registry_mapping: &HashMap<String, String>) {
         match value {
-            serde_json::Value::String(s) => {
-                if s.contains(':') && (s.starts_with('#') || s.chars().all(|c| c.is_alphanumeric() || c == ':' || c == '_' || c == '/')) {
-                    let is_tag = s.starts_with('#');
-                    let registry_type = self.infer_registry_with_mapping(path, registry_mapping);
-                    
-                    registries.push(RegistryDependency {
-                        registry: registry_type,
-                        identifier: s.clone(),
-                        is_tag,
-                    });
-                }
+            serde_json::Value::String(s) if s.contains(':') && ResourceId::is_valid(s) => {
+                let is_tag = s.starts_with('#');
+                let source_path = path.to_string();
+                let registry_type = self.infer_registry_with_mapping(&source_path, registry_mapping);
+                
+                registries.push(RegistryDependency {
+                    registry: registry_type,
+                    identifier: s.clone(),
+                    is_tag,
+                    source_path,
+                    pointer: path.to_pointer(),
+                });
             }
             serde_json::Value::Object(obj) => {
                 for (key, val) in obj {
-                    let new_path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
-                    self.scan_json_simple(val, &new_path, registries, registry_mapping);
+                    self.scan_json_simple(val, &path.key(key), registries, registry_mapping);
                 }
             }
             serde_json::Value::Array(arr) => {
-                for val in arr {
-                    self.scan_json_simple(val, path, registries, registry_mapping);
+                for (i, val) in arr.iter().enumerate() {
+                    self.scan_json_simple(val, &path.index(i), registries, registry_mapping);
                 }
             }
             _ => {}
@@ -182,12 +407,65 @@ impl RegistryManager {
             return mapped_type.clone();
         }
         
+        // Mappings are written without array indices: "ingredients[1].item" -> "ingredients.item"
+        if path.contains('[') {
+            let mut unindexed = String::with_capacity(path.len());
+            let mut in_index = false;
+            for ch in path.chars() {
+                match ch {
+                    '[' => in_index = true,
+                    ']' => in_index = false,
+                    _ if !in_index => unindexed.push(ch),
+                    _ => {}
+                }
+            }
+            if let Some(mapped_type) = registry_mapping.get(&unindexed) {
+                return mapped_type.clone();
+            }
+        }
+        
         "unknown".to_string()
     }
     
+    /// Unload every version of a registry, returns whether it was loaded
+    pub fn unload_registry(&mut self, name: &str) -> bool {
+        self.registries.remove(name).is_some()
+    }
+
     pub fn has_registry(&self, name: &str) -> bool {
         self.registries.contains_key(name)
     }
+
+    /// Register data attached to registry entries under a meta-registry name
+    pub fn register_attached_registry(&mut self, name: String, provider: AttachedRegistry) {
+        self.attached_registries.insert(name, Arc::new(provider));
+    }
+
+    pub fn attached_registry(&self, name: &str) -> Option<&AttachedRegistry> {
+        self.attached_registries.get(name).map(Arc::as_ref)
+    }
+    
+    /// Register resources defined by a datapack, they are valid entries of `registry`
+    /// whether or not it is loaded. Ids without namespace are in `minecraft`.
+    pub fn register_local_resources(&mut self, registry: &str, ids: impl IntoIterator<Item = String>) {
+        self.local_resources.entry(registry.to_string())
+            .or_default()
+            .extend(ids.into_iter().map(|id| Self::normalize_id(&id)));
+    }
+
+    pub fn is_local_resource(&self, registry: &str, resource_location: &str) -> bool {
+        self.local_resources.get(registry)
+            .is_some_and(|ids| ids.contains(&Self::normalize_id(resource_location)))
+    }
+
+    /// Forget every registered datapack resource
+    pub fn clear_local_resources(&mut self) {
+        self.local_resources.clear();
+    }
+
+    fn normalize_id(id: &str) -> String {
+        if id.contains(':') { id.to_string() } else { format!("minecraft:{}", id) }
+    }
     
     pub fn create_registry_mapping_from_config(config: Vec<(String, String)>) -> HashMap<String, String> {
         config.into_iter().collect()
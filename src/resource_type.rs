@@ -0,0 +1,173 @@
+//! Resource type inference from datapack and resourcepack file paths
+
+/// Maps file paths to the resource type used to find their MCDOC dispatch
+///
+/// Patterns are `/`-separated globs: `*` matches within a single path segment and
+/// `**` matches any number of segments. A pattern may match from any segment of the
+/// path, so `data/*/recipe/**` also matches `my_pack/data/demo/recipe/stone.json`.
+#[derive(Debug, Clone)]
+pub struct ResourceTypeResolver {
+    /// Custom mappings, checked before the defaults, latest first
+    custom: Vec<(String, String)>,
+    defaults: Vec<(String, String)>,
+}
+
+/// Datapack folders whose name is the resource type, with their pre-1.21 plural name if any
+const DATA_FOLDERS: &[(&str, Option<&str>)] = &[
+    ("advancement", Some("advancements")),
+    ("banner_pattern", None),
+    ("cat_variant", None),
+    ("chat_type", None),
+    ("chicken_variant", None),
+    ("cow_variant", None),
+    ("damage_type", None),
+    ("dialog", None),
+    ("dimension", None),
+    ("dimension_type", None),
+    ("enchantment", None),
+    ("enchantment_provider", None),
+    ("frog_variant", None),
+    ("instrument", None),
+    ("item_modifier", Some("item_modifiers")),
+    ("jukebox_song", None),
+    ("loot_table", Some("loot_tables")),
+    ("painting_variant", None),
+    ("pig_variant", None),
+    ("predicate", Some("predicates")),
+    ("recipe", Some("recipes")),
+    ("test_environment", None),
+    ("test_instance", None),
+    ("trial_spawner", None),
+    ("trim_material", None),
+    ("trim_pattern", None),
+    ("wolf_sound_variant", None),
+    ("wolf_variant", None),
+];
+
+/// Worldgen registries stored under `data/<namespace>/worldgen/`
+const WORLDGEN_FOLDERS: &[&str] = &[
+    "biome",
+    "configured_carver",
+    "configured_feature",
+    "density_function",
+    "flat_level_generator_preset",
+    "multi_noise_biome_source_parameter_list",
+    "noise",
+    "noise_settings",
+    "placed_feature",
+    "processor_list",
+    "structure",
+    "structure_set",
+    "template_pool",
+    "world_preset",
+];
+
+/// Resourcepack patterns and their resource type
+const ASSET_PATTERNS: &[(&str, &str)] = &[
+    ("assets/*/atlases/**", "atlas"),
+    ("assets/*/blockstates/**", "block_definition"),
+    ("assets/*/equipment/**", "equipment"),
+    ("assets/*/font/**/*.json", "font"),
+    ("assets/*/items/**", "item_definition"),
+    ("assets/*/lang/**", "lang"),
+    ("assets/*/models/**", "model"),
+    ("assets/*/particles/**", "particle"),
+    ("assets/*/post_effect/**", "post_effect"),
+    ("assets/*/shaders/**/*.json", "shader"),
+    ("assets/*/sounds.json", "sounds"),
+    ("assets/*/textures/**/*.mcmeta", "texture_meta"),
+    ("assets/*/waypoint_style/**", "waypoint_style"),
+];
+
+impl ResourceTypeResolver {
+    /// Create a resolver with the vanilla datapack and resourcepack layouts
+    pub fn new() -> Self {
+        let mut defaults = vec![("data/*/tags/**".to_string(), "tag".to_string())];
+
+        for (folder, plural) in DATA_FOLDERS {
+            defaults.push((format!("data/*/{}/**", folder), folder.to_string()));
+            if let Some(plural) = plural {
+                defaults.push((format!("data/*/{}/**", plural), folder.to_string()));
+            }
+        }
+        for folder in WORLDGEN_FOLDERS {
+            defaults.push((format!("data/*/worldgen/{}/**", folder), format!("worldgen/{}", folder)));
+        }
+        for (pattern, resource_type) in ASSET_PATTERNS {
+            defaults.push((pattern.to_string(), resource_type.to_string()));
+        }
+
+        Self { custom: Vec::new(), defaults }
+    }
+
+    /// Register a custom mapping, taking precedence over the defaults and
+    /// over previously added mappings
+    pub fn add_mapping(&mut self, pattern: impl Into<String>, resource_type: impl Into<String>) {
+        self.custom.insert(0, (pattern.into(), resource_type.into()));
+    }
+
+    /// Resource type of a file, or `None` when no mapping matches
+    pub fn resolve(&self, file_path: &str) -> Option<&str> {
+        let normalized = file_path.replace('\\', "/");
+        let segments: Vec<&str> = normalized.split('/').filter(|segment| !segment.is_empty()).collect();
+
+        self.custom.iter()
+            .chain(self.defaults.iter())
+            .find(|(pattern, _)| {
+                let pattern_segments: Vec<&str> = pattern.split('/').collect();
+                (0..segments.len()).any(|start| glob_segments_match(&pattern_segments, &segments[start..]))
+            })
+            .map(|(_, resource_type)| resource_type.as_str())
+    }
+
+    /// Resource type and id of a datapack file, like `("loot_table", "mypack:chests/dungeon")`
+    /// for `data/mypack/loot_tables/chests/dungeon.json`. The folder after the namespace
+    /// has as many segments as the resource type (`worldgen/biome`).
+    pub fn resource_location(&self, file_path: &str) -> Option<(&str, String)> {
+        let resource_type = self.resolve(file_path)?;
+        let normalized = file_path.replace('\\', "/");
+        let segments: Vec<&str> = normalized.split('/').filter(|segment| !segment.is_empty()).collect();
+        let data_index = segments.iter().position(|segment| *segment == "data")?;
+
+        let namespace = segments.get(data_index + 1)?;
+        let path = segments.get(data_index + 2 + resource_type.split('/').count()..)?;
+        if path.is_empty() {
+            return None;
+        }
+        let path = path.join("/");
+        let path = path.strip_suffix(".json").unwrap_or(&path);
+        Some((resource_type, format!("{}:{}", namespace, path)))
+    }
+}
+
+impl Default for ResourceTypeResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn glob_segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| glob_segments_match(rest, &path[skip..])),
+        Some((segment_pattern, rest)) => match path.split_first() {
+            Some((segment, path_rest)) => wildcard_match(segment_pattern, segment) && glob_segments_match(rest, path_rest),
+            None => false,
+        },
+    }
+}
+
+/// Match a single segment where `*` matches any run of characters
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((prefix, rest)) => {
+            let Some(text) = text.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=text.len())
+                .filter(|i| text.is_char_boundary(*i))
+                .any(|i| wildcard_match(rest, &text[i..]))
+        }
+    }
+}
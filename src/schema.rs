@@ -0,0 +1,313 @@
+//! Builders for defining schemas in Rust instead of MCDOC source
+//!
+//! A [`Schema`] renders to MCDOC text and loads like any other file through
+//! [`DatapackValidator::load_schema`](crate::validator::DatapackValidator::load_schema):
+//!
+//! ```
+//! use voxel_rsmcdoc::schema::{Schema, StructBuilder, Type};
+//!
+//! let schema = Schema::new("my_thing.mcdoc")
+//!     .structure(StructBuilder::new("MyThing")
+//!         .field("id", Type::id("item"))
+//!         .optional_field("count", Type::int().range(1, 64)))
+//!     .dispatch("resource", "my_thing", "MyThing");
+//!
+//! assert!(schema.to_mcdoc().contains("count?: int @ 1..64,"));
+//! ```
+
+use crate::parser::LiteralValue;
+use crate::printer;
+
+/// How a type was built, deciding where it needs parentheses
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TypeKind {
+    Single,
+    Annotated,
+    Constrained,
+}
+
+/// Type of a field, alias or dispatch, held as MCDOC syntax
+#[derive(Debug, Clone, PartialEq)]
+pub struct Type {
+    source: String,
+    kind: TypeKind,
+}
+
+impl Type {
+    fn single(source: impl Into<String>) -> Self {
+        Self { source: source.into(), kind: TypeKind::Single }
+    }
+
+    /// Type declared by name, like `Text` or `super::ItemStack`
+    pub fn named(name: &str) -> Self {
+        Self::single(name)
+    }
+
+    pub fn string() -> Self {
+        Self::single("string")
+    }
+
+    pub fn boolean() -> Self {
+        Self::single("boolean")
+    }
+
+    pub fn byte() -> Self {
+        Self::single("byte")
+    }
+
+    pub fn short() -> Self {
+        Self::single("short")
+    }
+
+    pub fn int() -> Self {
+        Self::single("int")
+    }
+
+    pub fn long() -> Self {
+        Self::single("long")
+    }
+
+    pub fn float() -> Self {
+        Self::single("float")
+    }
+
+    pub fn double() -> Self {
+        Self::single("double")
+    }
+
+    pub fn any() -> Self {
+        Self::single("any")
+    }
+
+    /// Resource location of a registry, `#[id="item"] string`
+    pub fn id(registry: &str) -> Self {
+        Self::string().annotation("id", registry)
+    }
+
+    /// String literal, `"value"`
+    pub fn literal(value: &str) -> Self {
+        Self::single(printer::literal(&LiteralValue::String(value.into())))
+    }
+
+    /// Number literal
+    pub fn number(value: impl Into<f64>) -> Self {
+        Self::single(printer::literal(&LiteralValue::Number(value.into())))
+    }
+
+    /// `[element]`
+    pub fn list(element: Type) -> Self {
+        Self::single(format!("[{}]", element.source))
+    }
+
+    /// `(A | B | ...)`
+    pub fn union(types: impl IntoIterator<Item = Type>) -> Self {
+        let branches: Vec<String> = types.into_iter().map(|branch| branch.single_source()).collect();
+        match branches.len() {
+            1 => Self::single(format!("({} |)", branches[0])),
+            _ => Self::single(format!("({})", branches.join(" | "))),
+        }
+    }
+
+    /// Value, or list length, within `min..max` inclusive
+    pub fn range(self, min: impl Into<f64>, max: impl Into<f64>) -> Self {
+        self.constrained(Some(min.into()), Some(max.into()))
+    }
+
+    /// `min..`
+    pub fn at_least(self, min: impl Into<f64>) -> Self {
+        self.constrained(Some(min.into()), None)
+    }
+
+    /// `..max`
+    pub fn at_most(self, max: impl Into<f64>) -> Self {
+        self.constrained(None, Some(max.into()))
+    }
+
+    /// Annotation in front of the type, `#[name="value"]`
+    pub fn annotation(self, name: &str, value: &str) -> Self {
+        Self {
+            source: format!("#[{}={}] {}", name, printer::literal(&LiteralValue::String(value.into())), self.source),
+            kind: TypeKind::Annotated,
+        }
+    }
+
+    fn constrained(self, min: Option<f64>, max: Option<f64>) -> Self {
+        let base = match self.kind {
+            TypeKind::Single => self.source,
+            TypeKind::Annotated | TypeKind::Constrained => format!("({})", self.source),
+        };
+        Self {
+            source: format!("{} @ {}", base, printer::range(min, max, false, false)),
+            kind: TypeKind::Constrained,
+        }
+    }
+
+    /// The type where only a single type is accepted: union branches and generic arguments
+    fn single_source(self) -> String {
+        match self.kind {
+            TypeKind::Single => self.source,
+            TypeKind::Annotated | TypeKind::Constrained => format!("({})", self.source),
+        }
+    }
+
+    /// MCDOC syntax of the type
+    pub fn as_mcdoc(&self) -> &str {
+        &self.source
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Member {
+    Field { name: String, field_type: Type, optional: bool, docs: Vec<String> },
+    DynamicField { key_type: Type, value_type: Type },
+    Spread(Type),
+}
+
+/// Named struct declaration
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructBuilder {
+    name: String,
+    docs: Vec<String>,
+    members: Vec<Member>,
+}
+
+impl StructBuilder {
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_string(), docs: Vec::new(), members: Vec::new() }
+    }
+
+    /// Doc comment line of the struct
+    pub fn doc(mut self, line: &str) -> Self {
+        self.docs.push(line.to_string());
+        self
+    }
+
+    /// Required field
+    pub fn field(self, name: &str, field_type: Type) -> Self {
+        self.push_field(name, field_type, false, Vec::new())
+    }
+
+    /// Field that may be left out
+    pub fn optional_field(self, name: &str, field_type: Type) -> Self {
+        self.push_field(name, field_type, true, Vec::new())
+    }
+
+    /// Field with doc comment lines
+    pub fn documented_field(self, name: &str, field_type: Type, optional: bool, docs: &[&str]) -> Self {
+        self.push_field(name, field_type, optional, docs.iter().map(|line| line.to_string()).collect())
+    }
+
+    /// Any key of `key_type`, `[key_type]: value_type`
+    pub fn dynamic_field(mut self, key_type: Type, value_type: Type) -> Self {
+        self.members.push(Member::DynamicField { key_type, value_type });
+        self
+    }
+
+    /// Fields of another struct, `...type`
+    pub fn spread(mut self, spread_type: Type) -> Self {
+        self.members.push(Member::Spread(spread_type));
+        self
+    }
+
+    fn push_field(mut self, name: &str, field_type: Type, optional: bool, docs: Vec<String>) -> Self {
+        self.members.push(Member::Field { name: name.to_string(), field_type, optional, docs });
+        self
+    }
+
+    fn write(&self, output: &mut String) {
+        write_docs(output, &self.docs, "");
+        output.push_str(&format!("struct {} {{\n", self.name));
+        for member in &self.members {
+            match member {
+                Member::Field { name, field_type, optional, docs } => {
+                    write_docs(output, docs, "\t");
+                    output.push_str(&format!(
+                        "\t{}{}: {},\n",
+                        printer::field_name(name),
+                        if *optional { "?" } else { "" },
+                        field_type.source,
+                    ));
+                }
+                Member::DynamicField { key_type, value_type } => {
+                    output.push_str(&format!("\t[{}]: {},\n", key_type.source, value_type.source));
+                }
+                Member::Spread(spread_type) => output.push_str(&format!("\t...{},\n", spread_type.source)),
+            }
+        }
+        output.push_str("}\n");
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Item {
+    Struct(StructBuilder),
+    Alias { name: String, alias_type: Type },
+    Dispatch { registry: String, key: String, type_name: String },
+}
+
+/// MCDOC file built declaration by declaration
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schema {
+    filename: String,
+    items: Vec<Item>,
+}
+
+impl Schema {
+    /// Empty schema, loaded under `filename`
+    pub fn new(filename: &str) -> Self {
+        Self { filename: filename.to_string(), items: Vec::new() }
+    }
+
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    /// `struct Name { ... }`
+    pub fn structure(mut self, builder: StructBuilder) -> Self {
+        self.items.push(Item::Struct(builder));
+        self
+    }
+
+    /// `type Name = alias_type`
+    pub fn alias(mut self, name: &str, alias_type: Type) -> Self {
+        self.items.push(Item::Alias { name: name.to_string(), alias_type });
+        self
+    }
+
+    /// `dispatch registry[key] to type_name`, the registry in the `minecraft` namespace unless given one
+    pub fn dispatch(mut self, registry: &str, key: &str, type_name: &str) -> Self {
+        let registry = if registry.contains(':') {
+            registry.to_string()
+        } else {
+            format!("minecraft:{}", registry)
+        };
+        self.items.push(Item::Dispatch { registry, key: key.to_string(), type_name: type_name.to_string() });
+        self
+    }
+
+    /// MCDOC source of the schema
+    pub fn to_mcdoc(&self) -> String {
+        let mut output = String::new();
+        for (i, item) in self.items.iter().enumerate() {
+            if i > 0 {
+                output.push('\n');
+            }
+            match item {
+                Item::Struct(builder) => builder.write(&mut output),
+                Item::Alias { name, alias_type } => {
+                    output.push_str(&format!("type {} = {}\n", name, alias_type.source));
+                }
+                Item::Dispatch { registry, key, type_name } => {
+                    output.push_str(&format!("dispatch {}[{}] to {}\n", registry, printer::dispatch_key(key), type_name));
+                }
+            }
+        }
+        output
+    }
+}
+
+fn write_docs(output: &mut String, docs: &[String], indent: &str) {
+    for line in docs {
+        output.push_str(&format!("{}/// {}\n", indent, line));
+    }
+}
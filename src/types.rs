@@ -1,71 +1,433 @@
 //! Public types for the MCDOC API
 
 use serde::{Deserialize, Serialize, Serializer, Deserializer};
-use crate::error::{ErrorType, ParseError};
+use crate::error::{ErrorCode, ErrorType, ParseError, Severity};
 use serde::ser::SerializeMap;
 use serde::de::{Visitor, MapAccess};
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// Kind of reference a dependency represents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DependencyKind {
+    /// Entry or tag of a content registry (from `#[id]`)
+    #[default]
+    Resource,
+    /// Dispatch key of a dispatcher registry (from `#[dispatcher_key]`)
+    DispatcherKey,
+}
+
+/// Step from a JSON value to one of its children
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum JsonPathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Location of a value in a JSON document
+///
+/// `Display` gives the dotted form used in messages (`pools[0].entries[1].name`),
+/// `to_pointer` the unambiguous RFC 6901 JSON Pointer (`/pools/0/entries/1/name`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct JsonPath {
+    segments: Vec<JsonPathSegment>,
+}
+
+impl JsonPath {
+    /// Path of the document root
+    pub fn root() -> Self {
+        Self::default()
+    }
+
+    /// Path of a field of the object at this path
+    pub fn key(&self, key: &str) -> Self {
+        self.child(JsonPathSegment::Key(key.to_string()))
+    }
+
+    /// Path of an element of the array at this path
+    pub fn index(&self, index: usize) -> Self {
+        self.child(JsonPathSegment::Index(index))
+    }
+
+    /// Path of the object or array holding the value at this path, `None` at the root
+    pub fn parent(&self) -> Option<Self> {
+        let (_, segments) = self.segments.split_last()?;
+        Some(Self { segments: segments.to_vec() })
+    }
+
+    fn child(&self, segment: JsonPathSegment) -> Self {
+        let mut segments = Vec::with_capacity(self.segments.len() + 1);
+        segments.extend(self.segments.iter().cloned());
+        segments.push(segment);
+        Self { segments }
+    }
+
+    pub fn segments(&self) -> &[JsonPathSegment] {
+        &self.segments
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// RFC 6901 JSON Pointer, `~` and `/` in keys are escaped as `~0` and `~1`
+    pub fn to_pointer(&self) -> String {
+        let mut pointer = String::new();
+        for segment in &self.segments {
+            pointer.push('/');
+            match segment {
+                JsonPathSegment::Key(key) => pointer.push_str(&key.replace('~', "~0").replace('/', "~1")),
+                JsonPathSegment::Index(index) => pointer.push_str(&index.to_string()),
+            }
+        }
+        pointer
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                JsonPathSegment::Key(key) if i == 0 => write!(f, "{}", key)?,
+                JsonPathSegment::Key(key) => write!(f, ".{}", key)?,
+                JsonPathSegment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Immutable string shared between the dependencies and errors that repeat it
+///
+/// Cloning doesn't copy the text. Serialized as a plain string, and usable as a `&str`.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct SharedStr(Arc<str>);
+
+impl SharedStr {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether both share the same text allocation
+    pub fn ptr_eq(&self, other: &SharedStr) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Deref for SharedStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for SharedStr {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for SharedStr {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for SharedStr {
+    fn from(text: &str) -> Self {
+        Self(Arc::from(text))
+    }
+}
+
+impl From<String> for SharedStr {
+    fn from(text: String) -> Self {
+        Self(Arc::from(text))
+    }
+}
+
+impl From<SharedStr> for String {
+    fn from(text: SharedStr) -> Self {
+        text.0.to_string()
+    }
+}
+
+impl PartialEq<str> for SharedStr {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<&str> for SharedStr {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl PartialEq<String> for SharedStr {
+    fn eq(&self, other: &String) -> bool {
+        *self.0 == **other
+    }
+}
+
+impl fmt::Debug for SharedStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+impl fmt::Display for SharedStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Serialize for SharedStr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for SharedStr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(SharedStr::from)
+    }
+}
+
+/// Set of shared strings, giving the same allocation for every occurrence of a text
+#[derive(Debug, Clone, Default)]
+pub struct StringInterner {
+    strings: HashSet<SharedStr>,
+}
+
+impl StringInterner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shared copy of `text`, allocated on its first occurrence only
+    pub fn intern(&mut self, text: &str) -> SharedStr {
+        if let Some(shared) = self.strings.get(text) {
+            return shared.clone();
+        }
+        let shared = SharedStr::from(text);
+        self.strings.insert(shared.clone());
+        shared
+    }
+
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
 
 /// Registry dependency extracted from a JSON
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct McDocDependency {
     /// Resource location (e.g., "minecraft:diamond_sword")
-    pub resource_location: String,
+    pub resource_location: SharedStr,
     /// Registry type (e.g., "item", "block", "recipe")
-    pub registry_type: String,
+    pub registry_type: SharedStr,
     /// Path in the source JSON (e.g., "result", "ingredients[0]")
     pub source_path: String,
+    /// JSON Pointer to the value (e.g., "/result", "/ingredients/0")
+    #[serde(default)]
+    pub pointer: String,
     /// Optional source file for datapack analysis
-    pub source_file: Option<String>,
+    pub source_file: Option<SharedStr>,
     /// Indicates if it's a tag reference (#minecraft:swords)
     pub is_tag: bool,
+    /// Content registry reference or dispatcher key
+    #[serde(default)]
+    pub dependency_kind: DependencyKind,
+    /// Number of occurrences merged into this entry, `source_path` is the first one
+    #[serde(default = "default_count")]
+    pub count: usize,
+}
+
+fn default_count() -> usize {
+    1
 }
 
 /// MCDOC validation error
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct McDocError {
-    /// File name where the error occurred
-    pub file: String,
+    /// File where the error occurred, empty when the document wasn't given one
+    /// (see `ValidationOptions::source_file`)
+    pub file: SharedStr,
+    /// Resource type or type the document was validated against, like `recipe`.
+    /// `None` for errors of MCDOC sources.
+    #[serde(default)]
+    pub resource_type: Option<SharedStr>,
     /// Path in the JSON structure
     pub path: String,
+    /// JSON Pointer to the value in error, unambiguous when keys contain `.`
+    #[serde(default)]
+    pub pointer: String,
     /// Detailed error message
     pub message: String,
     /// Error type for categorization
     pub error_type: ErrorType,
+    /// Specific reason of the error
+    #[serde(default)]
+    pub code: ErrorCode,
+    /// `Warning` for the entries of `ValidationResult::warnings`
+    #[serde(default)]
+    pub severity: Severity,
     /// Line in the file (if available)
     pub line: Option<u32>,
     /// Column in the file (if available)
     pub column: Option<u32>,
+    /// Line where the error ends (if available)
+    #[serde(default)]
+    pub end_line: Option<u32>,
+    /// Column where the error ends, excluded (if available)
+    #[serde(default)]
+    pub end_column: Option<u32>,
+    /// Column in UTF-16 code units, as editors count them (if available)
+    #[serde(default)]
+    pub column_utf16: Option<u32>,
+    /// Column in UTF-16 code units where the error ends, excluded (if available)
+    #[serde(default)]
+    pub end_column_utf16: Option<u32>,
+    /// Byte offset in the file (if available)
+    #[serde(default)]
+    pub offset: Option<usize>,
+    /// Byte offset where the error ends, excluded (if available)
+    #[serde(default)]
+    pub end_offset: Option<usize>,
+    /// Corrected value, when the value in error has an obvious fix
+    #[serde(default)]
+    pub suggestion: Option<String>,
+}
+
+impl McDocError {
+    /// Error of a file whose path maps to no resource type
+    pub fn unknown_resource_type(file: &str) -> Self {
+        McDocError {
+            file: file.into(),
+            resource_type: None,
+            path: String::new(),
+            pointer: String::new(),
+            message: format!("Could not infer the resource type of '{}'", file),
+            error_type: ErrorType::Resolution,
+            code: ErrorCode::SchemaNotFound,
+            severity: Severity::Error,
+            line: None,
+            column: None,
+            end_line: None,
+            end_column: None,
+            column_utf16: None,
+            end_column_utf16: None,
+            offset: None,
+            end_offset: None,
+            suggestion: None,
+        }
+    }
+
+    /// Error for a document that isn't well-formed JSON, at the position serde_json reports
+    pub fn invalid_json(file: &str, error: &serde_json::Error) -> Self {
+        McDocError {
+            file: file.into(),
+            resource_type: None,
+            path: String::new(),
+            pointer: String::new(),
+            message: format!("Invalid JSON: {}", error),
+            error_type: ErrorType::Validation,
+            code: ErrorCode::InvalidJson,
+            severity: Severity::Error,
+            line: Some(error.line() as u32),
+            column: Some(error.column() as u32),
+            end_line: None,
+            end_column: None,
+            column_utf16: None,
+            end_column_utf16: None,
+            offset: None,
+            end_offset: None,
+            suggestion: None,
+        }
+    }
 }
 
 impl From<ParseError> for McDocError {
     fn from(error: ParseError) -> Self {
-        let (line, column) = error.position()
-            .map(|pos| (Some(pos.line), Some(pos.column)))
-            .unwrap_or((None, None));
+        let position = error.position();
+        let end_position = error.end_position();
         
         McDocError {
-            file: String::new(), // Will be set by caller
+            file: SharedStr::default(), // Will be set by caller
+            resource_type: None,
             path: String::new(), // Will be set by caller
+            pointer: String::new(),
             message: error.to_string(),
             error_type: error.error_type(),
-            line,
-            column,
+            code: error.code(),
+            severity: Severity::Error,
+            line: position.map(|pos| pos.line),
+            column: position.map(|pos| pos.column),
+            end_line: end_position.map(|pos| pos.line),
+            end_column: end_position.map(|pos| pos.column),
+            column_utf16: position.map(|pos| pos.column_utf16),
+            end_column_utf16: end_position.map(|pos| pos.column_utf16),
+            offset: position.map(|pos| pos.offset),
+            end_offset: end_position.map(|pos| pos.offset),
+            suggestion: None,
         }
     }
 }
 
+/// Overall outcome of a validation run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ValidationStatus {
+    /// The whole document was validated without errors
+    Valid,
+    /// At least one error was found
+    Invalid,
+    /// Validation was truncated before finding any error
+    Unknown,
+}
+
+/// Budget that stopped a truncated validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TruncationReason {
+    TimeBudget,
+    NodeBudget,
+    ErrorBudget,
+}
+
 /// Validation result of a single JSON file
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ValidationResult {
     /// Is the JSON valid according to the MCDOC schema?
+    /// Always false when validation was truncated.
     pub is_valid: bool,
+    /// Valid, invalid, or unknown when truncated without errors
+    pub status: ValidationStatus,
     /// Detailed validation errors
     pub errors: Vec<McDocError>,
     /// Extracted registry dependencies
     pub dependencies: Vec<McDocDependency>,
+    /// Non-fatal findings such as deprecated fields, they don't affect validity
+    #[serde(default)]
+    pub warnings: Vec<McDocError>,
+    /// Did validation stop early because a budget was exhausted?
+    #[serde(default)]
+    pub truncated: bool,
+    /// Budget that stopped validation, if truncated
+    #[serde(default)]
+    pub truncation_reason: Option<TruncationReason>,
 }
 
 impl ValidationResult {
@@ -73,8 +435,12 @@ impl ValidationResult {
     pub fn success(dependencies: Vec<McDocDependency>) -> Self {
         Self {
             is_valid: true,
+            status: ValidationStatus::Valid,
             errors: Vec::new(),
             dependencies,
+            warnings: Vec::new(),
+            truncated: false,
+            truncation_reason: None,
         }
     }
     
@@ -82,8 +448,65 @@ impl ValidationResult {
     pub fn failure(errors: Vec<McDocError>) -> Self {
         Self {
             is_valid: false,
+            status: ValidationStatus::Invalid,
             errors,
             dependencies: Vec::new(),
+            warnings: Vec::new(),
+            truncated: false,
+            truncation_reason: None,
+        }
+    }
+    
+    /// Result of a file whose resource type could not be inferred: not validated,
+    /// with a warning
+    pub fn unknown_resource_type(file_path: &str) -> Self {
+        Self {
+            is_valid: false,
+            status: ValidationStatus::Unknown,
+            errors: Vec::new(),
+            dependencies: Vec::new(),
+            warnings: vec![McDocError {
+                file: file_path.into(),
+                resource_type: None,
+                path: String::new(),
+                pointer: String::new(),
+                message: format!("Unknown resource type for '{}', file was not validated", file_path),
+                error_type: ErrorType::Resolution,
+                code: ErrorCode::SchemaNotFound,
+                severity: Severity::Error,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+                column_utf16: None,
+                end_column_utf16: None,
+                offset: None,
+                end_offset: None,
+                suggestion: None,
+            }],
+            truncated: false,
+            truncation_reason: None,
+        }
+    }
+
+    /// Create a result, deriving its status from the errors and truncation
+    pub fn new(errors: Vec<McDocError>, dependencies: Vec<McDocDependency>, truncation_reason: Option<TruncationReason>) -> Self {
+        let status = if !errors.is_empty() {
+            ValidationStatus::Invalid
+        } else if truncation_reason.is_some() {
+            ValidationStatus::Unknown
+        } else {
+            ValidationStatus::Valid
+        };
+
+        Self {
+            is_valid: status == ValidationStatus::Valid,
+            status,
+            errors,
+            dependencies,
+            warnings: Vec::new(),
+            truncated: truncation_reason.is_some(),
+            truncation_reason,
         }
     }
     
@@ -91,12 +514,53 @@ impl ValidationResult {
     pub fn add_error(&mut self, error: McDocError) {
         self.errors.push(error);
         self.is_valid = false;
+        self.status = ValidationStatus::Invalid;
+    }
+    
+    /// Add a warning to the result, validity is unchanged
+    pub fn add_warning(&mut self, warning: McDocError) {
+        self.warnings.push(McDocError { severity: Severity::Warning, ..warning });
     }
     
     /// Add a dependency to the result
     pub fn add_dependency(&mut self, dependency: McDocDependency) {
         self.dependencies.push(dependency);
     }
+    
+    /// Merge dependencies referencing the same resource of the same registry into
+    /// their first occurrence, counting the occurrences. Order of first occurrence is kept.
+    pub fn deduplicate_dependencies(&mut self) {
+        let mut unique: Vec<McDocDependency> = Vec::with_capacity(self.dependencies.len());
+        let mut positions: HashMap<(SharedStr, SharedStr, bool, DependencyKind), usize> = HashMap::new();
+        for dependency in self.dependencies.drain(..) {
+            let key = (
+                dependency.registry_type.clone(),
+                dependency.resource_location.clone(),
+                dependency.is_tag,
+                dependency.dependency_kind,
+            );
+            match positions.get(&key) {
+                Some(&position) => unique[position].count += dependency.count,
+                None => {
+                    positions.insert(key, unique.len());
+                    unique.push(dependency);
+                }
+            }
+        }
+        self.dependencies = unique;
+    }
+    
+    /// Merge dependencies found by the heuristic pre-scan, skipping paths
+    /// already covered by a schema-derived dependency
+    pub fn merge_heuristic_dependencies(&mut self, dependencies: Vec<crate::RegistryDependency>) {
+        for dependency in dependencies {
+            let already_known = self.dependencies.iter()
+                .any(|known| known.source_path == dependency.source_path);
+            if !already_known {
+                self.dependencies.push(dependency.into());
+            }
+        }
+    }
 }
 
 /// Full datapack analysis result
@@ -109,6 +573,9 @@ pub struct DatapackResult {
     pub valid_files: usize,
     /// Validation errors per file
     pub errors: Vec<FileError>,
+    /// Validation warnings per file, and files whose resource type is unknown
+    #[serde(default)]
+    pub warnings: Vec<FileError>,
     /// All dependencies grouped by registry  
     #[serde(serialize_with = "serialize_fx_hashmap", deserialize_with = "deserialize_fx_hashmap")]
     pub dependencies: rustc_hash::FxHashMap<String, Vec<String>>,
@@ -121,7 +588,7 @@ pub struct DatapackResult {
 #[serde(rename_all = "camelCase")]
 pub struct FileError {
     /// File path
-    pub file_path: String,
+    pub file_path: SharedStr,
     /// Validation error
     pub error: McDocError,
 }
@@ -139,6 +606,7 @@ impl DatapackResult {
             total_files: 0,
             valid_files: 0,
             errors: Vec::new(),
+            warnings: Vec::new(),
             dependencies: rustc_hash::FxHashMap::default(),
             analysis_time_ms: 0,
         }
@@ -147,34 +615,185 @@ impl DatapackResult {
     /// Add file results
     pub fn add_file_result(&mut self, file_path: String, result: ValidationResult) {
         self.total_files += 1;
+        let file_path = SharedStr::from(file_path);
         
         if result.is_valid {
             self.valid_files += 1;
         }
         
-        // Add errors
-        for error in result.errors {
-            self.errors.push(FileError {
-                file_path: file_path.clone(),
-                error,
-            });
-        }
+        // Errors of the file share its path instead of each holding a copy
+        let in_file = |mut error: McDocError| {
+            if error.file.is_empty() || error.file == file_path {
+                error.file = file_path.clone();
+            }
+            FileError { file_path: file_path.clone(), error }
+        };
+        self.errors.extend(result.errors.into_iter().map(in_file));
+        self.warnings.extend(result.warnings.into_iter().map(in_file));
         
-        // Group dependencies by registry
+        // Group dependencies by registry, each resource once in order of first use
         for dependency in result.dependencies {
-            self.dependencies
-                .entry(dependency.registry_type)
-                .or_default()
-                .push(dependency.resource_location);
+            if !self.dependencies.contains_key(dependency.registry_type.as_str()) {
+                self.dependencies.insert(dependency.registry_type.to_string(), Vec::new());
+            }
+            let locations = self.dependencies.get_mut(dependency.registry_type.as_str()).expect("registry was just inserted");
+            if !locations.iter().any(|location| *location == *dependency.resource_location) {
+                locations.push(dependency.resource_location.to_string());
+            }
         }
     }
     
+    /// Count a file whose resource type could not be inferred, with a warning
+    pub fn add_unknown_file(&mut self, file_path: String) {
+        let result = ValidationResult::unknown_resource_type(&file_path);
+        self.add_file_result(file_path, result);
+    }
+    
     /// Set analysis time
     pub fn set_analysis_time(&mut self, time_ms: u32) {
         self.analysis_time_ms = time_ms;
     }
 }
 
+/// Outcome of loading several MCDOC sources, see `DatapackValidator::load_mcdoc_sources`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadReport {
+    /// Files that loaded, in the order they were given
+    pub loaded: Vec<String>,
+    /// Errors of each file that didn't load, by filename
+    pub diagnostics: BTreeMap<String, Vec<McDocError>>,
+}
+
+impl LoadReport {
+    /// Whether every file loaded
+    pub fn is_ok(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+}
+
+/// Kind of a top-level MCDOC declaration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DeclarationKind {
+    Struct,
+    Enum,
+    Type,
+    Dispatch,
+}
+
+/// Owned outline of a declaration, for tools that don't keep the source alive
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeclarationSummary {
+    pub kind: DeclarationKind,
+    /// Declared name, or the dispatch source like "minecraft:resource[recipe]"
+    pub name: String,
+    pub line: u32,
+    pub column: u32,
+    /// Field names of a struct (or dispatched struct), variant names of an enum
+    pub members: Vec<String>,
+}
+
+/// Owned outline of a parsed MCDOC file with its parse errors
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseSummary {
+    /// Imported paths like "::java::util::Text"
+    pub imports: Vec<String>,
+    pub declarations: Vec<DeclarationSummary>,
+    pub errors: Vec<McDocError>,
+}
+
+impl ParseSummary {
+    /// Parse a source leniently and outline whatever parsed
+    pub fn from_source(source: &str) -> Self {
+        let (file, errors) = crate::parse_mcdoc_lenient(source);
+        Self::new(&file, errors)
+    }
+
+    pub fn new(file: &crate::McDocFile, errors: Vec<ParseError>) -> Self {
+        use crate::parser::{Declaration, DispatchTarget, ImportPath, StructMember, TypeExpression};
+
+        let field_names = |members: &[StructMember]| -> Vec<String> {
+            members.iter()
+                .filter_map(|member| match member {
+                    StructMember::Field(field) => Some(field.name.to_string()),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        let imports = file.imports.iter()
+            .map(|import| {
+                let path = match &import.path {
+                    ImportPath::Absolute(segments) => format!("::{}", segments.join("::")),
+                    ImportPath::Relative { supers, segments } => format!("{}{}", "super::".repeat(*supers), segments.join("::")),
+                };
+                match import.alias {
+                    Some(alias) => format!("{} as {}", path, alias),
+                    None => path,
+                }
+            })
+            .collect();
+
+        let declarations = file.declarations.iter()
+            .map(|declaration| {
+                let (kind, name, position, members) = match declaration {
+                    Declaration::Struct(struct_decl) => {
+                        (DeclarationKind::Struct, struct_decl.name.to_string(), struct_decl.position, field_names(&struct_decl.members))
+                    }
+                    Declaration::Enum(enum_decl) => {
+                        let variants = enum_decl.variants.iter().map(|variant| variant.name.to_string()).collect();
+                        (DeclarationKind::Enum, enum_decl.name.to_string(), enum_decl.position, variants)
+                    }
+                    Declaration::Type(type_decl) => {
+                        let members = match &type_decl.type_expr {
+                            TypeExpression::Struct { members, .. } => field_names(members),
+                            _ => Vec::new(),
+                        };
+                        (DeclarationKind::Type, type_decl.name.to_string(), type_decl.position, members)
+                    }
+                    Declaration::Dispatch(dispatch) => {
+                        let keys: Vec<String> = dispatch.targets.iter()
+                            .map(|target| match target {
+                                DispatchTarget::Specific(key) => key.to_string(),
+                                DispatchTarget::Unknown => "%unknown".to_string(),
+                                DispatchTarget::None => "%none".to_string(),
+                                DispatchTarget::Special(name) => format!("%{}", name),
+                            })
+                            .collect();
+                        let name = format!("{}:{}[{}]", dispatch.source.namespace, dispatch.source.registry, keys.join(","));
+                        let members = match &dispatch.target_type {
+                            TypeExpression::Struct { members, .. } => field_names(members),
+                            _ => Vec::new(),
+                        };
+                        (DeclarationKind::Dispatch, name, dispatch.position, members)
+                    }
+                };
+                DeclarationSummary { kind, name, line: position.line, column: position.column, members }
+            })
+            .collect();
+
+        Self {
+            imports,
+            declarations,
+            errors: errors.into_iter().map(McDocError::from).collect(),
+        }
+    }
+}
+
+/// Primitive type names known without any declaration
+pub const BUILTIN_TYPES: &[&str] = &[
+    "any", "unknown", "boolean", "string",
+    "byte", "short", "int", "long", "float", "double",
+];
+
+/// Whether `name` is a built-in primitive type rather than a reference to a declaration
+pub fn is_builtin_type(name: &str) -> bool {
+    BUILTIN_TYPES.contains(&name)
+}
+
 /// Minecraft Version - SIMPLIFIED VERSION (type alias)
 /// Complex parsing is handled on the JavaScript side according to spec
 pub type MinecraftVersion = String;
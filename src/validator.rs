@@ -1,46 +1,521 @@
 //! Main MCDOC validator
 
-use crate::registry::RegistryManager;
-use crate::types::{ValidationResult, McDocError, McDocDependency};
-use crate::error::{McDocParserError, ErrorType};
-use crate::ResourceId;
-use crate::parser::{McDocFile, Declaration, TypeExpression};
+use crate::registry::{AttachedRegistry, RegistryManager};
+use crate::types::{ValidationResult, DatapackResult, LoadReport, McDocError, McDocDependency, DependencyKind, TruncationReason, JsonPath, SharedStr, StringInterner};
+use crate::error::{McDocParserError, ErrorCode, ErrorType, ParseError, Severity};
+use crate::{RegistryDependency, ResourceId};
+use crate::resource_type::ResourceTypeResolver;
+use crate::format::{FormatCheck, StringFormats};
+use crate::graph::{DependencyGraph, ResourceNode};
+use crate::inspect::SchemaDescription;
+use crate::json_schema::JsonSchemaExporter;
+use crate::owned::McDocFileOwned;
+use crate::printer;
+use crate::schema::Schema;
+use crate::version::compare_versions;
+use crate::types::{is_builtin_type, BUILTIN_TYPES};
+use crate::annotation::{IdAnnotation, IdTags};
+use crate::lexer::Lexer;
+use crate::parser::{
+    McDocFile, Declaration, EnumDeclaration, EnumVariant, StructDeclaration, TypeDeclaration, TypeExpression, LiteralValue, Parser,
+    StructMember, FieldDeclaration, DynamicFieldDeclaration, DispatchTarget, SpreadExpression, DynamicReference, DynamicReferenceType, PathSegment, ImportPath,
+    ArrayConstraints, TypeConstraints, AnnotationValue,
+};
 use rustc_hash::FxHashMap;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+/// Maximum number of known keys listed in an unknown dispatcher key error
+const MAX_LISTED_KEYS: usize = 10;
+
+/// Maximum number of type aliases followed when resolving the root type of a resource
+const MAX_ROOT_ALIASES: usize = 16;
+
+/// Maximum number of type aliases resolved in a row for the same value, for generic
+/// aliases instantiating themselves with ever larger arguments
+const MAX_ALIAS_DEPTH: usize = 64;
+
+/// Number of visited nodes between two time budget checks
+#[cfg(not(target_arch = "wasm32"))]
+const TIME_CHECK_INTERVAL: usize = 64;
+
+/// Options for a validation run
+#[derive(Debug, Clone)]
+pub struct ValidationOptions {
+    /// Stop validating after this much time.
+    /// Ignored on wasm32 where no clock is available, use `node_budget` there.
+    pub time_budget: Option<Duration>,
+    /// Stop validating after visiting this many JSON nodes
+    pub node_budget: Option<usize>,
+    /// Stop validating after this many errors
+    pub error_budget: Option<usize>,
+    /// Report object keys that no struct member declares.
+    /// Structs with dynamic fields or unresolved spreads accept any key.
+    pub strict_fields: bool,
+    /// Expand referenced tags and report those that are empty, circular or
+    /// contain entries missing from their registry
+    pub check_tag_contents: bool,
+    /// Report every occurrence of a dependency instead of merging repeated
+    /// references to the same resource (see `ValidationResult::deduplicate_dependencies`)
+    pub keep_duplicate_dependencies: bool,
+    /// Check dependencies against the loaded registries (default).
+    /// When disabled, only the structure is validated and dependencies are
+    /// extracted, references to missing or unloaded registries are not errors.
+    pub check_registries: bool,
+    /// Namespace of resource locations written without one, like `"stone"`.
+    /// Dependencies are reported as `namespace:path`.
+    pub default_namespace: String,
+    /// Deepest nesting of JSON objects and arrays that is validated. Values nested
+    /// deeper are reported with `MaxDepthExceeded` instead of being walked.
+    pub max_depth: usize,
+    /// File the document comes from, reported as the `file` of errors and the
+    /// `source_file` of dependencies
+    pub source_file: Option<String>,
+    /// Severity of the findings of a code, in place of their own. Findings downgraded
+    /// to `Warning` or `Info` are reported in `ValidationResult::warnings` and don't
+    /// make the document invalid, and `Error` makes warnings like `Deprecated` fatal.
+    pub severity_overrides: HashMap<ErrorCode, Severity>,
+    /// Reuse the members of spread structs, like `...ItemBase`, expanded by earlier
+    /// validations (default). Disabling it expands them again for every object,
+    /// with the same results.
+    pub memoize_spreads: bool,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        Self {
+            time_budget: None,
+            node_budget: None,
+            error_budget: None,
+            strict_fields: false,
+            check_tag_contents: false,
+            keep_duplicate_dependencies: false,
+            check_registries: true,
+            default_namespace: "minecraft".to_string(),
+            max_depth: 128,
+            source_file: None,
+            severity_overrides: HashMap::new(),
+            memoize_spreads: true,
+        }
+    }
+}
+
+impl ValidationOptions {
+    /// Options for turning validation on over packs written before it, reporting as
+    /// warnings what is often harmless there:
+    ///
+    /// - `UnknownField`, keys no struct member declares (with `strict_fields`)
+    /// - `RegistryMiss`, references to resources of other packs or custom namespaces
+    /// - `UnknownRegistry`, references to registries that aren't loaded
+    /// - `UnknownDispatchKey`, types no schema is registered for, like modded ones
+    /// - `SchemaNotFound`, documents of a resource type no schema describes
+    ///
+    /// `Deprecated` stays a warning. Other codes keep their severity.
+    pub fn lenient_legacy() -> Self {
+        let downgraded = [
+            ErrorCode::UnknownField,
+            ErrorCode::RegistryMiss,
+            ErrorCode::UnknownRegistry,
+            ErrorCode::UnknownDispatchKey,
+            ErrorCode::SchemaNotFound,
+        ];
+        Self {
+            severity_overrides: downgraded.into_iter().map(|code| (code, Severity::Warning)).collect(),
+            ..Self::default()
+        }
+    }
+}
+
+/// What loading a schema does when it conflicts with itself or a loaded schema:
+/// two declarations of the same name in one module, or two dispatches of the same
+/// registry key in any modules. Names declared by different modules don't conflict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictPolicy {
+    /// Refuse to load the schema, returning the conflicts as errors
+    Error,
+    /// Load the schema, the declaration loaded first wins. Conflicts are listed
+    /// by `schema_conflicts`.
+    #[default]
+    WarnAndKeepFirst,
+    /// Load the schema, its declarations replace the conflicting ones
+    Override,
+}
 
 /// Context for a single validation run.
 struct ValidationContext<'a> {
     errors: Vec<McDocError>,
+    warnings: Vec<McDocError>,
     dependencies: Vec<McDocDependency>,
     version: Option<&'a str>,
     resource_type: &'a str,
+    /// File of errors and dependencies, when the document comes from one
+    file: Option<SharedStr>,
+    /// `resource_type` as shared by the errors
+    shared_resource_type: SharedStr,
+    /// Strings repeated across dependencies, shared by every file of a datapack analysis
+    interner: &'a RefCell<StringInterner>,
+    /// Registries references are checked against, with the tags and resources of the
+    /// datapack during an analysis
+    registries: &'a RegistryManager,
+    options: &'a ValidationOptions,
+    nodes_visited: usize,
+    #[cfg(not(target_arch = "wasm32"))]
+    deadline: Option<Instant>,
+    truncation: Option<TruncationReason>,
+    /// Union branch attempts don't count their errors against the error budget
+    is_branch: bool,
+    /// Objects and arrays enclosing the value being validated, innermost last
+    ancestors: Vec<&'a serde_json::Value>,
+    /// Schema whose imports bind the type names being resolved
+    module: Option<String>,
+    /// Type aliases being resolved, with their type arguments, schema and the value
+    /// they're resolved for, innermost last
+    resolving: Vec<(String, String, &'a serde_json::Value)>,
 }
 
 impl<'a> ValidationContext<'a> {
-    fn new(version: Option<&'a str>, resource_type: &'a str) -> Self {
+    fn new(
+        version: Option<&'a str>,
+        resource_type: &'a str,
+        file: Option<&str>,
+        options: &'a ValidationOptions,
+        interner: &'a RefCell<StringInterner>,
+        registries: &'a RegistryManager,
+    ) -> Self {
         Self {
             errors: Vec::new(),
+            warnings: Vec::new(),
             dependencies: Vec::new(),
             version,
             resource_type,
+            file: file.map(|file| interner.borrow_mut().intern(file)),
+            shared_resource_type: interner.borrow_mut().intern(resource_type),
+            interner,
+            registries,
+            options,
+            nodes_visited: 0,
+            #[cfg(not(target_arch = "wasm32"))]
+            deadline: options.time_budget.map(|budget| Instant::now() + budget),
+            truncation: None,
+            is_branch: false,
+            ancestors: Vec::new(),
+            module: None,
+            resolving: Vec::new(),
+        }
+    }
+
+    /// Child context used to try a union branch, sharing the budgets
+    fn branch(&self) -> Self {
+        Self {
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            dependencies: Vec::new(),
+            version: self.version,
+            resource_type: self.resource_type,
+            file: self.file.clone(),
+            shared_resource_type: self.shared_resource_type.clone(),
+            interner: self.interner,
+            registries: self.registries,
+            options: self.options,
+            nodes_visited: self.nodes_visited,
+            #[cfg(not(target_arch = "wasm32"))]
+            deadline: self.deadline,
+            truncation: self.truncation,
+            is_branch: true,
+            ancestors: self.ancestors.clone(),
+            module: self.module.clone(),
+            resolving: self.resolving.clone(),
+        }
+    }
+
+    /// Child context for a value created during validation rather than read from the
+    /// document, like a dynamic field key. It has no enclosing objects.
+    fn detached<'k>(&self) -> ValidationContext<'k> where 'a: 'k {
+        ValidationContext {
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            dependencies: Vec::new(),
+            version: self.version,
+            resource_type: self.resource_type,
+            file: self.file.clone(),
+            shared_resource_type: self.shared_resource_type.clone(),
+            interner: self.interner,
+            registries: self.registries,
+            options: self.options,
+            nodes_visited: self.nodes_visited,
+            #[cfg(not(target_arch = "wasm32"))]
+            deadline: self.deadline,
+            truncation: self.truncation,
+            is_branch: self.is_branch,
+            ancestors: Vec::new(),
+            module: self.module.clone(),
+            resolving: self.resolving.clone(),
+        }
+    }
+
+    /// Carry the budget consumed by a branch back into this context
+    fn absorb_budget(&mut self, branch: &ValidationContext) {
+        self.nodes_visited = branch.nodes_visited;
+        self.truncation = self.truncation.or(branch.truncation);
+    }
+
+    /// Take over the findings and budget of a detached context
+    fn absorb(&mut self, detached: ValidationContext) {
+        self.absorb_budget(&detached);
+        self.errors.extend(detached.errors);
+        self.warnings.extend(detached.warnings);
+        self.dependencies.extend(detached.dependencies);
+    }
+
+    /// Account for a visited node; returns false once a budget is exhausted
+    fn enter_node(&mut self) -> bool {
+        if self.truncation.is_some() || self.error_budget_exhausted() {
+            return false;
+        }
+
+        self.nodes_visited += 1;
+        if self.options.node_budget.is_some_and(|budget| self.nodes_visited > budget) {
+            self.truncation = Some(TruncationReason::NodeBudget);
+            return false;
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.nodes_visited.is_multiple_of(TIME_CHECK_INTERVAL) && self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            self.truncation = Some(TruncationReason::TimeBudget);
+            return false;
+        }
+
+        true
+    }
+
+    fn error_budget_exhausted(&mut self) -> bool {
+        if self.is_branch {
+            return false;
+        }
+        if self.options.error_budget.is_some_and(|budget| self.errors.len() >= budget) {
+            self.truncation.get_or_insert(TruncationReason::ErrorBudget);
+            return true;
+        }
+        false
+    }
+
+    fn intern(&self, value: &str) -> SharedStr {
+        self.interner.borrow_mut().intern(value)
+    }
+
+    fn add_warning(&mut self, path: &JsonPath, code: ErrorCode, message: String) {
+        let warning = self.diagnostic(path.to_string(), path.to_pointer(), code, message, ErrorType::Validation, Severity::Warning);
+        self.push_diagnostic(warning);
+    }
+
+    fn into_result(self) -> ValidationResult {
+        let mut result = ValidationResult::new(self.errors, self.dependencies, self.truncation);
+        result.warnings = self.warnings;
+        if !self.options.keep_duplicate_dependencies {
+            result.deduplicate_dependencies();
         }
+        result
+    }
+
+    fn add_error(&mut self, path: &JsonPath, code: ErrorCode, message: String) {
+        self.add_error_of_type(path, code, message, ErrorType::Validation);
+    }
+
+    fn add_error_of_type(&mut self, path: &JsonPath, code: ErrorCode, message: String, error_type: ErrorType) {
+        self.push_error(path.to_string(), path.to_pointer(), code, message, error_type);
+    }
+
+    /// Report an error at the location a dependency was found
+    fn add_dependency_error(&mut self, dependency: &McDocDependency, code: ErrorCode, message: String) {
+        self.push_error(dependency.source_path.clone(), dependency.pointer.clone(), code, message, ErrorType::Validation);
+    }
+
+    /// Report an error whose value has an obvious fix, given in `McDocError::suggestion`
+    fn add_error_with_suggestion(&mut self, path: &JsonPath, code: ErrorCode, message: String, suggestion: String) {
+        let error = self.diagnostic(path.to_string(), path.to_pointer(), code, message, ErrorType::Validation, Severity::Error);
+        self.push_diagnostic(McDocError { suggestion: Some(suggestion), ..error });
+    }
+
+    fn push_error(&mut self, path: String, pointer: String, code: ErrorCode, message: String, error_type: ErrorType) {
+        let error = self.diagnostic(path, pointer, code, message, error_type, Severity::Error);
+        self.push_diagnostic(error);
     }
 
-    fn add_error(&mut self, path: &str, message: String) {
-        self.errors.push(McDocError {
-            file: self.resource_type.to_string(),
-            path: path.to_string(),
+    fn diagnostic(&self, path: String, pointer: String, code: ErrorCode, message: String, error_type: ErrorType, severity: Severity) -> McDocError {
+        McDocError {
+            file: self.file.clone().unwrap_or_default(),
+            resource_type: Some(self.shared_resource_type.clone()),
+            path,
+            pointer,
             message,
-            error_type: ErrorType::Validation,
+            error_type,
+            code,
+            severity,
             line: None,
             column: None,
-        });
+            end_line: None,
+            end_column: None,
+            column_utf16: None,
+            end_column_utf16: None,
+            offset: None,
+            end_offset: None,
+            suggestion: None,
+        }
+    }
+
+    /// Record a finding with the severity `ValidationOptions::severity_overrides` gives
+    /// its code: errors make the document invalid, the rest are reported as warnings
+    fn push_diagnostic(&mut self, mut diagnostic: McDocError) {
+        if let Some(severity) = self.options.severity_overrides.get(&diagnostic.code) {
+            diagnostic.severity = *severity;
+        }
+        match diagnostic.severity {
+            Severity::Error => self.errors.push(diagnostic),
+            Severity::Warning | Severity::Info => self.warnings.push(diagnostic),
+        }
+    }
+}
+
+/// Root type of a resource type, resolved once and reused until schemas change
+#[derive(Debug)]
+pub(crate) struct ResolvedRoot<'input> {
+    /// Schema whose imports bind the type names used in `type_expr`
+    pub(crate) module: String,
+    pub(crate) type_expr: TypeExpression<'input>,
+}
+
+/// Members of a struct with its spreads replaced, in runs borrowed from the schema,
+/// shared with the other validations or expanded for one document
+enum MemberRun<'a, 'input> {
+    Borrowed(&'a [StructMember<'input>]),
+    Shared(Arc<[StructMember<'input>]>),
+    Owned(Vec<StructMember<'input>>),
+}
+
+impl<'a, 'input> MemberRun<'a, 'input> {
+    fn members(&self) -> &[StructMember<'input>] {
+        match self {
+            MemberRun::Borrowed(members) => members,
+            MemberRun::Shared(members) => members,
+            MemberRun::Owned(members) => members,
+        }
+    }
+
+    /// Members of consecutive runs, in order
+    fn flatten<'r>(runs: &'r [MemberRun<'a, 'input>]) -> impl Iterator<Item = &'r StructMember<'input>> + Clone {
+        runs.iter().flat_map(MemberRun::members)
+    }
+}
+
+/// What expanding the spreads of a struct ran into
+#[derive(Default)]
+struct SpreadExpansion<'input> {
+    /// Dispatcher registry and discriminator of each dispatch spread, like
+    /// `...minecraft:recipe_serializer[[type]]`
+    discriminators: Vec<(String, DynamicReferenceType<'input>)>,
+    /// Targets of the spreads being expanded, innermost last
+    trail: Vec<String>,
+    /// Spreads of a struct being expanded, like `A -> B -> A`
+    cycles: Vec<String>,
+}
+
+impl SpreadExpansion<'_> {
+    /// Start expanding the target of `spread`, false when it's already being expanded
+    fn enter(&mut self, spread: &SpreadExpression) -> bool {
+        let target = printer::spread_summary(spread);
+        if let Some(start) = self.trail.iter().position(|expanding| *expanding == target) {
+            let cycle: Vec<&str> = self.trail[start..].iter().map(String::as_str).chain([target.as_str()]).collect();
+            self.cycles.push(cycle.join(" -> "));
+            return false;
+        }
+        self.trail.push(target);
+        true
+    }
+
+    /// Done expanding the target entered last, when `enter` returned true
+    fn leave(&mut self, entered: bool) {
+        if entered {
+            self.trail.pop();
+        }
     }
 }
 
+/// Spread target as written, with the schema resolving it when it's an import path, and version
+type SpreadKey = (Option<String>, String, Option<String>);
+
+/// Declaration an import path resolves to, borrowed from the loaded schema
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResolvedDeclaration<'a, 'input> {
+    Struct(&'a StructDeclaration<'input>),
+    Type(&'a TypeDeclaration<'input>),
+    Enum(&'a EnumDeclaration<'input>),
+}
+
+/// Storage a schema owned by the validator borrows from. Boxed so the borrowed
+/// text doesn't move with the map, and never read: only kept alive.
+#[allow(dead_code)]
+enum OwnedSchema {
+    Source(Box<str>),
+    Ast(Box<McDocFileOwned>),
+}
+
+/// Borrow the boxed content of an `OwnedSchema` for the validator's `'input`, the one
+/// place where a schema the validator owns is given a longer lifetime than its box.
+///
+/// # Safety
+/// The box must stay in `owned_sources` for as long as the schema borrowing from it is
+/// in `mcdoc_schemas`. Data of loaded schemas must only leave the validator borrowed
+/// from `&self` (see `schema` and `resolve_import_path`), never for `'input`: on a
+/// `DatapackValidator<'static>` it would otherwise outlive `unload_mcdoc`.
+unsafe fn borrow_owned<'input, T: ?Sized>(owned: &T) -> &'input T {
+    &*(owned as *const T)
+}
+
 /// Main MCDOC validator
+///
+/// Must not implement `Clone`: schemas loaded with `load_mcdoc_source` borrow
+/// from sources owned by the validator.
 pub struct DatapackValidator<'input> {
     pub registry_manager: RegistryManager,
-    pub mcdoc_schemas: FxHashMap<String, McDocFile<'input>>,
+    /// Loaded schemas by filename. Private because schemas parsed from owned sources
+    /// must not outlive the validator, use `schema` to read them.
+    mcdoc_schemas: FxHashMap<String, McDocFile<'input>>,
+    /// What schemas loaded with `load_mcdoc_source` or `load_owned_mcdoc` borrow from, by filename
+    owned_sources: FxHashMap<String, OwnedSchema>,
+    /// Dispatch declarations by "registry[key]" (e.g. "minecraft:resource[recipe]"),
+    /// as filename and declaration index in load order
+    dispatch_index: FxHashMap<String, Vec<(String, usize)>>,
+    /// Filenames declaring a struct, top-level or named inline, by struct name in load order
+    struct_index: FxHashMap<String, Vec<String>>,
+    /// Filenames declaring an enum, by enum name in load order
+    enum_index: FxHashMap<String, Vec<String>>,
+    /// Filenames declaring a type alias, by alias name in load order
+    alias_index: FxHashMap<String, Vec<String>>,
+    /// Filenames whose content is identical to an already loaded schema, mapped to that schema
+    pub schema_aliases: FxHashMap<String, String>,
+    /// Hash of the printed form of each loaded schema, positions left out, by filename
+    schema_hashes: FxHashMap<String, u64>,
+    /// Loaded schema by hash of its printed form, to find the schema a new one is identical to
+    content_index: FxHashMap<u64, String>,
+    /// Infers resource types from file paths in `analyze_datapack`
+    pub resource_types: ResourceTypeResolver,
+    /// Checks of strings annotated with a format, like `#[uuid]`
+    pub string_formats: StringFormats,
+    /// How schemas declaring the same name or dispatch key as a loaded one are loaded
+    pub conflict_policy: ConflictPolicy,
+    /// Root types by resource type, `None` when no schema declares one. Resolution
+    /// doesn't depend on the version, cleared whenever a schema is loaded or unloaded.
+    resolved_roots: RwLock<FxHashMap<String, Option<Arc<ResolvedRoot<'input>>>>>,
+    /// Members of spread structs with their own spreads expanded, by spread and version.
+    /// `None` when the expansion depends on the document, cleared like `resolved_roots`.
+    expanded_spreads: RwLock<FxHashMap<SpreadKey, Option<Arc<[StructMember<'input>]>>>>,
     _phantom: std::marker::PhantomData<&'input ()>,
 }
 
@@ -50,250 +525,2847 @@ impl<'input> DatapackValidator<'input> {
         Self {
             registry_manager: RegistryManager::new(),
             mcdoc_schemas: FxHashMap::default(),
+            owned_sources: FxHashMap::default(),
+            dispatch_index: FxHashMap::default(),
+            struct_index: FxHashMap::default(),
+            enum_index: FxHashMap::default(),
+            alias_index: FxHashMap::default(),
+            schema_aliases: FxHashMap::default(),
+            schema_hashes: FxHashMap::default(),
+            content_index: FxHashMap::default(),
+            resource_types: ResourceTypeResolver::new(),
+            string_formats: StringFormats::new(),
+            conflict_policy: ConflictPolicy::default(),
+            resolved_roots: RwLock::default(),
+            expanded_spreads: RwLock::default(),
             _phantom: std::marker::PhantomData,
         }
     }
     
     /// Load a previously parsed MCDOC schema
-    pub fn load_parsed_mcdoc(&mut self, filename: String, ast: McDocFile<'input>) -> Result<(), McDocParserError> {
-        self.mcdoc_schemas.insert(filename, ast);
+    ///
+    /// A schema structurally identical to one already loaded under another name is
+    /// registered as an alias of it instead of duplicating its declarations.
+    pub fn load_parsed_mcdoc(&mut self, filename: String, ast: McDocFile<'input>) -> Result<(), Vec<ParseError>> {
+        self.store_schema(filename, ast)
+    }
+
+    /// Parse and load an MCDOC schema from a source the validator takes ownership of
+    ///
+    /// Unlike `load_parsed_mcdoc` the source doesn't need to outlive the validator, and
+    /// it is freed when the schema is replaced or the validator is dropped.
+    pub fn load_mcdoc_source(&mut self, filename: String, source: String) -> Result<(), Vec<ParseError>> {
+        let source = source.into_boxed_str();
+        // SAFETY: the source is stored in `owned_sources` below when the AST is kept
+        let text: &'input str = unsafe { borrow_owned(source.as_ref()) };
+        let ast = crate::parse_mcdoc(text)?;
+
+        self.store_schema(filename.clone(), ast)?;
+        if self.mcdoc_schemas.contains_key(&filename) {
+            self.owned_sources.insert(filename, OwnedSchema::Source(source));
+        }
         Ok(())
     }
-    
-    /// Load a registry from JSON
-    pub fn load_registry(&mut self, name: String, version: String, json: &serde_json::Value) -> Result<(), McDocParserError> {
-        self.registry_manager.load_registry_from_json(name, version, json)
+
+    /// Load a schema from an owned AST, like one deserialized from a cache
+    ///
+    /// As with `load_mcdoc_source` the validator keeps the AST, so it doesn't need to
+    /// outlive the validator.
+    pub fn load_owned_mcdoc(&mut self, filename: String, ast: McDocFileOwned) -> Result<(), Vec<ParseError>> {
+        let ast = Box::new(ast);
+        // SAFETY: the AST is stored in `owned_sources` below when its borrowed form is kept
+        let owned: &'input McDocFileOwned = unsafe { borrow_owned(ast.as_ref()) };
+
+        self.store_schema(filename.clone(), owned.to_borrowed())?;
+        if self.mcdoc_schemas.contains_key(&filename) {
+            self.owned_sources.insert(filename, OwnedSchema::Ast(ast));
+        }
+        Ok(())
     }
-    
-    /// Validate JSON against MCDOC schemas
-    pub fn validate_json(
-        &self,
-        json: &serde_json::Value,
-        resource_type: &str,
-        version: Option<&str>,
-    ) -> ValidationResult {
-        let mut context = ValidationContext::new(version, resource_type);
 
-        if let Some(type_expr) = self.find_type_for_resource(resource_type) {
-            Self::validate_node(json, type_expr, "", &mut context, None);
-        } else {
-            context.add_error("", format!("No MCDOC schema found for resource type '{}'", resource_type));
+    /// Load every schema of a bundle made with `bundle::build_schema_bundle`, returning
+    /// how many were loaded. Nothing is loaded from an invalid or outdated bundle.
+    #[cfg(feature = "bundle")]
+    pub fn load_schema_bundle(&mut self, bytes: &[u8]) -> Result<usize, Vec<ParseError>> {
+        let files = crate::bundle::read_schema_bundle(bytes).map_err(|error| vec![error])?;
+        let count = files.len();
+        for (filename, ast) in files {
+            self.load_owned_mcdoc(filename, ast)?;
         }
+        Ok(count)
+    }
 
-        // 4. Valider les dépendances contre le registre
-        let dependencies = context.dependencies.clone(); 
-        for dependency in &dependencies {
-            if self.registry_manager.has_registry(&dependency.registry_type) {
-                match self.registry_manager.validate_resource_location(
-                    &dependency.registry_type,
-                    &dependency.resource_location,
-                    dependency.is_tag,
-                ) {
-                    Ok(false) => {
-                        context.add_error(&dependency.source_path, format!(
-                            "Resource '{}' not found in registry '{}'",
-                            dependency.resource_location,
-                            dependency.registry_type
-                        ));
-                    }
-                    Err(e) => {
-                        context.add_error(&dependency.source_path, e.to_string());
-                    }
-                    Ok(true) => {} // Valid
+    /// Load a schema built in Rust, under its filename
+    pub fn load_schema(&mut self, schema: Schema) -> Result<(), Vec<ParseError>> {
+        let source = schema.to_mcdoc();
+        self.load_mcdoc_source(schema.filename().to_string(), source)
+    }
+
+    /// Parse and load each `(filename, source)`, going on past the files that fail.
+    /// The report names the files that loaded and gives the errors of the others.
+    pub fn load_mcdoc_sources(&mut self, files: Vec<(String, String)>) -> LoadReport {
+        let mut report = LoadReport::default();
+        for (filename, source) in files {
+            match self.load_mcdoc_source(filename.clone(), source) {
+                Ok(()) => report.loaded.push(filename),
+                Err(errors) => {
+                    let file = SharedStr::from(filename.as_str());
+                    let errors = errors.into_iter()
+                        .map(|error| McDocError { file: file.clone(), ..error.into() })
+                        .collect();
+                    report.diagnostics.insert(filename, errors);
                 }
-            } else if dependency.registry_type != "unknown" {
-                context.add_error(&dependency.source_path, format!("Unknown registry '{}'", dependency.registry_type));
             }
         }
-        
-        ValidationResult {
-            is_valid: context.errors.is_empty(),
-            errors: context.errors,
-            dependencies: context.dependencies,
-        }
+        report
     }
 
-    /// Recursive validation function
-    fn validate_node(
-        json_node: &serde_json::Value,
-        mcdoc_node: &TypeExpression<'input>,
-        path: &str,
-        context: &mut ValidationContext,
-        annotations: Option<&Vec<crate::parser::Annotation<'input>>>,
-    ) {
-        if let Some(annotations) = annotations {
-            if let Some(id_annotation) = annotations.iter().find(|a| a.name == "id") {
-                if let Some(s) = json_node.as_str() {
-                    let registry_type = match &id_annotation.data {
-                        crate::parser::AnnotationData::Simple(registry) => registry.to_string(),
-                        crate::parser::AnnotationData::Complex(map) => {
-                            map.get("registry").unwrap_or(&"unknown").to_string()
-                        }
-                        _ => "unknown".to_string()
-                    };
-                    context.dependencies.push(McDocDependency {
-                        resource_location: s.to_string(),
-                        registry_type,
-                        source_path: path.to_string(),
-                        source_file: Some(context.resource_type.to_string()),
-                        is_tag: s.starts_with('#'),
-                    });
-                }
+    /// Load MCDOC modules whose filenames give their module path, like "java/util/text.mcdoc"
+    /// for `::java::util::text` ("mod.mcdoc" stands for its directory).
+    ///
+    /// All modules are loaded, then their `use` imports are checked against every loaded
+    /// schema: importing a missing module gives `ModuleNotFound`, importing a name the
+    /// module doesn't declare gives a resolution error.
+    pub fn load_mcdoc_modules(&mut self, modules: Vec<(String, McDocFile<'input>)>) -> Result<(), Vec<ParseError>> {
+        let filenames: Vec<String> = modules.iter().map(|(filename, _)| filename.clone()).collect();
+        let mut errors = Vec::new();
+        for (filename, ast) in modules {
+            if let Err(conflicts) = self.store_schema(filename, ast) {
+                errors.extend(conflicts);
             }
         }
 
-        match mcdoc_node {
-            TypeExpression::Simple(type_name) => {
-                let type_str = match json_node {
-                    serde_json::Value::String(_) => "string",
-                    serde_json::Value::Number(_) => "number",
-                    serde_json::Value::Bool(_) => "boolean",
-                    serde_json::Value::Array(_) => "array",
-                    serde_json::Value::Object(_) => "object",
-                    serde_json::Value::Null => "null",
+        errors.extend(filenames.iter().flat_map(|filename| self.unresolved_imports(filename)));
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Imports of a loaded schema that don't resolve to a loaded module or to a
+    /// declaration of that module (see `load_mcdoc_modules`)
+    pub fn unresolved_imports(&self, filename: &str) -> Vec<ParseError> {
+        let Some(schema) = self.loaded_schema(filename) else {
+            return Vec::new();
+        };
+        let current = Self::module_path(filename);
+
+        let mut errors = Vec::new();
+        for import in &schema.imports {
+            let Some(full_path) = Self::import_target(&current, &import.path) else {
+                let segments = import.path.segments().join("::");
+                let supers = match import.path {
+                    ImportPath::Relative { supers, .. } => supers,
+                    ImportPath::Absolute(_) => 0,
                 };
+                errors.push(ParseError::resolution(
+                    format!(
+                        "Import '{}{}' goes above the root module from {}",
+                        "super::".repeat(supers), segments, Self::module_name(&current)
+                    ),
+                    Some(filename.to_string()),
+                ));
+                continue;
+            };
+            let Some((name, module)) = full_path.split_last() else {
+                continue;
+            };
+            // `use ::java::util::text` imports the module itself
+            if self.module_file(&full_path).is_some() {
+                continue;
+            }
+            let module_name = Self::module_name(module);
+            match self.module_file(module) {
+                None => errors.push(ParseError::ModuleNotFound { module: module_name, from: filename.to_string() }),
+                Some(module_file) if self.module_declaration(module_file, name).is_none() => errors.push(ParseError::resolution(
+                    format!("'{}' is not declared in module {}", name, module_name),
+                    Some(filename.to_string()),
+                )),
+                Some(_) => {}
+            }
+        }
+        errors
+    }
 
-                match *type_name {
-                    "string" => if !json_node.is_string() {
-                        context.add_error(path, format!("Expected string, found {}", type_str));
-                    },
-                    "int" | "float" => if !json_node.is_number() {
-                        context.add_error(path, format!("Expected number, found {}", type_str));
-                    },
-                    "boolean" => if !json_node.is_boolean() {
-                        context.add_error(path, format!("Expected boolean, found {}", type_str));
-                    },
-                    _ => { /* It could be a reference to another type, needs resolver */ }
-                }
-            }
-            TypeExpression::Struct(members) => {
-                if let Some(obj) = json_node.as_object() {
-                    for member in members {
-                        match member {
-                            crate::parser::StructMember::Field(field) => {
-                                let field_name = field.name;
-                                let new_path = if path.is_empty() { field_name.to_string() } else { format!("{}.{}", path, field_name) };
-                                
-                                if let Some(value) = obj.get(field_name) {
-                                    Self::validate_node(value, &field.field_type, &new_path, context, Some(&field.annotations));
-                                } else if !field.optional {
-                                    context.add_error(&new_path, format!("Missing required field '{}'", field_name));
-                                }
-                            }
-                            crate::parser::StructMember::DynamicField(dynamic_field) => {
-                                // For dynamic fields like [#[id="mob_effect"] string]: MobEffectPredicate
-                                // We need to validate each key-value pair in the object
-                                for (key, value) in obj.iter() {
-                                    let key_path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
-                                    
-                                    // Validate the key against key_type
-                                    // For now, we assume the key is a string and skip key validation
-                                    // TODO: Implement proper key validation
-                                    
-                                    // Validate the value against value_type
-                                    Self::validate_node(value, &dynamic_field.value_type, &key_path, context, Some(&dynamic_field.annotations));
-                                }
-                            }
-                            crate::parser::StructMember::Spread(_spread) => {
-                                // TODO: Handle spread expressions - for now skip them
-                                // In a real implementation, we would need to resolve the spread target
-                                // and validate against its fields
-                            }
-                        }
-                    }
-                } else {
-                    context.add_error(path, "Expected object".to_string());
-                }
+    /// Module path segments of a schema filename, "java/util/mod.mcdoc" is `::java::util`
+    fn module_path(filename: &str) -> Vec<&str> {
+        let path = filename.strip_suffix(".mcdoc").unwrap_or(filename);
+        let mut segments: Vec<&str> = path.split(['/', '\\'])
+            .filter(|segment| !segment.is_empty() && *segment != ".")
+            .collect();
+        if segments.last() == Some(&"mod") {
+            segments.pop();
+        }
+        segments
+    }
+
+    /// Absolute path of an import written in the module `current`,
+    /// `None` when its `super` hops go above the root module
+    fn import_target<'p>(current: &[&'p str], path: &ImportPath<'p>) -> Option<Vec<&'p str>> {
+        match path {
+            ImportPath::Absolute(segments) => Some(segments.clone()),
+            // The first `super` is the parent of the current module
+            ImportPath::Relative { supers, segments } => {
+                let base = current.len().checked_sub(*supers)?;
+                Some(current[..base].iter().chain(segments).copied().collect())
             }
-            TypeExpression::Array { element_type, constraints } => {
-                if let Some(arr) = json_node.as_array() {
-                    if let Some(constraints) = constraints {
-                        if let Some(min) = constraints.min {
-                            if arr.len() < min as usize {
-                                context.add_error(path, format!("Expected at least {} elements, found {}", min, arr.len()));
-                            }
-                        }
-                        if let Some(max) = constraints.max {
-                            if arr.len() > max as usize {
-                                context.add_error(path, format!("Expected at most {} elements, found {}", max, arr.len()));
-                            }
-                        }
-                    }
+        }
+    }
 
-                    for (i, elem) in arr.iter().enumerate() {
-                        let new_path = format!("{}[{}]", path, i);
-                        Self::validate_node(elem, element_type, &new_path, context, None);
-                    }
-                } else {
-                    context.add_error(path, "Expected array".to_string());
-                }
-            }
-            TypeExpression::Union(types) => {
-                let mut local_errors = Vec::new();
-                for mcdoc_type in types {
-                    let mut temp_context = ValidationContext::new(context.version, context.resource_type);
-                    Self::validate_node(json_node, mcdoc_type, path, &mut temp_context, None);
-                    if temp_context.errors.is_empty() {
-                        // It matched one of the types in the union, so it's valid.
-                        // We also need to merge the dependencies found.
-                        context.dependencies.extend(temp_context.dependencies);
-                        return;
-                    }
-                    local_errors.extend(temp_context.errors);
-                }
-                
-                context.add_error(path, "JSON does not match any of the expected types".to_string());
-            }
-            TypeExpression::Literal(literal_value) => {
-                // Validate that the JSON value exactly matches the literal constraint
-                match literal_value {
-                    crate::parser::LiteralValue::String(expected) => {
-                        if let Some(actual) = json_node.as_str() {
-                            if actual != *expected {
-                                context.add_error(path, format!("Expected '{}', found '{}'", expected, actual));
-                            }
-                        } else {
-                            context.add_error(path, format!("Expected string '{}', found non-string", expected));
-                        }
-                    }
-                    crate::parser::LiteralValue::Number(expected) => {
-                        if let Some(actual) = json_node.as_f64() {
-                            if (actual - expected).abs() > f64::EPSILON {
-                                context.add_error(path, format!("Expected {}, found {}", expected, actual));
-                            }
-                        } else {
-                            context.add_error(path, format!("Expected number {}, found non-number", expected));
-                        }
-                    }
-                    crate::parser::LiteralValue::Boolean(expected) => {
-                        if let Some(actual) = json_node.as_bool() {
-                            if actual != *expected {
-                                context.add_error(path, format!("Expected {}, found {}", expected, actual));
-                            }
-                        } else {
-                            context.add_error(path, format!("Expected boolean {}, found non-boolean", expected));
-                        }
-                    }
-                }
+    fn module_name(module: &[&str]) -> String {
+        format!("::{}", module.join("::"))
+    }
+
+    /// Declaration named by an absolute import path like "::java::util::text::TextStyle".
+    /// Its names borrow from the validator, not from `'input`: the schema may be one
+    /// the validator owns, freed when it is unloaded, so they can't outlive it.
+    ///
+    /// ```compile_fail
+    /// use voxel_rsmcdoc::validator::{DatapackValidator, ResolvedDeclaration};
+    ///
+    /// let mut validator = DatapackValidator::new();
+    /// validator.load_mcdoc_source("a.mcdoc".to_string(), "struct Foo { x: int }".to_string()).unwrap();
+    /// let Some(ResolvedDeclaration::Struct(foo)) = validator.resolve_import_path("::a::Foo") else { panic!() };
+    /// let name: &str = foo.name;
+    /// validator.unload_mcdoc("a.mcdoc");
+    /// println!("{}", name);
+    /// ```
+    pub fn resolve_import_path(&self, path: &str) -> Option<ResolvedDeclaration<'_, '_>> {
+        let segments: Vec<&str> = path.strip_prefix("::").unwrap_or(path).split("::").collect();
+        let (name, module) = segments.split_last()?;
+        self.module_declaration(self.module_file(module)?, name)
+    }
+
+    /// Filename of the loaded schema for a module path, following aliases
+    fn module_file(&self, module: &[&str]) -> Option<&str> {
+        self.mcdoc_schemas.keys()
+            .chain(self.schema_aliases.keys())
+            .find(|filename| Self::module_path(filename) == module)
+            .map(|filename| self.schema_aliases.get(filename).unwrap_or(filename).as_str())
+    }
+
+    /// Top-level struct, enum or type alias named `name` in a loaded schema
+    fn module_declaration(&self, filename: &str, name: &str) -> Option<ResolvedDeclaration<'_, 'input>> {
+        self.loaded_schema(filename)?.declarations.iter().find_map(|decl| match decl {
+            Declaration::Struct(struct_decl) if struct_decl.name == name => Some(ResolvedDeclaration::Struct(struct_decl)),
+            Declaration::Enum(enum_decl) if enum_decl.name == name => Some(ResolvedDeclaration::Enum(enum_decl)),
+            Declaration::Type(type_decl) if type_decl.name == name => Some(ResolvedDeclaration::Type(type_decl)),
+            _ => None,
+        })
+    }
+
+    /// Loaded schema by filename, following aliases
+    fn loaded_schema(&self, filename: &str) -> Option<&McDocFile<'input>> {
+        let filename = self.schema_aliases.get(filename).map_or(filename, String::as_str);
+        self.mcdoc_schemas.get(filename)
+    }
+
+    /// Replace a schema with a new version of its source.
+    /// The previous version stays loaded if the new one doesn't parse.
+    pub fn reload_mcdoc(&mut self, filename: &str, source: String) -> Result<(), Vec<ParseError>> {
+        self.load_mcdoc_source(filename.to_string(), source)
+    }
+
+    /// Unload a schema or alias, returns whether `filename` was loaded
+    pub fn unload_mcdoc(&mut self, filename: &str) -> bool {
+        self.detach_schema(filename)
+    }
+
+    /// Loaded schema by filename
+    pub fn schema(&self, filename: &str) -> Option<&McDocFile<'_>> {
+        self.mcdoc_schemas.get(filename).map(|schema| schema as &McDocFile<'_>)
+    }
+
+    /// Filenames of the loaded schemas, aliases excluded
+    pub fn schema_names(&self) -> impl Iterator<Item = &str> {
+        self.mcdoc_schemas.keys().map(String::as_str)
+    }
+
+    /// Owned description of a loaded schema, with its types in MCDOC syntax
+    pub fn describe_schema(&self, filename: &str) -> Option<SchemaDescription> {
+        self.schema(filename).map(|schema| SchemaDescription::new(filename, schema))
+    }
+
+    /// Descriptions of every loaded schema, sorted by filename
+    pub fn describe_schemas(&self) -> Vec<SchemaDescription> {
+        let mut filenames: Vec<&str> = self.schema_names().collect();
+        filenames.sort_unstable();
+        filenames.into_iter().filter_map(|filename| self.describe_schema(filename)).collect()
+    }
+
+    /// Draft-07 JSON Schema of a resource type, for editors that understand JSON Schema.
+    ///
+    /// Named declarations are collected under `definitions`, registries of `#[id]`
+    /// strings are given as `x-registry`. Accepts any value when no schema is
+    /// dispatched for the resource type.
+    pub fn to_json_schema(&self, resource_type: &str) -> serde_json::Value {
+        match self.resolved_root(resource_type) {
+            Some(root) => JsonSchemaExporter::new(self).document(&root.module, &root.type_expr),
+            None => JsonSchemaExporter::any(),
+        }
+    }
+
+    /// Keys dispatched over a registry like `minecraft:recipe_serializer` (or
+    /// `recipe_serializer`) by the loaded schemas, sorted. Pseudo-keys like `%unknown`
+    /// and `%none` are left out, see `dispatch_fallbacks`.
+    pub fn dispatch_keys(&self, registry: &str) -> Vec<String> {
+        self.registry_dispatch_keys(registry, |key| !key.starts_with('%'))
+    }
+
+    /// Pseudo-keys (`%unknown`, `%none`, ...) dispatched over a registry, sorted
+    pub fn dispatch_fallbacks(&self, registry: &str) -> Vec<String> {
+        self.registry_dispatch_keys(registry, |key| key.starts_with('%'))
+    }
+
+    /// Registries with at least one dispatch in the loaded schemas, like
+    /// `minecraft:resource`, sorted
+    pub fn registries_with_dispatch(&self) -> Vec<String> {
+        let mut registries: Vec<String> = self.dispatch_index_entries()
+            .map(|(registry, _)| registry.to_string())
+            .collect();
+        registries.sort_unstable();
+        registries.dedup();
+        registries
+    }
+
+    fn registry_dispatch_keys(&self, registry: &str, include: fn(&str) -> bool) -> Vec<String> {
+        let registry = if registry.contains(':') {
+            Cow::Borrowed(registry)
+        } else {
+            Cow::Owned(format!("minecraft:{}", registry))
+        };
+        let mut keys: Vec<String> = self.dispatch_index_entries()
+            .filter(|(entry_registry, key)| *entry_registry == registry && include(key))
+            .map(|(_, key)| key.to_string())
+            .collect();
+        keys.sort_unstable();
+        keys.dedup();
+        keys
+    }
+
+    /// Registry and key of every dispatch index entry
+    fn dispatch_index_entries(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.dispatch_index.keys().filter_map(|index_key| {
+            index_key.strip_suffix(']')?.split_once('[')
+        })
+    }
+
+    /// Remove the schema stored under `filename`, handing it over to one of its aliases if any
+    fn detach_schema(&mut self, filename: &str) -> bool {
+        self.clear_resolved_roots();
+        if self.schema_aliases.remove(filename).is_some() {
+            return true;
+        }
+        // Declared before the schema so the schema borrowing from it is dropped first
+        let source = self.owned_sources.remove(filename);
+        let Some(schema) = self.mcdoc_schemas.remove(filename) else {
+            return false;
+        };
+        self.unindex_schema(filename);
+        let hash = self.schema_hashes.remove(filename);
+        if let Some(hash) = hash {
+            self.content_index.remove(&hash);
+        }
+
+        let mut aliases: Vec<String> = self.schema_aliases.iter()
+            .filter(|(_, original)| original.as_str() == filename)
+            .map(|(alias, _)| alias.clone())
+            .collect();
+        aliases.sort();
+
+        if let Some((heir, others)) = aliases.split_first() {
+            self.schema_aliases.remove(heir);
+            for other in others {
+                self.schema_aliases.insert(other.clone(), heir.clone());
             }
-            _ => {}
+            self.mcdoc_schemas.insert(heir.clone(), schema);
+            if let Some(source) = source {
+                self.owned_sources.insert(heir.clone(), source);
+            }
+            if let Some(hash) = hash {
+                self.schema_hashes.insert(heir.clone(), hash);
+                self.content_index.insert(hash, heir.clone());
+            }
+            self.index_schema(heir);
         }
+        true
     }
 
-    /// Finds the corresponding TypeExpression for a given resource type string.
-    fn find_type_for_resource(&self, resource_type: &str) -> Option<&TypeExpression<'input>> {
-        let parsed_id = ResourceId::parse(resource_type).ok()?;
-        
-        for schema in self.mcdoc_schemas.values() {
-            for decl in &schema.declarations {
-                if let Declaration::Dispatch(dispatch) = decl {
-                    if dispatch.source.key == Some(parsed_id.path.as_str()) {
-                         return Some(&dispatch.target_type);
-                    }
-                }
+    fn index_schema(&mut self, filename: &str) {
+        let schema = &self.mcdoc_schemas[filename];
+        let mut struct_names: Vec<&str> = Self::schema_structs(schema).into_iter().map(|(name, _)| name).collect();
+        struct_names.sort_unstable();
+        struct_names.dedup();
+        for name in struct_names {
+            self.struct_index.entry(name.to_string()).or_default().push(filename.to_string());
+        }
+
+        let mut enum_names = Vec::new();
+        let mut alias_names = Vec::new();
+        for decl in &self.mcdoc_schemas[filename].declarations {
+            match decl {
+                Declaration::Enum(enum_decl) => enum_names.push(enum_decl.name),
+                Declaration::Type(type_decl) => alias_names.push(type_decl.name),
+                _ => {}
             }
         }
-        None
+        for (index, mut names) in [(&mut self.enum_index, enum_names), (&mut self.alias_index, alias_names)] {
+            names.sort_unstable();
+            names.dedup();
+            for name in names {
+                index.entry(name.to_string()).or_default().push(filename.to_string());
+            }
+        }
+
+        let entries: Vec<(String, usize)> = self.mcdoc_schemas[filename].declarations.iter()
+            .enumerate()
+            .filter_map(|(index, decl)| match decl {
+                Declaration::Dispatch(dispatch) => Some((index, dispatch)),
+                _ => None,
+            })
+            .flat_map(|(index, dispatch)| {
+                let registry = format!("{}:{}", dispatch.source.namespace, dispatch.source.registry);
+                dispatch.targets.iter()
+                    .map(move |target| (Self::dispatch_index_key(&registry, &Self::dispatch_target_key(target)), index))
+            })
+            .collect();
+        for (index_key, index) in entries {
+            let targets = self.dispatch_index.entry(index_key).or_default();
+            // Lookups take the first target, an overriding schema goes before the others
+            if self.conflict_policy == ConflictPolicy::Override {
+                targets.insert(0, (filename.to_string(), index));
+            } else {
+                targets.push((filename.to_string(), index));
+            }
+        }
+    }
+
+    /// Dispatch key as indexed, `%unknown` and `%none` included
+    fn dispatch_target_key<'a>(target: &'a DispatchTarget<'input>) -> Cow<'a, str> {
+        match target {
+            DispatchTarget::Specific(key) => Cow::Borrowed(key),
+            DispatchTarget::Unknown => Cow::Borrowed("%unknown"),
+            DispatchTarget::None => Cow::Borrowed("%none"),
+            DispatchTarget::Special(name) => Cow::Owned(format!("%{}", name)),
+        }
+    }
+
+    fn unindex_schema(&mut self, filename: &str) {
+        self.dispatch_index.retain(|_, targets| {
+            targets.retain(|(target_file, _)| target_file != filename);
+            !targets.is_empty()
+        });
+        for index in [&mut self.struct_index, &mut self.enum_index, &mut self.alias_index] {
+            index.retain(|_, files| {
+                files.retain(|file| file != filename);
+                !files.is_empty()
+            });
+        }
+    }
+
+    /// Top-level structs and named inline structs of a schema, in declaration order
+    fn schema_structs<'a>(schema: &'a McDocFile<'input>) -> Vec<(&'input str, &'a [StructMember<'input>])> {
+        let mut found = Vec::new();
+        for decl in &schema.declarations {
+            match decl {
+                Declaration::Struct(struct_decl) => {
+                    found.push((struct_decl.name, struct_decl.members.as_slice()));
+                    Self::collect_member_structs(&struct_decl.members, &mut found);
+                }
+                Declaration::Type(type_decl) => Self::collect_named_structs(&type_decl.type_expr, &mut found),
+                Declaration::Dispatch(dispatch) => Self::collect_named_structs(&dispatch.target_type, &mut found),
+                Declaration::Enum(_) => {}
+            }
+        }
+        found
+    }
+
+    fn collect_named_structs<'a>(type_expr: &'a TypeExpression<'input>, found: &mut Vec<(&'input str, &'a [StructMember<'input>])>) {
+        match type_expr {
+            TypeExpression::Struct { name, members } => {
+                if let Some(name) = name {
+                    found.push((name, members.as_slice()));
+                }
+                Self::collect_member_structs(members, found);
+            }
+            TypeExpression::Array { element_type, .. } => Self::collect_named_structs(element_type, found),
+            TypeExpression::Union(types) | TypeExpression::Generic { type_args: types, .. } => {
+                for type_expr in types {
+                    Self::collect_named_structs(type_expr, found);
+                }
+            }
+            TypeExpression::Annotated { type_expr, .. } => Self::collect_named_structs(type_expr, found),
+            TypeExpression::Constrained { base_type, .. } => Self::collect_named_structs(base_type, found),
+            _ => {}
+        }
+    }
+
+    fn collect_member_structs<'a>(members: &'a [StructMember<'input>], found: &mut Vec<(&'input str, &'a [StructMember<'input>])>) {
+        for member in members {
+            match member {
+                StructMember::Field(field) => Self::collect_named_structs(&field.field_type, found),
+                StructMember::DynamicField(dynamic_field) => {
+                    Self::collect_named_structs(&dynamic_field.key_type, found);
+                    Self::collect_named_structs(&dynamic_field.value_type, found);
+                }
+                StructMember::Spread(spread) => {
+                    if let Some(target) = &spread.target {
+                        Self::collect_named_structs(target, found);
+                    }
+                }
+            }
+        }
+    }
+
+    fn dispatch_index_key(registry: &str, key: &str) -> String {
+        format!("{}[{}]", registry, Self::normalize_dispatch_key(key))
+    }
+
+    /// Single spelling of a dispatch key: quoted and bare keys are already the same
+    /// text, and the `minecraft:` namespace is implied
+    fn normalize_dispatch_key(key: &str) -> &str {
+        key.strip_prefix("minecraft:").unwrap_or(key)
+    }
+
+    /// Insert a schema, or record it as an alias of a loaded one with the same content.
+    /// With `ConflictPolicy::Error` a conflicting schema isn't loaded, and the schema
+    /// it would replace is kept.
+    fn store_schema(&mut self, filename: String, mut ast: McDocFile<'input>) -> Result<(), Vec<ParseError>> {
+        let printed = printer::print(&ast);
+        let hash = Self::content_hash(&printed);
+        // A hash collision is told apart by printing the loaded schema
+        let original = self.content_index.get(&hash)
+            .filter(|name| **name != filename && printer::print(&self.mcdoc_schemas[*name]) == printed)
+            .cloned();
+        if original.is_none() {
+            match self.conflict_policy {
+                ConflictPolicy::Error => {
+                    let conflicts = self.conflicts(&filename, &ast);
+                    if !conflicts.is_empty() {
+                        return Err(conflicts);
+                    }
+                }
+                ConflictPolicy::Override => Self::drop_shadowed_declarations(&mut ast),
+                ConflictPolicy::WarnAndKeepFirst => {}
+            }
+        }
+
+        self.detach_schema(&filename);
+        self.clear_resolved_roots();
+        match original {
+            Some(original) => {
+                self.schema_aliases.insert(filename, original);
+            }
+            None => {
+                self.mcdoc_schemas.insert(filename.clone(), ast);
+                self.index_schema(&filename);
+                self.schema_hashes.insert(filename.clone(), hash);
+                self.content_index.entry(hash).or_insert(filename);
+            }
+        }
+        Ok(())
+    }
+
+    /// Hash of a printed schema, which leaves out positions: the same declarations
+    /// written with other spacing have the same hash
+    fn content_hash(printed: &str) -> u64 {
+        let mut hasher = rustc_hash::FxHasher::default();
+        printed.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Notes about a schema that need no fix, like it being an alias of a schema
+    /// with the same content. Empty for a schema that isn't loaded.
+    pub fn schema_notes(&self, filename: &str) -> Vec<McDocError> {
+        let Some(original) = self.schema_aliases.get(filename) else {
+            return Vec::new();
+        };
+        let note = ParseError::resolution(format!("Same content as '{}', loaded as its alias", original), None);
+        vec![McDocError {
+            file: filename.into(),
+            severity: Severity::Info,
+            code: ErrorCode::DuplicateSchema,
+            ..note.into()
+        }]
+    }
+
+    /// Semantic problems of a loaded schema, like duplicate enum values or a range
+    /// whose minimum is above its maximum (see `check::check_mcdoc`).
+    /// Empty for a schema that isn't loaded.
+    pub fn validate_schema(&self, filename: &str) -> Vec<ParseError> {
+        self.loaded_schema(filename).map(crate::check::check_mcdoc).unwrap_or_default()
+    }
+
+    /// Conflicts of a loaded schema: names it declares more than once, and dispatch
+    /// keys it declares more than once or that another schema also declares.
+    /// Empty for a schema that isn't loaded or is an alias.
+    pub fn schema_conflicts(&self, filename: &str) -> Vec<ParseError> {
+        match self.mcdoc_schemas.get(filename) {
+            Some(schema) => self.conflicts(filename, schema),
+            None => Vec::new(),
+        }
+    }
+
+    /// Conflicts of `ast` as the schema `filename`, with itself and the other loaded schemas
+    fn conflicts(&self, filename: &str, ast: &McDocFile<'input>) -> Vec<ParseError> {
+        let conflict = |message: String| ParseError::resolution(message, Some(filename.to_string()));
+        let mut errors = Vec::new();
+
+        let mut names = HashSet::new();
+        for name in ast.declarations.iter().filter_map(Self::declared_name) {
+            if !names.insert(name) {
+                errors.push(conflict(format!("'{}' is declared more than once", name)));
+            }
+        }
+
+        let mut keys = HashSet::new();
+        for declaration in &ast.declarations {
+            let Declaration::Dispatch(dispatch) = declaration else {
+                continue;
+            };
+            let registry = format!("{}:{}", dispatch.source.namespace, dispatch.source.registry);
+            let mut printed = None;
+            for key in dispatch.targets.iter().map(Self::dispatch_target_key) {
+                let index_key = Self::dispatch_index_key(&registry, &key);
+                if !keys.insert(index_key.clone()) {
+                    errors.push(conflict(format!("Dispatch key '{}' of '{}' is declared more than once", key, registry)));
+                }
+                // Another schema declaring the same dispatch, spacing aside, is no conflict
+                let others = self.dispatch_index.get(&index_key).into_iter().flatten()
+                    .filter(|(other, _)| other != filename)
+                    .filter(|(other, index)| {
+                        let printed = printed.get_or_insert_with(|| printer::declaration(declaration));
+                        printer::declaration(&self.mcdoc_schemas[other].declarations[*index]) != *printed
+                    });
+                for (other, _) in others {
+                    errors.push(conflict(format!("Dispatch key '{}' of '{}' is also declared in {}", key, registry, other)));
+                }
+            }
+        }
+        errors
+    }
+
+    /// Name of a struct, enum or type alias declaration
+    fn declared_name(declaration: &Declaration<'input>) -> Option<&'input str> {
+        match declaration {
+            Declaration::Struct(declaration) => Some(declaration.name),
+            Declaration::Enum(declaration) => Some(declaration.name),
+            Declaration::Type(declaration) => Some(declaration.name),
+            Declaration::Dispatch(_) => None,
+        }
+    }
+
+    /// Keep only the last declaration of each name, as `ConflictPolicy::Override` wants
+    fn drop_shadowed_declarations(ast: &mut McDocFile<'input>) {
+        let mut seen = HashSet::new();
+        let mut keep: Vec<bool> = ast.declarations.iter().rev()
+            .map(|declaration| Self::declared_name(declaration).is_none_or(|name| seen.insert(name)))
+            .collect();
+        keep.reverse();
+        let mut keep = keep.into_iter();
+        ast.declarations.retain(|_| keep.next().unwrap_or(true));
+    }
+    
+    /// Load a registry from JSON, keeping the other loaded versions of the registry.
+    /// Validation uses the requested version, or the latest loaded one if it's missing.
+    pub fn load_registry(&mut self, name: String, version: String, json: &serde_json::Value) -> Result<(), McDocParserError> {
+        self.registry_manager.load_registry_from_json(name, version, json)
+    }
+
+    /// Load every registry of a multi-registry document (see `RegistryManager::load_registries_bundle`)
+    pub fn load_registries_bundle(&mut self, version: &str, json: &serde_json::Value) -> Result<usize, McDocParserError> {
+        self.registry_manager.load_registries_bundle(version, json)
+    }
+
+    /// Register resources defined by the datapack being validated (e.g. "mypack:chests/dungeon"
+    /// in "loot_table"), references to them are valid even if the registry doesn't list them.
+    /// They stay registered until `RegistryManager::clear_local_resources`, unlike the
+    /// files of an `analyze_datapack` call which only count for that analysis.
+    pub fn register_local_resources(&mut self, registry: &str, ids: impl IntoIterator<Item = String>) {
+        self.registry_manager.register_local_resources(registry, ids);
+    }
+
+    /// Unload every version of a registry, returns whether it was loaded
+    pub fn unload_registry(&mut self, name: &str) -> bool {
+        self.registry_manager.unload_registry(name)
+    }
+
+    /// Register data attached to registry entries, used by types like
+    /// `mcdoc:block_states[[block]]` (e.g. name "mcdoc:block_states")
+    pub fn register_attached_registry(&mut self, name: String, provider: AttachedRegistry) {
+        self.registry_manager.register_attached_registry(name, provider);
+    }
+    
+    /// Check strings annotated with `#[annotation]` using `check`, replacing any previous check
+    pub fn register_string_format(&mut self, annotation: impl Into<String>, check: FormatCheck) {
+        self.string_formats.register(annotation, check);
+    }
+    
+    /// Validate JSON against MCDOC schemas
+    pub fn validate_json(
+        &self,
+        json: &serde_json::Value,
+        resource_type: &str,
+        version: Option<&str>,
+    ) -> ValidationResult {
+        self.validate_json_with_options(json, resource_type, version, &ValidationOptions::default())
+    }
+
+    /// Validate JSON against MCDOC schemas with explicit options
+    pub fn validate_json_with_options(
+        &self,
+        json: &serde_json::Value,
+        resource_type: &str,
+        version: Option<&str>,
+        options: &ValidationOptions,
+    ) -> ValidationResult {
+        let interner = RefCell::new(StringInterner::new());
+        let context = ValidationContext::new(version, resource_type, options.source_file.as_deref(), options, &interner, &self.registry_manager);
+        self.validate_in_context(json, context)
+    }
+
+    /// Validate a file of a datapack or resourcepack, like `data/mypack/recipe/sword.json`,
+    /// inferring its resource type from its path. Errors and dependencies are reported
+    /// in `file_path`.
+    ///
+    /// The values of a tag file are checked against the registry named by the folders
+    /// after `tags`, and against the schema of `tag` when one is loaded. A path that
+    /// maps to no resource type gives a `SchemaNotFound` error.
+    pub fn validate_file(&self, file_path: &str, json: &serde_json::Value, version: Option<&str>) -> ValidationResult {
+        let Some(resource_type) = self.resource_types.resolve(file_path) else {
+            return ValidationResult::failure(vec![McDocError::unknown_resource_type(file_path)]);
+        };
+        let interner = RefCell::new(StringInterner::new());
+        let options = ValidationOptions::default();
+        let mut context = ValidationContext::new(version, resource_type, Some(file_path), &options, &interner, &self.registry_manager);
+        if resource_type != "tag" {
+            return self.validate_in_context(json, context);
+        }
+
+        if self.resolved_root(resource_type).is_some() {
+            self.validate_root(json, resource_type, &mut context);
+        }
+        match self.tag_file_target(file_path) {
+            Some((registry, _)) => Self::collect_tag_values(json, &registry, &mut context),
+            None => context.add_warning(&JsonPath::root(), ErrorCode::UnknownRegistry, format!(
+                "No loaded registry for tag file '{}', its values were not checked",
+                file_path
+            )),
+        }
+        self.check_dependencies(&mut context);
+        context.into_result()
+    }
+
+    /// Validate a document against the root type of the context's resource type,
+    /// then check its dependencies against the context's registries
+    fn validate_in_context<'j>(&self, json: &'j serde_json::Value, mut context: ValidationContext<'j>) -> ValidationResult {
+        let resource_type = context.resource_type;
+        self.validate_root(json, resource_type, &mut context);
+        self.check_dependencies(&mut context);
+        context.into_result()
+    }
+
+    /// Validate a document against the root type of its resource type
+    fn validate_root<'j>(&self, json: &'j serde_json::Value, resource_type: &str, context: &mut ValidationContext<'j>) {
+        if let Some(root) = self.resolved_root(resource_type) {
+            context.module = Some(root.module.clone());
+            self.validate_node(json, &root.type_expr, &JsonPath::root(), context, None);
+        } else {
+            context.add_error(&JsonPath::root(), ErrorCode::SchemaNotFound, format!("No MCDOC schema found for resource type '{}'", resource_type));
+        }
+    }
+
+    /// Record the entries and tags listed by a tag file as dependencies on `registry`.
+    /// Optional entries (`"required": false`) may be missing, so they aren't recorded.
+    fn collect_tag_values(json: &serde_json::Value, registry: &str, context: &mut ValidationContext) {
+        let Some(values) = json.get("values").and_then(serde_json::Value::as_array) else {
+            return;
+        };
+        let id = IdAnnotation { registry: Some(registry), tags: IdTags::Allowed, ..IdAnnotation::default() };
+        let values_path = JsonPath::root().key("values");
+        for (index, value) in values.iter().enumerate() {
+            let path = values_path.index(index);
+            match value {
+                serde_json::Value::String(entry) => Self::extract_id_dependency(entry, &id, &path, context),
+                serde_json::Value::Object(obj) if obj.get("required") != Some(&serde_json::Value::Bool(false)) => {
+                    if let Some(entry) = obj.get("id").and_then(serde_json::Value::as_str) {
+                        Self::extract_id_dependency(entry, &id, &path.key("id"), context);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Validate a JSON document given as text. Malformed JSON gives an invalid
+    /// result with an `InvalidJson` error instead of failing.
+    pub fn validate_json_str(
+        &self,
+        json_text: &str,
+        resource_type: &str,
+        version: Option<&str>,
+        options: &ValidationOptions,
+    ) -> ValidationResult {
+        match serde_json::from_str(json_text) {
+            Ok(json) => self.validate_json_with_options(&json, resource_type, version, options),
+            Err(e) => ValidationResult::failure(vec![McDocError {
+                resource_type: Some(resource_type.into()),
+                ..McDocError::invalid_json(options.source_file.as_deref().unwrap_or_default(), &e)
+            }]),
+        }
+    }
+
+    /// Validate JSON against a named struct, enum or type alias
+    ///
+    /// The name may be qualified by its schema module (`util::Ingredient` matches a
+    /// schema loaded as `util.mcdoc` or `data/util.mcdoc`) and may carry generic
+    /// arguments (`Conditions<ItemPredicate>`), which stand for the alias's parameters.
+    pub fn validate_against_type(
+        &self,
+        json: &serde_json::Value,
+        type_name: &str,
+        version: Option<&str>,
+    ) -> ValidationResult {
+        let options = ValidationOptions::default();
+        let interner = RefCell::new(StringInterner::new());
+        let mut context = ValidationContext::new(version, type_name, None, &options, &interner, &self.registry_manager);
+
+        match self.find_named_type(type_name) {
+            Ok(type_expr) => self.validate_node(json, &type_expr, &JsonPath::root(), &mut context, None),
+            Err(message) => context.add_error(&JsonPath::root(), ErrorCode::UnresolvedType, message),
+        }
+
+        self.check_dependencies(&mut context);
+        context.into_result()
+    }
+
+    /// Validate a "type + payload" object against a dispatcher registry like
+    /// `recipe_serializer`, the way `type: #[id="recipe_serializer"] string` followed
+    /// by `...minecraft:recipe_serializer[[type]]` does in a schema.
+    ///
+    /// `json["type"]` selects the dispatched type, its namespace being optional. Keys
+    /// without a dispatch fall back to `%unknown` and an absent `type` to `%none`, when
+    /// the schemas declare them. The type is reported as a dependency on the registry
+    /// and the other fields are validated against the dispatched type.
+    pub fn validate_dispatched(
+        &self,
+        json: &serde_json::Value,
+        dispatch_registry: &str,
+        version: Option<&str>,
+    ) -> ValidationResult {
+        let registry = if dispatch_registry.contains(':') {
+            dispatch_registry.to_string()
+        } else {
+            format!("minecraft:{}", dispatch_registry)
+        };
+        let key = json.get("type");
+        let payload = match json.as_object() {
+            Some(object) if key.is_some() => {
+                let mut payload = object.clone();
+                payload.remove("type");
+                Cow::Owned(serde_json::Value::Object(payload))
+            }
+            _ => Cow::Borrowed(json),
+        };
+
+        let options = ValidationOptions::default();
+        let interner = RefCell::new(StringInterner::new());
+        let mut context = ValidationContext::new(version, dispatch_registry, None, &options, &interner, &self.registry_manager);
+        let type_path = JsonPath::root().key("type");
+
+        let target = match key {
+            Some(serde_json::Value::String(key)) => {
+                let id = IdAnnotation { registry: Some(registry.strip_prefix("minecraft:").unwrap_or(&registry)), ..IdAnnotation::default() };
+                Self::extract_id_dependency(key, &id, &type_path, &mut context);
+                let target = self.find_dispatch_target(&registry, Some(key));
+                if target.is_none() {
+                    context.add_error_of_type(
+                        &type_path,
+                        ErrorCode::UnknownDispatchKey,
+                        format!("No dispatch registered for key '{}' in '{}'", key, registry),
+                        ErrorType::Resolution,
+                    );
+                }
+                target
+            }
+            Some(_) => {
+                context.add_error(&type_path, ErrorCode::TypeMismatch, "Expected string".to_string());
+                None
+            }
+            None => {
+                let target = self.find_dispatch_target(&registry, None);
+                if target.is_none() {
+                    context.add_error(&type_path, ErrorCode::MissingField, "Missing required field 'type'".to_string());
+                }
+                target
+            }
+        };
+
+        if let Some((filename, target)) = target {
+            context.module = Some(filename.to_string());
+            self.validate_node(&payload, target, &JsonPath::root(), &mut context, None);
+        }
+
+        self.check_dependencies(&mut context);
+        context.into_result()
+    }
+
+    /// Registry references of a JSON found by walking its schema, one per `#[id]` value.
+    ///
+    /// Unlike `RegistryManager::scan_required_registries` registries are known from the
+    /// schema rather than guessed, and nothing is checked against loaded registries, so
+    /// this tells which registries to load before calling `validate_json`.
+    pub fn get_required_registries(&self, json: &serde_json::Value, resource_type: &str) -> Vec<RegistryDependency> {
+        let options = ValidationOptions { keep_duplicate_dependencies: true, ..Default::default() };
+        let interner = RefCell::new(StringInterner::new());
+        let mut context = ValidationContext::new(None, resource_type, None, &options, &interner, &self.registry_manager);
+        if let Some(root) = self.resolved_root(resource_type) {
+            context.module = Some(root.module.clone());
+            self.validate_node(json, &root.type_expr, &JsonPath::root(), &mut context, None);
+        }
+
+        context.dependencies.into_iter()
+            .filter(|dependency| dependency.dependency_kind == DependencyKind::Resource)
+            .map(|dependency| RegistryDependency {
+                registry: dependency.registry_type.into(),
+                identifier: dependency.resource_location.into(),
+                is_tag: dependency.is_tag,
+                source_path: dependency.source_path,
+                pointer: dependency.pointer,
+            })
+            .collect()
+    }
+
+    /// Validate every file of a datapack, keyed by its path inside the datapack
+    ///
+    /// The resource type of each file is inferred from its path with `resource_types`.
+    /// Files it can't map are reported as warnings and not validated. Analysis time
+    /// is not measured on wasm32.
+    ///
+    /// Tag files (`data/<namespace>/tags/<registry>/...`) are loaded into their registry
+    /// and the other files are registered as local resources (see `register_local_resources`),
+    /// so files can reference the datapack's own tags and resources. Both only apply to
+    /// this analysis, the loaded registries are left as they were.
+    pub fn analyze_datapack(&self, files: &HashMap<String, serde_json::Value>) -> DatapackResult {
+        #[cfg(not(target_arch = "wasm32"))]
+        let start = Instant::now();
+
+        let mut file_paths: Vec<&String> = files.keys().collect();
+        file_paths.sort();
+
+        // Registries changed by the datapack are copied, the others are shared
+        let mut registries = self.registry_manager.clone();
+        for file_path in &file_paths {
+            match self.resource_types.resolve(file_path) {
+                Some("tag") => {
+                    // Tags of registries that aren't loaded can't be checked anyway
+                    if let Some((registry, tag_id)) = self.tag_file_target(file_path) {
+                        let _ = registries.load_tag_file(&registry, &tag_id, &files[*file_path]);
+                    }
+                }
+                Some(_) => {
+                    if let Some((registry, id)) = self.resource_types.resource_location(file_path) {
+                        registries.register_local_resources(registry, [id]);
+                    }
+                }
+                None => {}
+            }
+        }
+
+        // Registries, locations and file names repeat across files, share them all
+        let interner = RefCell::new(StringInterner::new());
+        let options = ValidationOptions::default();
+        let mut result = DatapackResult::new();
+        for file_path in file_paths {
+            match self.resource_types.resolve(file_path) {
+                Some(resource_type) => {
+                    let context = ValidationContext::new(None, resource_type, Some(file_path), &options, &interner, &registries);
+                    let file_result = self.validate_in_context(&files[file_path], context);
+                    result.add_file_result(file_path.clone(), file_result);
+                }
+                None => result.add_unknown_file(file_path.clone()),
+            }
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        result.set_analysis_time(start.elapsed().as_millis().min(u32::MAX as u128) as u32);
+
+        result
+    }
+
+    /// `analyze_datapack` for files given as text. Malformed files count as
+    /// invalid with an `InvalidJson` error and aren't loaded as tags or resources.
+    pub fn analyze_datapack_strs(&self, files: &HashMap<String, String>) -> DatapackResult {
+        let mut parsed = HashMap::with_capacity(files.len());
+        let mut malformed = Vec::new();
+        for (file_path, text) in files {
+            match serde_json::from_str(text) {
+                Ok(json) => {
+                    parsed.insert(file_path.clone(), json);
+                }
+                Err(e) => malformed.push((file_path.clone(), McDocError::invalid_json(file_path, &e))),
+            }
+        }
+
+        let mut result = self.analyze_datapack(&parsed);
+        malformed.sort_by(|a, b| a.0.cmp(&b.0));
+        for (file_path, error) in malformed {
+            result.add_file_result(file_path, ValidationResult::failure(vec![error]));
+        }
+        result
+    }
+
+    /// Validate files one at a time as they are pulled from the returned iterator
+    ///
+    /// Files are keyed by their path inside the datapack and their resource type is
+    /// inferred like in `analyze_datapack`. Files it can't map give an unvalidated result
+    /// with a warning. Nothing is validated ahead of the caller, so dropping the iterator
+    /// stops the validation. Unlike `analyze_datapack`, the files aren't loaded as tags or
+    /// local resources since they aren't known in advance.
+    pub fn validate_stream<'s, I>(&'s self, items: I) -> impl Iterator<Item = (String, ValidationResult)> + use<'s, 'input, I>
+    where
+        I: IntoIterator<Item = (String, serde_json::Value)>,
+    {
+        let interner = RefCell::new(StringInterner::new());
+        let options = ValidationOptions::default();
+        items.into_iter().map(move |(file_path, json)| {
+            let result = match self.resource_types.resolve(&file_path) {
+                Some(resource_type) => {
+                    let context = ValidationContext::new(None, resource_type, Some(&file_path), &options, &interner, &self.registry_manager);
+                    self.validate_in_context(&json, context)
+                }
+                None => ValidationResult::unknown_resource_type(&file_path),
+            };
+            (file_path, result)
+        })
+    }
+
+    /// `validate_stream` folded into a `DatapackResult`, keeping only the aggregated
+    /// errors, warnings and dependencies
+    pub fn analyze_stream<I>(&self, items: I) -> DatapackResult
+    where
+        I: IntoIterator<Item = (String, serde_json::Value)>,
+    {
+        #[cfg(not(target_arch = "wasm32"))]
+        let start = Instant::now();
+
+        let mut result = DatapackResult::new();
+        for (file_path, file_result) in self.validate_stream(items) {
+            result.add_file_result(file_path, file_result);
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        result.set_analysis_time(start.elapsed().as_millis().min(u32::MAX as u128) as u32);
+
+        result
+    }
+
+    /// Graph of the files of a datapack and the resources they reference, from their
+    /// validation results keyed by path inside the datapack (see `validate_stream`)
+    ///
+    /// The resource a file defines is inferred from its path like in `analyze_datapack`.
+    /// Resources registered with `register_local_resources` don't count as listed by
+    /// the registries, only the files of the graph provide local resources.
+    pub fn dependency_graph<'r>(&self, results: impl IntoIterator<Item = (&'r str, &'r ValidationResult)>) -> DependencyGraph {
+        let mut graph = DependencyGraph::new();
+        for (file_path, result) in results {
+            let resource = match self.resource_types.resolve(file_path) {
+                Some("tag") => self.tag_file_target(file_path).map(|(registry, id)| ResourceNode::new(&registry, &id, true)),
+                Some(_) => self.resource_types.resource_location(file_path)
+                    .map(|(registry, id)| ResourceNode::new(registry, &id, false)),
+                None => None,
+            };
+            graph.add_file(file_path, resource);
+
+            let mut added = Vec::new();
+            for dependency in &result.dependencies {
+                if dependency.dependency_kind != DependencyKind::Resource || &*dependency.registry_type == "unknown" {
+                    continue;
+                }
+                let resource = ResourceNode::new(&dependency.registry_type, &dependency.resource_location, dependency.is_tag);
+                if added.contains(&resource) {
+                    continue;
+                }
+                let in_registries = self.registry_manager.registry_contains(
+                    &dependency.registry_type,
+                    &dependency.resource_location,
+                    dependency.is_tag,
+                    None,
+                    Some("minecraft"),
+                );
+                graph.add_dependency(file_path, resource.clone(), in_registries);
+                added.push(resource);
+            }
+        }
+        graph
+    }
+
+    /// Registry and tag id of a tag file like `data/minecraft/tags/block/mineable/pickaxe.json`.
+    /// The registry is the longest loaded one matching the folders after `tags`, also
+    /// trying the pre-1.21 plural folder names (`blocks`).
+    fn tag_file_target(&self, file_path: &str) -> Option<(String, String)> {
+        let normalized = file_path.replace('\\', "/");
+        let segments: Vec<&str> = normalized.split('/').filter(|segment| !segment.is_empty()).collect();
+        let tags_index = segments.windows(3)
+            .position(|window| window[0] == "data" && window[2] == "tags")?;
+        let namespace = segments[tags_index + 1];
+        let rest = &segments[tags_index + 3..];
+
+        (1..rest.len()).rev().find_map(|split| {
+            let folder = rest[..split].join("/");
+            let registry = [folder.as_str(), folder.strip_suffix('s').unwrap_or(&folder)]
+                .into_iter()
+                .find(|name| self.registry_manager.has_registry(name))?
+                .to_string();
+            let tag_path = rest[split..].join("/");
+            let tag_path = tag_path.strip_suffix(".json").unwrap_or(&tag_path);
+            Some((registry, format!("{}:{}", namespace, tag_path)))
+        })
+    }
+
+    /// Check a JSON value against the base type and variant values of an enum.
+    /// Only variants available in the validated version count, and a deprecated
+    /// variant gives a warning.
+    fn validate_enum_value(
+        json_node: &serde_json::Value,
+        name: Option<&str>,
+        base_type: Option<&str>,
+        variants: &[EnumVariant<'input>],
+        path: &JsonPath,
+        context: &mut ValidationContext,
+    ) {
+        let enum_label = match name {
+            Some(name) => format!("enum '{}'", name),
+            None => "enum".to_string(),
+        };
+        let base_type_matches = match base_type {
+            Some("string") => json_node.is_string(),
+            Some("boolean") => json_node.is_boolean(),
+            Some(_) => json_node.is_number(),
+            None => true,
+        };
+        if !base_type_matches {
+            context.add_error(path, ErrorCode::TypeMismatch, format!(
+                "Expected {} value of {}, found {}",
+                base_type.unwrap_or_default(),
+                enum_label,
+                json_node
+            ));
+            return;
+        }
+
+        let available: Vec<(&EnumVariant<'input>, LiteralValue<'input>)> = variants.iter()
+            .filter(|variant| Self::is_available(&variant.annotations, context.version))
+            .map(|variant| (variant, variant.value.clone().unwrap_or(LiteralValue::String(Cow::Borrowed(variant.name)))))
+            .collect();
+        let matched = available.iter().find(|(_, value)| match value {
+            LiteralValue::String(s) => json_node.as_str() == Some(s.as_ref()),
+            LiteralValue::Number(n) => json_node.as_f64() == Some(*n),
+            LiteralValue::Boolean(b) => json_node.as_bool() == Some(*b),
+        });
+
+        if let Some((variant, _)) = matched {
+            Self::check_deprecated(&variant.annotations, &format!("{} {}", enum_label, json_node), path, context);
+        } else {
+            let values = available.into_iter().map(|(_, value)| value);
+            let allowed: Vec<String> = values
+                .map(|value| match value {
+                    LiteralValue::String(s) => format!("'{}'", s),
+                    LiteralValue::Number(n) => n.to_string(),
+                    LiteralValue::Boolean(b) => b.to_string(),
+                })
+                .collect();
+            context.add_error(path, ErrorCode::LiteralMismatch, format!(
+                "Invalid value {} for {}, expected one of: {}",
+                json_node,
+                enum_label,
+                allowed.join(", ")
+            ));
+        }
+    }
+
+    /// Whether `#[since]` and `#[until]` annotations allow an element in `version`.
+    /// Everything is available when no version is given.
+    fn is_available(annotations: &[crate::parser::Annotation<'input>], version: Option<&str>) -> bool {
+        let Some(version) = version else {
+            return true;
+        };
+
+        annotations.iter().all(|annotation| match (annotation.name, &annotation.data) {
+            ("since", crate::parser::AnnotationData::Simple(since)) => {
+                compare_versions(version, since) != Ordering::Less
+            }
+            ("until", crate::parser::AnnotationData::Simple(until)) => {
+                compare_versions(version, until) == Ordering::Less
+            }
+            _ => true,
+        })
+    }
+
+    /// Report keys of `obj` that are not declared by `members`
+    fn check_unknown_fields<'m>(
+        obj: &serde_json::Map<String, serde_json::Value>,
+        members: impl Iterator<Item = &'m crate::parser::StructMember<'input>> + Clone,
+        path: &JsonPath,
+        context: &mut ValidationContext,
+    )
+    where
+        'input: 'm,
+    {
+        let accepts_any_key = members.clone().any(|member| !matches!(member, crate::parser::StructMember::Field(_)));
+        if accepts_any_key {
+            return;
+        }
+
+        for key in obj.keys() {
+            let declared = members.clone().any(|member| {
+                matches!(member, crate::parser::StructMember::Field(field) if field.name == key)
+            });
+            if !declared {
+                context.add_error_of_type(&path.key(key), ErrorCode::UnknownField, format!("Unknown field '{}'", key), ErrorType::UnknownField);
+            }
+        }
+    }
+
+    /// Check the collected dependencies against the loaded registries
+    fn check_dependencies(&self, context: &mut ValidationContext) {
+        if !context.options.check_registries {
+            return;
+        }
+        let dependencies = context.dependencies.clone(); 
+        for dependency in dependencies.iter().filter(|d| d.dependency_kind == DependencyKind::Resource) {
+            if context.error_budget_exhausted() {
+                break;
+            }
+            let is_local = context.registries.is_local_resource(&dependency.registry_type, &dependency.resource_location);
+            if context.registries.has_registry(&dependency.registry_type) || is_local {
+                match context.registries.validate_resource_location_with_namespace(
+                    &dependency.registry_type,
+                    &dependency.resource_location,
+                    dependency.is_tag,
+                    context.version,
+                    Some(&context.options.default_namespace),
+                ) {
+                    Ok(false) => {
+                        context.add_dependency_error(dependency, ErrorCode::RegistryMiss, format!(
+                            "Resource '{}' not found in registry '{}'",
+                            dependency.resource_location,
+                            dependency.registry_type
+                        ));
+                    }
+                    Err(e) => {
+                        context.add_dependency_error(dependency, ErrorCode::UnknownRegistry, e.to_string());
+                    }
+                    Ok(true) if dependency.is_tag && context.options.check_tag_contents => {
+                        self.check_tag_contents(dependency, context);
+                    }
+                    Ok(true) => {} // Valid
+                }
+            } else if dependency.registry_type != "unknown" {
+                context.add_dependency_error(dependency, ErrorCode::UnknownRegistry, format!("Unknown registry '{}'", dependency.registry_type));
+            }
+        }
+        
+    }
+
+    /// Report a tag that expands to no entry or to entries missing from its registry
+    fn check_tag_contents(&self, dependency: &McDocDependency, context: &mut ValidationContext) {
+        let registry = &dependency.registry_type;
+        let tag = dependency.resource_location.trim_start_matches('#');
+        let namespace = Some(context.options.default_namespace.as_str());
+        let entries = match context.registries.expand_tag(registry, tag, context.version) {
+            Ok(entries) => entries,
+            Err(e) => {
+                context.add_dependency_error(dependency, ErrorCode::InvalidTag, e.to_string());
+                return;
+            }
+        };
+
+        if entries.is_empty() {
+            context.add_dependency_error(dependency, ErrorCode::InvalidTag, format!("Tag '#{}' in registry '{}' has no entries", tag, registry));
+            return;
+        }
+        let mut unknown: Vec<&String> = entries.iter()
+            .filter(|entry| !matches!(
+                context.registries.validate_resource_location_with_namespace(registry, entry, false, context.version, namespace),
+                Ok(true)
+            ))
+            .collect();
+        if !unknown.is_empty() {
+            unknown.sort();
+            let unknown: Vec<&str> = unknown.into_iter().map(String::as_str).collect();
+            context.add_dependency_error(dependency, ErrorCode::InvalidTag, format!(
+                "Tag '#{}' contains entries not found in registry '{}': {}",
+                tag, registry, unknown.join(", ")
+            ));
+        }
+    }
+
+    /// Recursive validation function
+    fn validate_node<'j>(
+        &self,
+        json_node: &'j serde_json::Value,
+        mcdoc_node: &TypeExpression<'input>,
+        path: &JsonPath,
+        context: &mut ValidationContext<'j>,
+        annotations: Option<&Vec<crate::parser::Annotation<'input>>>,
+    ) {
+        if !context.enter_node() {
+            return;
+        }
+        if context.ancestors.len() > context.options.max_depth {
+            context.add_error(path, ErrorCode::MaxDepthExceeded, format!("Value nested deeper than the maximum depth of {}", context.options.max_depth));
+            return;
+        }
+
+        if let Some(annotations) = annotations {
+            self.validate_annotations(json_node, annotations, path, context);
+        }
+
+        match mcdoc_node {
+            TypeExpression::Simple(type_name) => self.validate_simple_type(json_node, type_name, path, context),
+            TypeExpression::Struct { members, .. } => {
+                self.validate_struct(json_node, members, path, context);
+            }
+            TypeExpression::Array { element_type, constraints } => {
+                self.validate_array(json_node, element_type, constraints.as_ref(), path, context);
+            }
+            TypeExpression::Union(types) => self.validate_union(json_node, types, path, context),
+            TypeExpression::Generic { name, type_args } => self.validate_generic(json_node, name, type_args, path, context),
+            TypeExpression::Enum { name, base_type, variants } => {
+                Self::validate_enum_value(json_node, *name, *base_type, variants, path, context);
+            }
+            TypeExpression::Annotated { annotations, type_expr } => {
+                self.validate_node(json_node, type_expr, path, context, Some(annotations));
+                Self::check_exclusive_fields(json_node, annotations, path, context);
+            }
+            TypeExpression::Constrained { base_type, constraints } => {
+                self.validate_node(json_node, base_type, path, context, None);
+                Self::validate_constraints(json_node, constraints, path, context);
+            }
+            TypeExpression::Literal(literal_value) => Self::validate_literal(json_node, literal_value, path, context),
+            TypeExpression::Spread(spread) => self.validate_attached_spread(json_node, spread, path, context),
+            // A dispatch target by key, like `minecraft:block_entity[moving_piston]`
+            TypeExpression::Reference(ImportPath::Absolute(segments)) if segments.len() == 3 => {
+                self.validate_dispatch_reference(json_node, segments, path, context);
+            }
+            _ => {}
+        }
+    }
+
+    /// Warn about `subject` when `annotations` deprecate it in the validated version
+    fn check_deprecated(annotations: &[crate::parser::Annotation<'input>], subject: &str, path: &JsonPath, context: &mut ValidationContext) {
+        let Some(deprecated) = annotations.iter().find(|a| a.name == "deprecated") else {
+            return;
+        };
+        match &deprecated.data {
+            crate::parser::AnnotationData::Simple(since) => {
+                let applies = context.version
+                    .is_none_or(|version| compare_versions(version, since) != Ordering::Less);
+                if applies {
+                    context.add_warning(path, ErrorCode::Deprecated, format!("{} is deprecated since {}", subject, since));
+                }
+            }
+            _ => context.add_warning(path, ErrorCode::Deprecated, format!("{} is deprecated", subject)),
+        }
+    }
+
+    /// Check the `#[exclusive(fields=[...])]` groups of a struct, once its fields are
+    /// validated: at most one field of a group may be present, exactly one with `required=true`
+    fn check_exclusive_fields(json_node: &serde_json::Value, annotations: &[crate::parser::Annotation<'input>], path: &JsonPath, context: &mut ValidationContext) {
+        let Some(obj) = json_node.as_object() else {
+            return;
+        };
+        for annotation in annotations.iter().filter(|annotation| annotation.name == "exclusive") {
+            let crate::parser::AnnotationData::Complex(entries) = &annotation.data else {
+                continue;
+            };
+            let Some(AnnotationValue::List(fields)) = entries.get("fields") else {
+                continue;
+            };
+            let fields: Vec<&str> = fields.iter().filter_map(AnnotationValue::as_str).collect();
+            let present: Vec<String> = fields.iter()
+                .filter(|field| obj.contains_key(**field))
+                .map(|field| format!("'{}'", field))
+                .collect();
+            let required = matches!(entries.get("required"), Some(AnnotationValue::Boolean(true)));
+
+            if let Some((last, others)) = present.split_last().filter(|(_, others)| !others.is_empty()) {
+                context.add_error(path, ErrorCode::ExclusiveFields, format!("Fields {} and {} are mutually exclusive", others.join(", "), last));
+            } else if present.is_empty() && required {
+                let fields: Vec<String> = fields.iter().map(|field| format!("'{}'", field)).collect();
+                context.add_error(path, ErrorCode::ExclusiveFields, format!("Expected one of the fields {}", fields.join(", ")));
+            }
+        }
+    }
+
+    /// Checks given by the annotations of a value, like `#[id]` or `#[deprecated]`.
+    /// Kept out of `validate_node` so its stack frame stays small on deep documents.
+    fn validate_annotations<'j>(
+        &self,
+        json_node: &'j serde_json::Value,
+        annotations: &[crate::parser::Annotation<'input>],
+        path: &JsonPath,
+        context: &mut ValidationContext<'j>,
+    ) {
+        Self::check_deprecated(annotations, &format!("'{}'", path), path, context);
+
+        if let Some(id_annotation) = annotations.iter().find(|a| a.name == "id") {
+            if let Some((code, message, suggestion)) = json_node.as_str().and_then(Self::diagnose_resource_location) {
+                context.add_error_with_suggestion(path, code, message, suggestion);
+            } else if let Some(s) = json_node.as_str() {
+                Self::extract_id_dependency(s, &IdAnnotation::from_data(&id_annotation.data), path, context);
+            }
+        }
+
+        if let Some(dispatcher_annotation) = annotations.iter().find(|a| a.name == "dispatcher_key") {
+            if let (Some(s), crate::parser::AnnotationData::Simple(dispatcher)) = (json_node.as_str(), &dispatcher_annotation.data) {
+                self.validate_dispatcher_key(s, dispatcher, path, context);
+            }
+        }
+
+        if let Some(s) = json_node.as_str() {
+            for annotation in annotations {
+                let Some(check) = self.string_formats.get(annotation.name) else { continue };
+                let argument = match &annotation.data {
+                    crate::parser::AnnotationData::Simple(argument) => Some(argument.as_ref()),
+                    _ => None,
+                };
+                if let Err(reason) = check(s, argument) {
+                    context.add_error(path, ErrorCode::InvalidFormat, format!("Invalid {} '{}': {}", annotation.name, s, reason));
+                }
+            }
+        }
+    }
+
+    /// Built-in type like `int` or `string`, other names are resolved as declarations
+    fn validate_simple_type<'j>(&self, json_node: &'j serde_json::Value, type_name: &str, path: &JsonPath, context: &mut ValidationContext<'j>) {
+        let type_str = match json_node {
+            serde_json::Value::String(_) => "string",
+            serde_json::Value::Number(_) => "number",
+            serde_json::Value::Bool(_) => "boolean",
+            serde_json::Value::Array(_) => "array",
+            serde_json::Value::Object(_) => "object",
+            serde_json::Value::Null => "null",
+        };
+
+        match type_name {
+            "string" if !json_node.is_string() => {
+                context.add_error(path, ErrorCode::TypeMismatch, format!("Expected string, found {}", type_str));
+            },
+            "byte" | "short" | "int" | "long" | "float" | "double" if !json_node.is_number() => {
+                context.add_error(path, ErrorCode::TypeMismatch, format!("Expected number, found {}", type_str));
+            },
+            "byte" | "short" | "int" | "long" => {
+                Self::validate_integer(json_node, type_name, path, context);
+            },
+            "float" | "double" => {},
+            "boolean" if !json_node.is_boolean() => {
+                context.add_error(path, ErrorCode::TypeMismatch, format!("Expected boolean, found {}", type_str));
+            },
+            // `any` and `unknown` accept everything
+            _ if is_builtin_type(type_name) => {},
+            _ => self.validate_named_type(json_node, type_name, path, context),
+        }
+    }
+
+    fn validate_array<'j>(
+        &self,
+        json_node: &'j serde_json::Value,
+        element_type: &TypeExpression<'input>,
+        constraints: Option<&ArrayConstraints>,
+        path: &JsonPath,
+        context: &mut ValidationContext<'j>,
+    ) {
+        if let Some(arr) = json_node.as_array() {
+            if let Some(constraints) = constraints {
+                if let Some(min) = constraints.min {
+                    if arr.len() < min as usize || (constraints.min_exclusive && arr.len() == min as usize) {
+                        let bound = if constraints.min_exclusive { "more than" } else { "at least" };
+                        context.add_error(path, ErrorCode::OutOfRange, format!("Expected {} {} elements, found {}", bound, min, arr.len()));
+                    }
+                }
+                if let Some(max) = constraints.max {
+                    if arr.len() > max as usize || (constraints.max_exclusive && arr.len() == max as usize) {
+                        let bound = if constraints.max_exclusive { "fewer than" } else { "at most" };
+                        context.add_error(path, ErrorCode::OutOfRange, format!("Expected {} {} elements, found {}", bound, max, arr.len()));
+                    }
+                }
+            }
+
+            context.ancestors.push(json_node);
+            for (i, elem) in arr.iter().enumerate() {
+                let new_path = path.index(i);
+                self.validate_node(elem, element_type, &new_path, context, None);
+            }
+            context.ancestors.pop();
+        } else {
+            context.add_error(path, ErrorCode::TypeMismatch, "Expected array".to_string());
+        }
+    }
+
+    /// A union matches when any branch available in the version validates without errors
+    fn validate_union<'j>(&self, json_node: &'j serde_json::Value, types: &[TypeExpression<'input>], path: &JsonPath, context: &mut ValidationContext<'j>) {
+        // A variant picked by its literal field reports its own errors, not a union mismatch
+        if let Some((branch_type, variant)) = self.discriminated_branch(json_node, types, context) {
+            let mut temp_context = context.branch();
+            self.validate_node(json_node, branch_type, path, &mut temp_context, None);
+            context.absorb_budget(&temp_context);
+            if context.truncation.is_some() {
+                return;
+            }
+            for mut error in temp_context.errors {
+                error.message = format!("in variant {}: {}", variant, error.message);
+                context.errors.push(error);
+            }
+            context.dependencies.extend(temp_context.dependencies);
+            context.warnings.extend(temp_context.warnings);
+            return;
+        }
+
+        let mut branch_failures = Vec::new();
+        for mcdoc_type in types {
+            if let TypeExpression::Annotated { annotations, .. } = mcdoc_type {
+                if !Self::is_available(annotations, context.version) {
+                    continue;
+                }
+            }
+            let mut temp_context = context.branch();
+            self.validate_node(json_node, mcdoc_type, path, &mut temp_context, None);
+            context.absorb_budget(&temp_context);
+            if context.truncation.is_some() {
+                return;
+            }
+            if temp_context.errors.is_empty() {
+                // It matched one of the types in the union, so it's valid.
+                // We also need to merge the dependencies found.
+                context.dependencies.extend(temp_context.dependencies);
+                context.warnings.extend(temp_context.warnings);
+                return;
+            }
+            // A branch too deep to check can't tell whether the union matches
+            if temp_context.errors.iter().any(|error| error.code == ErrorCode::MaxDepthExceeded) {
+                context.errors.extend(temp_context.errors.into_iter().filter(|error| error.code == ErrorCode::MaxDepthExceeded));
+                return;
+            }
+            branch_failures.push((mcdoc_type, temp_context.errors));
+        }
+
+        let message = Self::describe_literal_union_failure(json_node, &branch_failures)
+            .unwrap_or_else(|| Self::describe_union_failure(&branch_failures, path));
+        context.add_error(path, ErrorCode::UnionNoMatch, message);
+    }
+
+    /// Branch of a union of structs selected by a field every branch declares with a
+    /// distinct literal value, like `type: "block"`, along with its `type="block"` label.
+    ///
+    /// `None` when a branch isn't a struct, no such field exists, or the JSON value of
+    /// the field matches no branch.
+    fn discriminated_branch<'t>(
+        &'t self,
+        json_node: &serde_json::Value,
+        types: &'t [TypeExpression<'input>],
+        context: &ValidationContext,
+    ) -> Option<(&'t TypeExpression<'input>, String)> {
+        let object = json_node.as_object()?;
+        let mut branches: Vec<(&TypeExpression<'input>, Vec<(&str, &LiteralValue<'input>)>)> = Vec::new();
+        for mcdoc_type in types {
+            let mut struct_type = mcdoc_type;
+            if let TypeExpression::Annotated { annotations, type_expr } = mcdoc_type {
+                if !Self::is_available(annotations, context.version) {
+                    continue;
+                }
+                struct_type = type_expr;
+            }
+            let members: &[StructMember<'input>] = match struct_type {
+                TypeExpression::Struct { members, .. } => members,
+                TypeExpression::Simple(name) => {
+                    let (scope, name) = self.scoped_name(context.module.as_deref(), name)?;
+                    self.find_struct(scope, name)?.1
+                }
+                _ => return None,
+            };
+            let literals = members.iter().filter_map(|member| {
+                let StructMember::Field(field) = member else { return None };
+                let mut field_type = &field.field_type;
+                while let TypeExpression::Annotated { type_expr, .. } = field_type {
+                    field_type = type_expr;
+                }
+                match field_type {
+                    TypeExpression::Literal(value) => Some((field.name, value)),
+                    _ => None,
+                }
+            }).collect();
+            branches.push((mcdoc_type, literals));
+        }
+        if branches.len() < 2 {
+            return None;
+        }
+
+        for &(field, _) in &branches[0].1 {
+            let values: Option<Vec<&LiteralValue>> = branches.iter()
+                .map(|(_, literals)| literals.iter().find(|(name, _)| *name == field).map(|&(_, value)| value))
+                .collect();
+            let Some(values) = values else { continue };
+            if values.iter().enumerate().any(|(index, value)| values[..index].contains(value)) {
+                continue;
+            }
+            let actual = object.get(field)?;
+            let index = values.iter().position(|value| Self::literal_matches(actual, value))?;
+            return Some((branches[index].0, format!("{}={}", field, printer::literal(values[index]))));
+        }
+        None
+    }
+
+    fn literal_matches(json_node: &serde_json::Value, literal_value: &LiteralValue) -> bool {
+        match literal_value {
+            LiteralValue::String(expected) => json_node.as_str() == Some(expected.as_ref()),
+            LiteralValue::Number(expected) => json_node.as_f64().is_some_and(|actual| (actual - expected).abs() <= f64::EPSILON),
+            LiteralValue::Boolean(expected) => json_node.as_bool() == Some(*expected),
+        }
+    }
+
+    /// Generic type alias instantiated with its arguments, like `Layer<int>`
+    fn validate_generic<'j>(
+        &self,
+        json_node: &'j serde_json::Value,
+        name: &str,
+        type_args: &[TypeExpression<'input>],
+        path: &JsonPath,
+        context: &mut ValidationContext<'j>,
+    ) {
+        // Unknown generic types are accepted like other unresolved references
+        let found = self.scoped_name(context.module.as_deref(), name)
+            .and_then(|(scope, name)| self.find_type_alias(scope, name));
+        if let Some((filename, type_decl)) = found {
+            match Self::check_type_arity(name, type_decl.type_params.len(), type_args.len()) {
+                Ok(()) => {
+                    let instantiated = Self::substitute_type_params(&type_decl.type_expr, &type_decl.type_params, type_args);
+                    let type_args: Vec<String> = type_args.iter().map(printer::type_summary).collect();
+                    let instance = format!("{}<{}>", type_decl.name, type_args.join(", "));
+                    self.validate_alias(json_node, filename, &instance, &instantiated, path, context);
+                }
+                Err(message) => context.add_error(path, ErrorCode::UnresolvedType, message),
+            }
+        }
+    }
+
+    /// Dispatch target named by registry and key, like `minecraft:block_entity[moving_piston]`
+    fn validate_dispatch_reference<'j>(
+        &self,
+        json_node: &'j serde_json::Value,
+        segments: &[&str],
+        path: &JsonPath,
+        context: &mut ValidationContext<'j>,
+    ) {
+        let registry = format!("{}:{}", segments[0], segments[1]);
+        match self.find_dispatch_target(&registry, Some(segments[2])) {
+            Some((filename, target)) => {
+                let outer = context.module.replace(filename.to_string());
+                self.validate_node(json_node, target, path, context, None);
+                context.module = outer;
+            }
+            None => context.add_error_of_type(
+                path,
+                ErrorCode::UnknownDispatchKey,
+                format!("No dispatch registered for key '{}' in '{}'", segments[2], registry),
+                ErrorType::Resolution,
+            ),
+        }
+    }
+
+    /// Range of a number, or of the length of a string
+    fn validate_constraints(json_node: &serde_json::Value, constraints: &TypeConstraints, path: &JsonPath, context: &mut ValidationContext) {
+        if let Some(value) = json_node.as_f64() {
+            if let Some(min) = constraints.min {
+                if value < min || (constraints.min_exclusive && value == min) {
+                    let bound = if constraints.min_exclusive { "greater than" } else { "of at least" };
+                    context.add_error(path, ErrorCode::OutOfRange, format!("Expected a value {} {}, found {}", bound, min, value));
+                }
+            }
+            if let Some(max) = constraints.max {
+                if value > max || (constraints.max_exclusive && value == max) {
+                    let bound = if constraints.max_exclusive { "less than" } else { "of at most" };
+                    context.add_error(path, ErrorCode::OutOfRange, format!("Expected a value {} {}, found {}", bound, max, value));
+                }
+            }
+        } else if let Some(text) = json_node.as_str() {
+            let length = text.chars().count() as f64;
+            if let Some(min) = constraints.min {
+                if length < min || (constraints.min_exclusive && length == min) {
+                    let bound = if constraints.min_exclusive { "more than" } else { "at least" };
+                    context.add_error(path, ErrorCode::OutOfRange, format!("Expected a string of {} {} characters, found {}", bound, min, length));
+                }
+            }
+            if let Some(max) = constraints.max {
+                if length > max || (constraints.max_exclusive && length == max) {
+                    let bound = if constraints.max_exclusive { "fewer than" } else { "at most" };
+                    context.add_error(path, ErrorCode::OutOfRange, format!("Expected a string of {} {} characters, found {}", bound, max, length));
+                }
+            }
+        }
+    }
+
+    /// Exact string, number or boolean value
+    fn validate_literal(json_node: &serde_json::Value, literal_value: &LiteralValue, path: &JsonPath, context: &mut ValidationContext) {
+        // Validate that the JSON value exactly matches the literal constraint
+        match literal_value {
+            LiteralValue::String(expected) => {
+                if let Some(actual) = json_node.as_str() {
+                    if actual != *expected {
+                        context.add_error(path, ErrorCode::LiteralMismatch, format!("Expected '{}', found '{}'", expected, actual));
+                    }
+                } else {
+                    context.add_error(path, ErrorCode::LiteralMismatch, format!("Expected string '{}', found non-string", expected));
+                }
+            }
+            LiteralValue::Number(expected) => {
+                if let Some(actual) = json_node.as_f64() {
+                    if (actual - expected).abs() > f64::EPSILON {
+                        context.add_error(path, ErrorCode::LiteralMismatch, format!("Expected {}, found {}", expected, actual));
+                    }
+                } else {
+                    context.add_error(path, ErrorCode::LiteralMismatch, format!("Expected number {}, found non-number", expected));
+                }
+            }
+            LiteralValue::Boolean(expected) => {
+                if let Some(actual) = json_node.as_bool() {
+                    if actual != *expected {
+                        context.add_error(path, ErrorCode::LiteralMismatch, format!("Expected {}, found {}", expected, actual));
+                    }
+                } else {
+                    context.add_error(path, ErrorCode::LiteralMismatch, format!("Expected boolean {}, found non-boolean", expected));
+                }
+            }
+        }
+    }
+
+    /// Properties of an attached registry entry, like `mcdoc:block_states[[block]]`.
+    /// Other spreads used as types are not resolved.
+    fn validate_attached_spread(&self, json_node: &serde_json::Value, spread: &SpreadExpression<'input>, path: &JsonPath, context: &mut ValidationContext) {
+        let registry = format!("{}:{}", spread.namespace, spread.registry);
+        let Some(attached) = context.registries.attached_registry(&registry) else {
+            return;
+        };
+        let entry = spread.dynamic_key.as_ref()
+            .and_then(|key| Self::resolve_dynamic_reference(&key.reference, &context.ancestors))
+            .and_then(|value| value.as_str());
+        // Unknown entries, like modded blocks, are accepted
+        if let Some((entry, properties)) = entry.and_then(|entry| Some((entry, attached.properties(entry)?))) {
+            Self::validate_attached_properties(json_node, properties, entry, path, context);
+        }
+    }
+
+    /// Check a property name, or an object of properties and values, against the
+    /// properties attached to `entry`
+    fn validate_attached_properties(
+        json_node: &serde_json::Value,
+        properties: &HashMap<String, Vec<String>>,
+        entry: &str,
+        path: &JsonPath,
+        context: &mut ValidationContext,
+    ) {
+        let check_name = |name: &str, path: &JsonPath, context: &mut ValidationContext| {
+            let known = properties.contains_key(name);
+            if !known {
+                context.add_error(path, ErrorCode::InvalidProperty, format!("Unknown property '{}' for '{}'", name, entry));
+            }
+            known
+        };
+
+        match json_node {
+            serde_json::Value::String(name) => {
+                check_name(name, path, context);
+            }
+            serde_json::Value::Object(obj) => {
+                for (name, value) in obj {
+                    let property_path = path.key(name);
+                    if !check_name(name, &property_path, context) {
+                        continue;
+                    }
+                    let text = match value {
+                        serde_json::Value::String(text) => text.clone(),
+                        serde_json::Value::Number(_) | serde_json::Value::Bool(_) => value.to_string(),
+                        _ => {
+                            context.add_error(&property_path, ErrorCode::InvalidProperty, format!("Expected a value for property '{}'", name));
+                            continue;
+                        }
+                    };
+                    let values = &properties[name.as_str()];
+                    if !values.contains(&text) {
+                        context.add_error(&property_path, ErrorCode::InvalidProperty, format!(
+                            "Invalid value '{}' for property '{}', expected one of: {}",
+                            text, name, values.join(", ")
+                        ));
+                    }
+                }
+            }
+            _ => context.add_error(path, ErrorCode::InvalidProperty, "Expected a property name or an object of properties".to_string()),
+        }
+    }
+
+    /// Record the discriminator a dispatch spread reads as a dependency: on the content
+    /// registry named like the dispatcher when one is loaded (`recipe_serializer` for
+    /// `minecraft:recipe_serializer`), else as a key of the dispatcher. A dependency
+    /// already recorded for it, like one from an `#[id]` on the field, isn't repeated.
+    fn record_discriminator(&self, registry: &str, reference: &DynamicReferenceType<'input>, path: &JsonPath, context: &mut ValidationContext) {
+        // Attached registries like `mcdoc:block_states` give properties, not dispatches
+        if context.registries.attached_registry(registry).is_some() {
+            return;
+        }
+        let Some(value) = Self::resolve_dynamic_reference(reference, &context.ancestors).and_then(|value| value.as_str()) else {
+            return;
+        };
+        let Some(source_path) = Self::dynamic_reference_path(reference, path) else {
+            return;
+        };
+
+        let content_registry = [registry.strip_prefix("minecraft:").unwrap_or(registry), registry].into_iter()
+            .find(|name| context.registries.has_registry(name));
+        let (registry_type, resource_location, dependency_kind) = match content_registry {
+            Some(name) => (name, Self::canonical_location(value, &context.options.default_namespace), DependencyKind::Resource),
+            None => (registry, value.to_string(), DependencyKind::DispatcherKey),
+        };
+        let source = source_path.to_string();
+        let recorded = context.dependencies.iter().any(|dependency| {
+            dependency.source_path == source
+                && &*dependency.registry_type == registry_type
+                && *dependency.resource_location == *resource_location
+        });
+        if recorded {
+            return;
+        }
+
+        context.dependencies.push(McDocDependency {
+            resource_location: context.intern(&resource_location),
+            registry_type: context.intern(registry_type),
+            source_path: source,
+            pointer: source_path.to_pointer(),
+            source_file: context.file.clone(),
+            is_tag: false,
+            dependency_kind,
+            count: 1,
+        });
+    }
+
+    /// Path of the value a dynamic reference reads, from the path of the current object
+    fn dynamic_reference_path(reference: &DynamicReferenceType<'input>, path: &JsonPath) -> Option<JsonPath> {
+        match reference {
+            DynamicReferenceType::Field(field) => Some(path.key(field)),
+            DynamicReferenceType::SpecialKey(_) => None,
+            DynamicReferenceType::Path(segments) => segments.iter().try_fold(path.clone(), |current, segment| match segment {
+                PathSegment::Parent => current.parent(),
+                PathSegment::Field(field) => Some(current.key(field)),
+                PathSegment::Key => None,
+            }),
+        }
+    }
+
+    /// Check a `#[dispatcher_key]` string against the keys dispatched by the schemas
+    fn validate_dispatcher_key(&self, value: &str, dispatcher: &str, path: &JsonPath, context: &mut ValidationContext) {
+        context.dependencies.push(McDocDependency {
+            resource_location: context.intern(value),
+            registry_type: context.intern(dispatcher),
+            source_path: path.to_string(),
+            pointer: path.to_pointer(),
+            source_file: context.file.clone(),
+            is_tag: false,
+            dependency_kind: DependencyKind::DispatcherKey,
+            count: 1,
+        });
+
+        let keys = self.dispatcher_keys(dispatcher);
+        if keys.contains(&Self::normalize_dispatch_key(value)) {
+            return;
+        }
+
+        let mut known: Vec<&str> = keys;
+        known.sort_unstable();
+        known.dedup();
+        let message = if known.is_empty() {
+            format!("Unknown key '{}' for dispatcher '{}' (no keys registered)", value, dispatcher)
+        } else if known.len() > MAX_LISTED_KEYS {
+            format!(
+                "Unknown key '{}' for dispatcher '{}', expected one of: {}, ... ({} more)",
+                value, dispatcher, known[..MAX_LISTED_KEYS].join(", "), known.len() - MAX_LISTED_KEYS
+            )
+        } else {
+            format!("Unknown key '{}' for dispatcher '{}', expected one of: {}", value, dispatcher, known.join(", "))
+        };
+        context.add_error(path, ErrorCode::UnknownDispatchKey, message);
+    }
+
+    /// All dispatch keys registered for a dispatcher registry like "minecraft:loot_function"
+    pub(crate) fn dispatcher_keys(&self, dispatcher: &str) -> Vec<&'input str> {
+        let (namespace, registry_path) = match dispatcher.split_once(':') {
+            Some((namespace, registry_path)) => (namespace, registry_path),
+            None => ("minecraft", dispatcher),
+        };
+
+        let mut keys = Vec::new();
+        for schema in self.mcdoc_schemas.values() {
+            for decl in &schema.declarations {
+                if let Declaration::Dispatch(dispatch) = decl {
+                    if dispatch.source.namespace == namespace && dispatch.source.registry == registry_path {
+                        keys.extend(dispatch.targets.iter().filter_map(|target| match target {
+                            DispatchTarget::Specific(key) => Some(Self::normalize_dispatch_key(key)),
+                            _ => None,
+                        }));
+                    }
+                }
+            }
+        }
+        keys
+    }
+
+    /// Check a JSON object against the members of a struct
+    fn validate_struct<'j>(
+        &self,
+        json_node: &'j serde_json::Value,
+        members: &[StructMember<'input>],
+        path: &JsonPath,
+        context: &mut ValidationContext<'j>,
+    ) {
+        if let Some(obj) = json_node.as_object() {
+            // A spread validates the same object again, it doesn't enclose itself
+            let entered = !context.ancestors.last().is_some_and(|current| std::ptr::eq(*current, json_node));
+            if entered {
+                context.ancestors.push(json_node);
+            }
+
+            let mut expansion = SpreadExpansion::default();
+            let members = self.expand_spreads(
+                &context.ancestors,
+                members,
+                context.version,
+                context.module.as_deref(),
+                context.options.memoize_spreads,
+                &mut expansion,
+            );
+            for cycle in &expansion.cycles {
+                context.add_error_of_type(path, ErrorCode::TypeCycle, format!("Spread cycle: {}", cycle), ErrorType::Resolution);
+            }
+            for member in MemberRun::flatten(&members) {
+                match member {
+                    crate::parser::StructMember::Field(field) => {
+                        let field_name = field.name;
+                        let new_path = path.key(field_name);
+                        
+                        if !Self::is_available(&field.annotations, context.version) {
+                            if obj.contains_key(field_name) {
+                                context.add_error(&new_path, ErrorCode::UnavailableField, format!(
+                                    "Field '{}' is not available in version {}",
+                                    field_name,
+                                    context.version.unwrap_or_default()
+                                ));
+                            }
+                            continue;
+                        }
+
+                        if let Some(value) = obj.get(field_name) {
+                            // Checks like `#[id]` come from the annotations of the type
+                            Self::check_deprecated(&field.annotations, &format!("'{}'", new_path), &new_path, context);
+                            self.validate_node(value, &field.field_type, &new_path, context, None);
+                        } else if !field.optional {
+                            context.add_error(&new_path, ErrorCode::MissingField, format!("Missing required field '{}'", field_name));
+                        }
+                    }
+                    crate::parser::StructMember::DynamicField(dynamic_field) => {
+                        // For dynamic fields like [#[id="mob_effect"] string]: MobEffectPredicate
+                        // every key not declared by a named field is validated with its value
+                        for (key, value) in obj.iter() {
+                            let declared = MemberRun::flatten(&members).any(|member| {
+                                matches!(member, crate::parser::StructMember::Field(field) if field.name == key)
+                            });
+                            if declared {
+                                continue;
+                            }
+                            let key_path = path.key(key);
+                            
+                            let key_value = serde_json::Value::String(key.clone());
+                            let mut key_context = context.detached();
+                            self.validate_node(&key_value, &dynamic_field.key_type, &key_path, &mut key_context, None);
+                            context.absorb(key_context);
+                            self.validate_node(value, &dynamic_field.value_type, &key_path, context, Some(&dynamic_field.annotations));
+                        }
+                    }
+                    crate::parser::StructMember::Spread(spread) => {
+                        // Inline struct targets were expanded above, other spreads are
+                        // validated against the whole object when they can be resolved
+                        if let Some(target) = self.find_spread_target(&context.ancestors, spread) {
+                            self.validate_node(json_node, target, path, context, None);
+                        }
+                    }
+                }
+            }
+
+            if context.options.strict_fields {
+                Self::check_unknown_fields(obj, MemberRun::flatten(&members), path, context);
+            }
+            // After the fields, so that a discriminator with its own `#[id]` is recorded once
+            for (registry, reference) in &expansion.discriminators {
+                self.record_discriminator(registry, reference, path, context);
+            }
+            if entered {
+                context.ancestors.pop();
+            }
+        } else {
+            context.add_error(path, ErrorCode::TypeMismatch, "Expected object".to_string());
+        }
+    }
+
+    /// Check that a number is integral and fits the range of `byte`, `short`, `int` or `long`
+    fn validate_integer(json_node: &serde_json::Value, type_name: &str, path: &JsonPath, context: &mut ValidationContext) {
+        let (min, max) = match type_name {
+            "byte" => (i8::MIN as i128, i8::MAX as i128),
+            "short" => (i16::MIN as i128, i16::MAX as i128),
+            "int" => (i32::MIN as i128, i32::MAX as i128),
+            _ => (i64::MIN as i128, i64::MAX as i128),
+        };
+
+        let value = match json_node.as_i64().map(i128::from).or_else(|| json_node.as_u64().map(i128::from)) {
+            Some(value) => value,
+            None => {
+                let value = json_node.as_f64().unwrap_or_default();
+                if value.fract() != 0.0 {
+                    context.add_error(path, ErrorCode::TypeMismatch, format!("Expected an integer, found {}", json_node));
+                    return;
+                }
+                value as i128
+            }
+        };
+
+        if value < min || value > max {
+            context.add_error(path, ErrorCode::OutOfRange, format!("Expected a {} between {} and {}, found {}", type_name, min, max, json_node));
+        }
+    }
+
+    /// Error message for a union, explaining every branch failure, closest branch first.
+    ///
+    /// Branches that only failed at the union's own path didn't match the kind of value
+    /// at all, so branches with nested errors come first, then fewer errors first.
+    fn describe_union_failure(branch_failures: &[(&TypeExpression<'input>, Vec<McDocError>)], path: &JsonPath) -> String {
+        let pointer = path.to_pointer();
+        let mut failures: Vec<_> = branch_failures.iter().collect();
+        failures.sort_by_key(|(_, errors)| (errors.iter().all(|error| error.pointer == pointer), errors.len()));
+
+        let mut descriptions: Vec<String> = Vec::new();
+        for (branch_type, errors) in failures {
+            let mut messages: Vec<String> = Vec::new();
+            for error in errors {
+                let message = if error.pointer == pointer {
+                    error.message.clone()
+                } else {
+                    format!("{} at '{}'", error.message, error.path)
+                };
+                if !messages.contains(&message) {
+                    messages.push(message);
+                }
+            }
+            let description = format!("as {}: {}", Self::describe_type(branch_type), messages.join(", "));
+            if !descriptions.contains(&description) {
+                descriptions.push(description);
+            }
+        }
+
+        if descriptions.is_empty() {
+            "JSON does not match any of the expected types".to_string()
+        } else {
+            format!("JSON does not match any of the expected types ({})", descriptions.join("; "))
+        }
+    }
+
+    /// Error message for a union of literals only, like `(0 | 90 | 180 | 270)`, listing
+    /// the allowed values. `None` when a branch isn't a literal.
+    fn describe_literal_union_failure(json_node: &serde_json::Value, branch_failures: &[(&TypeExpression<'input>, Vec<McDocError>)]) -> Option<String> {
+        if branch_failures.is_empty() {
+            return None;
+        }
+        let mut allowed: Vec<String> = Vec::new();
+        for (branch_type, _) in branch_failures {
+            let mut branch_type = *branch_type;
+            while let TypeExpression::Annotated { type_expr, .. } = branch_type {
+                branch_type = type_expr;
+            }
+            let TypeExpression::Literal(value) = branch_type else { return None };
+            let value = printer::literal(value);
+            if !allowed.contains(&value) {
+                allowed.push(value);
+            }
+        }
+        let found = match json_node {
+            serde_json::Value::Array(_) => "array".to_string(),
+            serde_json::Value::Object(_) => "object".to_string(),
+            scalar => scalar.to_string(),
+        };
+        Some(format!("Expected one of {}, found {}", allowed.join(", "), found))
+    }
+
+    /// Short name of a type for error messages
+    fn describe_type(type_expr: &TypeExpression<'input>) -> String {
+        match type_expr {
+            TypeExpression::Simple(name) => name.to_string(),
+            TypeExpression::Array { .. } => "array".to_string(),
+            TypeExpression::Union(_) => "union".to_string(),
+            TypeExpression::Struct { name: Some(name), .. } => format!("struct '{}'", name),
+            TypeExpression::Struct { name: None, .. } => "struct".to_string(),
+            TypeExpression::Generic { name, .. } => name.to_string(),
+            TypeExpression::Reference(_) => "reference".to_string(),
+            TypeExpression::Spread(_) => "spread".to_string(),
+            TypeExpression::Literal(LiteralValue::String(s)) => format!("'{}'", s),
+            TypeExpression::Literal(LiteralValue::Number(n)) => n.to_string(),
+            TypeExpression::Literal(LiteralValue::Boolean(b)) => b.to_string(),
+            TypeExpression::Enum { name, .. } => name.map_or("enum".to_string(), |name| format!("enum '{}'", name)),
+            TypeExpression::Annotated { type_expr, .. } => Self::describe_type(type_expr),
+            TypeExpression::Constrained { base_type, .. } => Self::describe_type(base_type),
+        }
+    }
+
+    /// Check an `#[id]` value against the annotation's tag rules and record the dependency
+    fn extract_id_dependency(value: &str, id: &IdAnnotation, path: &JsonPath, context: &mut ValidationContext) {
+        if value.is_empty() && id.empty_allowed {
+            return;
+        }
+
+        let tag_body = value.strip_prefix('#');
+        match (tag_body, id.tags) {
+            (Some(_), IdTags::None) => {
+                context.add_error(path, ErrorCode::InvalidResourceLocation, format!("Tags are not allowed here, found '{}'", value));
+                return;
+            }
+            (None, IdTags::Required) => {
+                context.add_error(path, ErrorCode::InvalidResourceLocation, format!("Expected a tag starting with '#', found '{}'", value));
+                return;
+            }
+            (Some(_), IdTags::Implicit) => {
+                context.add_error(path, ErrorCode::InvalidResourceLocation, format!("Expected a tag without '#', found '{}'", value));
+                return;
+            }
+            _ => {}
+        }
+
+        // Tags follow the same rules after their '#'
+        if let Err(ParseError::InvalidResourceId { reason, .. }) = ResourceId::parse(tag_body.unwrap_or(value)) {
+            context.add_error(path, ErrorCode::InvalidResourceLocation, format!("Invalid resource location '{}': {}", value, reason));
+            return;
+        }
+
+        if id.definition {
+            return;
+        }
+
+        let namespace = &context.options.default_namespace;
+        let resource_location = match tag_body {
+            Some(body) => format!("#{}", Self::canonical_location(&id.apply_path(body), namespace)),
+            None => Self::canonical_location(&id.apply_path(value), namespace),
+        };
+        context.dependencies.push(McDocDependency {
+            resource_location: context.intern(&resource_location),
+            registry_type: context.intern(id.registry.unwrap_or("unknown")),
+            source_path: path.to_string(),
+            pointer: path.to_pointer(),
+            source_file: context.file.clone(),
+            is_tag: tag_body.is_some() || id.tags == IdTags::Implicit,
+            dependency_kind: DependencyKind::Resource,
+            count: 1,
+        });
+    }
+
+    /// `namespace:path` form of a resource location, `stone` becomes `minecraft:stone`
+    fn canonical_location(location: &str, default_namespace: &str) -> String {
+        if location.contains(':') {
+            location.to_string()
+        } else {
+            format!("{}:{}", default_namespace, location)
+        }
+    }
+
+    /// Detects common malformed resource location shapes, giving the code of the shape,
+    /// the message and the corrected form
+    fn diagnose_resource_location(value: &str) -> Option<(ErrorCode, String, String)> {
+        let (tag_marker, body) = match value.strip_prefix('#') {
+            Some(rest) => ("#", rest),
+            None => ("", value),
+        };
+
+        if body.contains('#') {
+            let fixed = Self::with_default_namespace(&format!("#{}", body.replace('#', "")));
+            let message = format!(
+                "Invalid resource location '{}': the tag marker '#' must be the first character, did you mean '{}'?",
+                value, fixed
+            );
+            return Some((ErrorCode::MisplacedTagMarker, message, fixed));
+        }
+
+        let parts: Vec<&str> = body.split(':').collect();
+        let (code, reason, fixed) = match parts.as_slice() {
+            ["", path] => (ErrorCode::EmptyNamespace, "empty namespace".to_string(), format!("{}minecraft:{}", tag_marker, path)),
+            [first, second, path] if first == second => (
+                ErrorCode::RepeatedNamespace,
+                format!("repeated namespace '{}'", first),
+                format!("{}{}:{}", tag_marker, first, path),
+            ),
+            _ => return None,
+        };
+        let message = format!("Invalid resource location '{}': {}, did you mean '{}'?", value, reason, fixed);
+        Some((code, message, fixed))
+    }
+
+    fn with_default_namespace(value: &str) -> String {
+        let (tag_marker, body) = match value.strip_prefix('#') {
+            Some(rest) => ("#", rest),
+            None => ("", value),
+        };
+        match body.strip_prefix(':') {
+            Some(path) => format!("{}minecraft:{}", tag_marker, path),
+            None => value.to_string(),
+        }
+    }
+
+    /// Validate a value against the enum, struct or type alias a name refers to.
+    /// Names are resolved with the imports of the schema they're written in.
+    fn validate_named_type<'j>(&self, json_node: &'j serde_json::Value, type_name: &str, path: &JsonPath, context: &mut ValidationContext<'j>) {
+        let Some((scope, name)) = self.scoped_name(context.module.as_deref(), type_name) else {
+            context.add_error_of_type(path, ErrorCode::UnresolvedType, format!("Unresolved type '{}'", type_name), ErrorType::Resolution);
+            return;
+        };
+
+        if let Some((_, enum_decl)) = self.find_enum(scope, name) {
+            Self::validate_enum_value(json_node, Some(enum_decl.name), enum_decl.base_type, &enum_decl.variants, path, context);
+        } else if let Some((filename, members)) = self.find_struct(scope, name) {
+            let outer = context.module.replace(filename.to_string());
+            self.validate_struct(json_node, members, path, context);
+            context.module = outer;
+            if let Some(annotations) = self.struct_annotations(filename, name) {
+                Self::check_exclusive_fields(json_node, annotations, path, context);
+            }
+        } else if let Some((filename, type_decl)) = self.find_type_alias(scope, name) {
+            match Self::check_type_arity(type_name, type_decl.type_params.len(), 0) {
+                Ok(()) => self.validate_alias(json_node, filename, name, &type_decl.type_expr, path, context),
+                Err(message) => context.add_error(path, ErrorCode::UnresolvedType, message),
+            }
+        } else {
+            context.add_error_of_type(path, ErrorCode::UnresolvedType, format!("Unresolved type '{}'", type_name), ErrorType::Resolution);
+        }
+    }
+
+    /// Check a value against the type of the alias `name` declared by `filename`, with
+    /// its type arguments for generic aliases. Aliases of each other, like
+    /// `type A = B; type B = A`, come back to the same value without going into it
+    /// and are reported as a cycle.
+    fn validate_alias<'j>(
+        &self,
+        json_node: &'j serde_json::Value,
+        filename: &str,
+        name: &str,
+        type_expr: &TypeExpression<'input>,
+        path: &JsonPath,
+        context: &mut ValidationContext<'j>,
+    ) {
+        let cycle_start = context.resolving.iter()
+            .position(|(schema, alias, value)| schema == filename && alias == name && std::ptr::eq(*value, json_node));
+        if let Some(start) = cycle_start {
+            let aliases: Vec<&str> = context.resolving[start..].iter().map(|(_, alias, _)| alias.as_str()).chain([name]).collect();
+            context.add_error_of_type(path, ErrorCode::TypeCycle, format!("Type alias cycle: {}", aliases.join(" -> ")), ErrorType::Resolution);
+            return;
+        }
+        let depth = context.resolving.iter().rev().take_while(|(_, _, value)| std::ptr::eq(*value, json_node)).count();
+        if depth >= MAX_ALIAS_DEPTH {
+            let message = format!("Type alias '{}' resolved more than {} times for the same value", name, MAX_ALIAS_DEPTH);
+            context.add_error(path, ErrorCode::MaxDepthExceeded, message);
+            return;
+        }
+        context.resolving.push((filename.to_string(), name.to_string(), json_node));
+        let outer = context.module.replace(filename.to_string());
+        self.validate_node(json_node, type_expr, path, context, None);
+        context.module = outer;
+        context.resolving.pop();
+    }
+
+    /// Schema and declaration name a type name written in `module` refers to.
+    ///
+    /// A name bound by an import of the module refers to the imported declaration,
+    /// other names are searched in every schema (`None` scope). A name the module
+    /// imports under an alias isn't visible under its original name.
+    pub(crate) fn scoped_name<'a>(&'a self, module: Option<&str>, name: &'a str) -> Option<(Option<&'a str>, &'a str)> {
+        let Some((module, schema)) = module.and_then(|module| Some((module, self.loaded_schema(module)?))) else {
+            return Some((None, name));
+        };
+        let current = Self::module_path(module);
+
+        let mut hidden = false;
+        for import in &schema.imports {
+            let (Some(full_path), Some(&original)) = (Self::import_target(&current, &import.path), import.path.segments().last()) else {
+                continue;
+            };
+            if import.local_name() == Some(name) {
+                // A module that isn't loaded was reported when loading, look the name up anywhere
+                return Some((self.module_file(&full_path[..full_path.len() - 1]), original));
+            }
+            hidden |= import.alias.is_some() && original == name;
+        }
+
+        if hidden && self.module_declaration(module, name).is_none() {
+            None
+        } else {
+            Some((None, name))
+        }
+    }
+
+    /// Finds an enum declaration by name in the `scope` schema, or from the first schema declaring it
+    pub(crate) fn find_enum(&self, scope: Option<&str>, name: &str) -> Option<(&str, &EnumDeclaration<'input>)> {
+        let filename = Self::indexed_file(&self.enum_index, scope, name)?;
+        self.mcdoc_schemas.get(filename)?.declarations.iter().find_map(|decl| match decl {
+            Declaration::Enum(enum_decl) if enum_decl.name == name => Some((filename, enum_decl)),
+            _ => None,
+        })
+    }
+
+    /// File of `index` declaring `name`, the `scope` schema or else the first loaded
+    fn indexed_file<'a>(index: &'a FxHashMap<String, Vec<String>>, scope: Option<&str>, name: &str) -> Option<&'a str> {
+        let filenames = index.get(name)?;
+        match scope {
+            Some(scope) => filenames.iter().find(|filename| filename.as_str() == scope),
+            None => filenames.first(),
+        }.map(String::as_str)
+    }
+
+    /// Finds the members of a top-level or named inline struct in the `scope` schema,
+    /// or from the first schema declaring it
+    pub(crate) fn find_struct(&self, scope: Option<&str>, name: &str) -> Option<(&str, &[StructMember<'input>])> {
+        let filename = Self::indexed_file(&self.struct_index, scope, name)?;
+        Self::schema_structs(self.mcdoc_schemas.get(filename)?)
+            .into_iter()
+            .find_map(|(struct_name, members)| (struct_name == name).then_some((filename, members)))
+    }
+
+    /// Annotations of the top-level struct declaration `name` of a schema
+    fn struct_annotations(&self, filename: &str, name: &str) -> Option<&[crate::parser::Annotation<'input>]> {
+        self.mcdoc_schemas.get(filename)?.declarations.iter().find_map(|decl| match decl {
+            Declaration::Struct(struct_decl) if struct_decl.name == name => Some(struct_decl.annotations.as_slice()),
+            _ => None,
+        })
+    }
+
+    /// Finds a type alias declaration by name in the `scope` schema, or from the first schema declaring it
+    pub(crate) fn find_type_alias(&self, scope: Option<&str>, name: &str) -> Option<(&str, &TypeDeclaration<'input>)> {
+        let filename = Self::indexed_file(&self.alias_index, scope, name)?;
+        self.mcdoc_schemas.get(filename)?.declarations.iter().find_map(|decl| match decl {
+            Declaration::Type(type_decl) if type_decl.name == name => Some((filename, type_decl)),
+            _ => None,
+        })
+    }
+
+    /// Replace the generic parameters `params` by `args` in `type_expr`
+    pub(crate) fn substitute_type_params(
+        type_expr: &TypeExpression<'input>,
+        params: &[&'input str],
+        args: &[TypeExpression<'input>],
+    ) -> TypeExpression<'input> {
+        let substitute = |expr: &TypeExpression<'input>| Self::substitute_type_params(expr, params, args);
+
+        match type_expr {
+            TypeExpression::Simple(name) => match params.iter().position(|param| param == name) {
+                Some(index) => args[index].clone(),
+                None => type_expr.clone(),
+            },
+            TypeExpression::Array { element_type, constraints } => TypeExpression::Array {
+                element_type: Box::new(substitute(element_type)),
+                constraints: constraints.clone(),
+            },
+            TypeExpression::Union(types) => TypeExpression::Union(types.iter().map(substitute).collect()),
+            TypeExpression::Struct { name, members } => TypeExpression::Struct {
+                name: *name,
+                members: members.iter()
+                    .map(|member| match member {
+                        StructMember::Field(field) => StructMember::Field(FieldDeclaration {
+                            field_type: substitute(&field.field_type),
+                            ..field.clone()
+                        }),
+                        StructMember::DynamicField(dynamic_field) => StructMember::DynamicField(DynamicFieldDeclaration {
+                            key_type: substitute(&dynamic_field.key_type),
+                            value_type: substitute(&dynamic_field.value_type),
+                            ..dynamic_field.clone()
+                        }),
+                        StructMember::Spread(spread) => StructMember::Spread(SpreadExpression {
+                            target: spread.target.as_deref().map(|target| Box::new(substitute(target))),
+                            ..spread.clone()
+                        }),
+                    })
+                    .collect(),
+            },
+            TypeExpression::Generic { name, type_args } => TypeExpression::Generic {
+                name,
+                type_args: type_args.iter().map(substitute).collect(),
+            },
+            TypeExpression::Annotated { annotations, type_expr } => TypeExpression::Annotated {
+                annotations: annotations.clone(),
+                type_expr: Box::new(substitute(type_expr)),
+            },
+            TypeExpression::Constrained { base_type, constraints } => TypeExpression::Constrained {
+                base_type: Box::new(substitute(base_type)),
+                constraints: constraints.clone(),
+            },
+            TypeExpression::Reference(_) | TypeExpression::Spread(_) | TypeExpression::Literal(_) | TypeExpression::Enum { .. } => type_expr.clone(),
+        }
+    }
+
+    /// Root type of a resource type with declaration names followed, cached until schemas change
+    pub(crate) fn resolved_root(&self, resource_type: &str) -> Option<Arc<ResolvedRoot<'input>>> {
+        let cached = self.resolved_roots.read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(resource_type)
+            .cloned();
+        if let Some(root) = cached {
+            return root;
+        }
+
+        let root = self.find_type_for_resource(resource_type)
+            .map(|(module, type_expr)| Arc::new(self.flatten_root(module, type_expr)));
+        self.resolved_roots.write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(resource_type.to_string(), root.clone());
+        root
+    }
+
+    /// Follow a root naming a declaration, like `dispatch minecraft:resource[recipe] to Recipe`,
+    /// to the declared type so validations don't look it up again. Generic aliases and
+    /// unresolved names are kept as written and reported during validation.
+    fn flatten_root(&self, module: &str, type_expr: &TypeExpression<'input>) -> ResolvedRoot<'input> {
+        let mut module = module.to_string();
+        let mut type_expr = type_expr;
+        // Aliases naming each other would loop forever
+        for _ in 0..MAX_ROOT_ALIASES {
+            let TypeExpression::Simple(type_name) = type_expr else { break };
+            let Some((scope, name)) = self.scoped_name(Some(&module), type_name).filter(|_| !is_builtin_type(type_name)) else {
+                break;
+            };
+
+            if let Some((_, enum_decl)) = self.find_enum(scope, name) {
+                let type_expr = TypeExpression::Enum {
+                    name: Some(enum_decl.name),
+                    base_type: enum_decl.base_type,
+                    variants: enum_decl.variants.clone(),
+                };
+                return ResolvedRoot { module, type_expr };
+            } else if let Some((filename, members)) = self.find_struct(scope, name) {
+                let mut type_expr = TypeExpression::Struct { name: None, members: members.to_vec() };
+                // The struct's `#[exclusive]` groups are checked like those of a named struct
+                let exclusive: Vec<_> = self.struct_annotations(filename, name).unwrap_or_default().iter()
+                    .filter(|annotation| annotation.name == "exclusive")
+                    .cloned()
+                    .collect();
+                if !exclusive.is_empty() {
+                    type_expr = TypeExpression::Annotated { annotations: exclusive, type_expr: Box::new(type_expr) };
+                }
+                return ResolvedRoot { module: filename.to_string(), type_expr };
+            } else if let Some((filename, type_decl)) = self.find_type_alias(scope, name).filter(|(_, decl)| decl.type_params.is_empty()) {
+                module = filename.to_string();
+                type_expr = &type_decl.type_expr;
+            } else {
+                break;
+            }
+        }
+        ResolvedRoot { module, type_expr: type_expr.clone() }
+    }
+
+    fn clear_resolved_roots(&mut self) {
+        self.resolved_roots.get_mut().unwrap_or_else(PoisonError::into_inner).clear();
+        self.expanded_spreads.get_mut().unwrap_or_else(PoisonError::into_inner).clear();
+    }
+
+    /// Finds the corresponding TypeExpression for a given resource type string.
+    ///
+    /// `recipe` and `minecraft:recipe` look up `minecraft:resource[recipe]`, other
+    /// dispatchers are named explicitly like `minecraft:trigger[placed_block]`.
+    fn find_type_for_resource(&self, resource_type: &str) -> Option<(&str, &TypeExpression<'input>)> {
+        let (registry, key) = match resource_type.strip_suffix(']').and_then(|s| s.split_once('[')) {
+            Some((registry, key)) if registry.contains(':') => (registry.to_string(), key.to_string()),
+            Some((registry, key)) => (format!("minecraft:{}", registry), key.to_string()),
+            None => {
+                let parsed_id = ResourceId::parse(resource_type).ok()?;
+                ("minecraft:resource".to_string(), parsed_id.path)
+            }
+        };
+
+        self.find_dispatch_target(&registry, Some(&key))
+    }
+
+    /// Dispatched type for `key` in a registry like "minecraft:recipe_serializer".
+    ///
+    /// Keys without a specific dispatch fall back to `%unknown`, an absent key
+    /// uses `%none`, when the schemas declare them. Returns the declaring schema with the type.
+    pub(crate) fn find_dispatch_target(&self, registry: &str, key: Option<&str>) -> Option<(&str, &TypeExpression<'input>)> {
+        let targets = match key {
+            Some(key) => self.dispatch_index.get(&Self::dispatch_index_key(registry, key))
+                .or_else(|| self.dispatch_index.get(&Self::dispatch_index_key(registry, "%unknown"))),
+            None => self.dispatch_index.get(&Self::dispatch_index_key(registry, "%none")),
+        };
+        let (filename, index) = targets?.first()?;
+        match &self.mcdoc_schemas.get(filename)?.declarations[*index] {
+            Declaration::Dispatch(dispatch) => Some((filename.as_str(), &dispatch.target_type)),
+            _ => None,
+        }
+    }
+
+    /// Target of a spread like `...minecraft:recipe_serializer[[type]]`, chosen by
+    /// the discriminator its dynamic reference reads from the enclosing objects
+    fn find_spread_target(
+        &self,
+        ancestors: &[&serde_json::Value],
+        spread: &SpreadExpression<'input>,
+    ) -> Option<&TypeExpression<'input>> {
+        let DynamicReference { reference, .. } = spread.dynamic_key.as_ref()?;
+        if spread.namespace.is_empty() || spread.registry.is_empty() {
+            return None;
+        }
+
+        let registry = format!("{}:{}", spread.namespace, spread.registry);
+        match Self::resolve_dynamic_reference(reference, ancestors) {
+            Some(value) => {
+                let key = value.as_str()?;
+                self.find_dispatch_target(&registry, Some(key))
+            }
+            None => self.find_dispatch_target(&registry, None),
+        }
+        .map(|(_, type_expr)| type_expr)
+    }
+
+    /// Value a dynamic reference points to, `ancestors` are the enclosing objects and
+    /// arrays with the current one last. `%key` isn't known here and resolves to no value.
+    fn resolve_dynamic_reference<'j>(
+        reference: &DynamicReferenceType<'input>,
+        ancestors: &[&'j serde_json::Value],
+    ) -> Option<&'j serde_json::Value> {
+        let segments = match reference {
+            DynamicReferenceType::Field(field) => return ancestors.last()?.get(*field),
+            DynamicReferenceType::SpecialKey(_) => return None,
+            DynamicReferenceType::Path(segments) => segments,
+        };
+
+        // Walk up the enclosing objects, then down into nested fields
+        let mut depth = ancestors.len().checked_sub(1)?;
+        let mut current: Option<&'j serde_json::Value> = None;
+        for segment in segments {
+            match segment {
+                PathSegment::Parent if current.is_none() => depth = depth.checked_sub(1)?,
+                PathSegment::Field(field) => {
+                    let container = current.unwrap_or(ancestors[depth]);
+                    current = Some(container.as_object()?.get(*field)?);
+                }
+                PathSegment::Parent | PathSegment::Key => return None,
+            }
+        }
+        current
+    }
+
+    /// Members of the struct a spread refers to: an inline struct, a named struct or
+    /// (generic) type alias of a struct, or the struct a dispatch resolves to.
+    /// Import paths like `...::java::util::Base` are resolved from `module`.
+    fn spread_members<'a>(
+        &'a self,
+        ancestors: &[&serde_json::Value],
+        spread: &'a SpreadExpression<'input>,
+        module: Option<&str>,
+    ) -> Option<Cow<'a, [StructMember<'input>]>> {
+        let Some(target) = spread.target.as_deref() else {
+            return match self.find_spread_target(ancestors, spread)? {
+                TypeExpression::Struct { members, .. } => Some(Cow::Borrowed(members)),
+                _ => None,
+            };
+        };
+
+        let (name, type_args) = match target {
+            TypeExpression::Struct { members, .. } => return Some(Cow::Borrowed(members)),
+            TypeExpression::Simple(name) => (*name, &[][..]),
+            TypeExpression::Reference(path) => match self.import_path_members(module, path) {
+                Some(members) => return Some(Cow::Borrowed(members)),
+                // Schemas not loaded under their module path are searched by name
+                None => (*path.segments().last()?, &[][..]),
+            },
+            TypeExpression::Generic { name, type_args } => (*name, type_args.as_slice()),
+            _ => return None,
+        };
+        if type_args.is_empty() {
+            if let Some((_, members)) = self.find_struct(None, name) {
+                return Some(Cow::Borrowed(members));
+            }
+        }
+
+        let (_, type_decl) = self.find_type_alias(None, name)?;
+        if type_decl.type_params.len() != type_args.len() {
+            return None;
+        }
+        if type_args.is_empty() {
+            return match &type_decl.type_expr {
+                TypeExpression::Struct { members, .. } => Some(Cow::Borrowed(members)),
+                _ => None,
+            };
+        }
+        match Self::substitute_type_params(&type_decl.type_expr, &type_decl.type_params, type_args) {
+            TypeExpression::Struct { members, .. } => Some(Cow::Owned(members)),
+            _ => None,
+        }
+    }
+
+    /// Members of the struct, or type alias of a struct, that an import path written
+    /// in `module` names
+    fn import_path_members(&self, module: Option<&str>, path: &ImportPath<'input>) -> Option<&[StructMember<'input>]> {
+        let current = module.map(Self::module_path).unwrap_or_default();
+        let full_path = Self::import_target(&current, path)?;
+        let (name, module_path) = full_path.split_last()?;
+        match self.module_declaration(self.module_file(module_path)?, name)? {
+            ResolvedDeclaration::Struct(struct_decl) => Some(&struct_decl.members),
+            ResolvedDeclaration::Type(type_decl) if type_decl.type_params.is_empty() => match &type_decl.type_expr {
+                TypeExpression::Struct { members, .. } => Some(members),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Replace spreads of a struct by that struct's members.
+    /// Spreads not available in `version` are dropped, as are spreads of a struct being
+    /// expanded, which are added to the cycles of `expansion`. With `memoize`, structs
+    /// named by spreads are expanded once per version and shared (see `memoized_spread`).
+    fn expand_spreads<'a>(
+        &'a self,
+        ancestors: &[&serde_json::Value],
+        members: &'a [StructMember<'input>],
+        version: Option<&str>,
+        module: Option<&str>,
+        memoize: bool,
+        expansion: &mut SpreadExpansion<'input>,
+    ) -> Vec<MemberRun<'a, 'input>> {
+        let mut runs = Vec::new();
+        let mut start = 0;
+        for (index, member) in members.iter().enumerate() {
+            let StructMember::Spread(spread) = member else { continue };
+            let mut entered = false;
+            let replacement = if !Self::is_available(&spread.annotations, version) {
+                Some(MemberRun::Borrowed(&[]))
+            } else {
+                let is_dispatch = !spread.namespace.is_empty() && !spread.registry.is_empty();
+                if let Some(key) = spread.dynamic_key.as_ref().filter(|_| is_dispatch) {
+                    expansion.discriminators.push((format!("{}:{}", spread.namespace, spread.registry), key.reference.clone()));
+                }
+                match memoize.then(|| self.memoized_spread(ancestors, spread, version, module, &mut expansion.trail)).flatten() {
+                    Some(target_members) => Some(MemberRun::Shared(target_members)),
+                    None => {
+                        entered = expansion.enter(spread);
+                        if entered {
+                            self.spread_members(ancestors, spread, module).map(|target_members| match target_members {
+                                Cow::Borrowed(target_members) => MemberRun::Borrowed(target_members),
+                                Cow::Owned(target_members) => MemberRun::Owned(target_members),
+                            })
+                        } else {
+                            Some(MemberRun::Borrowed(&[]))
+                        }
+                    }
+                }
+            };
+            // Spreads that can't be resolved are kept, see `validate_struct`
+            let Some(replacement) = replacement else {
+                expansion.leave(entered);
+                continue;
+            };
+            if start < index {
+                runs.push(MemberRun::Borrowed(&members[start..index]));
+            }
+            start = index + 1;
+            match replacement {
+                MemberRun::Borrowed(target_members) => {
+                    runs.extend(self.expand_spreads(ancestors, target_members, version, module, memoize, expansion));
+                }
+                MemberRun::Owned(target_members) => {
+                    let expanded = self.expand_spreads(ancestors, &target_members, version, module, memoize, expansion);
+                    runs.push(MemberRun::Owned(MemberRun::flatten(&expanded).cloned().collect()));
+                }
+                // Already expanded
+                shared @ MemberRun::Shared(_) => runs.push(shared),
+            }
+            expansion.leave(entered);
+        }
+        if start < members.len() {
+            runs.push(MemberRun::Borrowed(&members[start..]));
+        }
+        runs
+    }
+
+    /// Members of a spread naming a struct, like `...ItemBase`, with its own spreads
+    /// expanded. They are expanded once per version until schemas change. `None` for
+    /// other spreads and for structs whose expansion depends on the document, like
+    /// those spreading `...minecraft:item_component[[id]]`, or in a spread cycle.
+    /// `trail` holds the targets being expanded, see `SpreadExpansion`.
+    fn memoized_spread(
+        &self,
+        ancestors: &[&serde_json::Value],
+        spread: &SpreadExpression<'input>,
+        version: Option<&str>,
+        module: Option<&str>,
+        trail: &mut Vec<String>,
+    ) -> Option<Arc<[StructMember<'input>]>> {
+        let key = match spread.target.as_deref()? {
+            TypeExpression::Simple(name) => (None, name.to_string(), version.map(str::to_string)),
+            // Import paths are relative to the schema spreading them
+            target @ TypeExpression::Reference(_) => (module.map(str::to_string), printer::type_summary(target), version.map(str::to_string)),
+            _ => return None,
+        };
+        let cached = self.expanded_spreads.read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&key)
+            .cloned();
+        if let Some(members) = cached {
+            return members;
+        }
+
+        // Expanded along with the targets already being expanded so that cycles end. Cycles
+        // aren't cached, they are reported when expanded again without the cache.
+        let mut expansion = SpreadExpansion { trail: std::mem::take(trail), ..Default::default() };
+        let entered = expansion.enter(spread);
+        let members = self.spread_members(ancestors, spread, module)
+            .filter(|_| entered)
+            .map(|members| MemberRun::flatten(&self.expand_spreads(ancestors, &members, version, module, true, &mut expansion)).cloned().collect::<Vec<_>>())
+            .filter(|_| expansion.discriminators.is_empty() && expansion.cycles.is_empty())
+            .map(Arc::from);
+        expansion.leave(entered);
+        *trail = expansion.trail;
+        self.expanded_spreads.write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(key, members.clone());
+        members
+    }
+
+    /// Finds the struct, enum or type alias named by `type_name` (see `validate_against_type`)
+    fn find_named_type(&self, type_name: &str) -> Result<Cow<'_, TypeExpression<'input>>, String> {
+        let (path_part, generic_part) = match type_name.find('<') {
+            Some(index) => (&type_name[..index], Some(&type_name[index..])),
+            None => (type_name, None),
+        };
+        let segments: Vec<&str> = path_part.trim().trim_start_matches("::").split("::").map(str::trim).collect();
+        let (name, module) = segments.split_last().ok_or_else(|| format!("Unknown type '{}'", type_name))?;
+        let module = module.join("::");
+
+        let type_args = match generic_part {
+            Some(generic) => {
+                let source = format!("{}{}", name, generic);
+                let tokens = Lexer::new(&source).tokenize()
+                    .map_err(|e| format!("Invalid type name '{}': {}", type_name, e))?;
+                match Parser::new(tokens).parse_type_expression() {
+                    Ok(TypeExpression::Generic { type_args, .. }) => type_args.iter()
+                        .map(|type_arg| self.type_argument(type_arg, type_name))
+                        .collect::<Result<Vec<_>, _>>()?,
+                    Ok(_) => Vec::new(),
+                    Err(e) => return Err(format!("Invalid type name '{}': {}", type_name, e)),
+                }
+            }
+            None => Vec::new(),
+        };
+        let arg_count = type_args.len();
+
+        let mut schema_names: Vec<&String> = self.mcdoc_schemas.keys()
+            .filter(|schema_name| module.is_empty() || Self::schema_module_matches(schema_name, &module))
+            .collect();
+        schema_names.sort();
+
+        for schema_name in schema_names {
+            for decl in &self.mcdoc_schemas[schema_name].declarations {
+                match decl {
+                    Declaration::Struct(s) if s.name == *name => {
+                        return Self::check_type_arity(type_name, 0, arg_count)
+                            .map(|_| Cow::Owned(TypeExpression::Struct { name: Some(s.name), members: s.members.clone() }));
+                    }
+                    Declaration::Enum(e) if e.name == *name => {
+                        let variants = e.variants.iter()
+                            .map(|variant| TypeExpression::Literal(variant.value.clone()
+                                .unwrap_or(LiteralValue::String(Cow::Borrowed(variant.name)))))
+                            .collect();
+                        return Self::check_type_arity(type_name, 0, arg_count)
+                            .map(|_| Cow::Owned(TypeExpression::Union(variants)));
+                    }
+                    Declaration::Type(t) if t.name == *name => {
+                        Self::check_type_arity(type_name, t.type_params.len(), arg_count)?;
+                        if t.type_params.is_empty() {
+                            return Ok(Cow::Borrowed(&t.type_expr));
+                        }
+                        return Ok(Cow::Owned(Self::substitute_type_params(&t.type_expr, &t.type_params, &type_args)));
+                    }
+                    _ => {}
+                }
+            }
+
+            let inline_struct = Self::schema_structs(&self.mcdoc_schemas[schema_name])
+                .into_iter()
+                .find(|(struct_name, _)| struct_name == name);
+            if let Some((struct_name, members)) = inline_struct {
+                return Self::check_type_arity(type_name, 0, arg_count)
+                    .map(|_| Cow::Owned(TypeExpression::Struct { name: Some(struct_name), members: members.to_vec() }));
+            }
+        }
+
+        Err(format!("Unknown type '{}'", type_name))
+    }
+
+    /// A type argument parsed from a type name, with its names taken from the built-in
+    /// types and the loaded declarations so that it lives as long as the schemas
+    fn type_argument(&self, type_arg: &TypeExpression<'_>, type_name: &str) -> Result<TypeExpression<'input>, String> {
+        let argument = |type_arg: &TypeExpression<'_>| self.type_argument(type_arg, type_name);
+        let declared_name = |name: &str| BUILTIN_TYPES.iter().copied().find(|builtin| *builtin == name)
+            .or_else(|| self.declared_type_name(name))
+            .ok_or_else(|| format!("Unknown type '{}' in '{}'", name, type_name));
+
+        Ok(match type_arg {
+            TypeExpression::Simple(name) => TypeExpression::Simple(declared_name(name)?),
+            TypeExpression::Generic { name, type_args } => TypeExpression::Generic {
+                name: declared_name(name)?,
+                type_args: type_args.iter().map(argument).collect::<Result<_, _>>()?,
+            },
+            TypeExpression::Array { element_type, constraints } => TypeExpression::Array {
+                element_type: Box::new(argument(element_type)?),
+                constraints: constraints.clone(),
+            },
+            TypeExpression::Union(types) => TypeExpression::Union(types.iter().map(argument).collect::<Result<_, _>>()?),
+            TypeExpression::Constrained { base_type, constraints } => TypeExpression::Constrained {
+                base_type: Box::new(argument(base_type)?),
+                constraints: constraints.clone(),
+            },
+            TypeExpression::Literal(value) => TypeExpression::Literal(match value {
+                LiteralValue::String(text) => LiteralValue::String(Cow::Owned(text.to_string())),
+                LiteralValue::Number(number) => LiteralValue::Number(*number),
+                LiteralValue::Boolean(boolean) => LiteralValue::Boolean(*boolean),
+            }),
+            _ => return Err(format!("Unsupported type argument '{}' in '{}'", printer::type_summary(type_arg), type_name)),
+        })
+    }
+
+    /// The name of the struct, enum or type alias called `name` in the loaded schemas
+    fn declared_type_name(&self, name: &str) -> Option<&'input str> {
+        self.mcdoc_schemas.values().find_map(|schema| {
+            schema.declarations.iter()
+                .find_map(|decl| match decl {
+                    Declaration::Struct(s) if s.name == name => Some(s.name),
+                    Declaration::Enum(e) if e.name == name => Some(e.name),
+                    Declaration::Type(t) if t.name == name => Some(t.name),
+                    _ => None,
+                })
+                .or_else(|| Self::schema_structs(schema).into_iter().map(|(struct_name, _)| struct_name).find(|struct_name| *struct_name == name))
+        })
+    }
+
+    /// Whether a schema file name such as `data/util.mcdoc` is the module `util` or `data::util`
+    fn schema_module_matches(schema_name: &str, module: &str) -> bool {
+        let normalized = schema_name.trim_end_matches(".mcdoc").replace(['/', '\\'], "::");
+        let normalized = normalized.strip_suffix("::mod").unwrap_or(&normalized);
+        normalized == module || normalized.ends_with(&format!("::{}", module))
+    }
+
+    fn check_type_arity(type_name: &str, expected: usize, found: usize) -> Result<(), String> {
+        if expected == found {
+            Ok(())
+        } else {
+            Err(format!("Type '{}' expects {} type argument(s), found {}", type_name, expected, found))
+        }
     }
 }
 
@@ -0,0 +1,32 @@
+//! Minecraft version comparison for `#[since]` and `#[until]` annotations
+
+use std::cmp::Ordering;
+
+/// Compare two release versions like "1.19.1" and "1.20"
+///
+/// Missing components count as zero ("1.20" == "1.20.0"). Only the leading digits of a
+/// component are compared, so "1.21-pre1" compares as "1.21".
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let a_parts = version_components(a);
+    let b_parts = version_components(b);
+    let len = a_parts.len().max(b_parts.len());
+
+    for i in 0..len {
+        let ordering = a_parts.get(i).unwrap_or(&0).cmp(b_parts.get(i).unwrap_or(&0));
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+fn version_components(version: &str) -> Vec<u32> {
+    version
+        .trim()
+        .split('.')
+        .map(|part| {
+            let digits_end = part.find(|c: char| !c.is_ascii_digit()).unwrap_or(part.len());
+            part[..digits_end].parse().unwrap_or(0)
+        })
+        .collect()
+}
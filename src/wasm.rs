@@ -8,7 +8,92 @@ use wasm_bindgen::prelude::*;
 use crate::validator::DatapackValidator as InnerValidator;
 
 #[cfg(feature = "wasm")]
-use std::collections::HashMap;
+use crate::validator::ValidationOptions;
+
+#[cfg(feature = "wasm")]
+use crate::error::{ErrorCode, Severity};
+
+#[cfg(feature = "wasm")]
+use std::collections::{BTreeMap, HashMap};
+
+#[cfg(feature = "wasm")]
+use serde::Serialize;
+
+#[cfg(feature = "wasm")]
+use crate::graph::{DependencyEdge, DependencyGraph, FileNode};
+
+/// Validation options as passed from JavaScript, every field is optional
+#[cfg(feature = "wasm")]
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct JsValidationOptions {
+    node_budget: Option<usize>,
+    error_budget: Option<usize>,
+    strict_fields: bool,
+    check_tag_contents: bool,
+    keep_duplicate_dependencies: bool,
+    check_registries: bool,
+    default_namespace: String,
+    max_depth: usize,
+    source_file: Option<String>,
+    /// Part de `ValidationOptions::lenient_legacy`
+    lenient: bool,
+    /// Sévérité par code, comme `{ unknownField: "warning" }`, appliquée après `lenient`
+    severity_overrides: HashMap<ErrorCode, Severity>,
+}
+
+#[cfg(feature = "wasm")]
+impl Default for JsValidationOptions {
+    fn default() -> Self {
+        let defaults = ValidationOptions::default();
+        Self {
+            node_budget: defaults.node_budget,
+            error_budget: defaults.error_budget,
+            strict_fields: defaults.strict_fields,
+            check_tag_contents: defaults.check_tag_contents,
+            keep_duplicate_dependencies: defaults.keep_duplicate_dependencies,
+            check_registries: defaults.check_registries,
+            default_namespace: defaults.default_namespace,
+            max_depth: defaults.max_depth,
+            source_file: defaults.source_file,
+            lenient: false,
+            severity_overrides: defaults.severity_overrides,
+        }
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl From<JsValidationOptions> for ValidationOptions {
+    fn from(options: JsValidationOptions) -> Self {
+        let base = if options.lenient { ValidationOptions::lenient_legacy() } else { ValidationOptions::default() };
+        let mut severity_overrides = base.severity_overrides.clone();
+        severity_overrides.extend(options.severity_overrides);
+        ValidationOptions {
+            node_budget: options.node_budget,
+            error_budget: options.error_budget,
+            strict_fields: options.strict_fields,
+            check_tag_contents: options.check_tag_contents,
+            keep_duplicate_dependencies: options.keep_duplicate_dependencies,
+            check_registries: options.check_registries,
+            default_namespace: options.default_namespace,
+            max_depth: options.max_depth,
+            source_file: options.source_file,
+            severity_overrides,
+            ..base
+        }
+    }
+}
+
+/// Dependency graph with its pack-level queries already answered
+#[cfg(feature = "wasm")]
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsDependencyGraph<'g> {
+    #[serde(flatten)]
+    graph: &'g DependencyGraph,
+    missing_references: Vec<&'g DependencyEdge>,
+    unused_local_resources: Vec<&'g FileNode>,
+}
 
 /// Helper function to convert errors to JsValue (eliminating DRY violations)
 #[cfg(feature = "wasm")]
@@ -16,6 +101,33 @@ fn to_js_error(msg: &str, error: impl std::fmt::Display) -> JsValue {
     JsValue::from_str(&format!("{}: {}", msg, error))
 }
 
+/// Erreur JS pour un fichier MCDOC invalide, avec les erreurs structurées
+/// (ligne, colonne et offsets en octets) dans sa propriété `errors`
+#[cfg(feature = "wasm")]
+fn to_js_parse_errors(filename: &str, parse_errors: Vec<crate::error::ParseError>) -> JsValue {
+    let error_msg = format!("MCDOC parse errors in {}: {:?}", filename, parse_errors);
+    let js_error = js_sys::Error::new(&format!("MCDOC parsing failed: {}", error_msg));
+    let errors: Vec<crate::types::McDocError> = parse_errors.into_iter()
+        .map(|error| crate::types::McDocError { file: filename.into(), ..error.into() })
+        .collect();
+    if let Ok(errors) = serde_wasm_bindgen::to_value(&errors) {
+        let _ = js_sys::Reflect::set(&js_error, &JsValue::from_str("errors"), &errors);
+    }
+    js_error.into()
+}
+
+/// Charge les registries d'un objet JS `{ nom: contenu }` pour `version`
+#[cfg(feature = "wasm")]
+fn load_js_registries(validator: &mut InnerValidator<'static>, registries: JsValue, version: &str) -> Result<(), JsValue> {
+    let registries_map: HashMap<String, serde_json::Value> = serde_wasm_bindgen::from_value(registries)
+        .map_err(|e| to_js_error("Invalid registries format", e))?;
+    for (name, registry_data) in registries_map {
+        validator.load_registry(name, version.to_string(), &registry_data)
+            .map_err(|e| to_js_error("Registry loading failed", e))?;
+    }
+    Ok(())
+}
+
 #[cfg(all(feature = "wasm", feature = "console_error_panic_hook"))]
 #[wasm_bindgen(start)]
 pub fn main() {
@@ -39,42 +151,104 @@ impl DatapackValidator {
         let mut inner_validator = InnerValidator::new();
 
         // 1. Charger les registries
-        let registries_map: HashMap<String, serde_json::Value> = serde_wasm_bindgen::from_value(registries)
-            .map_err(|e| to_js_error("Invalid registries format", e))?;
-        for (name, registry_data) in registries_map {
-            inner_validator.load_registry(name, version.clone(), &registry_data)
-                .map_err(|e| to_js_error("Registry loading failed", e))?;
-        }
+        load_js_registries(&mut inner_validator, registries, &version)?;
 
         // 2. Charger les fichiers MCDOC
         let files_map: HashMap<String, String> = serde_wasm_bindgen::from_value(mcdoc_files)
             .map_err(|e| to_js_error("Invalid MCDOC files format", e))?;
         for (filename, content) in files_map {
-            // Convert content to static lifetime by leaking memory (acceptable for WASM usage)
-            let static_content: &'static str = Box::leak(content.into_boxed_str());
-            match crate::parse_mcdoc(static_content) {
-                Ok(ast) => {
-                    inner_validator.load_parsed_mcdoc(filename, ast)
-                        .map_err(|e| to_js_error("Failed to load MCDOC schema", e))?;
-                }
-                Err(parse_errors) => {
-                    let error_msg = format!("MCDOC parse errors in {}: {:?}", filename, parse_errors);
-                    return Err(to_js_error("MCDOC parsing failed", &error_msg[..]));
-                }
+            if let Err(parse_errors) = inner_validator.load_mcdoc_source(filename.clone(), content) {
+                return Err(to_js_parse_errors(&filename, parse_errors));
             }
         }
         
         Ok(DatapackValidator { inner: Box::new(inner_validator) })
     }
 
-    /// Validation d'un JSON unique
+    /// Comme `init`, mais les fichiers MCDOC invalides sont ignorés au lieu d'échouer.
+    /// Retourne `{ validator, diagnostics }`, `diagnostics` donnant les erreurs
+    /// (line, column, message, errorType...) de chaque fichier non chargé.
+    #[wasm_bindgen]
+    pub fn init_lenient(registries: JsValue, mcdoc_files: JsValue, version: String) -> Result<JsValue, JsValue> {
+        let mut inner_validator = InnerValidator::new();
+        load_js_registries(&mut inner_validator, registries, &version)?;
+
+        // Triés par nom de fichier, pour que l'ordre de chargement ne dépende pas du hachage
+        let files_map: BTreeMap<String, String> = serde_wasm_bindgen::from_value(mcdoc_files)
+            .map_err(|e| to_js_error("Invalid MCDOC files format", e))?;
+        let report = inner_validator.load_mcdoc_sources(files_map.into_iter().collect());
+
+        // Objet JS simple plutôt qu'une `Map`, indexé par nom de fichier
+        let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+        let diagnostics = report.diagnostics.serialize(&serializer)
+            .map_err(|e| to_js_error("Serialization error", e))?;
+
+        let result = js_sys::Object::new();
+        let validator = DatapackValidator { inner: Box::new(inner_validator) };
+        js_sys::Reflect::set(&result, &JsValue::from_str("validator"), &validator.into())?;
+        js_sys::Reflect::set(&result, &JsValue::from_str("diagnostics"), &diagnostics)?;
+        Ok(result.into())
+    }
+
+    /// Validation d'un JSON unique, `options` is an optional object like
+    /// `{ checkRegistries: false, strictFields: true, nodeBudget: 10000 }`
     #[wasm_bindgen]
-    pub fn validate(&self, json: JsValue, resource_type: &str, version: Option<String>) -> Result<JsValue, JsValue> {
+    pub fn validate(&self, json: JsValue, resource_type: &str, version: Option<String>, options: JsValue) -> Result<JsValue, JsValue> {
         let json_value: serde_json::Value = serde_wasm_bindgen::from_value(json)
             .map_err(|e| to_js_error("Invalid JSON format", e))?;
+        let options = validation_options(options)?;
         
-        let result = self.inner.validate_json(&json_value, resource_type, version.as_deref());
+        let result = self.inner.validate_json_with_options(&json_value, resource_type, version.as_deref(), &options);
+        
+        serde_wasm_bindgen::to_value(&result)
+            .map_err(|e| to_js_error("Serialization error", e))
+    }
+
+    /// Validation d'un JSON donné en texte, sans conversion JS -> serde.
+    /// Malformed JSON gives an invalid result with an `invalidJson` error.
+    #[wasm_bindgen]
+    pub fn validate_str(&self, json_text: &str, resource_type: &str, version: Option<String>, options: JsValue) -> Result<JsValue, JsValue> {
+        let options = validation_options(options)?;
         
+        let result = self.inner.validate_json_str(json_text, resource_type, version.as_deref(), &options);
+        
+        serde_wasm_bindgen::to_value(&result)
+            .map_err(|e| to_js_error("Serialization error", e))
+    }
+
+    /// Validation d'un fragment JSON contre un type nommé
+    #[wasm_bindgen(js_name = validateType)]
+    pub fn validate_type(&self, json: JsValue, type_name: &str, version: Option<String>) -> Result<JsValue, JsValue> {
+        let json_value: serde_json::Value = serde_wasm_bindgen::from_value(json)
+            .map_err(|e| to_js_error("Invalid JSON format", e))?;
+        
+        let result = self.inner.validate_against_type(&json_value, type_name, version.as_deref());
+        
+        serde_wasm_bindgen::to_value(&result)
+            .map_err(|e| to_js_error("Serialization error", e))
+    }
+
+    /// Validation d'un objet "type + contenu" contre un registre dispatché (ex: "recipe_serializer")
+    #[wasm_bindgen]
+    pub fn validate_dispatched(&self, json: JsValue, dispatch_registry: &str, version: Option<String>) -> Result<JsValue, JsValue> {
+        let json_value: serde_json::Value = serde_wasm_bindgen::from_value(json)
+            .map_err(|e| to_js_error("Invalid JSON format", e))?;
+
+        let result = self.inner.validate_dispatched(&json_value, dispatch_registry, version.as_deref());
+
+        serde_wasm_bindgen::to_value(&result)
+            .map_err(|e| to_js_error("Serialization error", e))
+    }
+
+    /// Validation d'un fichier dont le type de ressource est déduit du chemin
+    /// (ex: "data/mypack/recipe/sword.json")
+    #[wasm_bindgen]
+    pub fn validate_file(&self, path: &str, json: JsValue, version: Option<String>) -> Result<JsValue, JsValue> {
+        let json_value: serde_json::Value = serde_wasm_bindgen::from_value(json)
+            .map_err(|e| to_js_error("Invalid JSON format", e))?;
+
+        let result = self.inner.validate_file(path, &json_value, version.as_deref());
+
         serde_wasm_bindgen::to_value(&result)
             .map_err(|e| to_js_error("Serialization error", e))
     }
@@ -85,37 +259,118 @@ impl DatapackValidator {
         let files_map: HashMap<String, serde_json::Value> = serde_wasm_bindgen::from_value(files)
             .map_err(|e| to_js_error("Invalid files format", e))?;
         
-        let mut results = HashMap::new();
+        let results = self.inner.analyze_datapack(&files_map);
         
-        for (file_path, json_content) in files_map {
-            // Generic resource type inference from file path
-            let resource_type = if file_path.contains("/recipes/") {
-                "recipe"
-            } else if file_path.contains("/loot_tables/") {
-                "loot_table"
-            } else if file_path.contains("/advancements/") {
-                "advancement"
-            } else if file_path.contains("/structures/") {
-                "structure"
-            } else if file_path.contains("/tags/") {
-                "tag"
-            } else {
-                // Extract from path: data/namespace/type/file.json -> type
-                let parts: Vec<&str> = file_path.split('/').collect();
-                if parts.len() >= 4 && parts[0] == "data" {
-                    parts[2] // Get the type part
-                } else {
-                    "unknown"
-                }
-            };
-            
-            let result = self.inner.validate_json(&json_content, resource_type, None);
-            results.insert(file_path, result);
-        }
+        serde_wasm_bindgen::to_value(&results)
+            .map_err(|e| to_js_error("Serialization error", e))
+    }
+
+    /// Analyse d'un datapack dont les fichiers sont donnés en texte
+    #[wasm_bindgen]
+    pub fn analyze_datapack_strs(&self, files: JsValue) -> Result<JsValue, JsValue> {
+        let files_map: HashMap<String, String> = serde_wasm_bindgen::from_value(files)
+            .map_err(|e| to_js_error("Invalid files format", e))?;
+        
+        let results = self.inner.analyze_datapack_strs(&files_map);
         
         serde_wasm_bindgen::to_value(&results)
             .map_err(|e| to_js_error("Serialization error", e))
     }
+
+    /// Graphe des dépendances d'un datapack : ressources manquantes, fichiers inutilisés
+    #[wasm_bindgen]
+    pub fn dependency_graph(&self, files: JsValue) -> Result<JsValue, JsValue> {
+        let files_map: HashMap<String, serde_json::Value> = serde_wasm_bindgen::from_value(files)
+            .map_err(|e| to_js_error("Invalid files format", e))?;
+
+        let mut files: Vec<(String, serde_json::Value)> = files_map.into_iter().collect();
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+        let results: Vec<_> = self.inner.validate_stream(files).collect();
+        let graph = self.inner.dependency_graph(results.iter().map(|(path, result)| (path.as_str(), result)));
+
+        let view = JsDependencyGraph {
+            missing_references: graph.missing_references(),
+            unused_local_resources: graph.unused_local_resources(),
+            graph: &graph,
+        };
+        serde_wasm_bindgen::to_value(&view)
+            .map_err(|e| to_js_error("Serialization error", e))
+    }
+
+    /// Clés dispatchées sur un registre (ex: "minecraft:recipe_serializer"), triées
+    #[wasm_bindgen]
+    pub fn dispatch_keys(&self, registry: &str) -> Result<JsValue, JsValue> {
+        serde_wasm_bindgen::to_value(&self.inner.dispatch_keys(registry))
+            .map_err(|e| to_js_error("Serialization error", e))
+    }
+
+    /// Pseudo-clés (`%unknown`, `%none`) dispatchées sur un registre, triées
+    #[wasm_bindgen]
+    pub fn dispatch_fallbacks(&self, registry: &str) -> Result<JsValue, JsValue> {
+        serde_wasm_bindgen::to_value(&self.inner.dispatch_fallbacks(registry))
+            .map_err(|e| to_js_error("Serialization error", e))
+    }
+
+    /// Description des schémas chargés (déclarations, champs, types en syntaxe MCDOC),
+    /// d'un seul schéma si `filename` est donné
+    #[wasm_bindgen]
+    pub fn describe_schemas(&self, filename: Option<String>) -> Result<JsValue, JsValue> {
+        let descriptions = match filename {
+            Some(filename) => self.inner.describe_schema(&filename).into_iter().collect(),
+            None => self.inner.describe_schemas(),
+        };
+        serde_wasm_bindgen::to_value(&descriptions)
+            .map_err(|e| to_js_error("Serialization error", e))
+    }
+
+    /// JSON Schema (draft-07) d'un type de ressource, pour les éditeurs comme Monaco
+    #[wasm_bindgen]
+    pub fn to_json_schema(&self, resource_type: &str) -> Result<JsValue, JsValue> {
+        // Objets JS simples plutôt que des `Map`, comme attendu par Monaco
+        let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+        self.inner.to_json_schema(resource_type).serialize(&serializer)
+            .map_err(|e| to_js_error("Serialization error", e))
+    }
+
+    /// Registres ayant au moins un dispatch, triés
+    #[wasm_bindgen]
+    pub fn registries_with_dispatch(&self) -> Result<JsValue, JsValue> {
+        serde_wasm_bindgen::to_value(&self.inner.registries_with_dispatch())
+            .map_err(|e| to_js_error("Serialization error", e))
+    }
+}
+
+#[cfg(all(feature = "wasm", feature = "bundle"))]
+#[wasm_bindgen]
+impl DatapackValidator {
+    /// Charge un bundle de schémas précompilés (voir `bundle::build_schema_bundle`),
+    /// retourne le nombre de schémas chargés
+    #[wasm_bindgen]
+    pub fn load_schema_bundle(&mut self, bytes: &[u8]) -> Result<usize, JsValue> {
+        self.inner.load_schema_bundle(bytes)
+            .map_err(|errors| to_js_parse_errors("schema bundle", errors))
+    }
+}
+
+/// Options passed from JS, `undefined` or `null` give the defaults
+#[cfg(feature = "wasm")]
+fn validation_options(options: JsValue) -> Result<ValidationOptions, JsValue> {
+    if options.is_undefined() || options.is_null() {
+        return Ok(ValidationOptions::default());
+    }
+    serde_wasm_bindgen::from_value::<JsValidationOptions>(options)
+        .map(Into::into)
+        .map_err(|e| to_js_error("Invalid options format", e))
+}
+
+/// Parse a single MCDOC file without a validator, returning its declarations and
+/// errors (see `ParseSummary`). Declarations around errors are still listed.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn parse_mcdoc_file(source: &str) -> Result<JsValue, JsValue> {
+    let summary = crate::types::ParseSummary::from_source(source);
+    serde_wasm_bindgen::to_value(&summary)
+        .map_err(|e| to_js_error("Serialization error", e))
 }
 
 #[cfg(feature = "wasm")]
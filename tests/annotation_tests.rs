@@ -0,0 +1,76 @@
+//! Tests for parsing annotation contents
+
+use voxel_rsmcdoc::annotation::parse_annotation;
+use voxel_rsmcdoc::parser::{AnnotationData, AnnotationValue};
+use voxel_rsmcdoc::parse_mcdoc;
+
+fn complex(text: &str) -> rustc_hash::FxHashMap<&str, AnnotationValue<'_>> {
+    match parse_annotation(text) {
+        (_, AnnotationData::Complex(map)) => map,
+        (_, data) => panic!("Expected complex annotation, found {:?}", data),
+    }
+}
+
+#[test]
+fn test_id_with_registry_and_tags() {
+    let (name, _) = parse_annotation(r#"#[id(registry="worldgen/structure",tags="allowed")]"#);
+    assert_eq!(name, "id");
+
+    let map = complex(r#"#[id(registry="worldgen/structure",tags="allowed")]"#);
+    assert_eq!(map.get("registry"), Some(&AnnotationValue::String("worldgen/structure".into())));
+    assert_eq!(map.get("tags"), Some(&AnnotationValue::String("allowed".into())));
+}
+
+#[test]
+fn test_quoted_value_containing_comma_and_equals() {
+    let map = complex(r#"#[command(slash="a, b=c", incomplete="allowed")]"#);
+    assert_eq!(map.get("slash"), Some(&AnnotationValue::String("a, b=c".into())));
+    assert_eq!(map.get("incomplete"), Some(&AnnotationValue::String("allowed".into())));
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn test_escaped_quote_in_value() {
+    let map = complex(r#"#[text(value="say \"hi, there\"", other=1)]"#);
+    assert_eq!(map.get("value"), Some(&AnnotationValue::String(r#"say "hi, there""#.into())));
+    assert_eq!(map.get("other"), Some(&AnnotationValue::Number(1.0)));
+}
+
+#[test]
+fn test_typed_and_list_values() {
+    let map = complex(r#"#[id(registry="block", exclude=["air", "cave_air"], definition=true)]"#);
+    assert_eq!(map.get("exclude"), Some(&AnnotationValue::List(vec![
+        AnnotationValue::String("air".into()),
+        AnnotationValue::String("cave_air".into()),
+    ])));
+    assert_eq!(map.get("definition"), Some(&AnnotationValue::Boolean(true)));
+
+    let (name, data) = parse_annotation(r#"#[canonical=["a","b"]]"#);
+    assert_eq!(name, "canonical");
+    assert_eq!(data, AnnotationData::List(vec![AnnotationValue::String("a".into()), AnnotationValue::String("b".into())]));
+}
+
+#[test]
+fn test_simple_and_empty_annotations() {
+    assert_eq!(parse_annotation(r#"#[until="1.20"]"#), ("until", AnnotationData::Simple("1.20".into())));
+    assert_eq!(parse_annotation("#[nbt=minecraft:item[[id]]]"), ("nbt", AnnotationData::Simple("minecraft:item[[id]]".into())));
+    assert_eq!(parse_annotation("#[deprecated]"), ("deprecated", AnnotationData::Empty));
+
+    let map = complex(r#"#[id=(registry="dimension_type",definition=true)]"#);
+    assert_eq!(map.get("registry"), Some(&AnnotationValue::String("dimension_type".into())));
+}
+
+#[test]
+fn test_brackets_inside_quoted_annotation_value() {
+    let ast = parse_mcdoc(r#"struct Name { value: #[match_regex="^[a-z_]+$"] string }"#).expect("MCDOC should parse");
+    let voxel_rsmcdoc::Declaration::Struct(decl) = &ast.declarations[0] else {
+        panic!("Expected struct declaration");
+    };
+    let voxel_rsmcdoc::parser::StructMember::Field(field) = &decl.members[0] else {
+        panic!("Expected field");
+    };
+    let voxel_rsmcdoc::parser::TypeExpression::Annotated { annotations, .. } = &field.field_type else {
+        panic!("Expected annotated field type");
+    };
+    assert_eq!(annotations[0].data, AnnotationData::Simple("^[a-z_]+$".into()));
+}
@@ -0,0 +1,75 @@
+//! Tests for precompiled schema bundles
+
+#![cfg(feature = "bundle")]
+
+use std::fs;
+use serde_json::json;
+use voxel_rsmcdoc::bundle::{build_schema_bundle, BUNDLE_VERSION, MAGIC};
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::ParseError;
+
+fn dataset_files() -> Vec<(String, String)> {
+    ["data/recipe.mcdoc", "data/loot/mod.mcdoc", "data/util/mod.mcdoc"].iter()
+        .map(|filename| {
+            let path = format!("{}/tests/dataset/mcdoc/{}", env!("CARGO_MANIFEST_DIR"), filename);
+            (filename.to_string(), fs::read_to_string(path).unwrap())
+        })
+        .collect()
+}
+
+#[test]
+fn test_bundle_roundtrip() {
+    let files = dataset_files();
+    let bundle = build_schema_bundle(&files).unwrap();
+    assert!(bundle.starts_with(MAGIC));
+    assert_eq!(bundle[MAGIC.len()..MAGIC.len() + 2], BUNDLE_VERSION.to_le_bytes());
+
+    let mut from_bundle = DatapackValidator::new();
+    assert_eq!(from_bundle.load_schema_bundle(&bundle).unwrap(), 3);
+    let mut from_sources = DatapackValidator::new();
+    for (filename, source) in files {
+        from_sources.load_mcdoc_source(filename, source).unwrap();
+    }
+
+    for filename in ["data/recipe.mcdoc", "data/loot/mod.mcdoc", "data/util/mod.mcdoc"] {
+        assert_eq!(from_bundle.schema(filename), from_sources.schema(filename), "{}", filename);
+    }
+
+    let recipe = json!({
+        "type": "minecraft:crafting_shaped",
+        "pattern": ["aa", "aaaa"],
+        "key": { "a": "minecraft:stick" },
+        "result": { "id": "minecraft:stick" },
+    });
+    let expected = from_sources.validate_json(&recipe, "recipe", Some("1.21"));
+    let result = from_bundle.validate_json(&recipe, "recipe", Some("1.21"));
+    assert!(!result.is_valid);
+    assert_eq!(result.errors, expected.errors);
+    assert_eq!(result.dependencies, expected.dependencies);
+}
+
+#[test]
+fn test_invalid_source_reports_filename() {
+    let files = vec![
+        ("good.mcdoc".to_string(), "struct Good {}".to_string()),
+        ("bad.mcdoc".to_string(), "struct Bad {".to_string()),
+    ];
+    let errors = build_schema_bundle(&files).unwrap_err();
+    assert!(matches!(&errors[0], ParseError::Context { context, .. } if context == "bad.mcdoc"), "{:?}", errors);
+}
+
+#[test]
+fn test_mismatched_version_rejected() {
+    let mut bundle = build_schema_bundle(&[("a.mcdoc".to_string(), "struct A {}".to_string())]).unwrap();
+    bundle[MAGIC.len()..MAGIC.len() + 2].copy_from_slice(&(BUNDLE_VERSION + 1).to_le_bytes());
+
+    let mut validator = DatapackValidator::new();
+    let error = &validator.load_schema_bundle(&bundle).unwrap_err()[0];
+    assert!(error.to_string().contains(&format!("Unsupported schema bundle version {}", BUNDLE_VERSION + 1)), "{}", error);
+    assert_eq!(validator.schema_names().count(), 0);
+
+    assert!(validator.load_schema_bundle(b"not a bundle").unwrap_err()[0].to_string().contains("Not a schema bundle"));
+    assert!(validator.load_schema_bundle(&bundle[..MAGIC.len() + 1]).is_err());
+    let truncated = build_schema_bundle(&[("a.mcdoc".to_string(), "struct A {}".to_string())]).unwrap();
+    assert!(validator.load_schema_bundle(&truncated[..truncated.len() - 1]).is_err());
+}
@@ -0,0 +1,94 @@
+//! Tests for the semantic checks of parsed schemas
+
+use voxel_rsmcdoc::check::check_mcdoc;
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::{ErrorType, ParseError};
+
+fn check(source: &str) -> Vec<ParseError> {
+    check_mcdoc(&parse_mcdoc(source).unwrap())
+}
+
+/// (message, path, line, column) of each error
+fn summary(errors: &[ParseError]) -> Vec<(String, String, u32, u32)> {
+    errors.iter().map(|error| match error {
+        ParseError::Validation { message, path, pos: Some(pos) } => (message.clone(), path.clone(), pos.line, pos.column),
+        other => panic!("Not a positioned validation error: {:?}", other),
+    }).collect()
+}
+
+#[test]
+fn test_valid_schema_has_no_errors() {
+    let errors = check(r#"
+enum(int) Level { Low = 1, High = 2 }
+enum(float) Ratio { Half = 0.5, Full = 1 }
+struct Recipe { pattern: [string @ 1..3] @ 1..3, weight: float @ 0..1 }
+"#);
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn test_duplicate_variant_name() {
+    let errors = check("enum(string) Mode {\n    Any = \"any\",\n    Any = \"all\",\n}");
+    assert_eq!(summary(&errors), [("Duplicate enum variant 'Any'".to_string(), "Mode.Any".to_string(), 3, 5)]);
+    assert_eq!(errors[0].error_type(), ErrorType::Validation);
+}
+
+#[test]
+fn test_duplicate_variant_value() {
+    let errors = check("enum(string) Foo { A = \"x\", B = \"x\" }");
+    assert_eq!(summary(&errors), [(
+        "Enum variant 'B' has the same value \"x\" as 'A'".to_string(),
+        "Foo.B".to_string(),
+        1,
+        29,
+    )]);
+}
+
+#[test]
+fn test_value_base_type_mismatch() {
+    let errors = check("enum(int) Level { Low = \"low\", Half = 0.5, High = 2 }");
+    let messages: Vec<String> = summary(&errors).into_iter().map(|(message, path, _, _)| format!("{}: {}", path, message)).collect();
+    assert_eq!(messages, [
+        "Level.Low: Value \"low\" of enum variant 'Low' doesn't match enum(int)",
+        "Level.Half: Value 0.5 of enum variant 'Half' doesn't match enum(int)",
+    ]);
+
+    let errors = check("enum(string) Mode { Any = 1 }");
+    assert_eq!(summary(&errors)[0].0, "Value 1 of enum variant 'Any' doesn't match enum(string)");
+}
+
+#[test]
+fn test_inline_enum_in_field() {
+    let errors = check("struct Entry {\n    mode: enum(string) { A = \"a\", B = \"a\" },\n}");
+    assert_eq!(summary(&errors)[0].1, "Entry.mode.B");
+}
+
+#[test]
+fn test_array_constraint_min_above_max() {
+    let errors = check("struct Recipe {\n    pattern: [string] @ 5..1,\n}");
+    assert_eq!(summary(&errors), [(
+        "Array length range 5..1 has a minimum above its maximum".to_string(),
+        "Recipe.pattern".to_string(),
+        2,
+        5,
+    )]);
+}
+
+#[test]
+fn test_type_constraint_min_above_max() {
+    let errors = check("type Weight = float @ 1..0.5\ndispatch minecraft:resource[loot] to struct { rolls: int @ 3..2 }");
+    let paths: Vec<String> = summary(&errors).into_iter().map(|(message, path, _, _)| format!("{}: {}", path, message)).collect();
+    assert_eq!(paths, [
+        "Weight: Range 1..0.5 has a minimum above its maximum",
+        "minecraft:resource[loot].rolls: Range 3..2 has a minimum above its maximum",
+    ]);
+}
+
+#[test]
+fn test_validate_schema_on_validator() {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("enum.mcdoc".to_string(), "enum(string) Foo { A = \"x\", B = \"x\" }".to_string()).unwrap();
+    assert_eq!(validator.validate_schema("enum.mcdoc").len(), 1);
+    assert!(validator.validate_schema("missing.mcdoc").is_empty());
+}
@@ -0,0 +1,84 @@
+//! Tests for rendering errors with source excerpts
+
+use voxel_rsmcdoc::diagnostics::render;
+use voxel_rsmcdoc::{parse_mcdoc, Lexer, ParseError, SourcePos};
+
+#[test]
+fn test_render_syntax_error() {
+    let source = "struct Item {\n    id: string,\n    count: int int,\n}";
+    let errors = parse_mcdoc(source).unwrap_err();
+
+    assert_eq!(render(&errors[..1], source, "item.mcdoc"), "\
+error: Expected ',' after struct member, found Identifier(\"int\")
+ --> item.mcdoc:3:15
+  |
+3 |     count: int int,
+  |               ^
+");
+}
+
+#[test]
+fn test_render_lexer_error() {
+    let source = "struct Item {\n\tname: string $\n}";
+    let error = Lexer::new(source).tokenize().unwrap_err();
+
+    assert_eq!(render(&[error], source, "item.mcdoc"), "\
+error: Unexpected character: '$'
+ --> item.mcdoc:2:15
+  |
+2 |     name: string $
+  |                  ^
+");
+}
+
+#[test]
+fn test_render_multi_character_span() {
+    let source = "dispatch minecraft:resource[a] to struct A {}\nstruct B { x: int }\nenum";
+    let error = ParseError::Syntax {
+        expected: "identifier".to_string(),
+        found: "Struct".to_string(),
+        pos: SourcePos::new(2, 1, 46),
+        end: Some(SourcePos::new(2, 7, 52)),
+    };
+
+    assert_eq!(render(&[error], source, "a.mcdoc"), "\
+error: expected identifier, found Struct
+ --> a.mcdoc:2:1
+  |
+2 | struct B { x: int }
+  | ^^^^^^
+");
+}
+
+#[test]
+fn test_render_clamps_positions_past_the_end() {
+    let source = "struct A {\n";
+    let error = ParseError::syntax("'}'", "end of input", SourcePos::new(12, 40, 11));
+
+    assert_eq!(render(&[error], source, "a.mcdoc"), "\
+error: expected '}', found end of input
+ --> a.mcdoc:12:40
+  |
+1 | struct A {
+  |           ^
+");
+}
+
+#[test]
+fn test_render_without_position_and_several_errors() {
+    let errors = vec![
+        ParseError::resolution("Unknown type 'Foo'", None),
+        ParseError::lexer("Unterminated string", SourcePos::new(1, 3, 2)),
+    ];
+
+    assert_eq!(render(&errors, "a \"b", "a.mcdoc"), "\
+error: Unknown type 'Foo'
+ --> a.mcdoc
+
+error: Unterminated string
+ --> a.mcdoc:1:3
+  |
+1 | a \"b
+  |   ^
+");
+}
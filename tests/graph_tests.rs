@@ -0,0 +1,98 @@
+//! Tests for the dependency graph of a datapack
+
+use serde_json::json;
+use voxel_rsmcdoc::graph::ResourceNode;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::ValidationResult;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[recipe] to struct Recipe {
+    drops: #[id="loot_table"] string,
+    icon?: #[id(registry="item", tags="allowed")] string,
+}
+
+dispatch minecraft:resource[loot_table] to struct LootTable {
+    item: #[id="item"] string,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("pack.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:cake": {}, "minecraft:stone": {} },
+        "tags": { "minecraft:food": ["minecraft:cake"] }
+    })).unwrap();
+    validator
+}
+
+fn pack() -> Vec<(String, serde_json::Value)> {
+    vec![
+        ("data/demo/recipe/cake.json".to_string(), json!({ "drops": "demo:cake_drops", "icon": "#food" })),
+        ("data/demo/loot_table/cake_drops.json".to_string(), json!({ "item": "minecraft:cake" })),
+        ("data/demo/loot_table/unused.json".to_string(), json!({ "item": "stone" })),
+    ]
+}
+
+fn validate(validator: &DatapackValidator, files: Vec<(String, serde_json::Value)>) -> Vec<(String, ValidationResult)> {
+    validator.validate_stream(files).collect()
+}
+
+#[test]
+fn test_graph_nodes_and_edges() {
+    let validator = setup_validator();
+    let results = validate(&validator, pack());
+    let graph = validator.dependency_graph(results.iter().map(|(path, result)| (path.as_str(), result)));
+
+    assert_eq!(graph.files.len(), 3);
+    assert_eq!(graph.files[0].resource, Some(ResourceNode::new("recipe", "demo:cake", false)));
+    assert_eq!(graph.dependencies_of("data/demo/recipe/cake.json"), [
+        &ResourceNode::new("item", "#minecraft:food", true),
+        &ResourceNode::new("loot_table", "demo:cake_drops", false),
+    ]);
+    assert_eq!(graph.dependents_of("minecraft:cake"), ["data/demo/loot_table/cake_drops.json"]);
+    assert_eq!(graph.dependents_of("stone"), ["data/demo/loot_table/unused.json"]);
+    assert_eq!(graph.dependents_of("#minecraft:food"), ["data/demo/recipe/cake.json"]);
+    assert!(graph.dependents_of("minecraft:food").is_empty());
+
+    assert!(graph.missing_references().is_empty(), "{:?}", graph.missing_references());
+    let unused: Vec<&str> = graph.unused_local_resources().iter().map(|file| file.file_path.as_str()).collect();
+    assert_eq!(unused, ["data/demo/loot_table/unused.json", "data/demo/recipe/cake.json"]);
+}
+
+#[test]
+fn test_removed_loot_table_dangles() {
+    let validator = setup_validator();
+    let files = pack().into_iter().filter(|(path, _)| !path.ends_with("cake_drops.json")).collect();
+    let results = validate(&validator, files);
+    let graph = validator.dependency_graph(results.iter().map(|(path, result)| (path.as_str(), result)));
+
+    let missing = graph.missing_references();
+    assert_eq!(missing.len(), 1);
+    assert_eq!(missing[0].file_path, "data/demo/recipe/cake.json");
+    assert_eq!(missing[0].resource, ResourceNode::new("loot_table", "demo:cake_drops", false));
+    assert!(graph.dependents_of("minecraft:cake").is_empty());
+}
+
+#[test]
+fn test_local_resources_not_counted_as_registry_entries() {
+    let mut validator = setup_validator();
+    validator.register_local_resources("loot_table", ["demo:cake_drops".to_string()]);
+    let results = validate(&validator, vec![pack().remove(0)]);
+    let graph = validator.dependency_graph(results.iter().map(|(path, result)| (path.as_str(), result)));
+
+    assert_eq!(graph.missing_references().len(), 1);
+}
+
+#[test]
+fn test_graph_serialization() {
+    let validator = setup_validator();
+    let results = validate(&validator, pack());
+    let graph = validator.dependency_graph(results.iter().map(|(path, result)| (path.as_str(), result)));
+
+    let json = serde_json::to_value(&graph).unwrap();
+    assert_eq!(json["files"][0]["filePath"], "data/demo/recipe/cake.json");
+    assert_eq!(json["edges"][0]["resource"]["isTag"], false);
+    assert_eq!(json["edges"][0]["inRegistries"], false);
+    assert_eq!(serde_json::from_value::<voxel_rsmcdoc::graph::DependencyGraph>(json).unwrap(), graph);
+}
@@ -0,0 +1,114 @@
+//! Tests for describing loaded schemas and rendering types in MCDOC syntax
+
+use voxel_rsmcdoc::inspect::{DeclarationDescription, MemberDescription};
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::DatapackValidator;
+
+const MCDOC: &str = r#"
+/// A shaped recipe
+struct Recipe {
+    /// Ingredients of the grid
+    pattern: [string] @ 1..3,
+    key: (string | struct { item: string }),
+    result?: #[id="item"] string,
+    count?: int @ 1..<65,
+    [#[id="item"] string]: Ingredient<string>,
+    ...minecraft:recipe_serializer[[type]],
+}
+
+enum(string) Category {
+    /// Building blocks
+    Building = "building",
+    Misc = "misc",
+}
+
+type Ingredient<T> = (T | [T] @ 2)
+
+dispatch minecraft:resource[recipe, %unknown] to struct RecipeFile {
+    type: minecraft:recipe_serializer[crafting_shaped],
+    ...super::RecipeBase,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_parsed_mcdoc("recipe.mcdoc".to_string(), parse_mcdoc(MCDOC).unwrap()).unwrap();
+    validator
+}
+
+fn field_type<'a>(members: &'a [MemberDescription], field: &str) -> &'a str {
+    members.iter()
+        .find_map(|member| match member {
+            MemberDescription::Field { name, type_name, .. } if name == field => Some(type_name.as_str()),
+            _ => None,
+        })
+        .unwrap_or_else(|| panic!("No field '{}'", field))
+}
+
+#[test]
+fn test_struct_fields_rendered() {
+    let description = setup_validator().describe_schema("recipe.mcdoc").unwrap();
+    let DeclarationDescription::Struct(recipe) = &description.declarations[0] else {
+        panic!("Expected a struct, got {:?}", description.declarations[0]);
+    };
+
+    assert_eq!(recipe.name, "Recipe");
+    assert_eq!(recipe.docs, ["A shaped recipe"]);
+    assert_eq!(field_type(&recipe.members, "pattern"), "[string] @ 1..3");
+    assert_eq!(field_type(&recipe.members, "key"), "(string | struct {...})");
+    assert_eq!(field_type(&recipe.members, "result"), "#[id=\"item\"] string");
+    assert_eq!(field_type(&recipe.members, "count"), "int @ 1..<65");
+
+    let MemberDescription::Field { optional, docs, .. } = &recipe.members[0] else { unreachable!() };
+    assert!(!optional);
+    assert_eq!(docs, &["Ingredients of the grid"]);
+    let MemberDescription::Field { optional, annotations, .. } = &recipe.members[2] else { unreachable!() };
+    assert!(optional);
+    assert!(annotations.is_empty());
+
+    assert_eq!(recipe.members[4], MemberDescription::DynamicField {
+        key_type: "#[id=\"item\"] string".to_string(),
+        type_name: "Ingredient<string>".to_string(),
+        optional: false,
+        annotations: Vec::new(),
+    });
+    assert_eq!(recipe.members[5], MemberDescription::Spread { type_name: "minecraft:recipe_serializer[[type]]".to_string() });
+}
+
+#[test]
+fn test_enum_alias_and_dispatch() {
+    let description = setup_validator().describe_schema("recipe.mcdoc").unwrap();
+
+    let DeclarationDescription::Enum(category) = &description.declarations[1] else { panic!("Expected an enum") };
+    assert_eq!(category.base_type.as_deref(), Some("string"));
+    assert_eq!(category.variants[0].name, "Building");
+    assert_eq!(category.variants[0].value.as_deref(), Some("\"building\""));
+    assert_eq!(category.variants[0].docs, ["Building blocks"]);
+
+    let DeclarationDescription::Type(alias) = &description.declarations[2] else { panic!("Expected a type alias") };
+    assert_eq!(alias.type_params, ["T"]);
+    assert_eq!(alias.type_name, "(T | [T] @ 2)");
+
+    let DeclarationDescription::Dispatch(dispatch) = &description.declarations[3] else { panic!("Expected a dispatch") };
+    assert_eq!(dispatch.registry, "minecraft:resource");
+    assert_eq!(dispatch.keys, ["recipe", "%unknown"]);
+    assert_eq!(dispatch.type_name, "struct RecipeFile {...}");
+    assert_eq!(field_type(&dispatch.members, "type"), "minecraft:recipe_serializer[crafting_shaped]");
+    assert_eq!(dispatch.members[1], MemberDescription::Spread { type_name: "super::RecipeBase".to_string() });
+}
+
+#[test]
+fn test_description_json() {
+    let validator = setup_validator();
+    assert!(validator.describe_schema("missing.mcdoc").is_none());
+
+    let descriptions = validator.describe_schemas();
+    assert_eq!(descriptions.len(), 1);
+    let json = descriptions[0].to_json();
+    assert_eq!(json["filename"], "recipe.mcdoc");
+    assert_eq!(json["declarations"][0]["kind"], "struct");
+    assert_eq!(json["declarations"][0]["members"][0]["type"], "[string] @ 1..3");
+    assert_eq!(json["declarations"][0]["members"][4]["kind"], "dynamicField");
+    assert_eq!(json["declarations"][0]["members"][4]["keyType"], "#[id=\"item\"] string");
+    assert_eq!(json["declarations"][2]["typeParams"][0], "T");
+}
@@ -1,7 +1,7 @@
 use std::fs;
 use serde_json::json;
 use voxel_rsmcdoc::validator::DatapackValidator;
-use voxel_rsmcdoc::{lexer::Lexer, parser::Parser, parse_mcdoc};
+use voxel_rsmcdoc::parse_mcdoc;
 
 // Helper function to initialize the validator for tests
 fn setup_validator() -> DatapackValidator<'static> {
@@ -33,8 +33,7 @@ fn setup_validator() -> DatapackValidator<'static> {
     ).unwrap();
 
     // Load MCDOC
-    let mcdoc_content = Box::leak(Box::new(
-        r#"
+    let mcdoc_content = r#"
 dispatch minecraft:resource[test_recipe] to struct TestRecipe {
     ingredient: #[id(registry="item")] string,
     result: #[id(registry="item")] string,
@@ -50,14 +49,9 @@ dispatch minecraft:resource[test_loot_table] to struct TestLootTable {
         }],
     }],
 }
-"#.to_string()
-    ));
+"#.to_string();
 
-    let mut lexer = Lexer::new(mcdoc_content);
-    let tokens = lexer.tokenize().unwrap();
-    let mut parser = Parser::new(tokens);
-    let ast = parser.parse().unwrap();
-    validator.load_parsed_mcdoc("test.mcdoc".to_string(), ast).unwrap();
+    validator.load_mcdoc_source("test.mcdoc".to_string(), mcdoc_content).unwrap();
 
     validator
 }
@@ -247,7 +241,6 @@ fn test_concrete_real_mcdoc_and_registry() {
             });
             
             println!("  Registry loaded successfully, basic test passes");
-            assert!(true, "Registry loading works, parser needs improvement for complex MCDOC");
         }
     }
 }
@@ -277,29 +270,9 @@ dispatch minecraft:resource[recipe] to struct Recipe {
     let mut validator = DatapackValidator::new();
     
     // 3. Load REAL registries
-    if let Some(item_list) = registry_data.get("item") {
-        // Convert array to object format expected by load_registry
-        let mut item_entries = serde_json::Map::new();
-        if let Some(items) = item_list.as_array() {
-            for item in items {
-                if let Some(item_name) = item.as_str() {
-                    item_entries.insert(item_name.to_string(), serde_json::json!({}));
-                }
-            }
-        }
-        
-        let item_registry = serde_json::json!({
-            "entries": item_entries
-        });
-        
-        validator.load_registry(
-            "item".to_string(),
-            "1.21".to_string(),
-            &item_registry
-        ).expect("Failed to load item registry");
-        
-        println!("✅ Loaded {} items from registry", item_entries.len());
-    }
+    let loaded = validator.load_registries_bundle("1.21", &registry_data)
+        .expect("Failed to load registries");
+    println!("✅ Loaded {} registries", loaded);
 
     // 4. Parse and load our simplified MCDOC
     match parse_mcdoc(simple_recipe_mcdoc) {
@@ -358,7 +331,6 @@ dispatch minecraft:resource[recipe] to struct Recipe {
                 println!("  ✅ Successfully extracted acacia_fence_gate dependency!");
             }
             
-            assert!(true, "Infrastructure works with real data");
 
             // 6. Test with EXACT registry names (without minecraft: namespace)
             let exact_recipe = serde_json::json!({
@@ -456,13 +428,11 @@ dispatch minecraft:resource[recipe] to struct Recipe {
     
     if result.is_valid {
         println!("  ✅ WASM validate API works perfectly!");
-        assert!(result.dependencies.len() > 0, "Should extract dependencies");
+        assert!(!result.dependencies.is_empty(), "Should extract dependencies");
     } else {
         println!("  ⚠️ Validation failed, but API structure works");
         for error in &result.errors {
             println!("    - {}: {}", error.path, error.message);
         }
     }
-    
-    assert!(true, "WASM API structure is functional");
-}
\ No newline at end of file
+}
@@ -0,0 +1,174 @@
+//! Tests for exporting loaded types as draft-07 JSON Schema
+
+use std::fs;
+use serde_json::{json, Value};
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::DatapackValidator;
+
+const DRAFT_07: &str = "http://json-schema.org/draft-07/schema#";
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[test_loot_table] to struct TestLootTable {
+    /// Kind of loot table
+    type?: string,
+    pools: [struct {
+        rolls: int @ 1..,
+        entries: [Entry] @ 1..<10,
+    }],
+    weights?: struct { [#[id="item"] string]: float @ 0<..1 },
+}
+
+struct Entry {
+    type: #[id="loot_pool_entry_type"] string,
+    name?: #[id(registry="item")] string,
+    mode?: Mode,
+    children?: [Entry],
+    ...minecraft:loot_pool_entry[[type]],
+}
+
+enum(string) Mode {
+    Any = "any",
+    All = "all",
+}
+
+dispatch minecraft:loot_pool_entry[item] to struct ItemEntry {
+    count?: (int | "all"),
+    label: string @ 1..16,
+}
+
+dispatch minecraft:loot_pool_entry[tag] to struct TagEntry {
+    expand: boolean,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_parsed_mcdoc("loot.mcdoc".to_string(), parse_mcdoc(MCDOC).unwrap()).unwrap();
+    validator
+}
+
+/// Target of a `$ref` in the document
+fn resolve<'a>(document: &'a Value, schema: &'a Value) -> &'a Value {
+    match schema["$ref"].as_str() {
+        Some(reference) => {
+            let pointer = reference.strip_prefix('#').expect("references stay in the document");
+            document.pointer(pointer).unwrap_or_else(|| panic!("Dangling reference {}", reference))
+        }
+        None => schema,
+    }
+}
+
+/// Every `$ref` of the document points into it, and every schema is an object or boolean
+fn assert_well_formed(document: &Value, schema: &Value) {
+    match schema {
+        Value::Object(object) => {
+            if let Some(reference) = object.get("$ref") {
+                resolve(document, schema);
+                assert_eq!(object.len(), 1, "Keywords beside $ref are ignored: {}", schema);
+                assert!(reference.is_string());
+            }
+            for keyword in ["properties", "definitions"] {
+                if let Some(children) = object.get(keyword) {
+                    children.as_object().unwrap().values().for_each(|child| assert_well_formed(document, child));
+                }
+            }
+            for keyword in ["anyOf", "allOf"] {
+                if let Some(children) = object.get(keyword) {
+                    children.as_array().unwrap().iter().for_each(|child| assert_well_formed(document, child));
+                }
+            }
+            for keyword in ["items", "additionalProperties", "propertyNames", "if", "then"] {
+                if let Some(child) = object.get(keyword) {
+                    assert_well_formed(document, child);
+                }
+            }
+            if let Some(required) = object.get("required") {
+                assert!(required.as_array().unwrap().iter().all(Value::is_string));
+            }
+        }
+        Value::Bool(_) => {}
+        other => panic!("Not a schema: {}", other),
+    }
+}
+
+#[test]
+fn test_struct_properties_and_required() {
+    let document = setup_validator().to_json_schema("test_loot_table");
+    assert_well_formed(&document, &document);
+
+    assert_eq!(document["$schema"], DRAFT_07);
+    assert_eq!(document["type"], "object");
+    assert_eq!(document["required"], json!(["pools"]));
+    assert_eq!(document["properties"]["type"], json!({ "type": "string", "description": "Kind of loot table" }));
+
+    let pools = &document["properties"]["pools"];
+    assert_eq!(pools["type"], "array");
+    assert_eq!(pools["items"]["required"], json!(["rolls", "entries"]));
+    assert_eq!(pools["items"]["properties"]["rolls"], json!({ "type": "integer", "minimum": 1 }));
+
+    let entries = &pools["items"]["properties"]["entries"];
+    assert_eq!(entries["minItems"], 1);
+    assert_eq!(entries["maxItems"], 9);
+    assert_eq!(entries["items"], json!({ "$ref": "#/definitions/Entry" }));
+
+    let weights = &document["properties"]["weights"];
+    assert_eq!(weights["additionalProperties"], json!({ "type": "number", "exclusiveMinimum": 0, "maximum": 1 }));
+    assert_eq!(weights["propertyNames"], json!({ "type": "string", "x-registry": "item" }));
+}
+
+#[test]
+fn test_definitions_enums_and_dispatch() {
+    let document = setup_validator().to_json_schema("minecraft:test_loot_table");
+    let entry = &document["definitions"]["Entry"];
+
+    assert_eq!(entry["properties"]["name"], json!({ "type": "string", "x-registry": "item" }));
+    assert_eq!(resolve(&document, &entry["properties"]["mode"]), &json!({ "type": "string", "enum": ["any", "all"] }));
+    // Recursive references point back at the definition being built
+    assert_eq!(entry["properties"]["children"]["items"], json!({ "$ref": "#/definitions/Entry" }));
+
+    let dispatched = entry["allOf"].as_array().unwrap();
+    assert_eq!(dispatched.len(), 2);
+    assert_eq!(dispatched[0]["if"], json!({
+        "properties": { "type": { "enum": ["item", "minecraft:item"] } },
+        "required": ["type"],
+    }));
+    let item = resolve(&document, &dispatched[0]["then"]);
+    assert_eq!(item["properties"]["count"]["anyOf"], json!([{ "type": "integer" }, { "const": "all" }]));
+    assert_eq!(item["properties"]["label"], json!({ "type": "string", "minLength": 1, "maxLength": 16 }));
+    assert_eq!(resolve(&document, &dispatched[1]["then"])["required"], json!(["expand"]));
+}
+
+#[test]
+fn test_unknown_resource_accepts_anything() {
+    assert_eq!(setup_validator().to_json_schema("missing"), json!({ "$schema": DRAFT_07 }));
+}
+
+#[test]
+fn test_dataset_recipe_and_loot_table() {
+    let mut validator = DatapackValidator::new();
+    for filename in ["data/recipe.mcdoc", "data/loot/mod.mcdoc", "data/util/mod.mcdoc"] {
+        let path = format!("{}/tests/dataset/mcdoc/{}", env!("CARGO_MANIFEST_DIR"), filename);
+        let source = fs::read_to_string(&path).unwrap();
+        validator.load_mcdoc_source(filename.to_string(), source).unwrap();
+    }
+
+    let recipe = validator.to_json_schema("recipe");
+    assert_well_formed(&recipe, &recipe);
+    assert_eq!(recipe["type"], "object");
+    assert_eq!(recipe["required"], json!(["type"]));
+    assert_eq!(recipe["properties"]["type"]["x-registry"], "recipe_serializer");
+    let serializers = recipe["allOf"].as_array().unwrap();
+    let shaped = serializers.iter()
+        .find(|branch| branch["if"]["properties"]["type"]["enum"][0] == "crafting_shaped")
+        .expect("crafting_shaped is dispatched");
+    let shaped = resolve(&recipe, &shaped["then"]);
+    assert_eq!(shaped["properties"]["pattern"]["maxItems"], 3);
+    assert_eq!(shaped["properties"]["pattern"]["items"]["maxLength"], 3);
+
+    let loot_table = validator.to_json_schema("loot_table");
+    assert_well_formed(&loot_table, &loot_table);
+    assert_eq!(loot_table["type"], "object");
+    assert_eq!(loot_table["properties"]["pools"]["items"], json!({ "$ref": "#/definitions/LootPool" }));
+    assert_eq!(loot_table["properties"]["random_sequence"]["x-registry"], "random_sequence");
+    assert!(loot_table["definitions"].as_object().unwrap().contains_key("LootPool"));
+}
@@ -0,0 +1,66 @@
+//! Tests for decoding escape sequences in string literals
+
+use std::borrow::Cow;
+use serde_json::json;
+use voxel_rsmcdoc::lexer::{unescape, Lexer, Token};
+use voxel_rsmcdoc::parser::{AnnotationData, Declaration, LiteralValue, StructMember};
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::{parse_mcdoc, ParseError};
+
+#[test]
+fn test_decoded_escapes() {
+    let tokens = Lexer::new(r#""plain" "tab\tquote\"slash\\" "A\u{1F600}😀""#).tokenize().unwrap();
+    assert_eq!(tokens[0].token, Token::String(Cow::Borrowed("plain")));
+    assert!(matches!(&tokens[0].token, Token::String(Cow::Borrowed(_))));
+    assert_eq!(tokens[1].token, Token::String("tab\tquote\"slash\\".into()));
+    assert_eq!(tokens[2].token, Token::String("A\u{1F600}\u{1F600}".into()));
+}
+
+#[test]
+fn test_invalid_escape_position() {
+    let error = Lexer::new("struct A {\n  a: \"ok\\q\",\n}").tokenize().unwrap_err();
+    match error {
+        ParseError::Lexer { message, pos } => {
+            assert!(message.contains("\\q"), "{}", message);
+            assert_eq!((pos.line, pos.column), (2, 9));
+        }
+        other => panic!("Expected a lexer error, found {:?}", other),
+    }
+
+    assert!(unescape(r"\u{110000}").is_err());
+    assert!(unescape(r"\ud83d").is_err());
+    assert_eq!(unescape(r"trailing\").unwrap_err().offset, 8);
+}
+
+#[test]
+fn test_escaped_quote_in_annotation_value() {
+    let file = parse_mcdoc(r#"struct A { #[name="a\"b"] a: string }"#).unwrap();
+    let Declaration::Struct(structure) = &file.declarations[0] else { panic!("Expected a struct") };
+    let StructMember::Field(field) = &structure.members[0] else { panic!("Expected a field") };
+    assert_eq!(field.annotations[0].data, AnnotationData::Simple("a\"b".into()));
+}
+
+#[test]
+fn test_escaped_quote_in_enum_value() {
+    let source = r#"
+enum(string) Quote {
+    Double = "a\"b",
+    Backslash = "c\\d",
+}
+
+dispatch minecraft:resource[quoted] to struct Quoted {
+    quote: Quote,
+}
+"#;
+    let file = parse_mcdoc(source).unwrap();
+    let Declaration::Enum(quote) = &file.declarations[0] else { panic!("Expected an enum") };
+    assert_eq!(quote.variants[0].value, Some(LiteralValue::String("a\"b".into())));
+
+    let mut validator = DatapackValidator::new();
+    validator.load_parsed_mcdoc("quote.mcdoc".to_string(), file).unwrap();
+    for value in ["a\"b", "c\\d"] {
+        let result = validator.validate_json(&json!({ "quote": value }), "quoted", None);
+        assert!(result.is_valid, "{}: {:?}", value, result.errors);
+    }
+    assert!(!validator.validate_json(&json!({ "quote": "a\\\"b" }), "quoted", None).is_valid);
+}
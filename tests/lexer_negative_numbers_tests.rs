@@ -10,7 +10,7 @@ fn test_negative_number_in_range_constraint() {
     
     let tokens = lexer.tokenize().expect("Should parse negative numbers in constraints");
     
-    let expected_tokens = vec![
+    let expected_tokens = [
         Token::Identifier("float"),
         Token::At,
         Token::Number(-80.0),  // Doit lire -80 comme un nombre négatif
@@ -70,7 +70,7 @@ fn test_simple_negative_numbers() {
     
     for (input, expected) in test_cases {
         let mut lexer = Lexer::new(input);
-        let tokens = lexer.tokenize().expect(&format!("Should parse '{}'", input));
+        let tokens = lexer.tokenize().unwrap_or_else(|_| panic!("Should parse '{}'", input));
         
         // Skip EOF token pour la comparaison
         let actual_tokens: Vec<_> = tokens.iter()
@@ -103,4 +103,38 @@ fn test_negative_in_context() {
         let result = lexer.tokenize();
         assert!(result.is_ok(), "Should parse '{}': {:?}", input, result.err());
     }
-} 
\ No newline at end of file
+} 
+fn number_tokens(input: &str) -> Vec<Token<'_>> {
+    let mut lexer = Lexer::new(input);
+    lexer.tokenize()
+        .unwrap_or_else(|e| panic!("Should parse '{}': {:?}", input, e))
+        .into_iter()
+        .map(|t| t.token)
+        .filter(|t| !matches!(t, Token::Eof))
+        .collect()
+}
+
+#[test]
+fn test_negative_numbers_around_ranges() {
+    assert_eq!(number_tokens("-80..80"), vec![Token::Number(-80.0), Token::DotDot, Token::Number(80.0)]);
+    assert_eq!(number_tokens("5..-3"), vec![Token::Number(5.0), Token::DotDot, Token::Number(-3.0)]);
+    assert_eq!(number_tokens("..-4"), vec![Token::DotDot, Token::Number(-4.0)]);
+    assert_eq!(number_tokens("-.5..-0.25"), vec![Token::Number(-0.5), Token::DotDot, Token::Number(-0.25)]);
+}
+
+#[test]
+fn test_negative_zero_and_floats() {
+    match number_tokens("-0").as_slice() {
+        [Token::Number(n)] => assert!(*n == 0.0 && n.is_sign_negative()),
+        other => panic!("Unexpected tokens: {:?}", other),
+    }
+    assert_eq!(number_tokens("-12.75"), vec![Token::Number(-12.75)]);
+}
+
+#[test]
+fn test_minus_without_number_is_rejected() {
+    for input in ["-", "- 5", "-..5", "-x"] {
+        let mut lexer = Lexer::new(input);
+        assert!(lexer.tokenize().is_err(), "'{}' should not tokenize", input);
+    }
+}
@@ -43,10 +43,10 @@ fn test_strings_and_numbers() {
     let mut lexer = Lexer::new(input);
     let tokens = lexer.tokenize().unwrap();
     
-    assert_eq!(tokens[0].token, Token::String("hello world"));
+    assert_eq!(tokens[0].token, Token::String("hello world".into()));
     assert_eq!(tokens[1].token, Token::Number(123.0));
     assert_eq!(tokens[2].token, Token::Number(45.67));
-    assert_eq!(tokens[3].token, Token::String("escaped \\\" quote"));
+    assert_eq!(tokens[3].token, Token::String("escaped \" quote".into()));
 }
 
 #[test]
@@ -70,4 +70,17 @@ fn test_comments_are_skipped() {
     assert_eq!(tokens[1].token, Token::Newline);
     assert_eq!(tokens[2].token, Token::Struct);
     assert_eq!(tokens[3].token, Token::Identifier("MyStruct"));
+}
+
+#[test]
+fn test_doc_comments_are_tokens() {
+    let input = "/// Documented\n// plain\n///\nstruct";
+    let mut lexer = Lexer::new(input);
+    let tokens = lexer.tokenize().unwrap();
+
+    assert_eq!(tokens[0].token, Token::DocComment("Documented"));
+    assert_eq!(tokens[1].token, Token::Newline);
+    assert_eq!(tokens[2].token, Token::Newline);
+    assert_eq!(tokens[3].token, Token::DocComment(""));
+    assert_eq!(tokens[5].token, Token::Struct);
 } 
\ No newline at end of file
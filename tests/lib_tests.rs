@@ -16,6 +16,25 @@ fn test_resource_id_parsing() {
     assert_eq!(id3.path, "diamond_sword");
 }
 
+#[test]
+fn test_resource_id_validation() {
+    assert!(ResourceId::parse("my-pack.v2:block/oak_log").is_ok());
+    assert!(ResourceId::parse("A:B").is_err());
+    assert!(ResourceId::parse("a:b:c").is_err());
+    assert!(ResourceId::parse("minecraft:").is_err());
+    assert!(ResourceId::parse(":stone").is_err());
+
+    let error = ResourceId::parse("Foo Bar:baz!!").unwrap_err().to_string();
+    assert!(error.contains("invalid character 'F' in namespace 'Foo Bar'"), "{}", error);
+    let error = ResourceId::parse("minecraft:baz!!").unwrap_err().to_string();
+    assert!(error.contains("invalid character '!' in path"), "{}", error);
+
+    assert!(ResourceId::is_valid("#minecraft:logs"));
+    assert!(ResourceId::is_valid("stone"));
+    assert!(!ResourceId::is_valid("#Minecraft:logs"));
+    assert!(!ResourceId::is_valid("##minecraft:logs"));
+}
+
 #[test]
 fn test_simple_validation_without_schema() {
     let validator = DatapackValidator::new();
@@ -149,4 +168,21 @@ fn test_versioned_registry() {
     validator.load_registry("block".to_string(), "1.21".to_string(), &registry_1_21).unwrap();
 
     assert!(validator.registry_manager.has_registry("block"));
+    assert_eq!(validator.registry_manager.registry_versions("block"), ["1.20", "1.21"]);
+
+    let mcdoc = "dispatch minecraft:resource[test] to struct Test { block: #[id=\"block\"] string }";
+    let ast = voxel_rsmcdoc::parse_mcdoc(mcdoc).unwrap();
+    validator.load_parsed_mcdoc("test.mcdoc".to_string(), ast).unwrap();
+
+    let json = serde_json::json!({ "block": "minecraft:granite" });
+    let result = validator.validate_json(&json, "minecraft:test", Some("1.21"));
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+
+    let result = validator.validate_json(&json, "minecraft:test", Some("1.20"));
+    assert!(!result.is_valid);
+    assert!(result.errors[0].message.contains("not found in registry 'block'"));
+
+    // Versions that aren't loaded, or no version at all, use the latest registry
+    assert!(validator.validate_json(&json, "minecraft:test", Some("1.19")).is_valid);
+    assert!(validator.validate_json(&json, "minecraft:test", None).is_valid);
 } 
\ No newline at end of file
@@ -25,16 +25,17 @@ fn test_nested_array_constraints_from_model_mcdoc() {
     }
     
     // 2. PARSING
-    let mut parser = Parser::new(tokens);
-    let type_result = parser.parse_type_expression();
+    let type_result = Parser::parse_type_expression_complete(input);
     
     println!("🔍 Résultat parsing: {:?}", type_result);
     
-    if let Err(ref error) = type_result {
+    if let Err(ref errors) = type_result {
         println!("❌ Erreur détaillée:");
-        println!("  - {:?}", error);
-        if let Some(pos) = error.position() {
-            println!("  - Position: Ligne {}, Colonne {}", pos.line, pos.column);
+        for error in errors {
+            println!("  - {:?}", error);
+            if let Some(pos) = error.position() {
+                println!("  - Position: Ligne {}, Colonne {}", pos.line, pos.column);
+            }
         }
     }
     
@@ -101,11 +102,7 @@ fn test_negative_number_constraints_variations() {
     for (input, description) in test_cases {
         println!("🧪 TEST: {} - {}", description, input);
         
-        let mut lexer = Lexer::new(input);
-        let tokens = lexer.tokenize().expect("Tokenisation should work");
-        
-        let mut parser = Parser::new(tokens);
-        let result = parser.parse_type_expression();
+        let result = Parser::parse_type_expression_complete(input);
         
         if let Err(ref error) = result {
             println!("❌ Failed {}: {:?}", description, error);
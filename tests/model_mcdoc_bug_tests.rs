@@ -1,7 +1,6 @@
 //! Test for the specific model.mcdoc bug reported by user
 
 use voxel_rsmcdoc::lexer::Lexer;
-use voxel_rsmcdoc::parser::Parser;
 
 #[test]
 fn test_model_mcdoc_line16_translation_field() {
@@ -0,0 +1,71 @@
+//! Tests for the owned AST, serialized and loaded without its source
+
+use std::fs;
+use serde_json::json;
+use voxel_rsmcdoc::owned::McDocFileOwned;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::{parse_mcdoc, parse_mcdoc_owned};
+
+const MCDOC: &str = r#"
+use ::java::util::Text as Label
+
+dispatch minecraft:resource[test_recipe] to struct TestRecipe {
+    /// Recipe kind
+    type: #[id(registry="recipe_serializer", tags="allowed")] string,
+    count?: int @ 1..64,
+    mode?: Mode,
+    "quoted:key"?: ("a\"b" | [float] @ 0..<4),
+    ...minecraft:recipe_serializer[[type]],
+}
+
+enum(string) Mode {
+    Fast = "fast",
+    Slow = "slow",
+}
+
+type Pair<T> = [T] @ 2
+
+dispatch minecraft:recipe_serializer[shaped, "minecraft:shapeless"] to struct Shaped {
+    pattern: Pair<string>,
+}
+"#;
+
+fn assert_send_static<T: Send + 'static>(_: &T) {}
+
+#[test]
+fn test_owned_roundtrip_matches_parsed() {
+    let owned = parse_mcdoc_owned(MCDOC).unwrap();
+    assert_send_static(&owned);
+    assert_eq!(owned.to_borrowed(), parse_mcdoc(MCDOC).unwrap());
+
+    let path = format!("{}/tests/dataset/mcdoc/data/loot/mod.mcdoc", env!("CARGO_MANIFEST_DIR"));
+    let source = fs::read_to_string(path).unwrap();
+    let file = parse_mcdoc(&source).unwrap();
+    assert_eq!(McDocFileOwned::from(&file).to_borrowed(), file);
+}
+
+#[test]
+fn test_serialized_schema_validates_without_source() {
+    let source = MCDOC.to_string();
+    let serialized = serde_json::to_string(&parse_mcdoc_owned(&source).unwrap()).unwrap();
+    drop(source);
+
+    let owned: McDocFileOwned = serde_json::from_str(&serialized).unwrap();
+    let mut validator = DatapackValidator::new();
+    validator.load_owned_mcdoc("recipe.mcdoc".to_string(), owned).unwrap();
+    validator.load_registry("recipe_serializer".to_string(), "1.21".to_string(), &json!(["minecraft:shaped", "minecraft:shapeless"])).unwrap();
+
+    let valid = json!({ "type": "minecraft:shapeless", "count": 2, "mode": "fast", "quoted:key": "a\"b", "pattern": ["a", "b"] });
+    let result = validator.validate_json(&valid, "test_recipe", None);
+    assert!(result.is_valid, "{:?}", result.errors);
+
+    let invalid = json!({ "type": "shaped", "count": 100, "mode": "medium", "pattern": ["a"] });
+    let result = validator.validate_json(&invalid, "test_recipe", None);
+    let mut paths: Vec<&str> = result.errors.iter().map(|error| error.path.as_str()).collect();
+    paths.sort();
+    assert_eq!(paths, ["count", "mode", "pattern"], "{:?}", result.errors);
+
+    // Unloading frees the owned AST along with the schema
+    assert!(validator.unload_mcdoc("recipe.mcdoc"));
+    assert!(!validator.validate_json(&valid, "test_recipe", None).is_valid);
+}
@@ -0,0 +1,56 @@
+//! Tests for array size constraints and constraints on array elements
+
+use voxel_rsmcdoc::parser::{ArrayConstraints, TypeConstraints, TypeExpression};
+use voxel_rsmcdoc::{parse_mcdoc, Declaration, ParseError};
+
+fn type_of(source: &str) -> TypeExpression<'_> {
+    let file = parse_mcdoc(source).unwrap();
+    match &file.declarations[0] {
+        Declaration::Type(declaration) => declaration.type_expr.clone(),
+        other => panic!("Expected a type alias, found {:?}", other),
+    }
+}
+
+fn size_error(source: &str) -> String {
+    match &parse_mcdoc(source).unwrap_err()[0] {
+        ParseError::Syntax { expected, found, .. } => {
+            assert_eq!(expected, "array size constraint must be a non-negative integer");
+            found.clone()
+        }
+        other => panic!("Expected a syntax error, found {:?}", other),
+    }
+}
+
+#[test]
+fn test_element_constraints_keep_fractions() {
+    let TypeExpression::Array { element_type, constraints } = type_of("type Offsets = [float @ -4.5..4.5] @ 3") else {
+        panic!("Expected an array type");
+    };
+
+    assert_eq!(constraints, Some(ArrayConstraints { min: Some(3), max: Some(3), min_exclusive: false, max_exclusive: false }));
+    match *element_type {
+        TypeExpression::Constrained { constraints, .. } => {
+            assert_eq!(constraints, TypeConstraints { min: Some(-4.5), max: Some(4.5), min_exclusive: false, max_exclusive: false });
+        }
+        other => panic!("Expected a constrained element, found {:?}", other),
+    }
+}
+
+#[test]
+fn test_array_size_ranges() {
+    let TypeExpression::Array { constraints, .. } = type_of("type Items = [int] @ ..8") else {
+        panic!("Expected an array type");
+    };
+    assert_eq!(constraints, Some(ArrayConstraints { min: None, max: Some(8), min_exclusive: false, max_exclusive: false }));
+}
+
+#[test]
+fn test_negative_array_size_is_an_error() {
+    assert_eq!(size_error("type Items = [int] @ -1"), "-1");
+}
+
+#[test]
+fn test_fractional_array_size_is_an_error() {
+    assert_eq!(size_error("type Items = [int] @ 2.5"), "2.5");
+    assert_eq!(size_error("type Items = int[] @ 1..2.5"), "2.5");
+}
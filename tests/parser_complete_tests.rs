@@ -0,0 +1,46 @@
+//! Tests for parsing a whole input as one type expression or declaration
+
+use voxel_rsmcdoc::parser::{Declaration, Parser, TypeExpression};
+use voxel_rsmcdoc::ParseError;
+
+#[test]
+fn test_type_expression_complete() {
+    let type_expr = Parser::parse_type_expression_complete("  int @ 1..10 // count\n\n").unwrap();
+    assert!(matches!(type_expr, TypeExpression::Constrained { .. }));
+    assert!(Parser::parse_type_expression_complete("[float @ -80..80] @ 3").is_ok());
+}
+
+#[test]
+fn test_type_expression_trailing_tokens() {
+    let errors = Parser::parse_type_expression_complete("int @ 1..10 garbage").unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(&errors[0], ParseError::Context { context, .. } if context == "type expression"));
+    assert_eq!(errors[0].to_string(), "Unexpected trailing tokens in type expression (1:13)");
+}
+
+#[test]
+fn test_type_expression_errors() {
+    assert!(Parser::parse_type_expression_complete("").is_err());
+    assert!(Parser::parse_type_expression_complete("\"unterminated").is_err());
+}
+
+#[test]
+fn test_declaration_complete() {
+    let declaration = Parser::parse_declaration_complete("/// A level\n#[since=\"1.20\"]\nstruct Level { value: int };\n").unwrap();
+    match declaration {
+        Declaration::Struct(declaration) => {
+            assert_eq!(declaration.name, "Level");
+            assert_eq!(declaration.docs, ["A level"]);
+            assert_eq!(declaration.annotations.len(), 1);
+        }
+        other => panic!("Expected a struct, found {:?}", other),
+    }
+}
+
+#[test]
+fn test_declaration_trailing_tokens() {
+    let errors = Parser::parse_declaration_complete("type Count = int\nstruct Other {}").unwrap_err();
+    assert_eq!(errors[0].to_string(), "Unexpected trailing tokens in declaration (2:1)");
+
+    assert!(Parser::parse_declaration_complete("  \n").is_err());
+}
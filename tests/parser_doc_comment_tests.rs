@@ -0,0 +1,65 @@
+//! Tests for `///` doc comments attached to declarations, fields and enum variants
+
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::parser::{Declaration, StructMember};
+
+const MCDOC: &str = r#"
+/// A recipe.
+/// Second line.
+#[since="1.20"]
+struct Recipe {
+    /// The result item
+    result: string,
+    // Not documentation
+    count: int,
+}
+
+/// Kinds of things
+enum(string) Kind {
+    /// The first kind
+    First = "first",
+    Second = "second",
+}
+
+/// Alias
+type Alias = string
+
+/// Dispatched recipe
+dispatch minecraft:resource[recipe] to Recipe
+"#;
+
+#[test]
+fn test_docs_are_attached() {
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    assert_eq!(ast.declarations.len(), 4);
+
+    let Declaration::Struct(recipe) = &ast.declarations[0] else {
+        panic!("Expected struct declaration");
+    };
+    assert_eq!(recipe.docs, vec!["A recipe.", "Second line."]);
+    assert_eq!(recipe.annotations.len(), 1);
+
+    let fields: Vec<_> = recipe.members.iter().filter_map(|member| match member {
+        StructMember::Field(field) => Some(field),
+        _ => None,
+    }).collect();
+    assert_eq!(fields[0].docs, vec!["The result item"]);
+    assert!(fields[1].docs.is_empty());
+
+    let Declaration::Enum(kind) = &ast.declarations[1] else {
+        panic!("Expected enum declaration");
+    };
+    assert_eq!(kind.docs, vec!["Kinds of things"]);
+    assert_eq!(kind.variants[0].docs, vec!["The first kind"]);
+    assert!(kind.variants[1].docs.is_empty());
+
+    let Declaration::Type(alias) = &ast.declarations[2] else {
+        panic!("Expected type declaration");
+    };
+    assert_eq!(alias.docs, vec!["Alias"]);
+
+    let Declaration::Dispatch(dispatch) = &ast.declarations[3] else {
+        panic!("Expected dispatch declaration");
+    };
+    assert_eq!(dispatch.docs, vec!["Dispatched recipe"]);
+}
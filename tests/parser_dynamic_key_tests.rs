@@ -0,0 +1,85 @@
+//! Tests for the key types of dynamic struct fields
+
+use voxel_rsmcdoc::parser::{Declaration, DynamicFieldDeclaration, StructMember, TypeConstraints, TypeExpression};
+use voxel_rsmcdoc::parse_mcdoc;
+
+/// Dynamic fields of the first declaration, a struct
+fn dynamic_fields<'a>(ast: &'a voxel_rsmcdoc::McDocFile<'a>) -> Vec<&'a DynamicFieldDeclaration<'a>> {
+    let Declaration::Struct(struct_decl) = &ast.declarations[0] else {
+        panic!("Expected struct declaration");
+    };
+    struct_decl.members.iter()
+        .filter_map(|member| match member {
+            StructMember::DynamicField(field) => Some(field),
+            _ => None,
+        })
+        .collect()
+}
+
+fn at_least_one() -> TypeConstraints {
+    TypeConstraints { min: Some(1.0), max: None, min_exclusive: false, max_exclusive: false }
+}
+
+#[test]
+fn test_annotated_key_with_constraints() {
+    let ast = parse_mcdoc(r#"
+struct Serializers {
+    [#[id="recipe_serializer"] string @ 1..]: Serializer,
+    [#[id(registry="item", tags="allowed")] string @ 1..]?: int,
+    after: boolean,
+}
+"#).expect("Parser should succeed");
+    let fields = dynamic_fields(&ast);
+    assert_eq!(fields.len(), 2);
+
+    for field in &fields {
+        let TypeExpression::Constrained { base_type, constraints } = &field.key_type else {
+            panic!("Expected constrained key, found {:?}", field.key_type);
+        };
+        assert_eq!(constraints, &at_least_one());
+        let TypeExpression::Annotated { annotations, type_expr } = base_type.as_ref() else {
+            panic!("Expected annotated key, found {:?}", base_type);
+        };
+        assert_eq!(annotations[0].name, "id");
+        assert_eq!(type_expr.as_ref(), &TypeExpression::Simple("string"));
+    }
+    assert_eq!(fields[0].value_type, TypeExpression::Simple("Serializer"));
+    assert!(!fields[0].optional);
+    assert!(fields[1].optional);
+}
+
+#[test]
+fn test_constrained_key_across_lines() {
+    let ast = parse_mcdoc("struct Tags {\n    [\n        #[id=\"item\"]\n        string @ 1..16\n    ]: int,\n}")
+        .expect("Parser should succeed");
+    let TypeExpression::Constrained { constraints, .. } = &dynamic_fields(&ast)[0].key_type else {
+        panic!("Expected constrained key");
+    };
+    assert_eq!(constraints.max, Some(16.0));
+}
+
+#[test]
+fn test_enum_keys() {
+    let ast = parse_mcdoc(r#"
+struct Slots {
+    [Slot]: int,
+    [enum(string) { Head = "head", Chest = "chest" }]?: string,
+}
+"#).expect("Parser should succeed");
+    let fields = dynamic_fields(&ast);
+    assert_eq!(fields[0].key_type, TypeExpression::Simple("Slot"));
+
+    let TypeExpression::Enum { name, base_type, variants } = &fields[1].key_type else {
+        panic!("Expected inline enum key, found {:?}", fields[1].key_type);
+    };
+    assert_eq!(*name, None);
+    assert_eq!(*base_type, Some("string"));
+    assert_eq!(variants.iter().map(|variant| variant.name).collect::<Vec<_>>(), ["Head", "Chest"]);
+    assert!(fields[1].optional);
+}
+
+#[test]
+fn test_key_is_not_an_array() {
+    let errors = parse_mcdoc("struct Tags { [string @ 1..][]: int }").unwrap_err();
+    assert!(errors[0].to_string().contains("Expected ':' after dynamic field key"), "{}", errors[0]);
+}
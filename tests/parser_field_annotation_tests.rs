@@ -0,0 +1,101 @@
+//! Tests for annotations of a field kept apart from the annotations of its type
+
+use serde_json::json;
+use voxel_rsmcdoc::parser::{FieldDeclaration, StructMember, TypeExpression};
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::{parse_mcdoc, printer, Declaration, ErrorCode};
+
+const MCDOC: &str = r#"
+struct Drop {
+    #[until="1.20"] item?: #[id="item"] string,
+    #[deprecated="1.19"] count?: #[since="1.19"] int @ 1..64,
+    name?: string,
+}
+
+dispatch minecraft:resource[drop] to Drop
+"#;
+
+fn with_field(source: &str, name: &str, check: impl FnOnce(&FieldDeclaration)) {
+    let file = parse_mcdoc(source).unwrap();
+    let Declaration::Struct(declaration) = &file.declarations[0] else {
+        panic!("Expected a struct");
+    };
+    let field = declaration.members.iter().find_map(|member| match member {
+        StructMember::Field(field) if field.name == name => Some(field),
+        _ => None,
+    });
+    check(field.unwrap_or_else(|| panic!("No field '{}'", name)))
+}
+
+fn names(annotations: &[voxel_rsmcdoc::parser::Annotation]) -> Vec<String> {
+    annotations.iter().map(|annotation| annotation.name.to_string()).collect()
+}
+
+#[test]
+fn test_field_and_type_annotations_split() {
+    with_field(MCDOC, "item", |field| {
+        assert_eq!(names(&field.annotations), ["until"]);
+        let TypeExpression::Annotated { annotations, type_expr } = &field.field_type else {
+            panic!("Expected an annotated type, found {:?}", field.field_type);
+        };
+        assert_eq!(names(annotations), ["id"]);
+        assert_eq!(**type_expr, TypeExpression::Simple("string"));
+    });
+
+    // Type annotations cover the whole type, constraints included
+    with_field(MCDOC, "count", |field| {
+        assert_eq!(names(&field.annotations), ["deprecated"]);
+        let TypeExpression::Annotated { annotations, type_expr } = &field.field_type else {
+            panic!("Expected an annotated type, found {:?}", field.field_type);
+        };
+        assert_eq!(names(annotations), ["since"]);
+        assert!(matches!(**type_expr, TypeExpression::Constrained { .. }));
+    });
+
+    with_field(MCDOC, "name", |field| {
+        assert!(field.annotations.is_empty());
+        assert_eq!(field.field_type, TypeExpression::Simple("string"));
+    });
+}
+
+#[test]
+fn test_printed_annotations_reparse_to_the_same_split() {
+    let file = parse_mcdoc(MCDOC).unwrap();
+    let printed = printer::print(&file);
+    assert!(printed.contains("item?: #[id=\"item\"] string"), "{}", printed);
+    assert_eq!(printer::print(&parse_mcdoc(&printed).unwrap()), printed);
+}
+
+#[test]
+fn test_validator_reads_each_annotation_from_its_place() {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("drop.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({ "entries": { "minecraft:stone": {} } })).unwrap();
+
+    // `#[id]` of the type gives the dependency, `#[until]` of the field gates it
+    let result = validator.validate_json(&json!({ "item": "minecraft:stone" }), "drop", Some("1.19"));
+    assert!(result.is_valid, "{:?}", result.errors);
+    assert_eq!(result.dependencies.len(), 1);
+    assert_eq!(result.dependencies[0].resource_location.as_ref(), "minecraft:stone");
+
+    let result = validator.validate_json(&json!({ "item": "minecraft:stone" }), "drop", Some("1.21"));
+    assert_eq!(result.errors[0].code, ErrorCode::UnavailableField);
+
+    // `#[deprecated]` of the field warns, `#[since]` of the type gates nothing
+    let result = validator.validate_json(&json!({ "count": 3 }), "drop", Some("1.19"));
+    assert!(result.is_valid, "{:?}", result.errors);
+    assert_eq!(result.warnings[0].code, ErrorCode::Deprecated);
+}
+
+#[test]
+fn test_field_level_id_is_not_a_dependency() {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source(
+        "drop.mcdoc".to_string(),
+        "dispatch minecraft:resource[drop] to struct { #[id=\"item\"] item: string }".to_string(),
+    ).unwrap();
+
+    let result = validator.validate_json(&json!({ "item": "minecraft:stone" }), "drop", None);
+    assert!(result.is_valid, "{:?}", result.errors);
+    assert!(result.dependencies.is_empty());
+}
@@ -1,5 +1,6 @@
 #[cfg(test)]
 use voxel_rsmcdoc::{lexer::Lexer, parser::Parser};
+use voxel_rsmcdoc::parser::{ImportPath, StructMember, TypeExpression};
 
 #[test]
 fn test_debug_tokens() {
@@ -41,7 +42,8 @@ dispatch minecraft:resource[test_recipe] to struct TestRecipe {
     
     let decl = &mcdoc_file.declarations[0];
     if let voxel_rsmcdoc::parser::Declaration::Dispatch(dispatch_decl) = decl {
-        if let voxel_rsmcdoc::parser::TypeExpression::Struct(members) = &dispatch_decl.target_type {
+        if let voxel_rsmcdoc::parser::TypeExpression::Struct { name, members } = &dispatch_decl.target_type {
+            assert_eq!(*name, Some("TestRecipe"));
             assert_eq!(members.len(), 2);
             if let voxel_rsmcdoc::parser::StructMember::Field(field) = &members[0] {
                 assert_eq!(field.name, "ingredient");
@@ -78,8 +80,15 @@ fn test_multiline_dispatch_targets() {
     match result {
         Ok(ast) => {
             assert_eq!(ast.declarations.len(), 1);
-            if let voxel_rsmcdoc::parser::Declaration::Dispatch(_dispatch_decl) = &ast.declarations[0] {
-                // Successfully parsed multiline dispatch
+            if let voxel_rsmcdoc::parser::Declaration::Dispatch(dispatch_decl) = &ast.declarations[0] {
+                use voxel_rsmcdoc::parser::DispatchTarget;
+                assert_eq!(dispatch_decl.targets, vec![
+                    DispatchTarget::Specific("axolotl_bucket"),
+                    DispatchTarget::Specific("cod_bucket"),
+                    DispatchTarget::Specific("salmon_bucket"),
+                    DispatchTarget::Specific("pufferfish_bucket"),
+                    DispatchTarget::Specific("tadpole_bucket"),
+                ]);
             } else {
                 panic!("Expected dispatch declaration");
             }
@@ -259,7 +268,7 @@ fn test_hypothese_4_trace_chat_type_exact() {
     
     println!("TRACE EXACT chat_type.mcdoc ligne 4:");
     for (i, token) in tokens.iter().enumerate() {
-        if i >= 10 && i <= 20 {  // Zone problématique ligne 4 colonne 9
+        if (10..=20).contains(&i) {  // Zone problématique ligne 4 colonne 9
             println!("  {}: {:?} at line {} col {}", i, token.token, token.position.line, token.position.column);
         }
     }
@@ -718,6 +727,21 @@ fn test_conditional_spread_with_version_annotations() {
                     .collect();
                 
                 assert_eq!(spreads.len(), 2, "Should have 2 conditional spreads");
+
+                // Each spread keeps its annotation and the members of its inline struct
+                let style_fields: Vec<(&str, bool)> = spreads.iter()
+                    .map(|spread| match spread.target.as_deref() {
+                        Some(voxel_rsmcdoc::parser::TypeExpression::Struct { members, .. }) => match &members[..] {
+                            [voxel_rsmcdoc::parser::StructMember::Field(field)] => {
+                                assert_eq!(field.name, "style");
+                                (spread.annotations[0].name, field.optional)
+                            }
+                            other => panic!("Expected a single style field, found {:?}", other),
+                        },
+                        other => panic!("Expected inline struct target, found {:?}", other),
+                    })
+                    .collect();
+                assert_eq!(style_fields, vec![("until", false), ("since", true)]);
             } else {
                 panic!("Expected struct declaration");
             }
@@ -726,6 +750,18 @@ fn test_conditional_spread_with_version_annotations() {
             panic!("Parser failed with errors: {:?}", errors);
         }
     }
+
+    // `style` is required before 1.19.1 and optional after
+    let mut validator = voxel_rsmcdoc::validator::DatapackValidator::new();
+    validator.load_mcdoc_source("chat_decoration.mcdoc".to_string(), input.to_string()).unwrap();
+    let json = serde_json::json!({ "translation_key": "chat.type.text", "parameters": [] });
+
+    let old = validator.validate_against_type(&json, "ChatDecoration", Some("1.19"));
+    assert_eq!(old.errors.len(), 1, "Unexpected errors: {:?}", old.errors);
+    assert_eq!(old.errors[0].message, "Missing required field 'style'");
+
+    let new = validator.validate_against_type(&json, "ChatDecoration", Some("1.20"));
+    assert!(new.is_valid, "Validation failed: {:?}", new.errors);
 }
 
 #[test]
@@ -986,4 +1022,137 @@ struct Test {
 
 
 
- 
\ No newline at end of file
+ 
+#[test]
+fn test_dispatch_fallback_targets() {
+    let content = r#"dispatch minecraft:recipe_serializer[%unknown, %none, "custom:key"] to struct {}"#;
+
+    let mut lexer = Lexer::new(content);
+    let tokens = lexer.tokenize().expect("Tokenization should succeed");
+    let ast = Parser::new(tokens).parse().expect("Parsing should succeed");
+
+    if let voxel_rsmcdoc::parser::Declaration::Dispatch(dispatch_decl) = &ast.declarations[0] {
+        use voxel_rsmcdoc::parser::DispatchTarget;
+        assert_eq!(dispatch_decl.targets, vec![
+            DispatchTarget::Unknown,
+            DispatchTarget::None,
+            DispatchTarget::Specific("custom:key"),
+        ]);
+    } else {
+        panic!("Expected dispatch declaration");
+    }
+}
+
+#[test]
+fn test_chat_type_union_branch_annotations() {
+    use voxel_rsmcdoc::parser::{Declaration, StructMember, TypeExpression};
+
+    let content = r#"struct ChatType {
+    chat?: (
+        #[until="1.19.1"] TextDisplay |
+        #[since="1.19.1"]
+        // Moved to a decoration in 1.19.1
+        ChatDecoration |
+    ),
+}"#;
+
+    let tokens = Lexer::new(content).tokenize().expect("Tokenization should succeed");
+    let ast = Parser::new(tokens).parse().expect("Parsing should succeed");
+
+    let Declaration::Struct(struct_decl) = &ast.declarations[0] else {
+        panic!("Expected struct declaration");
+    };
+    let StructMember::Field(field) = &struct_decl.members[0] else {
+        panic!("Expected field");
+    };
+    let TypeExpression::Union(branches) = &field.field_type else {
+        panic!("Expected union, found {:?}", field.field_type);
+    };
+
+    let attached: Vec<(&str, &str)> = branches.iter()
+        .map(|branch| match branch {
+            TypeExpression::Annotated { annotations, type_expr } => {
+                assert_eq!(annotations.len(), 1);
+                match type_expr.as_ref() {
+                    TypeExpression::Simple(name) => (annotations[0].name, *name),
+                    other => panic!("Expected simple type, found {:?}", other),
+                }
+            }
+            other => panic!("Expected annotated branch, found {:?}", other),
+        })
+        .collect();
+    assert_eq!(attached, vec![("until", "TextDisplay"), ("since", "ChatDecoration")]);
+}
+
+#[test]
+fn test_annotations_separated_by_newlines() {
+    use voxel_rsmcdoc::parser::{Declaration, StructMember, TypeExpression};
+
+    let content = r#"struct Test {
+    #[since="1.20"]
+
+    #[deprecated="1.21"]
+    field: #[id="item"]
+        string,
+    list: [
+        #[id="block"]
+        string
+    ],
+}"#;
+
+    let tokens = Lexer::new(content).tokenize().expect("Tokenization should succeed");
+    let ast = Parser::new(tokens).parse().expect("Parsing should succeed");
+
+    let Declaration::Struct(struct_decl) = &ast.declarations[0] else {
+        panic!("Expected struct declaration");
+    };
+    let StructMember::Field(field) = &struct_decl.members[0] else {
+        panic!("Expected field");
+    };
+    let names: Vec<&str> = field.annotations.iter().map(|a| a.name).collect();
+    assert_eq!(names, vec!["since", "deprecated"]);
+    match &field.field_type {
+        TypeExpression::Annotated { annotations, type_expr } => {
+            assert_eq!(annotations[0].name, "id");
+            assert_eq!(**type_expr, TypeExpression::Simple("string"));
+        }
+        other => panic!("Expected annotated field type, found {:?}", other),
+    }
+
+    let StructMember::Field(list) = &struct_decl.members[1] else {
+        panic!("Expected field");
+    };
+    match &list.field_type {
+        TypeExpression::Array { element_type, .. } => match element_type.as_ref() {
+            TypeExpression::Annotated { annotations, type_expr } => {
+                assert_eq!(annotations[0].name, "id");
+                assert_eq!(**type_expr, TypeExpression::Simple("string"));
+            }
+            other => panic!("Expected annotated element, found {:?}", other),
+        },
+        other => panic!("Expected array, found {:?}", other),
+    }
+}
+
+#[test]
+fn test_absolute_path_spread_keeps_segments() {
+    let content = r#"struct Spawner {
+    ...::java::data::util::MobCategory,
+    ...super::super::util::Weighted,
+}"#;
+
+    let ast = voxel_rsmcdoc::parse_mcdoc(content).expect("Parsing should succeed");
+    let voxel_rsmcdoc::parser::Declaration::Struct(struct_decl) = &ast.declarations[0] else {
+        panic!("Expected struct declaration");
+    };
+    let spread_path = |index: usize| match &struct_decl.members[index] {
+        StructMember::Spread(spread) => match spread.target.as_deref() {
+            Some(TypeExpression::Reference(path)) => path.clone(),
+            other => panic!("Expected an import path target, got {:?}", other),
+        },
+        other => panic!("Expected a spread, got {:?}", other),
+    };
+
+    assert_eq!(spread_path(0), ImportPath::Absolute(vec!["java", "data", "util", "MobCategory"]));
+    assert_eq!(spread_path(1), ImportPath::Relative { supers: 2, segments: vec!["util", "Weighted"] });
+}
@@ -21,7 +21,7 @@ dispatch minecraft:resource[test_instance] to struct TestInstance {
         // DEBUG: Afficher les tokens pour voir ce qui est parsé
         println!("🔍 TOKENS:");
         for (i, token) in tokens.iter().enumerate() {
-            if i >= 15 && i <= 25 {  // Autour de la ligne problématique
+            if (15..=25).contains(&i) {  // Autour de la ligne problématique
                 println!("  {}: {:?}", i, token);
             }
         }
@@ -141,8 +141,16 @@ struct Test {
         let mut parser = Parser::new(tokens);
         
         let result = parser.parse();
-        // Ce test doit passer - les commentaires doivent être ignorés
         assert!(result.is_ok(), "Triple slash comments should be parsed correctly");
+
+        let ast = result.unwrap();
+        let voxel_rsmcdoc::parser::Declaration::Struct(decl) = &ast.declarations[0] else {
+            panic!("Expected struct declaration");
+        };
+        let voxel_rsmcdoc::parser::StructMember::Field(field) = &decl.members[0] else {
+            panic!("Expected field");
+        };
+        assert_eq!(field.docs, vec!["This is a doc comment"]);
     }
 
     // Test H4: Cas simplifié du problème gametest
@@ -0,0 +1,88 @@
+//! Tests for the type arguments of generic types
+
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::parser::{Declaration, DynamicReferenceType, PathSegment, SpreadExpression, TypeExpression};
+
+/// Type of the first declaration, a type alias
+fn alias_type(input: &str) -> TypeExpression<'_> {
+    let ast = parse_mcdoc(input).unwrap_or_else(|errors| panic!("{} should parse: {:?}", input, errors));
+    match ast.declarations.into_iter().next() {
+        Some(Declaration::Type(declaration)) => declaration.type_expr,
+        other => panic!("Expected type declaration, found {:?}", other),
+    }
+}
+
+fn generic<'a>(type_expr: &'a TypeExpression<'a>) -> (&'a str, &'a [TypeExpression<'a>]) {
+    match type_expr {
+        TypeExpression::Generic { name, type_args } => (name, type_args),
+        other => panic!("Expected generic type, found {:?}", other),
+    }
+}
+
+fn dispatch_reference<'a>(type_expr: &'a TypeExpression<'a>) -> &'a SpreadExpression<'a> {
+    match type_expr {
+        TypeExpression::Spread(spread) => spread,
+        other => panic!("Expected dispatch reference, found {:?}", other),
+    }
+}
+
+#[test]
+fn test_dynamic_reference_argument() {
+    let type_expr = alias_type("type Page = Filterable<minecraft:text_component[[type]]>");
+    let (name, args) = generic(&type_expr);
+    assert_eq!(name, "Filterable");
+    assert_eq!(args.len(), 1);
+
+    let spread = dispatch_reference(&args[0]);
+    assert_eq!((spread.namespace, spread.registry), ("minecraft", "text_component"));
+    assert_eq!(spread.dynamic_key.as_ref().unwrap().reference, DynamicReferenceType::Field("type"));
+}
+
+#[test]
+fn test_dynamic_reference_argument_among_others() {
+    let type_expr = alias_type("type Entry = Pair<minecraft:loot_function[[%parent.function]], int>");
+    let (_, args) = generic(&type_expr);
+    assert_eq!(args.len(), 2);
+    assert_eq!(
+        dispatch_reference(&args[0]).dynamic_key.as_ref().unwrap().reference,
+        DynamicReferenceType::Path(vec![PathSegment::Parent, PathSegment::Field("function")]),
+    );
+    assert_eq!(args[1], TypeExpression::Simple("int"));
+}
+
+#[test]
+fn test_nested_generics_closed_together() {
+    for input in ["type Nested = A<B<C>>", "type Nested = A<B<C> >", "type Nested = A<\n    B<C>,\n>"] {
+        let type_expr = alias_type(input);
+        let (name, args) = generic(&type_expr);
+        assert_eq!(name, "A");
+        let (inner, inner_args) = generic(&args[0]);
+        assert_eq!(inner, "B");
+        assert_eq!(inner_args, [TypeExpression::Simple("C")]);
+    }
+
+    let type_expr = alias_type("type Deep = A<B<minecraft:x[[%key]]>>[]");
+    let TypeExpression::Array { element_type, .. } = &type_expr else {
+        panic!("Expected array, found {:?}", type_expr);
+    };
+    let (_, args) = generic(element_type);
+    let (_, inner_args) = generic(&args[0]);
+    assert_eq!(dispatch_reference(&inner_args[0]).dynamic_key.as_ref().unwrap().reference, DynamicReferenceType::SpecialKey("key"));
+}
+
+#[test]
+fn test_constrained_and_union_arguments() {
+    let type_expr = alias_type("type Limited = A<int @ 1..3, #[id=\"item\"] string | boolean>");
+    let (_, args) = generic(&type_expr);
+    assert_eq!(args.len(), 2);
+    let TypeExpression::Constrained { base_type, constraints } = &args[0] else {
+        panic!("Expected constrained argument, found {:?}", args[0]);
+    };
+    assert_eq!(**base_type, TypeExpression::Simple("int"));
+    assert_eq!((constraints.min, constraints.max), (Some(1.0), Some(3.0)));
+    assert!(matches!(&args[1], TypeExpression::Union(branches) if branches.len() == 2));
+
+    let type_expr = alias_type("type Nested = A<B<int @ 1..>>");
+    let (_, args) = generic(&type_expr);
+    assert!(matches!(generic(&args[0]).1, [TypeExpression::Constrained { .. }]));
+}
@@ -2,7 +2,7 @@
 //! Issues observées dans equipment.mcdoc avec Layer<T> et WingsLayer<T>
 
 use voxel_rsmcdoc::lexer::Lexer;
-use voxel_rsmcdoc::parser::Parser;
+use voxel_rsmcdoc::parser::{Parser, TypeExpression};
 
 #[test]
 fn test_generic_type_declaration() {
@@ -130,9 +130,23 @@ type WingsLayer<T> = struct {
     match result {
         Ok(file) => {
             assert_eq!(file.declarations.len(), 1, "Should parse 1 type declaration");
+
+            let voxel_rsmcdoc::parser::Declaration::Type(type_decl) = &file.declarations[0] else {
+                panic!("Expected type declaration");
+            };
+            let TypeExpression::Struct { members, .. } = &type_decl.type_expr else {
+                panic!("Expected struct, found {:?}", type_decl.type_expr);
+            };
+            let voxel_rsmcdoc::parser::StructMember::Spread(spread) = &members[0] else {
+                panic!("Expected spread, found {:?}", members[0]);
+            };
+            assert_eq!(spread.target.as_deref(), Some(&TypeExpression::Generic {
+                name: "Layer",
+                type_args: vec![TypeExpression::Simple("T")],
+            }));
         },
         Err(errors) => {
             panic!("Should not fail with spread generics after fix. Errors: {:?}", errors);
         }
     }
-} 
\ No newline at end of file
+}
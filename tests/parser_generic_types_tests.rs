@@ -17,7 +17,7 @@ fn test_generic_type_conditions_simple() {
                     assert_eq!(type_decl.name, "Conditions");
                     // Vérifier que c'est un type générique
                     match &type_decl.type_expr {
-                        TypeExpression::Struct(_) => {
+                        TypeExpression::Struct { .. } => {
                             // Should be ok
                         }
                         _ => panic!("Expected struct type expression")
@@ -61,8 +61,8 @@ dispatch minecraft:trigger[allay_drop_item_on_block] to Conditions<struct AllayD
             // Collect expected errors to validate hypotheses
             let syntax_errors: Vec<_> = errors.iter()
                 .filter_map(|e| match e {
-                    voxel_rsmcdoc::error::ParseError::Syntax { expected, found, pos } => {
-                        Some((expected.clone(), found.clone(), pos.clone()))
+                    voxel_rsmcdoc::error::ParseError::Syntax { expected, found, pos, .. } => {
+                        Some((expected.clone(), found.clone(), *pos))
                     }
                     _ => None
                 })
@@ -0,0 +1,58 @@
+//! Tests for best-effort parsing that keeps the declarations around errors
+
+use voxel_rsmcdoc::{parse_mcdoc, parse_mcdoc_lenient, Declaration};
+
+const BROKEN: &str = r#"
+use ::java::util::Text
+
+struct First {
+    name: string,
+}
+
+dispatch minecraft:resource[broken] to struct Broken {
+    value: ,
+}
+
+struct Second {
+    count: int,
+}
+"#;
+
+fn struct_names<'a>(declarations: &'a [Declaration<'a>]) -> Vec<&'a str> {
+    declarations.iter()
+        .filter_map(|declaration| match declaration {
+            Declaration::Struct(struct_decl) => Some(struct_decl.name),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_lenient_parse_keeps_valid_declarations() {
+    let (file, errors) = parse_mcdoc_lenient(BROKEN);
+
+    assert!(!errors.is_empty());
+    assert_eq!(file.imports.len(), 1);
+    assert_eq!(struct_names(&file.declarations), ["First", "Second"]);
+}
+
+#[test]
+fn test_strict_parse_reports_the_same_errors() {
+    let (_, lenient_errors) = parse_mcdoc_lenient(BROKEN);
+    let strict_errors = parse_mcdoc(BROKEN).unwrap_err();
+    assert_eq!(strict_errors, lenient_errors);
+}
+
+#[test]
+fn test_lenient_parse_of_valid_file() {
+    let (file, errors) = parse_mcdoc_lenient("struct Only { a: int }");
+    assert!(errors.is_empty());
+    assert_eq!(struct_names(&file.declarations), ["Only"]);
+}
+
+#[test]
+fn test_lenient_parse_with_lexer_error() {
+    let (file, errors) = parse_mcdoc_lenient("struct Broken { name: \"unterminated }");
+    assert_eq!(errors.len(), 1);
+    assert!(file.declarations.is_empty());
+}
@@ -77,4 +77,26 @@ struct TestUnion {
     
     let result = parser.parse();
     assert!(result.is_ok(), "Parser should handle literal constraints in unions: {:?}", result.err());
-} 
\ No newline at end of file
+} 
+#[test]
+fn test_number_literal_unions() {
+    use voxel_rsmcdoc::parser::{LiteralValue, TypeExpression};
+
+    let literals = |input: &str| -> Vec<LiteralValue<'static>> {
+        match Parser::parse_type_expression_complete(input).expect("Union should parse") {
+            TypeExpression::Union(types) => types.into_iter().map(|branch| match branch {
+                TypeExpression::Literal(LiteralValue::Number(n)) => LiteralValue::Number(n),
+                TypeExpression::Literal(LiteralValue::Boolean(b)) => LiteralValue::Boolean(b),
+                other => panic!("Expected a number or boolean literal, found {:?}", other),
+            }).collect(),
+            other => panic!("Expected a union, found {:?}", other),
+        }
+    };
+
+    let numbers = |values: &[f64]| values.iter().map(|n| LiteralValue::Number(*n)).collect::<Vec<_>>();
+    assert_eq!(literals("(0 | 90 | 180 | 270)"), numbers(&[0.0, 90.0, 180.0, 270.0]));
+    assert_eq!(literals("0 | 90 | 180 | 270"), numbers(&[0.0, 90.0, 180.0, 270.0]));
+    assert_eq!(literals("-1 | 0 | 1.5"), numbers(&[-1.0, 0.0, 1.5]));
+    assert_eq!(literals("(\n\t| -90\n\t| 90\n)"), numbers(&[-90.0, 90.0]));
+    assert_eq!(literals("true | false"), [LiteralValue::Boolean(true), LiteralValue::Boolean(false)]);
+}
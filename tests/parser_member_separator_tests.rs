@@ -0,0 +1,41 @@
+//! Tests for separators between struct members and in type lists
+
+use voxel_rsmcdoc::{parse_mcdoc, Declaration, ParseError, SourcePos};
+
+fn member_count(source: &str) -> usize {
+    let file = parse_mcdoc(source).unwrap();
+    match &file.declarations[0] {
+        Declaration::Struct(declaration) => declaration.members.len(),
+        other => panic!("Expected a struct, found {:?}", other),
+    }
+}
+
+#[test]
+fn test_newline_separated_members() {
+    let source = "struct Item {\n    id: string\n    count?: int @ 1..64 // amount\n    [string]: int\n    ...Base\n}";
+    assert_eq!(member_count(source), 4);
+}
+
+#[test]
+fn test_trailing_commas() {
+    assert_eq!(member_count("struct Item { id: string, count: int, }"), 2);
+    assert_eq!(member_count("struct Item {\n    id: string,\n    ...Base,\n}"), 2);
+
+    parse_mcdoc("type Pair<A, B,> = struct { first: A, second: B }").unwrap();
+    parse_mcdoc("type Entry = Pair<int, string,>").unwrap();
+    parse_mcdoc("dispatch minecraft:resource[a, b,] to struct Item {}").unwrap();
+}
+
+#[test]
+fn test_missing_comma_is_reported_after_previous_member() {
+    let source = "struct Item {\n    id: string count: int,\n}";
+    let errors = parse_mcdoc(source).unwrap_err();
+
+    match &errors[0] {
+        ParseError::Syntax { expected, pos, .. } => {
+            assert_eq!(expected, "Expected ',' after struct member");
+            assert_eq!(*pos, SourcePos::new(2, 15, 28));
+        }
+        other => panic!("Expected a syntax error, found {:?}", other),
+    }
+}
@@ -25,8 +25,7 @@ fn test_hypothesis_1_nested_array_constraints() {
     }
     
     // 2. PARSING DU TYPE
-    let mut parser = Parser::new(tokens);
-    let type_result = parser.parse_type_expression();
+    let type_result = Parser::parse_type_expression_complete(input);
     
     println!("🔍 Résultat parsing type: {:?}", type_result);
     
@@ -89,8 +88,7 @@ fn test_hypothesis_2_negative_numbers_in_constraints() {
     
     assert!(negative_number_found, "❌ Nombre négatif -80 pas trouvé dans les tokens");
     
-    let mut parser = Parser::new(tokens);
-    let type_result = parser.parse_type_expression();
+    let type_result = Parser::parse_type_expression_complete(input);
     
     println!("🔍 Résultat parsing type avec nombres négatifs: {:?}", type_result);
     
@@ -158,21 +156,21 @@ fn test_trace_parsing_step_by_step() {
     }
     
     // ÉTAPE 2: Parsing avec traçage
-    let mut parser = Parser::new(tokens);
-    
-    // Tenter de parser comme type expression
-    let type_result = parser.parse_type_expression();
+    // Tenter de parser comme type expression, jusqu'à la fin de l'entrée
+    let type_result = Parser::parse_type_expression_complete(input);
     
-    println!("🔍 ÉTAPE 2 - Résultat parse_type_expression: {:?}", type_result);
+    println!("🔍 ÉTAPE 2 - Résultat parse_type_expression_complete: {:?}", type_result);
     
     // Analyser où exactement ça échoue
-    if let Err(ref error) = type_result {
+    if let Err(ref errors) = type_result {
         println!("❌ ERREUR DÉTAILLÉE:");
-        println!("  Message: {:?}", error);
-        if let Some(pos) = error.position() {
-            println!("  Position: Ligne {}, Colonne {}", pos.line, pos.column);
-        } else {
-            println!("  Position: Non disponible");
+        for error in errors {
+            println!("  Message: {:?}", error);
+            if let Some(pos) = error.position() {
+                println!("  Position: Ligne {}, Colonne {}", pos.line, pos.column);
+            } else {
+                println!("  Position: Non disponible");
+            }
         }
     }
 }
@@ -187,11 +185,7 @@ fn test_array_2d_syntax_validation() {
     for (name, input) in [("simple", simple_case), ("complex", complex_case)] {
         println!("🧪 TEST ARRAY 2D {} - Input: {}", name, input);
         
-        let mut lexer = Lexer::new(input);
-        let tokens = lexer.tokenize().expect("Tokenisation should work");
-        
-        let mut parser = Parser::new(tokens);
-        let result = parser.parse_type_expression();
+        let result = Parser::parse_type_expression_complete(input);
         
         println!("🔍 Résultat {}: {:?}", name, result);
         
@@ -199,7 +193,7 @@ fn test_array_2d_syntax_validation() {
             Ok(ast) => println!("✅ {} parsed successfully: {:?}", name, ast),
             Err(error) => {
                 println!("❌ {} failed: {:?}", name, error);
-                assert!(false, "Array 2D syntax should parse correctly for {}", name);
+                panic!("Array 2D syntax should parse correctly for {}", name);
             }
         }
     }
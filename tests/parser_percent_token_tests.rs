@@ -107,4 +107,31 @@ fn test_lexer_percent_token() {
     // Chercher le token Percent
     let has_percent = tokens.iter().any(|t| matches!(t.token, voxel_rsmcdoc::lexer::Token::Percent));
     assert!(has_percent, "Should contain Percent token");
-} 
\ No newline at end of file
+} 
+#[test]
+fn test_dynamic_reference_paths() {
+    use voxel_rsmcdoc::parser::{DynamicReferenceType, PathSegment, TypeExpression};
+
+    let reference = |input: &'static str| {
+        match Parser::parse_type_expression_complete(input).expect("Parsing should succeed") {
+            TypeExpression::Spread(spread) => spread.dynamic_key.expect("Expected dynamic reference").reference,
+            other => panic!("Expected spread, found {:?}", other),
+        }
+    };
+
+    assert_eq!(reference("mcdoc:test[[type]]"), DynamicReferenceType::Field("type"));
+    assert_eq!(reference("mcdoc:test[[%key]]"), DynamicReferenceType::SpecialKey("key"));
+    assert_eq!(reference("mcdoc:block_item_states[[%parent.id]]"), DynamicReferenceType::Path(vec![
+        PathSegment::Parent,
+        PathSegment::Field("id"),
+    ]));
+    assert_eq!(reference("minecraft:block[[%parent.output_state.Name]]"), DynamicReferenceType::Path(vec![
+        PathSegment::Parent,
+        PathSegment::Field("output_state"),
+        PathSegment::Field("Name"),
+    ]));
+    assert_eq!(reference("mcdoc:test[[..type]]"), DynamicReferenceType::Path(vec![
+        PathSegment::Parent,
+        PathSegment::Field("type"),
+    ]));
+}
@@ -0,0 +1,35 @@
+//! Tests for struct fields whose names are quoted strings
+
+use voxel_rsmcdoc::lexer::Lexer;
+use voxel_rsmcdoc::parser::{Declaration, Parser, StructMember, TypeExpression};
+
+#[test]
+fn test_quoted_namespaced_field_names() {
+    let input = r#"
+struct Components {
+    "minecraft:stores_enchantments"?: Enchantments,
+    "fabric:load_conditions": [string],
+    plain: int,
+}
+"#;
+    let tokens = Lexer::new(input).tokenize().expect("Lexer should tokenize successfully");
+    let ast = Parser::new(tokens).parse().expect("Parser should succeed");
+
+    let Declaration::Struct(struct_decl) = &ast.declarations[0] else {
+        panic!("Expected struct declaration");
+    };
+    let fields: Vec<(&str, bool)> = struct_decl.members.iter()
+        .map(|member| match member {
+            StructMember::Field(field) => (field.name, field.optional),
+            other => panic!("Expected field, found {:?}", other),
+        })
+        .collect();
+    assert_eq!(fields, vec![
+        ("minecraft:stores_enchantments", true),
+        ("fabric:load_conditions", false),
+        ("plain", false),
+    ]);
+
+    let StructMember::Field(field) = &struct_decl.members[0] else { unreachable!() };
+    assert_eq!(field.field_type, TypeExpression::Simple("Enchantments"));
+}
@@ -0,0 +1,104 @@
+//! Tests for the start and end positions of tokens, errors and declarations
+
+use voxel_rsmcdoc::{parse_mcdoc, Declaration, Lexer, McDocError, ParseError, SourcePos, Token};
+use voxel_rsmcdoc::parser::StructMember;
+
+#[test]
+fn test_tokens_have_end_positions() {
+    let source = "struct Item {\n    id: string,\n}";
+    let tokens = Lexer::new(source).tokenize().unwrap();
+
+    let string_token = tokens.iter().find(|token| token.token == Token::Identifier("string")).unwrap();
+    assert_eq!((string_token.position.line, string_token.position.column), (2, 9));
+    assert_eq!((string_token.end.line, string_token.end.column), (2, 15));
+    assert_eq!(string_token.span().slice(source), "string");
+}
+
+#[test]
+fn test_syntax_error_reports_start_and_end() {
+    // `int` after the field name is the error, spanning the whole identifier
+    let source = "struct Item {\n    id: string,\n    count int,\n}";
+    let errors = parse_mcdoc(source).unwrap_err();
+
+    match &errors[0] {
+        ParseError::Syntax { pos, end, .. } => {
+            assert_eq!(*pos, SourcePos::new(3, 11, 40));
+            assert_eq!(*end, Some(SourcePos::new(3, 14, 43)));
+        }
+        other => panic!("Expected a syntax error, found {:?}", other),
+    }
+
+    let error = McDocError::from(errors[0].clone());
+    assert_eq!((error.line, error.column), (Some(3), Some(11)));
+    assert_eq!((error.end_line, error.end_column), (Some(3), Some(14)));
+    let serialized = serde_json::to_value(&error).unwrap();
+    assert_eq!(serialized["endColumn"], 14);
+}
+
+#[test]
+fn test_declaration_spans() {
+    let source = "#[since=\"1.20\"]\nstruct Item {\n    id: #[id=\"item\"] string,\n    count?: int @ 1..64,\n}\n";
+    let file = parse_mcdoc(source).unwrap();
+
+    let Declaration::Struct(item) = &file.declarations[0] else {
+        panic!("Expected a struct");
+    };
+    assert_eq!(item.span().slice(source), "struct Item {\n    id: #[id=\"item\"] string,\n    count?: int @ 1..64,\n}");
+    assert_eq!(item.annotations[0].span().slice(source), "#[since=\"1.20\"]");
+
+    let fields: Vec<&str> = item.members.iter()
+        .filter_map(|member| match member {
+            StructMember::Field(field) => Some(field.span().slice(source)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(fields, ["id: #[id=\"item\"] string", "count?: int @ 1..64"]);
+}
+
+#[test]
+fn test_error_offsets_after_multibyte_characters() {
+    // `é` is one column but two bytes
+    let source = "struct Item {\n    café int,\n}";
+    let errors = parse_mcdoc(source).unwrap_err();
+
+    match &errors[0] {
+        ParseError::Syntax { pos, end, .. } => {
+            assert_eq!(*pos, SourcePos::new(2, 10, 24));
+            assert_eq!(*end, Some(SourcePos::new(2, 13, 27)));
+        }
+        other => panic!("Expected a syntax error, found {:?}", other),
+    }
+    let range = errors[0].byte_range().unwrap();
+    assert_eq!(&source[range], "int");
+
+    let error = McDocError::from(errors[0].clone());
+    assert_eq!((error.offset, error.end_offset), (Some(24), Some(27)));
+    let serialized = serde_json::to_value(&error).unwrap();
+    assert_eq!(serialized["endOffset"], 27);
+
+    let source = "type Name = \"é\\q\"";
+    let errors = parse_mcdoc(source).unwrap_err();
+    assert_eq!(errors[0].position(), Some(SourcePos::new(1, 15, 15)));
+    assert_eq!(errors[0].byte_range(), Some(15..15));
+    assert_eq!(&source[15..], "\\q\"");
+}
+
+#[test]
+fn test_utf16_columns_after_emoji() {
+    // `🎉` is one character, two UTF-16 code units and four bytes
+    let source = "/// Party 🎉\nstruct Item { #[label=\"🎉\"] id int }";
+    let tokens = Lexer::new(source).tokenize().unwrap();
+    let struct_token = tokens.iter().find(|token| token.token == Token::Struct).unwrap();
+    assert_eq!((struct_token.position.column, struct_token.position.column_utf16), (1, 1));
+
+    let errors = parse_mcdoc(source).unwrap_err();
+    let pos = errors[0].position().unwrap();
+    assert_eq!((pos.line, pos.column, pos.column_utf16), (2, 31, 32));
+    assert_eq!(&source[errors[0].byte_range().unwrap()], "int");
+
+    let error = McDocError::from(errors[0].clone());
+    assert_eq!((error.column, error.column_utf16), (Some(31), Some(32)));
+    assert_eq!(error.end_column_utf16, Some(35));
+    let serialized = serde_json::to_value(&error).unwrap();
+    assert_eq!(serialized["columnUtf16"], 32);
+}
@@ -239,11 +239,12 @@ fn test_array_with_annotations() {
             assert!(field.optional);
             // Should be an array type
             if let voxel_rsmcdoc::parser::TypeExpression::Array { element_type, .. } = &field.field_type {
-                // Element should be string (annotation is parsed but not stored in TypeExpression for now)
-                if let voxel_rsmcdoc::parser::TypeExpression::Simple(type_name) = element_type.as_ref() {
-                    assert_eq!(*type_name, "string");
+                // Element should be string, wrapped with its annotation
+                if let voxel_rsmcdoc::parser::TypeExpression::Annotated { annotations, type_expr } = element_type.as_ref() {
+                    assert_eq!(annotations[0].name, "regex_pattern");
+                    assert_eq!(**type_expr, voxel_rsmcdoc::parser::TypeExpression::Simple("string"));
                 } else {
-                    panic!("Expected simple type 'string' as array element");
+                    panic!("Expected annotated type 'string' as array element");
                 }
             } else {
                 panic!("Expected array type for renderer field");
@@ -0,0 +1,80 @@
+//! Tests for unions whose pipes are on a different line than their branches
+
+use voxel_rsmcdoc::lexer::Lexer;
+use voxel_rsmcdoc::parser::{Declaration, Parser, TypeExpression};
+
+fn parse_type_alias(input: &str) -> TypeExpression<'_> {
+    let tokens = Lexer::new(input).tokenize().expect("Lexer should tokenize successfully");
+    let ast = Parser::new(tokens).parse().expect("Parser should succeed");
+    match ast.declarations.into_iter().next() {
+        Some(Declaration::Type(type_decl)) => type_decl.type_expr,
+        other => panic!("Expected type declaration, found {:?}", other),
+    }
+}
+
+#[test]
+fn test_leading_pipes_on_new_lines() {
+    let type_expr = parse_type_alias(r#"
+type Choice = (
+    | TypeA
+    | TypeB
+    | TypeC
+)
+"#);
+
+    assert_eq!(type_expr, TypeExpression::Union(vec![
+        TypeExpression::Simple("TypeA"),
+        TypeExpression::Simple("TypeB"),
+        TypeExpression::Simple("TypeC"),
+    ]));
+}
+
+#[test]
+fn test_pipe_on_next_line_without_parentheses() {
+    let type_expr = parse_type_alias(r#"
+type Choice = TypeA
+    | TypeB
+
+struct After {}
+"#);
+
+    assert_eq!(type_expr, TypeExpression::Union(vec![
+        TypeExpression::Simple("TypeA"),
+        TypeExpression::Simple("TypeB"),
+    ]));
+}
+
+#[test]
+fn test_trailing_pipe_before_closing_parenthesis() {
+    let type_expr = parse_type_alias(r#"
+type Choice = (
+    TypeA |
+    TypeB |
+)
+"#);
+
+    assert_eq!(type_expr, TypeExpression::Union(vec![
+        TypeExpression::Simple("TypeA"),
+        TypeExpression::Simple("TypeB"),
+    ]));
+}
+
+#[test]
+fn test_union_does_not_swallow_next_declaration() {
+    let input = r#"
+type Single = TypeA
+
+struct After {
+    value: (string
+        | int),
+}
+"#;
+    let tokens = Lexer::new(input).tokenize().expect("Lexer should tokenize successfully");
+    let ast = Parser::new(tokens).parse().expect("Parser should succeed");
+
+    assert_eq!(ast.declarations.len(), 2);
+    match &ast.declarations[0] {
+        Declaration::Type(type_decl) => assert_eq!(type_decl.type_expr, TypeExpression::Simple("TypeA")),
+        other => panic!("Expected type declaration, found {:?}", other),
+    }
+}
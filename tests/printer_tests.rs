@@ -0,0 +1,216 @@
+//! Tests for printing parsed MCDOC back to source
+
+use std::fs;
+use std::path::Path;
+use voxel_rsmcdoc::printer::print;
+use voxel_rsmcdoc::parser::{Annotation, EnumVariant, SpreadExpression, StructMember};
+use voxel_rsmcdoc::{parse_mcdoc, Declaration, McDocFile, Position, TypeExpression};
+
+const CHAT_TYPE: &str = include_str!("dataset/mcdoc/data/chat_type.mcdoc");
+const GPU_WARNLIST: &str = include_str!("dataset/mcdoc/assets/gpu_warnlist.mcdoc");
+
+const FEATURES: &str = r#"
+use ::java::util::text::Text
+use super::super::item::{ItemStack, ItemPredicate as Predicate}
+
+/// A recipe with every kind of member
+#[since="1.20"]
+struct Recipe {
+    /// Shown in the book
+    #[id(registry="item", tags="allowed")]
+    icon?: string,
+    "minecraft:custom_data"?: int @ 0..<10,
+    type: #[id="recipe_serializer"] string,
+    pattern: [string] @ 1..3,
+    grid: [[int @ -1..1] @ 3] @ 3,
+    sizes: int[] @ 2..,
+    key: (string | struct Key { item: string } | (int @ 1<..5)),
+    lone: (Text |),
+    values: List<#[id="item"] string, (float @ ..1.5)>,
+    mode: ("a" | "b" | 3 | true),
+    [#[id="item"] string]?: ItemStack,
+    ...minecraft:recipe_serializer[[type]],
+    ...minecraft:recipe_serializer[[%parent.%key]],
+    ...super::RecipeBase,
+    ...Layer<int>,
+    #[until="1.21"]
+    ...struct {
+        legacy?: boolean,
+    },
+    block: minecraft:block_entity[furnace],
+    states: mcdoc:block_states,
+    inline: enum(string) { A = "a" },
+}
+
+enum(int) Count {
+    /// Nothing
+    Zero = 0,
+    #[since="1.21"]
+    Negative = -1,
+}
+
+enum Plain {
+    First,
+    Second,
+}
+
+type Pair<A, B,> = [(A | B)] @ 2
+
+#[deprecated]
+dispatch minecraft:resource[recipe, "worldgen/biome", %unknown, %none] to struct RecipeFile {}
+
+dispatch mcdoc:marker to Recipe
+"#;
+
+/// The file with every source position reset, to compare files printed differently
+fn without_positions(mut file: McDocFile) -> McDocFile {
+    for import in &mut file.imports {
+        import.position = Position::default();
+    }
+    for declaration in &mut file.declarations {
+        match declaration {
+            Declaration::Struct(struct_decl) => {
+                struct_decl.position = Position::default();
+                struct_decl.end = Position::default();
+                clear_annotations(&mut struct_decl.annotations);
+                clear_members(&mut struct_decl.members);
+            }
+            Declaration::Enum(enum_decl) => {
+                enum_decl.position = Position::default();
+                clear_annotations(&mut enum_decl.annotations);
+                clear_variants(&mut enum_decl.variants);
+            }
+            Declaration::Type(type_decl) => {
+                type_decl.position = Position::default();
+                clear_annotations(&mut type_decl.annotations);
+                clear_type(&mut type_decl.type_expr);
+            }
+            Declaration::Dispatch(dispatch) => {
+                dispatch.position = Position::default();
+                dispatch.source.position = Position::default();
+                clear_annotations(&mut dispatch.annotations);
+                clear_type(&mut dispatch.target_type);
+            }
+        }
+    }
+    file
+}
+
+fn clear_annotations(annotations: &mut [Annotation]) {
+    for annotation in annotations {
+        annotation.position = Position::default();
+        annotation.end = Position::default();
+    }
+}
+
+fn clear_variants(variants: &mut [EnumVariant]) {
+    for variant in variants {
+        variant.position = Position::default();
+        clear_annotations(&mut variant.annotations);
+    }
+}
+
+fn clear_members(members: &mut [StructMember]) {
+    for member in members {
+        match member {
+            StructMember::Field(field) => {
+                field.position = Position::default();
+                field.end = Position::default();
+                clear_annotations(&mut field.annotations);
+                clear_type(&mut field.field_type);
+            }
+            StructMember::DynamicField(dynamic_field) => {
+                dynamic_field.position = Position::default();
+                clear_annotations(&mut dynamic_field.annotations);
+                clear_type(&mut dynamic_field.key_type);
+                clear_type(&mut dynamic_field.value_type);
+            }
+            StructMember::Spread(spread) => clear_spread(spread),
+        }
+    }
+}
+
+fn clear_spread(spread: &mut SpreadExpression) {
+    spread.position = Position::default();
+    clear_annotations(&mut spread.annotations);
+    if let Some(dynamic_key) = &mut spread.dynamic_key {
+        dynamic_key.position = Position::default();
+    }
+    if let Some(target) = &mut spread.target {
+        clear_type(target);
+    }
+}
+
+fn clear_type(type_expr: &mut TypeExpression) {
+    match type_expr {
+        TypeExpression::Array { element_type, .. } => clear_type(element_type),
+        TypeExpression::Union(types) | TypeExpression::Generic { type_args: types, .. } => {
+            types.iter_mut().for_each(clear_type);
+        }
+        TypeExpression::Struct { members, .. } => clear_members(members),
+        TypeExpression::Spread(spread) => clear_spread(spread),
+        TypeExpression::Enum { variants, .. } => clear_variants(variants),
+        TypeExpression::Annotated { annotations, type_expr } => {
+            clear_annotations(annotations);
+            clear_type(type_expr);
+        }
+        TypeExpression::Constrained { base_type, .. } => clear_type(base_type),
+        TypeExpression::Simple(_) | TypeExpression::Reference(_) | TypeExpression::Literal(_) => {}
+    }
+}
+
+fn assert_round_trip(source: &str, name: &str) {
+    let file = parse_mcdoc(source).unwrap_or_else(|errors| panic!("{} should parse: {:?}", name, errors));
+    let printed = print(&file);
+    let reparsed = parse_mcdoc(&printed)
+        .unwrap_or_else(|errors| panic!("Printed {} should parse: {:?}\n{}", name, errors, printed));
+    assert!(without_positions(reparsed.clone()) == without_positions(file), "{} changed when printed:\n{}", name, printed);
+    assert_eq!(print(&reparsed), printed, "Printing {} is not stable", name);
+}
+
+#[test]
+fn test_round_trip_samples() {
+    assert_round_trip(CHAT_TYPE, "chat_type");
+    assert_round_trip(GPU_WARNLIST, "gpu_warnlist");
+}
+
+#[test]
+fn test_round_trip_features() {
+    assert_round_trip(FEATURES, "features");
+}
+
+#[test]
+fn test_round_trip_dataset() {
+    fn visit(directory: &Path, sources: &mut Vec<(String, String)>) {
+        for entry in fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                visit(&path, sources);
+            } else if path.extension().is_some_and(|extension| extension == "mcdoc") {
+                sources.push((path.display().to_string(), fs::read_to_string(&path).unwrap()));
+            }
+        }
+    }
+
+    let mut sources = Vec::new();
+    visit(Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/dataset/mcdoc")), &mut sources);
+    assert!(!sources.is_empty());
+    for (name, source) in &sources {
+        if parse_mcdoc(source).is_ok() {
+            assert_round_trip(source, name);
+        }
+    }
+}
+
+#[test]
+fn test_printed_layout() {
+    let file = parse_mcdoc(GPU_WARNLIST).unwrap();
+    assert_eq!(print(&file), "\
+#[since=\"1.16\"]
+dispatch minecraft:resource[gpu_warnlist] to struct GpuWarnlist {
+\trenderer?: [#[regex_pattern] string],
+\tversion?: [#[regex_pattern] string],
+\tvendor?: [#[regex_pattern] string],
+}
+");
+}
@@ -42,6 +42,41 @@ fn test_registry_from_json() {
     assert!(registry.contains_tag("minecraft:gems"));
 }
 
+#[test]
+fn test_registry_from_json_shapes() {
+    let array = Registry::from_json("item".to_string(), "1.21".to_string(), &json!(["stone", "minecraft:diamond"])).unwrap();
+    assert!(array.contains("stone"));
+    assert!(array.contains("minecraft:diamond"));
+
+    let vanilla = json!({
+        "default": "minecraft:air",
+        "entries": {
+            "minecraft:air": { "protocol_id": 0 },
+            "minecraft:stone": { "protocol_id": 1 }
+        },
+        "protocol_id": 7
+    });
+    let vanilla = Registry::from_json("item".to_string(), "1.21".to_string(), &vanilla).unwrap();
+    assert_eq!(vanilla.entries.len(), 2);
+    assert!(vanilla.contains("minecraft:stone"));
+
+    assert!(Registry::from_json("item".to_string(), "1.21".to_string(), &json!("minecraft:stone")).is_err());
+}
+
+#[test]
+fn test_load_registries_bundle() {
+    let mut manager = RegistryManager::new();
+    let bundle = json!({
+        "minecraft:item": { "entries": { "minecraft:stone": { "protocol_id": 1 } }, "protocol_id": 7 },
+        "block": ["stone", "granite"]
+    });
+
+    assert_eq!(manager.load_registries_bundle("1.21", &bundle).unwrap(), 2);
+    assert!(manager.validate_resource_location("item", "minecraft:stone", false, None).unwrap());
+    assert!(manager.validate_resource_location_with_namespace("block", "minecraft:granite", false, None, Some("minecraft")).unwrap());
+    assert!(manager.load_registries_bundle("1.21", &json!([])).is_err());
+}
+
 #[test]
 fn test_registry_manager() {
     let mut manager = RegistryManager::new();
@@ -96,13 +131,13 @@ fn test_validate_resource_location() {
     manager.load_registry_from_json("item".to_string(), "1.20".to_string(), &json).unwrap();
     
     // Test valid resource
-    assert!(manager.validate_resource_location("item", "minecraft:diamond_sword", false).unwrap());
+    assert!(manager.validate_resource_location("item", "minecraft:diamond_sword", false, None).unwrap());
     
     // Test invalid resource
-    assert!(!manager.validate_resource_location("item", "minecraft:nonexistent", false).unwrap());
+    assert!(!manager.validate_resource_location("item", "minecraft:nonexistent", false, None).unwrap());
     
     // Test valid tag
-    assert!(manager.validate_resource_location("item", "minecraft:swords", true).unwrap());
+    assert!(manager.validate_resource_location("item", "minecraft:swords", true, None).unwrap());
 }
 
 #[test]
@@ -130,4 +165,154 @@ fn test_load_minecraft_data() {
     assert!(manager.load_registry_from_json("block".to_string(), "1.20".to_string(), test_data.get("block").unwrap()).is_ok());
     assert!(manager.has_registry("item"));
     assert!(manager.has_registry("block"));
-} 
\ No newline at end of file
+} 
+#[test]
+fn test_scan_required_registries_source_paths() {
+    let manager = RegistryManager::new();
+    
+    let json = json!({
+        "result": { "id": "minecraft:diamond_sword" },
+        "ingredients": ["minecraft:diamond", "#minecraft:planks"]
+    });
+    
+    let dependencies = manager.scan_required_registries(&json);
+    let paths: Vec<_> = dependencies.iter().map(|dep| dep.source_path.as_str()).collect();
+    assert!(paths.contains(&"result.id"));
+    assert!(paths.contains(&"ingredients[0]"));
+    assert!(paths.contains(&"ingredients[1]"));
+    
+    let tag = dependencies.iter().find(|dep| dep.source_path == "ingredients[1]").unwrap();
+    assert_eq!(tag.identifier, "#minecraft:planks");
+    assert_eq!(tag.pointer, "/ingredients/1");
+    assert!(tag.is_tag);
+}
+
+#[test]
+fn test_scan_required_registries_with_mapping() {
+    let manager = RegistryManager::new();
+    let mapping = RegistryManager::create_registry_mapping_from_config(vec![
+        ("result.id".to_string(), "item".to_string()),
+        ("ingredients".to_string(), "item".to_string()),
+    ]);
+    
+    let json = json!({
+        "result": { "id": "minecraft:diamond_sword" },
+        "ingredients": ["minecraft:diamond", "minecraft:stick"],
+        "type": "minecraft:crafting_shapeless"
+    });
+    
+    let dependencies = manager.scan_required_registries_with_mapping(&json, &mapping);
+    for dep in &dependencies {
+        let expected = if dep.source_path == "type" { "unknown" } else { "item" };
+        assert_eq!(dep.registry, expected, "Wrong registry for {}", dep.source_path);
+    }
+    assert_eq!(dependencies.len(), 4);
+}
+
+fn tag_registry() -> Registry {
+    Registry::from_json("block".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:oak_planks": {}, "minecraft:birch_planks": {}, "minecraft:oak_log": {} },
+        "tags": {
+            "minecraft:planks": ["minecraft:oak_planks", "minecraft:birch_planks"],
+            "minecraft:logs": ["minecraft:oak_log"],
+            "minecraft:wooden": ["#minecraft:planks", "#minecraft:logs", "minecraft:oak_planks"],
+            "minecraft:loop_a": ["#minecraft:loop_b"],
+            "minecraft:loop_b": ["minecraft:oak_log", "#minecraft:loop_a"],
+            "minecraft:broken": ["#minecraft:missing"]
+        }
+    })).unwrap()
+}
+
+#[test]
+fn test_resolve_tag_of_tags() {
+    let registry = tag_registry();
+    let mut entries: Vec<String> = registry.resolve_tag("#minecraft:wooden").unwrap().into_iter().collect();
+    entries.sort();
+    assert_eq!(entries, vec!["minecraft:birch_planks", "minecraft:oak_log", "minecraft:oak_planks"]);
+}
+
+#[test]
+fn test_resolve_circular_tags() {
+    let registry = tag_registry();
+    let error = registry.resolve_tag("minecraft:loop_a").unwrap_err();
+    assert!(error.to_string().contains("Circular tag reference: #minecraft:loop_a -> #minecraft:loop_b -> #minecraft:loop_a"), "Unexpected error: {}", error);
+}
+
+#[test]
+fn test_resolve_tag_with_unknown_nested_tag() {
+    let registry = tag_registry();
+    let error = registry.resolve_tag("minecraft:broken").unwrap_err();
+    assert!(error.to_string().contains("Unknown tag '#minecraft:missing'"), "Unexpected error: {}", error);
+}
+
+#[test]
+fn test_manager_expand_tag() {
+    let mut manager = RegistryManager::new();
+    manager.load_registry_from_json("block".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:oak_log": {} },
+        "tags": { "minecraft:logs": ["minecraft:oak_log"] }
+    })).unwrap();
+
+    assert_eq!(manager.expand_tag("block", "#minecraft:logs", None).unwrap().len(), 1);
+    assert!(manager.expand_tag("item", "#minecraft:logs", None).is_err());
+}
+
+#[test]
+fn test_load_tag_file_merges_and_replaces() {
+    let mut manager = RegistryManager::new();
+    manager.load_registry_from_json("block".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:oak_log": {}, "minecraft:birch_log": {}, "minecraft:stone": {} },
+        "tags": { "minecraft:logs": ["minecraft:oak_log"] }
+    })).unwrap();
+
+    manager.load_tag_file("block", "logs", &json!({
+        "values": ["minecraft:oak_log", { "id": "minecraft:birch_log", "required": false }]
+    })).unwrap();
+    assert_eq!(manager.expand_tag("block", "#minecraft:logs", None).unwrap().len(), 2);
+
+    manager.load_tag_file("block", "#minecraft:logs", &json!({
+        "replace": true,
+        "values": ["minecraft:stone"]
+    })).unwrap();
+    let entries: Vec<String> = manager.expand_tag("block", "#minecraft:logs", None).unwrap().into_iter().collect();
+    assert_eq!(entries, ["minecraft:stone"]);
+}
+
+#[test]
+fn test_load_tag_file_errors() {
+    let mut manager = RegistryManager::new();
+    manager.load_registry_from_json("block".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:stone": {} }
+    })).unwrap();
+
+    assert!(manager.load_tag_file("item", "minecraft:gems", &json!({ "values": [] })).is_err());
+    assert!(manager.load_tag_file("block", "minecraft:rocks", &json!({})).is_err());
+    assert!(manager.load_tag_file("block", "minecraft:rocks", &json!({ "values": [1] })).is_err());
+}
+
+#[test]
+fn test_registry_versions_are_kept_apart() {
+    let mut manager = RegistryManager::new();
+    manager.load_registry_from_json("block".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:stone": {}, "minecraft:granite": {} }
+    })).unwrap();
+    manager.load_registry_from_json("block".to_string(), "1.20.4".to_string(), &json!({
+        "entries": { "minecraft:stone": {} }
+    })).unwrap();
+    manager.load_registry_from_json("block".to_string(), "1.9".to_string(), &json!({
+        "entries": {}
+    })).unwrap();
+
+    assert_eq!(manager.registry_versions("block"), ["1.9", "1.20.4", "1.21"]);
+    assert_eq!(manager.registry("block", None).unwrap().version, "1.21");
+    assert_eq!(manager.registry("block", Some("1.20.4")).unwrap().version, "1.20.4");
+    assert_eq!(manager.registry("block", Some("1.18")).unwrap().version, "1.21");
+    assert!(!manager.validate_resource_location("block", "minecraft:granite", false, Some("1.20.4")).unwrap());
+
+    // Reloading a version replaces it
+    manager.load_registry_from_json("block".to_string(), "1.20.4".to_string(), &json!({
+        "entries": { "minecraft:granite": {} }
+    })).unwrap();
+    assert_eq!(manager.registry_versions("block").len(), 3);
+    assert!(manager.validate_resource_location("block", "minecraft:granite", false, Some("1.20.4")).unwrap());
+}
@@ -0,0 +1,55 @@
+use voxel_rsmcdoc::resource_type::ResourceTypeResolver;
+
+#[test]
+fn test_plural_and_singular_folders() {
+    let resolver = ResourceTypeResolver::new();
+
+    assert_eq!(resolver.resolve("data/minecraft/recipes/stone.json"), Some("recipe"));
+    assert_eq!(resolver.resolve("data/minecraft/recipe/stone.json"), Some("recipe"));
+    assert_eq!(resolver.resolve("data/minecraft/loot_tables/blocks/stone.json"), Some("loot_table"));
+    assert_eq!(resolver.resolve("data/minecraft/loot_table/blocks/stone.json"), Some("loot_table"));
+    assert_eq!(resolver.resolve("data/minecraft/item_modifiers/x.json"), Some("item_modifier"));
+    assert_eq!(resolver.resolve("data/minecraft/predicate/x.json"), Some("predicate"));
+}
+
+#[test]
+fn test_other_vanilla_layouts() {
+    let resolver = ResourceTypeResolver::new();
+
+    assert_eq!(resolver.resolve("data/minecraft/tags/item/logs.json"), Some("tag"));
+    assert_eq!(resolver.resolve("data/minecraft/dimension_type/overworld.json"), Some("dimension_type"));
+    assert_eq!(resolver.resolve("data/minecraft/worldgen/biome/plains.json"), Some("worldgen/biome"));
+    assert_eq!(resolver.resolve("my_pack\\data\\demo\\advancements\\root.json"), Some("advancement"));
+    assert_eq!(resolver.resolve("assets/minecraft/models/block/stone.json"), Some("model"));
+    assert_eq!(resolver.resolve("assets/minecraft/textures/block/lava.png.mcmeta"), Some("texture_meta"));
+    assert_eq!(resolver.resolve("assets/minecraft/sounds.json"), Some("sounds"));
+    assert_eq!(resolver.resolve("pack.mcmeta"), None);
+    assert_eq!(resolver.resolve("data/minecraft/machines/press.json"), None);
+}
+
+#[test]
+fn test_custom_mappings_take_precedence() {
+    let mut resolver = ResourceTypeResolver::new();
+    resolver.add_mapping("data/*/machines/**", "mymod:machine");
+    resolver.add_mapping("data/mymod/recipe/special_*.json", "mymod:special_recipe");
+
+    assert_eq!(resolver.resolve("data/mymod/machines/press.json"), Some("mymod:machine"));
+    assert_eq!(resolver.resolve("data/mymod/recipe/special_press.json"), Some("mymod:special_recipe"));
+    assert_eq!(resolver.resolve("data/mymod/recipe/press.json"), Some("recipe"));
+}
+
+#[test]
+fn test_resource_location_from_path() {
+    let resolver = ResourceTypeResolver::new();
+
+    assert_eq!(
+        resolver.resource_location("data/mypack/loot_tables/chests/dungeon.json"),
+        Some(("loot_table", "mypack:chests/dungeon".to_string()))
+    );
+    assert_eq!(
+        resolver.resource_location("pack\\data\\demo\\worldgen\\biome\\plains.json"),
+        Some(("worldgen/biome", "demo:plains".to_string()))
+    );
+    assert_eq!(resolver.resource_location("assets/minecraft/models/block/stone.json"), None);
+    assert_eq!(resolver.resource_location("data/mypack/recipe"), None);
+}
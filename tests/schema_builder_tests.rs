@@ -0,0 +1,73 @@
+//! Tests for defining schemas with the Rust builders
+
+use voxel_rsmcdoc::schema::{Schema, StructBuilder, Type};
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::{parse_mcdoc, ErrorCode};
+use serde_json::json;
+
+fn my_thing() -> Schema {
+    Schema::new("my_thing.mcdoc")
+        .structure(StructBuilder::new("MyThing")
+            .doc("Something built in Rust")
+            .field("id", Type::id("item"))
+            .optional_field("count", Type::int().range(1, 64))
+            .optional_field("tags", Type::list(Type::string()).at_least(1))
+            .optional_field("mode", Type::union([Type::literal("fast"), Type::literal("slow")])))
+        .dispatch("resource", "my_thing", "MyThing")
+}
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_schema(my_thing()).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:stone": {}, "minecraft:diamond": {} }
+    })).unwrap();
+    validator
+}
+
+#[test]
+fn test_schema_to_mcdoc() {
+    assert_eq!(my_thing().to_mcdoc(), "\
+/// Something built in Rust
+struct MyThing {
+\tid: #[id=\"item\"] string,
+\tcount?: int @ 1..64,
+\ttags?: [string] @ 1..,
+\tmode?: (\"fast\" | \"slow\"),
+}
+
+dispatch minecraft:resource[my_thing] to MyThing
+");
+}
+
+#[test]
+fn test_built_schema_validates_and_extracts_dependencies() {
+    let validator = setup_validator();
+
+    let result = validator.validate_json(&json!({ "id": "minecraft:diamond", "count": 3 }), "my_thing", None);
+    assert!(result.is_valid, "{:?}", result.errors);
+    assert!(result.dependencies.iter().any(|dependency| {
+        &*dependency.resource_location == "minecraft:diamond" && &*dependency.registry_type == "item"
+    }), "{:?}", result.dependencies);
+
+    let result = validator.validate_json(&json!({ "id": "minecraft:diamond", "count": 100 }), "my_thing", None);
+    assert!(!result.is_valid);
+    assert!(result.errors.iter().any(|error| error.code == ErrorCode::OutOfRange), "{:?}", result.errors);
+}
+
+#[test]
+fn test_nested_constraints_parenthesized() {
+    let constrained = Type::int().range(0, 10).annotation("since", "1.21").at_most(5.5);
+    assert_eq!(constrained.as_mcdoc(), "(#[since=\"1.21\"] int @ 0..10) @ ..5.5");
+
+    let schema = Schema::new("nested.mcdoc")
+        .alias("Grid", Type::list(Type::union([Type::int().range(-1, 1), Type::id("block")])).range(3, 3))
+        .structure(StructBuilder::new("Holder")
+            .field("type", Type::named("Grid"))
+            .field("minecraft:data", Type::any())
+            .dynamic_field(Type::id("item"), Type::boolean()));
+    let source = schema.to_mcdoc();
+    assert!(source.contains("type Grid = [((int @ -1..1) | (#[id=\"block\"] string))] @ 3\n"), "{}", source);
+    assert!(source.contains("\ttype: Grid,\n\t\"minecraft:data\": any,\n"), "{}", source);
+    parse_mcdoc(&source).unwrap_or_else(|errors| panic!("{:?}\n{}", errors, source));
+}
@@ -0,0 +1,65 @@
+//! Tests for JSON paths and their JSON Pointer form
+
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::types::{JsonPath, JsonPathSegment};
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+#[test]
+fn test_display_and_pointer() {
+    let path = JsonPath::root().key("pools").index(0).key("entries").index(1).key("name");
+    assert_eq!(path.to_string(), "pools[0].entries[1].name");
+    assert_eq!(path.to_pointer(), "/pools/0/entries/1/name");
+    assert_eq!(path.segments()[1], JsonPathSegment::Index(0));
+
+    let root = JsonPath::root();
+    assert!(root.is_root());
+    assert_eq!(root.to_string(), "");
+    assert_eq!(root.to_pointer(), "");
+    assert_eq!(root.index(2).to_string(), "[2]");
+}
+
+#[test]
+fn test_pointer_escapes_keys() {
+    let path = JsonPath::root().key("a/b").key("m~n").key("minecraft:custom_data");
+    assert_eq!(path.to_pointer(), "/a~1b/m~0n/minecraft:custom_data");
+
+    let dotted = JsonPath::root().key("a.b");
+    let nested = JsonPath::root().key("a").key("b");
+    assert_eq!(dotted.to_string(), nested.to_string());
+    assert_ne!(dotted.to_pointer(), nested.to_pointer());
+}
+
+#[test]
+fn test_errors_and_dependencies_carry_pointers() {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(r#"
+dispatch minecraft:resource[components] to struct Components {
+    [string]: struct Component {
+        count: int,
+        item?: #[id="item"] string,
+    },
+}
+"#).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("components.mcdoc".to_string(), ast).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:stone": {} }
+    })).unwrap();
+
+    let json = json!({
+        "minecraft:custom.data": { "count": "many" },
+        "a/b": { "count": 1, "item": "minecraft:dirt" },
+    });
+    let result = validator.validate_json(&json, "components", None);
+
+    let type_error = result.errors.iter().find(|error| error.message.contains("Expected number")).unwrap();
+    assert_eq!(type_error.path, "minecraft:custom.data.count");
+    assert_eq!(type_error.pointer, "/minecraft:custom.data/count");
+
+    let registry_error = result.errors.iter().find(|error| error.message.contains("not found in registry")).unwrap();
+    assert_eq!(registry_error.pointer, "/a~1b/item");
+    assert_eq!(result.dependencies[0].pointer, "/a~1b/item");
+
+    let serialized = serde_json::to_value(&result).unwrap();
+    assert!(serialized["errors"].as_array().unwrap().iter().any(|error| error["pointer"] == "/a~1b/item"));
+}
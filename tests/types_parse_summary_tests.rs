@@ -0,0 +1,70 @@
+//! Tests for the owned outline of parsed MCDOC files
+
+use voxel_rsmcdoc::types::{DeclarationKind, ParseSummary};
+use serde_json::json;
+
+const SOURCE: &str = r#"use ::java::util::Text
+
+struct Item {
+    id: string,
+    count?: int,
+}
+
+enum(string) Rarity {
+    Common = "common",
+    Epic = "epic",
+}
+
+type Tag = string
+
+dispatch minecraft:resource[recipe, %unknown] to struct Recipe {
+    type: string,
+}
+"#;
+
+#[test]
+fn test_summary_of_declarations() {
+    let summary = ParseSummary::from_source(SOURCE);
+    assert!(summary.errors.is_empty());
+    assert_eq!(summary.imports, ["::java::util::Text"]);
+
+    let outline: Vec<(DeclarationKind, &str, u32, Vec<&str>)> = summary.declarations.iter()
+        .map(|declaration| (
+            declaration.kind,
+            declaration.name.as_str(),
+            declaration.line,
+            declaration.members.iter().map(String::as_str).collect(),
+        ))
+        .collect();
+    assert_eq!(outline, [
+        (DeclarationKind::Struct, "Item", 3, vec!["id", "count"]),
+        (DeclarationKind::Enum, "Rarity", 8, vec!["Common", "Epic"]),
+        (DeclarationKind::Type, "Tag", 13, vec![]),
+        (DeclarationKind::Dispatch, "minecraft:resource[recipe,%unknown]", 15, vec!["type"]),
+    ]);
+}
+
+#[test]
+fn test_summary_keeps_declarations_around_errors() {
+    let summary = ParseSummary::from_source("struct A { a: int }\nstruct Broken { x: }\nstruct B { b: int }\n");
+
+    let names: Vec<&str> = summary.declarations.iter().map(|declaration| declaration.name.as_str()).collect();
+    assert_eq!(names, ["A", "B"]);
+    assert!(!summary.errors.is_empty());
+    assert_eq!(summary.errors[0].line, Some(2));
+}
+
+#[test]
+fn test_summary_serialization() {
+    let summary = ParseSummary::from_source("struct A { a: int }");
+    let serialized = serde_json::to_value(&summary).unwrap();
+
+    assert_eq!(serialized["declarations"][0], json!({
+        "kind": "struct",
+        "name": "A",
+        "line": 1,
+        "column": 1,
+        "members": ["a"],
+    }));
+    assert_eq!(serialized["errors"], json!([]));
+}
@@ -0,0 +1,136 @@
+//! Tests for the strings shared between dependencies and errors
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::collections::HashMap;
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::{DatapackValidator, ValidationOptions};
+use voxel_rsmcdoc::{DependencyKind, McDocDependency, SharedStr, StringInterner};
+use serde_json::json;
+
+/// Counts the allocations of the current thread, tests run in parallel
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations_during(action: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    action();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[recipe] to struct Recipe {
+    ingredients: [#[id="item"] string],
+    result: #[id="item"] string,
+    count: int,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_parsed_mcdoc("recipe.mcdoc".to_string(), parse_mcdoc(MCDOC).unwrap()).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:stone": {}, "minecraft:diamond": {} }
+    })).unwrap();
+    validator
+}
+
+#[test]
+fn test_interner_allocates_each_text_once() {
+    let mut interner = StringInterner::new();
+    let first = interner.intern("minecraft:stone");
+
+    let allocations = allocations_during(|| {
+        for _ in 0..100 {
+            let again = interner.intern("minecraft:stone");
+            assert!(again.ptr_eq(&first));
+        }
+    });
+    assert_eq!(allocations, 0);
+    assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn test_dependencies_share_repeated_strings() {
+    let validator = setup_validator();
+    let options = ValidationOptions { keep_duplicate_dependencies: true, ..Default::default() };
+    let json = json!({
+        "ingredients": ["minecraft:stone", "minecraft:stone", "minecraft:diamond"],
+        "result": "minecraft:stone",
+        "count": 1,
+    });
+
+    let result = validator.validate_json_with_options(&json, "recipe", None, &options);
+    assert!(result.is_valid, "Unexpected errors: {:?}", result.errors);
+    let dependencies = &result.dependencies;
+    assert_eq!(dependencies.len(), 4);
+    assert!(dependencies[0].resource_location.ptr_eq(&dependencies[1].resource_location));
+    assert!(dependencies[0].resource_location.ptr_eq(&dependencies[3].resource_location));
+    assert!(dependencies.iter().all(|dependency| dependency.registry_type.ptr_eq(&dependencies[0].registry_type)));
+
+    // Cloning the shared strings doesn't copy them
+    let allocations = allocations_during(|| {
+        for dependency in dependencies {
+            let _ = (dependency.resource_location.clone(), dependency.registry_type.clone(), dependency.source_file.clone());
+        }
+    });
+    assert_eq!(allocations, 0);
+}
+
+#[test]
+fn test_datapack_files_share_strings() {
+    let validator = setup_validator();
+    let files = HashMap::from([
+        ("data/demo/recipe/first.json".to_string(), json!({ "ingredients": [], "result": "minecraft:stone", "count": "one" })),
+        ("data/demo/recipe/second.json".to_string(), json!({ "ingredients": [], "result": "minecraft:stone", "count": "two" })),
+    ]);
+
+    let result = validator.analyze_datapack(&files);
+    assert_eq!(result.errors.len(), 2, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].error.file, "data/demo/recipe/first.json");
+    assert!(result.errors[0].error.file.ptr_eq(&result.errors[0].file_path));
+    let resource_types: Vec<_> = result.errors.iter().map(|error| error.error.resource_type.clone().unwrap()).collect();
+    assert_eq!(resource_types[0], "recipe");
+    assert!(resource_types[0].ptr_eq(&resource_types[1]));
+    assert_eq!(result.dependencies["item"], ["minecraft:stone"]);
+}
+
+#[test]
+fn test_serialized_as_plain_strings() {
+    let dependency = McDocDependency {
+        resource_location: "minecraft:stone".into(),
+        registry_type: "item".into(),
+        source_path: "result".to_string(),
+        pointer: "/result".to_string(),
+        source_file: Some("recipe".into()),
+        is_tag: false,
+        dependency_kind: DependencyKind::Resource,
+        count: 1,
+    };
+
+    let value = serde_json::to_value(&dependency).unwrap();
+    assert_eq!(value["resourceLocation"], "minecraft:stone");
+    assert_eq!(value["registryType"], "item");
+    assert_eq!(value["sourceFile"], "recipe");
+
+    let round_trip: McDocDependency = serde_json::from_value(value).unwrap();
+    assert_eq!(round_trip, dependency);
+    assert_eq!(SharedStr::from("item"), "item");
+}
@@ -1,4 +1,4 @@
-use voxel_rsmcdoc::types::{ValidationResult, McDocError, McDocDependency, DatapackResult, MinecraftVersion};
+use voxel_rsmcdoc::types::{ValidationResult, McDocError, McDocDependency, DependencyKind, DatapackResult, MinecraftVersion};
 
 #[test]
 fn test_validation_result_creation() {
@@ -34,11 +34,14 @@ fn test_version_comparison_simple() {
 #[test]
 fn test_mcdoc_dependency() {
     let dependency = McDocDependency {
-        resource_location: "minecraft:diamond_sword".to_string(),
-        registry_type: "item".to_string(),
+        resource_location: "minecraft:diamond_sword".into(),
+        registry_type: "item".into(),
         source_path: "result.item".to_string(),
-        source_file: Some("recipes/diamond_sword.json".to_string()),
+        pointer: "/result/item".to_string(),
+        source_file: Some("recipes/diamond_sword.json".into()),
         is_tag: false,
+        dependency_kind: DependencyKind::Resource,
+        count: 1,
     };
     
     assert_eq!(dependency.resource_location, "minecraft:diamond_sword");
@@ -49,17 +52,31 @@ fn test_mcdoc_dependency() {
 #[test]
 fn test_mcdoc_error() {
     let error = McDocError {
-        file: "test.json".to_string(),
+        file: "test.json".into(),
+        resource_type: Some("recipe".into()),
         path: "result.item".to_string(),
+        pointer: "/result/item".to_string(),
         message: "Invalid item reference".to_string(),
         error_type: voxel_rsmcdoc::error::ErrorType::Validation,
+        code: voxel_rsmcdoc::error::ErrorCode::RegistryMiss,
+        severity: voxel_rsmcdoc::error::Severity::Error,
         line: Some(10),
         column: Some(15),
+        end_line: Some(10),
+        end_column: Some(20),
+        column_utf16: Some(15),
+        end_column_utf16: Some(20),
+        offset: Some(180),
+        end_offset: Some(185),
+        suggestion: None,
     };
     
     assert_eq!(error.file, "test.json");
     assert_eq!(error.message, "Invalid item reference");
     assert_eq!(error.line, Some(10));
+
+    let serialized = serde_json::to_value(&error).unwrap();
+    assert_eq!(serialized["code"], "registryMiss");
 }
 
 #[test]
@@ -75,11 +92,14 @@ fn test_datapack_result() {
     // Test adding file results
     let validation_result = ValidationResult::success(vec![
         McDocDependency {
-            resource_location: "minecraft:diamond".to_string(),
-            registry_type: "item".to_string(),
+            resource_location: "minecraft:diamond".into(),
+            registry_type: "item".into(),
             source_path: "ingredients[0]".to_string(),
+            pointer: "/ingredients/0".to_string(),
             source_file: None,
             is_tag: false,
+            dependency_kind: DependencyKind::Resource,
+            count: 1,
         }
     ]);
     
@@ -105,4 +125,43 @@ fn test_error_variants() {
     // Test display formatting
     let error_msg = format!("{:?}", validation_error);
     assert!(error_msg.contains("Validation"));
-} 
\ No newline at end of file
+} 
+#[test]
+fn test_merge_heuristic_dependencies() {
+    use voxel_rsmcdoc::RegistryDependency;
+    
+    let mut result = ValidationResult::success(vec![
+        McDocDependency {
+            resource_location: "minecraft:diamond".into(),
+            registry_type: "item".into(),
+            source_path: "result.id".to_string(),
+            pointer: "/result/id".to_string(),
+            source_file: None,
+            is_tag: false,
+            dependency_kind: DependencyKind::Resource,
+            count: 1,
+        }
+    ]);
+    
+    result.merge_heuristic_dependencies(vec![
+        RegistryDependency {
+            registry: "unknown".to_string(),
+            identifier: "minecraft:diamond".to_string(),
+            is_tag: false,
+            source_path: "result.id".to_string(),
+            pointer: "/result/id".to_string(),
+        },
+        RegistryDependency {
+            registry: "unknown".to_string(),
+            identifier: "#minecraft:planks".to_string(),
+            is_tag: true,
+            source_path: "ingredients[1]".to_string(),
+            pointer: "/ingredients/1".to_string(),
+        },
+    ]);
+    
+    assert_eq!(result.dependencies.len(), 2);
+    assert_eq!(result.dependencies[0].registry_type, "item");
+    assert_eq!(result.dependencies[1].source_path, "ingredients[1]");
+    assert!(result.dependencies[1].is_tag);
+}
@@ -0,0 +1,66 @@
+//! Tests for annotations attached to array elements and union branches
+
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[shapeless] to struct Shapeless {
+    ingredients: [#[id="item"] string],
+    result?: (
+        #[id="item"] string |
+        struct { id: #[id="item"] string }
+    ),
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("shapeless.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:stone": {}, "minecraft:stick": {}, "minecraft:diamond": {} }
+    })).unwrap();
+    validator
+}
+
+#[test]
+fn test_array_element_annotation_extracts_dependencies() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({
+        "ingredients": ["minecraft:stone", "minecraft:stick"]
+    }), "shapeless", None);
+
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+    let items: Vec<(&str, &str)> = result.dependencies.iter()
+        .filter(|dep| dep.registry_type == "item")
+        .map(|dep| (dep.resource_location.as_str(), dep.source_path.as_str()))
+        .collect();
+    assert_eq!(items, vec![
+        ("minecraft:stone", "ingredients[0]"),
+        ("minecraft:stick", "ingredients[1]"),
+    ]);
+}
+
+#[test]
+fn test_union_branch_annotation_extracts_dependency() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({
+        "ingredients": [],
+        "result": "minecraft:diamond"
+    }), "shapeless", None);
+
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+    assert!(result.dependencies.iter().any(|dep|
+        dep.registry_type == "item" && dep.resource_location == "minecraft:diamond" && dep.source_path == "result"
+    ), "Missing dependency: {:?}", result.dependencies);
+}
+
+#[test]
+fn test_array_element_annotation_checks_registry() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({
+        "ingredients": ["minecraft:stone", "minecraft:missing"]
+    }), "shapeless", None);
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "ingredients[1]");
+}
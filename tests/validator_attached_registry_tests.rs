@@ -0,0 +1,99 @@
+//! Tests for attached registries like `mcdoc:block_states[[block]]`
+
+use voxel_rsmcdoc::error::ErrorCode;
+use voxel_rsmcdoc::registry::AttachedRegistry;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[placement] to struct Placement {
+    block: #[id="block"] string,
+    state?: mcdoc:block_states[[block]],
+    keys?: [mcdoc:block_states[[%parent.block]]],
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("placement.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator.load_registry("block".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:furnace": {}, "minecraft:stone": {}, "minecraft:modded": {} }
+    })).unwrap();
+
+    let block_states = AttachedRegistry::from_json(&json!({
+        "minecraft:furnace": {
+            "facing": ["north", "south", "east", "west"],
+            "lit": ["true", "false"]
+        },
+        "stone": [{}, {}]
+    })).unwrap();
+    validator.register_attached_registry("mcdoc:block_states".to_string(), block_states);
+    validator
+}
+
+#[test]
+fn test_valid_block_states() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({
+        "block": "minecraft:furnace",
+        "state": { "facing": "north", "lit": true }
+    }), "placement", None);
+
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+}
+
+#[test]
+fn test_invalid_state_value() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({
+        "block": "minecraft:furnace",
+        "state": { "facing": "up" }
+    }), "placement", None);
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "state.facing");
+    assert_eq!(result.errors[0].message, "Invalid value 'up' for property 'facing', expected one of: north, south, east, west");
+    assert_eq!(result.errors[0].code, ErrorCode::InvalidProperty);
+}
+
+#[test]
+fn test_unknown_state_key() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({
+        "block": "minecraft:stone",
+        "state": { "lit": "true" }
+    }), "placement", None);
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "state.lit");
+    assert_eq!(result.errors[0].message, "Unknown property 'lit' for 'minecraft:stone'");
+}
+
+#[test]
+fn test_property_names_read_parent_block() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({
+        "block": "minecraft:furnace",
+        "keys": ["facing", "waterlogged"]
+    }), "placement", None);
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "keys[1]");
+}
+
+#[test]
+fn test_block_without_attached_data_is_accepted() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({
+        "block": "minecraft:modded",
+        "state": { "anything": "goes" }
+    }), "placement", None);
+
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+}
+
+#[test]
+fn test_invalid_attached_registry_json() {
+    assert!(AttachedRegistry::from_json(&json!(["minecraft:furnace"])).is_err());
+    assert!(AttachedRegistry::from_json(&json!({ "minecraft:furnace": "facing" })).is_err());
+}
@@ -0,0 +1,121 @@
+//! Tests for budgeted validation (node, error and time budgets)
+
+use std::time::Duration;
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::types::{TruncationReason, ValidationStatus};
+use voxel_rsmcdoc::validator::{DatapackValidator, ValidationOptions};
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[big_list] to struct BigList {
+    values: [struct Entry {
+        name: string,
+        count: int,
+    }],
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("big_list.mcdoc".to_string(), ast).unwrap();
+    validator
+}
+
+fn large_document(entries: usize, valid: bool) -> serde_json::Value {
+    let values: Vec<_> = (0..entries)
+        .map(|i| if valid {
+            json!({ "name": format!("entry_{}", i), "count": i })
+        } else {
+            json!({ "name": i, "count": "many" })
+        })
+        .collect();
+    json!({ "values": values })
+}
+
+#[test]
+fn test_node_budget_truncates_deterministically() {
+    let validator = setup_validator();
+    let json = large_document(500, true);
+    let options = ValidationOptions { node_budget: Some(10), ..Default::default() };
+
+    let first = validator.validate_json_with_options(&json, "big_list", None, &options);
+    let second = validator.validate_json_with_options(&json, "big_list", None, &options);
+
+    assert!(first.truncated);
+    assert_eq!(first.truncation_reason, Some(TruncationReason::NodeBudget));
+    assert_eq!(first.status, ValidationStatus::Unknown);
+    assert!(!first.is_valid, "A truncated pass must not be reported as valid");
+    assert!(first.errors.is_empty());
+    assert_eq!(first.errors, second.errors);
+    assert_eq!(first.dependencies, second.dependencies);
+}
+
+#[test]
+fn test_node_budget_with_errors_is_invalid() {
+    let validator = setup_validator();
+    let json = large_document(500, false);
+    let options = ValidationOptions { node_budget: Some(10), ..Default::default() };
+
+    let result = validator.validate_json_with_options(&json, "big_list", None, &options);
+    assert!(result.truncated);
+    assert_eq!(result.status, ValidationStatus::Invalid);
+    assert!(!result.is_valid);
+    assert!(!result.errors.is_empty());
+    assert!(result.errors.len() < 1000);
+}
+
+#[test]
+fn test_error_budget_stops_after_limit() {
+    let validator = setup_validator();
+    let json = large_document(500, false);
+    let options = ValidationOptions { error_budget: Some(3), ..Default::default() };
+
+    let result = validator.validate_json_with_options(&json, "big_list", None, &options);
+    assert!(result.truncated);
+    assert_eq!(result.truncation_reason, Some(TruncationReason::ErrorBudget));
+    assert_eq!(result.errors.len(), 3);
+}
+
+#[test]
+fn test_no_budget_reproduces_full_results() {
+    let validator = setup_validator();
+    let json = large_document(500, false);
+
+    let default_result = validator.validate_json(&json, "big_list", None);
+    let generous = ValidationOptions {
+        node_budget: Some(1_000_000),
+        error_budget: Some(1_000_000),
+        time_budget: Some(Duration::from_secs(3600)),
+        ..Default::default()
+    };
+    let budgeted_result = validator.validate_json_with_options(&json, "big_list", None, &generous);
+
+    assert!(!default_result.truncated);
+    assert_eq!(default_result.truncation_reason, None);
+    assert_eq!(default_result.errors.len(), 1000);
+    assert_eq!(default_result.errors, budgeted_result.errors);
+    assert!(!budgeted_result.truncated);
+}
+
+#[test]
+fn test_valid_document_without_budget_is_valid() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&large_document(50, true), "big_list", None);
+
+    assert!(result.is_valid);
+    assert_eq!(result.status, ValidationStatus::Valid);
+    assert!(!result.truncated);
+}
+
+#[test]
+fn test_truncation_is_serialized() {
+    let validator = setup_validator();
+    let options = ValidationOptions { node_budget: Some(5), ..Default::default() };
+    let result = validator.validate_json_with_options(&large_document(100, true), "big_list", None, &options);
+
+    let serialized = serde_json::to_value(&result).unwrap();
+    assert_eq!(serialized["truncated"], json!(true));
+    assert_eq!(serialized["truncationReason"], json!("nodeBudget"));
+    assert_eq!(serialized["status"], json!("unknown"));
+}
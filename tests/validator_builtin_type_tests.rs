@@ -0,0 +1,104 @@
+//! Tests for built-in primitive types and unresolved type references
+
+use voxel_rsmcdoc::error::{ErrorCode, ErrorType};
+use voxel_rsmcdoc::types::is_builtin_type;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+type Weight = int
+
+type Ping = Pong
+type Pong = Ping
+
+type Tree = struct {
+    children?: [Tree],
+}
+
+dispatch minecraft:resource[loose] to struct Loose {
+    anything?: any,
+    opaque?: unknown,
+    weight?: Weight,
+    typo?: Strnig,
+    echo?: Ping,
+    tree?: Tree,
+}
+
+dispatch minecraft:resource[echo] to Ping
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("loose.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator
+}
+
+#[test]
+fn test_is_builtin_type() {
+    for name in ["any", "unknown", "boolean", "string", "byte", "short", "int", "long", "float", "double"] {
+        assert!(is_builtin_type(name), "'{}' should be built-in", name);
+    }
+    assert!(!is_builtin_type("Weight"));
+    assert!(!is_builtin_type("Strnig"));
+}
+
+#[test]
+fn test_any_and_unknown_accept_everything() {
+    let validator = setup_validator();
+    for value in [json!(1), json!("text"), json!(null), json!([1, "two"]), json!({ "nested": true })] {
+        let result = validator.validate_json(&json!({ "anything": value, "opaque": value }), "loose", None);
+        assert!(result.is_valid, "Validation failed for {}: {:?}", value, result.errors);
+    }
+}
+
+#[test]
+fn test_type_alias_reference_is_resolved() {
+    let validator = setup_validator();
+    assert!(validator.validate_json(&json!({ "weight": 3 }), "loose", None).is_valid);
+
+    let result = validator.validate_json(&json!({ "weight": "heavy" }), "loose", None);
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "weight");
+}
+
+#[test]
+fn test_undeclared_type_is_unresolved() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({ "typo": "text" }), "loose", None);
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "typo");
+    assert_eq!(result.errors[0].message, "Unresolved type 'Strnig'");
+    assert_eq!(result.errors[0].error_type, ErrorType::Resolution);
+}
+
+#[test]
+fn test_alias_cycle_is_reported() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({ "echo": 1 }), "loose", None);
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "echo");
+    assert_eq!(result.errors[0].code, ErrorCode::TypeCycle);
+    assert_eq!(result.errors[0].message, "Type alias cycle: Ping -> Pong -> Ping");
+}
+
+#[test]
+fn test_root_alias_cycle_is_reported() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({}), "echo", None);
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].code, ErrorCode::TypeCycle);
+}
+
+#[test]
+fn test_recursive_alias_going_into_the_value_is_not_a_cycle() {
+    let validator = setup_validator();
+    let tree = json!({ "tree": { "children": [{ "children": [] }, { "children": [{}] }] } });
+    assert!(validator.validate_json(&tree, "loose", None).is_valid);
+
+    let result = validator.validate_json(&json!({ "tree": { "children": [{ "children": 1 }] } }), "loose", None);
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "tree.children[0].children");
+}
@@ -0,0 +1,141 @@
+//! Tests for duplicate declarations and dispatch keys when loading schemas
+
+use serde_json::json;
+use voxel_rsmcdoc::error::ParseError;
+use voxel_rsmcdoc::validator::{ConflictPolicy, DatapackValidator};
+use voxel_rsmcdoc::{parse_mcdoc, parse_mcdoc_owned};
+
+const FIRST_MCDOC: &str = "dispatch minecraft:resource[recipe] to struct Recipe { result: string }";
+const SECOND_MCDOC: &str = "dispatch minecraft:resource[recipe] to struct OtherRecipe { result: int }";
+const DUPLICATE_NAMES_MCDOC: &str = r#"
+struct Entry { name: string }
+struct Entry { name: int }
+dispatch minecraft:resource[entry] to Entry
+"#;
+
+fn validator_with(policy: ConflictPolicy) -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.conflict_policy = policy;
+    validator
+}
+
+fn messages(errors: &[ParseError]) -> Vec<String> {
+    errors.iter().map(|error| error.to_string()).collect()
+}
+
+fn recipe_is_valid(validator: &DatapackValidator, result: serde_json::Value) -> bool {
+    validator.validate_json(&json!({ "result": result }), "recipe", None).is_valid
+}
+
+#[test]
+fn test_error_rejects_duplicate_dispatch_key() {
+    let mut validator = validator_with(ConflictPolicy::Error);
+    validator.load_mcdoc_source("first.mcdoc".to_string(), FIRST_MCDOC.to_string()).unwrap();
+
+    let errors = validator.load_mcdoc_source("second.mcdoc".to_string(), SECOND_MCDOC.to_string()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(&errors[0], ParseError::Resolution { path: Some(path), .. } if path == "second.mcdoc"));
+    assert!(messages(&errors)[0].contains("Dispatch key 'recipe' of 'minecraft:resource' is also declared in first.mcdoc"));
+
+    assert!(validator.schema("second.mcdoc").is_none());
+    assert!(recipe_is_valid(&validator, json!("minecraft:stone")));
+}
+
+#[test]
+fn test_error_rejects_duplicate_declaration() {
+    let mut validator = validator_with(ConflictPolicy::Error);
+    let errors = validator.load_mcdoc_source("entry.mcdoc".to_string(), DUPLICATE_NAMES_MCDOC.to_string()).unwrap_err();
+    assert_eq!(messages(&errors), ["'Entry' is declared more than once (path: entry.mcdoc)"]);
+    assert!(validator.schema("entry.mcdoc").is_none());
+}
+
+#[test]
+fn test_error_allows_reloading_same_file() {
+    let mut validator = validator_with(ConflictPolicy::Error);
+    validator.load_mcdoc_source("first.mcdoc".to_string(), FIRST_MCDOC.to_string()).unwrap();
+    validator.load_mcdoc_source("first.mcdoc".to_string(), SECOND_MCDOC.to_string()).unwrap();
+    assert!(recipe_is_valid(&validator, json!(3)));
+}
+
+#[test]
+fn test_warn_keeps_first() {
+    let mut validator = validator_with(ConflictPolicy::WarnAndKeepFirst);
+    validator.load_mcdoc_source("first.mcdoc".to_string(), FIRST_MCDOC.to_string()).unwrap();
+    validator.load_mcdoc_source("second.mcdoc".to_string(), SECOND_MCDOC.to_string()).unwrap();
+    validator.load_mcdoc_source("entry.mcdoc".to_string(), DUPLICATE_NAMES_MCDOC.to_string()).unwrap();
+
+    assert!(recipe_is_valid(&validator, json!("minecraft:stone")));
+    assert!(!recipe_is_valid(&validator, json!(3)));
+    assert!(validator.validate_json(&json!({ "name": "a" }), "entry", None).is_valid);
+
+    assert_eq!(messages(&validator.schema_conflicts("second.mcdoc")).len(), 1);
+    assert_eq!(messages(&validator.schema_conflicts("first.mcdoc")).len(), 1);
+    assert_eq!(validator.schema_conflicts("entry.mcdoc").len(), 1);
+    assert!(validator.schema_conflicts("missing.mcdoc").is_empty());
+}
+
+#[test]
+fn test_override_replaces_earlier() {
+    let mut validator = validator_with(ConflictPolicy::Override);
+    validator.load_mcdoc_source("first.mcdoc".to_string(), FIRST_MCDOC.to_string()).unwrap();
+    validator.load_mcdoc_source("second.mcdoc".to_string(), SECOND_MCDOC.to_string()).unwrap();
+    validator.load_mcdoc_source("entry.mcdoc".to_string(), DUPLICATE_NAMES_MCDOC.to_string()).unwrap();
+
+    assert!(recipe_is_valid(&validator, json!(3)));
+    assert!(!recipe_is_valid(&validator, json!("minecraft:stone")));
+    assert!(validator.validate_json(&json!({ "name": 1 }), "entry", None).is_valid);
+    assert!(validator.schema_conflicts("entry.mcdoc").is_empty());
+
+    // Unloading the override brings the earlier dispatch back
+    assert!(validator.unload_mcdoc("second.mcdoc"));
+    assert!(recipe_is_valid(&validator, json!("minecraft:stone")));
+}
+
+#[test]
+fn test_duplicate_dispatch_key_in_one_module() {
+    let mut validator = validator_with(ConflictPolicy::Error);
+    let source = format!("{}\n{}", FIRST_MCDOC, SECOND_MCDOC);
+    let errors = validator.load_mcdoc_source("both.mcdoc".to_string(), source).unwrap_err();
+    assert!(messages(&errors)[0].contains("Dispatch key 'recipe' of 'minecraft:resource' is declared more than once (path: both.mcdoc)"));
+}
+
+#[test]
+fn test_parsed_and_owned_schemas_report_every_conflict() {
+    let source = format!("{}\n{}", DUPLICATE_NAMES_MCDOC, SECOND_MCDOC);
+    let ast = parse_mcdoc(&source).unwrap();
+
+    let mut validator = DatapackValidator::new();
+    validator.conflict_policy = ConflictPolicy::Error;
+    validator.load_mcdoc_source("first.mcdoc".to_string(), FIRST_MCDOC.to_string()).unwrap();
+    let errors = validator.load_parsed_mcdoc("both.mcdoc".to_string(), ast).unwrap_err();
+    assert_eq!(errors.len(), 2, "{:?}", messages(&errors));
+
+    let errors = validator.load_owned_mcdoc("both.mcdoc".to_string(), parse_mcdoc_owned(&source).unwrap()).unwrap_err();
+    assert_eq!(errors.len(), 2, "{:?}", messages(&errors));
+    assert!(validator.schema("both.mcdoc").is_none());
+}
+
+#[test]
+fn test_duplicate_enum_and_alias_names_keep_first_loaded() {
+    let gem_is_valid = |validator: &DatapackValidator, gem: &str, size: i64| {
+        validator.validate_json(&json!({ "gem": gem, "size": size }), "gem", None).is_valid
+    };
+    let gems = ["ruby", "jade", "opal", "onyx", "topaz", "amber"];
+
+    for order in [gems.to_vec(), gems.iter().rev().copied().collect()] {
+        let mut validator = validator_with(ConflictPolicy::WarnAndKeepFirst);
+        validator.load_mcdoc_source("use.mcdoc".to_string(), "dispatch minecraft:resource[gem] to struct { gem: Gem, size: Size }".to_string()).unwrap();
+        for (index, gem) in order.iter().enumerate() {
+            let schema = format!("enum(string) Gem {{ Only = \"{}\" }}\ntype Size = int @ {}", gem, index);
+            validator.load_mcdoc_source(format!("{}.mcdoc", gem), schema).unwrap();
+        }
+
+        assert!(gem_is_valid(&validator, order[0], 0), "{:?}", order);
+        assert!(!gem_is_valid(&validator, order[1], 0), "{:?}", order);
+        assert!(!gem_is_valid(&validator, order[0], 1), "{:?}", order);
+
+        // Unloading the first brings the next one loaded
+        assert!(validator.unload_mcdoc(&format!("{}.mcdoc", order[0])));
+        assert!(gem_is_valid(&validator, order[1], 1), "{:?}", order);
+    }
+}
@@ -0,0 +1,198 @@
+//! Tests for analyzing a whole datapack with the native validator
+
+use std::collections::HashMap;
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[recipe] to struct Recipe {
+    type: string,
+    result: #[id="item"] string,
+}
+
+dispatch minecraft:resource["worldgen/biome"] to struct Biome {
+    temperature: float,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("datapack.mcdoc".to_string(), ast).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:stone": {}, "minecraft:diamond": {} }
+    })).unwrap();
+    validator
+}
+
+#[test]
+fn test_analyze_datapack_aggregates_results() {
+    let validator = setup_validator();
+    let files = HashMap::from([
+        ("data/demo/recipes/stone.json".to_string(), json!({ "type": "crafting", "result": "minecraft:stone" })),
+        ("data/demo/recipes/diamond.json".to_string(), json!({ "type": "crafting", "result": "minecraft:diamond" })),
+        ("data/demo/recipes/broken.json".to_string(), json!({ "type": "crafting" })),
+        ("data/demo/worldgen/biome/plains.json".to_string(), json!({ "temperature": 0.8 })),
+    ]);
+
+    let result = validator.analyze_datapack(&files);
+    assert_eq!(result.total_files, 4);
+    assert_eq!(result.valid_files, 3);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].file_path, "data/demo/recipes/broken.json");
+
+    let mut items = result.dependencies["item"].clone();
+    items.sort();
+    assert_eq!(items, ["minecraft:diamond", "minecraft:stone"]);
+}
+
+#[test]
+fn test_analyze_datapack_unknown_folder() {
+    let validator = setup_validator();
+    let files = HashMap::from([
+        ("data/demo/machines/press.json".to_string(), json!({})),
+        ("data/demo/recipe/stone.json".to_string(), json!({ "type": "crafting", "result": "minecraft:stone" })),
+    ]);
+
+    let result = validator.analyze_datapack(&files);
+    assert_eq!(result.total_files, 2);
+    assert_eq!(result.valid_files, 1);
+    assert!(result.errors.is_empty());
+    assert_eq!(result.warnings.len(), 1);
+    assert_eq!(result.warnings[0].file_path, "data/demo/machines/press.json");
+}
+
+#[test]
+fn test_analyze_datapack_custom_mapping() {
+    let mut validator = setup_validator();
+    validator.resource_types.add_mapping("data/*/machines/**", "recipe");
+    let files = HashMap::from([
+        ("data/demo/machines/press.json".to_string(), json!({ "type": "press", "result": "minecraft:stone" })),
+    ]);
+
+    let result = validator.analyze_datapack(&files);
+    assert_eq!(result.valid_files, 1);
+    assert!(result.warnings.is_empty());
+}
+
+/// Validator for recipes whose ingredient may be an item tag, with the vanilla
+/// `minecraft:gems` tag loaded
+fn tag_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(r#"
+dispatch minecraft:resource[recipe] to struct Recipe {
+    ingredient: #[id(registry="item", tags="allowed")] string,
+}
+
+dispatch minecraft:resource[tag] to struct Tag {
+    values: [string],
+}
+"#).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("datapack.mcdoc".to_string(), ast).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:diamond": {}, "minecraft:emerald": {}, "minecraft:stone": {} },
+        "tags": { "minecraft:gems": ["minecraft:diamond", "minecraft:emerald"] }
+    })).unwrap();
+    validator
+}
+
+#[test]
+fn test_analyze_datapack_loads_own_tags() {
+    let validator = tag_validator();
+    let files = HashMap::from([
+        ("data/mypack/tags/item/gems.json".to_string(), json!({ "values": ["minecraft:diamond", "minecraft:emerald"] })),
+        ("data/mypack/recipe/gem_block.json".to_string(), json!({ "ingredient": "#mypack:gems" })),
+    ]);
+
+    let result = validator.analyze_datapack(&files);
+    assert!(result.errors.is_empty(), "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.valid_files, 2);
+    // Only loaded for the analysis
+    assert!(validator.registry_manager.expand_tag("item", "#mypack:gems", None).is_err());
+}
+
+#[test]
+fn test_analyze_datapack_legacy_plural_tag_folder() {
+    let validator = tag_validator();
+    let files = HashMap::from([
+        ("data/mypack/tags/items/shiny.json".to_string(), json!({ "values": ["minecraft:diamond"] })),
+        ("data/mypack/recipe/shiny_block.json".to_string(), json!({ "ingredient": "#mypack:shiny" })),
+    ]);
+
+    let result = validator.analyze_datapack(&files);
+    assert!(result.errors.is_empty(), "Unexpected errors: {:?}", result.errors);
+}
+
+#[test]
+fn test_analyze_datapack_tags_stay_in_their_analysis() {
+    let validator = tag_validator();
+    let pack_a = HashMap::from([
+        ("data/mypack/tags/item/shiny.json".to_string(), json!({ "values": ["minecraft:diamond"] })),
+        // Replaces the vanilla tag for this pack only
+        ("data/minecraft/tags/item/gems.json".to_string(), json!({ "replace": true, "values": ["minecraft:stone"] })),
+    ]);
+    let pack_b = HashMap::from([
+        ("data/other/recipe/shiny_block.json".to_string(), json!({ "ingredient": "#mypack:shiny" })),
+    ]);
+
+    assert!(validator.analyze_datapack(&pack_a).errors.is_empty());
+    let result = validator.analyze_datapack(&pack_b);
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].file_path, "data/other/recipe/shiny_block.json");
+
+    let gems = validator.registry_manager.expand_tag("item", "#minecraft:gems", None).unwrap();
+    assert_eq!(gems.len(), 2);
+    assert!(gems.contains("minecraft:diamond"));
+}
+
+#[test]
+fn test_analyze_datapack_local_resources() {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(r#"
+dispatch minecraft:resource[advancement] to struct Advancement {
+    rewards: struct {
+        loot: [#[id="loot_table"] string],
+    },
+}
+
+dispatch minecraft:resource[loot_table] to struct LootTable {
+    pools: [any],
+}
+"#).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("datapack.mcdoc".to_string(), ast).unwrap();
+
+    let files = HashMap::from([
+        ("data/mypack/loot_tables/chests/dungeon.json".to_string(), json!({ "pools": [] })),
+        ("data/mypack/advancement/explore.json".to_string(), json!({ "rewards": { "loot": ["mypack:chests/dungeon"] } })),
+    ]);
+
+    let result = validator.analyze_datapack(&files);
+    assert!(result.errors.is_empty(), "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.valid_files, 2);
+
+    let missing = validator.validate_json(&json!({ "rewards": { "loot": ["mypack:chests/missing"] } }), "advancement", None);
+    assert!(!missing.is_valid);
+
+    // Only registered for the analysis
+    let later = validator.validate_json(&json!({ "rewards": { "loot": ["mypack:chests/dungeon"] } }), "advancement", None);
+    assert!(!later.is_valid);
+    let other_pack = HashMap::from([
+        ("data/other/advancement/explore.json".to_string(), json!({ "rewards": { "loot": ["mypack:chests/dungeon"] } })),
+    ]);
+    assert_eq!(validator.analyze_datapack(&other_pack).errors.len(), 1);
+}
+
+#[test]
+fn test_register_local_resources() {
+    let mut validator = setup_validator();
+    let json = json!({ "type": "crafting", "result": "mypack:ruby" });
+    assert!(!validator.validate_json(&json, "recipe", None).is_valid);
+
+    validator.register_local_resources("item", ["mypack:ruby".to_string(), "sapphire".to_string()]);
+    assert!(validator.validate_json(&json, "recipe", None).is_valid);
+    assert!(validator.validate_json(&json!({ "type": "crafting", "result": "minecraft:sapphire" }), "recipe", None).is_valid);
+
+    validator.registry_manager.clear_local_resources();
+    assert!(!validator.validate_json(&json, "recipe", None).is_valid);
+}
@@ -0,0 +1,57 @@
+//! Tests for resource locations written without a namespace
+
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::{DatapackValidator, ValidationOptions};
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[recipe] to struct Recipe {
+    result: #[id="item"] string,
+    tag?: #[id(registry="item", tags="allowed")] string,
+}
+"#;
+
+fn setup_validator(entries: serde_json::Value) -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({
+        "entries": entries,
+        "tags": { "minecraft:logs": ["minecraft:stone"] }
+    })).unwrap();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("recipe.mcdoc".to_string(), ast).unwrap();
+    validator
+}
+
+#[test]
+fn test_bare_and_prefixed_ids_give_the_same_dependency() {
+    let validator = setup_validator(json!({ "minecraft:stone": {} }));
+
+    for id in ["stone", "minecraft:stone"] {
+        let result = validator.validate_json(&json!({ "result": id }), "recipe", None);
+        assert!(result.is_valid, "{}: {:?}", id, result.errors);
+        assert_eq!(result.dependencies[0].resource_location, "minecraft:stone");
+    }
+}
+
+#[test]
+fn test_prefixed_ids_match_bare_registry_entries() {
+    let validator = setup_validator(json!(["stone"]));
+
+    let result = validator.validate_json(&json!({ "result": "minecraft:stone", "tag": "#logs" }), "recipe", None);
+    assert!(result.is_valid, "{:?}", result.errors);
+    assert_eq!(result.dependencies[1].resource_location, "#minecraft:logs");
+}
+
+#[test]
+fn test_default_namespace_is_configurable() {
+    let validator = setup_validator(json!({ "mypack:ruby": {} }));
+    let options = ValidationOptions { default_namespace: "mypack".to_string(), ..Default::default() };
+
+    let result = validator.validate_json_with_options(&json!({ "result": "ruby" }), "recipe", None, &options);
+    assert!(result.is_valid, "{:?}", result.errors);
+    assert_eq!(result.dependencies[0].resource_location, "mypack:ruby");
+
+    let result = validator.validate_json(&json!({ "result": "ruby" }), "recipe", None);
+    assert!(!result.is_valid);
+    assert_eq!(result.dependencies[0].resource_location, "minecraft:ruby");
+}
@@ -0,0 +1,81 @@
+//! Tests for merging repeated dependencies in validation results
+
+use std::collections::HashMap;
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::{DatapackValidator, ValidationOptions};
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[loot_table] to struct LootTable {
+    entries: [struct Entry {
+        name: #[id(registry="item", tags="allowed")] string,
+    }],
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("loot_table.mcdoc".to_string(), ast).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:stone": {}, "minecraft:dirt": {} },
+        "tags": { "minecraft:stone": ["minecraft:stone"] }
+    })).unwrap();
+    validator
+}
+
+fn loot_table() -> serde_json::Value {
+    json!({ "entries": [
+        { "name": "minecraft:stone" },
+        { "name": "minecraft:dirt" },
+        { "name": "minecraft:stone" },
+        { "name": "#minecraft:stone" },
+        { "name": "minecraft:stone" },
+    ] })
+}
+
+#[test]
+fn test_repeated_dependencies_are_merged() {
+    let result = setup_validator().validate_json(&loot_table(), "loot_table", None);
+    assert!(result.is_valid, "Unexpected errors: {:?}", result.errors);
+
+    let shape: Vec<(&str, bool, usize, &str)> = result.dependencies.iter()
+        .map(|dependency| (dependency.resource_location.as_str(), dependency.is_tag, dependency.count, dependency.source_path.as_str()))
+        .collect();
+    assert_eq!(shape, [
+        ("minecraft:stone", false, 3, "entries[0].name"),
+        ("minecraft:dirt", false, 1, "entries[1].name"),
+        ("#minecraft:stone", true, 1, "entries[3].name"),
+    ]);
+}
+
+#[test]
+fn test_keep_duplicate_dependencies() {
+    let options = ValidationOptions { keep_duplicate_dependencies: true, ..Default::default() };
+    let result = setup_validator().validate_json_with_options(&loot_table(), "loot_table", None, &options);
+
+    assert_eq!(result.dependencies.len(), 5);
+    assert!(result.dependencies.iter().all(|dependency| dependency.count == 1));
+}
+
+#[test]
+fn test_errors_are_reported_for_each_occurrence() {
+    let json = json!({ "entries": [{ "name": "minecraft:gold" }, { "name": "minecraft:gold" }] });
+    let result = setup_validator().validate_json(&json, "loot_table", None);
+
+    assert_eq!(result.errors.len(), 2);
+    assert_eq!(result.dependencies.len(), 1);
+    assert_eq!(result.dependencies[0].count, 2);
+}
+
+#[test]
+fn test_datapack_dependencies_are_merged_across_files() {
+    let validator = setup_validator();
+    let files = HashMap::from([
+        ("data/demo/loot_table/a.json".to_string(), loot_table()),
+        ("data/demo/loot_table/b.json".to_string(), json!({ "entries": [{ "name": "minecraft:dirt" }] })),
+    ]);
+
+    let result = validator.analyze_datapack(&files);
+    assert_eq!(result.dependencies["item"], ["minecraft:stone", "minecraft:dirt", "#minecraft:stone"]);
+}
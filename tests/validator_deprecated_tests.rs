@@ -0,0 +1,74 @@
+//! Tests for `#[deprecated]` fields and union branches reported as warnings
+
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[old_stuff] to struct OldStuff {
+    #[deprecated]
+    legacy?: string,
+    #[deprecated="1.16"]
+    old_name?: string,
+    value?: (
+        #[deprecated] int |
+        string
+    ),
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("old_stuff.mcdoc".to_string(), ast).unwrap();
+    validator
+}
+
+#[test]
+fn test_unconditional_deprecation_is_a_warning() {
+    let validator = setup_validator();
+
+    let result = validator.validate_json(&json!({ "legacy": "x" }), "old_stuff", Some("1.15"));
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+    assert_eq!(result.warnings.len(), 1);
+    assert_eq!(result.warnings[0].path, "legacy");
+    assert_eq!(result.warnings[0].message, "'legacy' is deprecated");
+}
+
+#[test]
+fn test_versioned_deprecation() {
+    let validator = setup_validator();
+    let json = json!({ "old_name": "x" });
+
+    let older = validator.validate_json(&json, "old_stuff", Some("1.15.2"));
+    assert!(older.is_valid);
+    assert!(older.warnings.is_empty());
+
+    let newer = validator.validate_json(&json, "old_stuff", Some("1.16"));
+    assert!(newer.is_valid);
+    assert_eq!(newer.warnings.len(), 1);
+    assert_eq!(newer.warnings[0].message, "'old_name' is deprecated since 1.16");
+}
+
+#[test]
+fn test_deprecated_union_branch() {
+    let validator = setup_validator();
+
+    let deprecated = validator.validate_json(&json!({ "value": 3 }), "old_stuff", None);
+    assert!(deprecated.is_valid);
+    assert_eq!(deprecated.warnings.len(), 1);
+    assert_eq!(deprecated.warnings[0].path, "value");
+
+    let current = validator.validate_json(&json!({ "value": "three" }), "old_stuff", None);
+    assert!(current.warnings.is_empty());
+}
+
+#[test]
+fn test_warnings_are_serialized() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({ "legacy": "x" }), "old_stuff", None);
+
+    let serialized = serde_json::to_value(&result).unwrap();
+    assert_eq!(serialized["isValid"], json!(true));
+    assert_eq!(serialized["warnings"][0]["path"], json!("legacy"));
+}
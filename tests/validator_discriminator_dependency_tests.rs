@@ -0,0 +1,83 @@
+//! Tests for the dependencies recorded for the discriminators of dispatch spreads
+
+use serde_json::json;
+use std::fs;
+use voxel_rsmcdoc::types::DependencyKind;
+use voxel_rsmcdoc::validator::{DatapackValidator, ValidationOptions};
+use voxel_rsmcdoc::{ErrorCode, McDocDependency};
+
+fn recipe_mcdoc() -> String {
+    fs::read_to_string("tests/dataset/mcdoc/data/recipe.mcdoc").expect("Failed to read recipe.mcdoc")
+}
+
+fn setup_validator(mcdoc: String) -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("recipe.mcdoc".to_string(), mcdoc).unwrap();
+    validator.load_registry("recipe_serializer".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:crafting_shaped": {}, "minecraft:stonecutting": {} }
+    })).unwrap();
+    validator
+}
+
+fn type_dependencies(validator: &DatapackValidator, json: &serde_json::Value) -> Vec<McDocDependency> {
+    let options = ValidationOptions { keep_duplicate_dependencies: true, ..Default::default() };
+    let result = validator.validate_json_with_options(json, "recipe", None, &options);
+    result.dependencies.into_iter().filter(|dependency| dependency.source_path == "type").collect()
+}
+
+fn stonecutting() -> serde_json::Value {
+    json!({ "type": "minecraft:stonecutting", "ingredient": "minecraft:stone", "result": { "id": "minecraft:stone_slab" } })
+}
+
+#[test]
+fn test_discriminator_without_id_is_a_dependency() {
+    let mcdoc = recipe_mcdoc().replace("type: #[id=\"recipe_serializer\"] string,", "type: string,");
+    assert!(!mcdoc.contains("#[id=\"recipe_serializer\"]"));
+    let validator = setup_validator(mcdoc);
+
+    let dependencies = type_dependencies(&validator, &stonecutting());
+    assert_eq!(dependencies.len(), 1, "{:?}", dependencies);
+    assert_eq!(dependencies[0].registry_type.as_ref(), "recipe_serializer");
+    assert_eq!(dependencies[0].resource_location.as_ref(), "minecraft:stonecutting");
+    assert_eq!(dependencies[0].pointer, "/type");
+    assert!(!dependencies[0].is_tag);
+    assert_eq!(dependencies[0].dependency_kind, DependencyKind::Resource);
+}
+
+#[test]
+fn test_discriminator_with_id_is_recorded_once() {
+    let validator = setup_validator(recipe_mcdoc());
+    let dependencies = type_dependencies(&validator, &stonecutting());
+    assert_eq!(dependencies.len(), 1, "{:?}", dependencies);
+    assert_eq!(dependencies[0].registry_type.as_ref(), "recipe_serializer");
+}
+
+#[test]
+fn test_unknown_serializer_is_flagged() {
+    let mcdoc = recipe_mcdoc().replace("type: #[id=\"recipe_serializer\"] string,", "type: string,");
+    let validator = setup_validator(mcdoc);
+
+    let result = validator.validate_json(&json!({ "type": "minecraft:cooking" }), "recipe", None);
+    let errors: Vec<_> = result.errors.iter().filter(|error| error.path == "type").collect();
+    assert_eq!(errors.len(), 1, "{:?}", result.errors);
+    assert_eq!(errors[0].code, ErrorCode::RegistryMiss);
+}
+
+#[test]
+fn test_discriminator_of_unloaded_registry_is_a_dispatcher_key() {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("effect.mcdoc".to_string(), r#"
+dispatch minecraft:resource[effect] to struct Effect {
+    kind: string,
+    ...minecraft:effect_kind[[kind]],
+}
+dispatch minecraft:effect_kind[glow] to struct { color: int }
+"#.to_string()).unwrap();
+
+    let result = validator.validate_json(&json!({ "kind": "glow", "color": 3 }), "effect", None);
+    assert!(result.is_valid, "{:?}", result.errors);
+    assert_eq!(result.dependencies.len(), 1);
+    assert_eq!(result.dependencies[0].registry_type.as_ref(), "minecraft:effect_kind");
+    assert_eq!(result.dependencies[0].resource_location.as_ref(), "glow");
+    assert_eq!(result.dependencies[0].dependency_kind, DependencyKind::DispatcherKey);
+}
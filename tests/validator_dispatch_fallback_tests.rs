@@ -0,0 +1,80 @@
+//! Tests for `%unknown` and `%none` dispatch fallbacks
+
+use voxel_rsmcdoc::validator::{DatapackValidator, ValidationOptions};
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[recipe] to struct Recipe {
+    type?: string,
+    ...minecraft:recipe_serializer[[type]],
+}
+
+dispatch minecraft:recipe_serializer[crafting_shapeless] to struct Shapeless {
+    ingredients: [string],
+}
+
+dispatch minecraft:recipe_serializer[%unknown] to struct UnknownSerializer {
+    weird_value: int,
+}
+
+dispatch minecraft:recipe_serializer[%none] to struct NoSerializer {
+    result: string,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("recipe.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator
+}
+
+#[test]
+fn test_specific_key_is_preferred() {
+    let validator = setup_validator();
+
+    let valid = validator.validate_json(&json!({ "type": "minecraft:crafting_shapeless", "ingredients": ["minecraft:stick"] }), "recipe", None);
+    assert!(valid.is_valid, "Validation failed: {:?}", valid.errors);
+
+    let invalid = validator.validate_json(&json!({ "type": "crafting_shapeless", "ingredients": "minecraft:stick" }), "recipe", None);
+    assert!(!invalid.is_valid);
+    assert_eq!(invalid.errors[0].path, "ingredients");
+}
+
+#[test]
+fn test_unmatched_key_uses_unknown_target() {
+    let validator = setup_validator();
+
+    let valid = validator.validate_json(&json!({ "type": "mymod:weird_serializer", "weird_value": 3 }), "recipe", None);
+    assert!(valid.is_valid, "Validation failed: {:?}", valid.errors);
+
+    let invalid = validator.validate_json(&json!({ "type": "mymod:weird_serializer", "weird_value": "3" }), "recipe", None);
+    assert!(!invalid.is_valid);
+    assert_eq!(invalid.errors[0].path, "weird_value");
+}
+
+#[test]
+fn test_missing_key_uses_none_target() {
+    let validator = setup_validator();
+
+    let valid = validator.validate_json(&json!({ "result": "minecraft:stone" }), "recipe", None);
+    assert!(valid.is_valid, "Validation failed: {:?}", valid.errors);
+
+    let invalid = validator.validate_json(&json!({}), "recipe", None);
+    assert!(!invalid.is_valid);
+    assert!(invalid.errors[0].message.contains("result"));
+}
+
+#[test]
+fn test_dispatched_fields_are_known_in_strict_mode() {
+    let validator = setup_validator();
+    let options = ValidationOptions { strict_fields: true, ..Default::default() };
+
+    let json = json!({ "type": "crafting_shapeless", "ingredients": [] });
+    let valid = validator.validate_json_with_options(&json, "recipe", None, &options);
+    assert!(valid.is_valid, "Validation failed: {:?}", valid.errors);
+
+    let json = json!({ "type": "crafting_shapeless", "ingredients": [], "weird_value": 3 });
+    let invalid = validator.validate_json_with_options(&json, "recipe", None, &options);
+    assert_eq!(invalid.errors.len(), 1);
+    assert_eq!(invalid.errors[0].path, "weird_value");
+}
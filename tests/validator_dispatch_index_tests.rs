@@ -0,0 +1,47 @@
+//! Tests for the dispatch index used to find the schema of a resource type
+
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[recipe] to struct Recipe {
+    result: string,
+}
+
+dispatch minecraft:recipe_serializer[recipe] to struct RecipeSerializer {
+    serializer: int,
+}
+"#;
+
+#[test]
+fn test_same_key_on_different_registries_does_not_collide() {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("recipe.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+
+    let resource = validator.validate_json(&json!({ "result": "minecraft:stone" }), "recipe", None);
+    assert!(resource.is_valid, "Validation failed: {:?}", resource.errors);
+
+    let serializer = validator.validate_json(&json!({ "serializer": 1 }), "minecraft:recipe_serializer[recipe]", None);
+    assert!(serializer.is_valid, "Validation failed: {:?}", serializer.errors);
+
+    let short_form = validator.validate_json(&json!({ "serializer": 1 }), "recipe_serializer[recipe]", None);
+    assert!(short_form.is_valid, "Validation failed: {:?}", short_form.errors);
+
+    assert!(!validator.validate_json(&json!({ "serializer": 1 }), "minecraft:recipe", None).is_valid);
+}
+
+#[test]
+fn test_index_follows_replaced_schemas() {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("a.mcdoc".to_string(), "dispatch minecraft:resource[thing] to struct A { a: int }".to_string()).unwrap();
+    validator.load_mcdoc_source("b.mcdoc".to_string(), "dispatch minecraft:resource[thing] to struct B { b: int }".to_string()).unwrap();
+
+    assert!(validator.validate_json(&json!({ "a": 1 }), "thing", None).is_valid);
+
+    validator.unload_mcdoc("a.mcdoc");
+    assert!(validator.validate_json(&json!({ "b": 1 }), "thing", None).is_valid);
+
+    validator.reload_mcdoc("b.mcdoc", "dispatch minecraft:resource[other] to struct B { b: int }".to_string()).unwrap();
+    let missing = validator.validate_json(&json!({ "b": 1 }), "thing", None);
+    assert_eq!(missing.errors[0].message, "No MCDOC schema found for resource type 'thing'");
+}
@@ -0,0 +1,53 @@
+//! Tests for listing the keys and registries dispatched by the loaded schemas
+
+use voxel_rsmcdoc::validator::DatapackValidator;
+
+const CRAFTING_MCDOC: &str = r#"
+dispatch minecraft:recipe_serializer[crafting_shaped, crafting_shapeless] to struct Crafting {
+    group?: string,
+}
+
+dispatch minecraft:recipe_serializer[%unknown] to struct AnyRecipe {}
+
+dispatch minecraft:resource[recipe] to struct Recipe {
+    type: string,
+}
+"#;
+
+const COOKING_MCDOC: &str = r#"
+dispatch minecraft:recipe_serializer[smelting, "minecraft:blasting", crafting_shaped] to struct Cooking {
+    cookingtime?: int,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("crafting.mcdoc".to_string(), CRAFTING_MCDOC.to_string()).unwrap();
+    validator.load_mcdoc_source("cooking.mcdoc".to_string(), COOKING_MCDOC.to_string()).unwrap();
+    validator
+}
+
+#[test]
+fn test_dispatch_keys_union_sorted() {
+    let validator = setup_validator();
+    let expected = ["blasting", "crafting_shaped", "crafting_shapeless", "smelting"];
+
+    assert_eq!(validator.dispatch_keys("minecraft:recipe_serializer"), expected);
+    assert_eq!(validator.dispatch_keys("recipe_serializer"), expected);
+    assert_eq!(validator.dispatch_fallbacks("minecraft:recipe_serializer"), ["%unknown"]);
+}
+
+#[test]
+fn test_registries_with_dispatch() {
+    let validator = setup_validator();
+    assert_eq!(validator.registries_with_dispatch(), ["minecraft:recipe_serializer", "minecraft:resource"]);
+    assert!(validator.dispatch_keys("minecraft:block").is_empty());
+}
+
+#[test]
+fn test_dispatch_keys_follow_unloading() {
+    let mut validator = setup_validator();
+    assert!(validator.unload_mcdoc("cooking.mcdoc"));
+
+    assert_eq!(validator.dispatch_keys("minecraft:recipe_serializer"), ["crafting_shaped", "crafting_shapeless"]);
+}
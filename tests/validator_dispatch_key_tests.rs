@@ -0,0 +1,70 @@
+//! Tests for quoted dispatch keys containing `/` and namespaced lookups
+
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::parser::{Declaration, DispatchTarget};
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource["worldgen/biome"] to struct Biome {
+    temperature: float,
+}
+
+dispatch minecraft:resource["minecraft:worldgen/noise"] to struct Noise {
+    firstOctave: int,
+}
+
+dispatch minecraft:resource[worldgen] to struct Worldgen {
+    type: string,
+    ...minecraft:worldgen_source[[type]],
+}
+
+dispatch minecraft:worldgen_source["worldgen/biome", fixed] to struct Source {
+    biome: string,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("worldgen.mcdoc".to_string(), ast).unwrap();
+    validator
+}
+
+#[test]
+fn test_quoted_key_is_kept_verbatim() {
+    let ast = parse_mcdoc(MCDOC).unwrap();
+    let Declaration::Dispatch(dispatch) = &ast.declarations[0] else { panic!("Expected dispatch") };
+    assert_eq!(dispatch.targets, vec![DispatchTarget::Specific("worldgen/biome")]);
+}
+
+#[test]
+fn test_path_key_with_and_without_namespace() {
+    let validator = setup_validator();
+    let biome = json!({ "temperature": 0.5 });
+
+    for resource_type in ["worldgen/biome", "minecraft:worldgen/biome", "resource[worldgen/biome]"] {
+        let result = validator.validate_json(&biome, resource_type, None);
+        assert!(result.is_valid, "Validation as '{}' failed: {:?}", resource_type, result.errors);
+        let result = validator.validate_json(&json!({ "temperature": "warm" }), resource_type, None);
+        assert!(!result.is_valid, "'{}' found no schema", resource_type);
+    }
+}
+
+#[test]
+fn test_namespaced_key_in_declaration() {
+    let validator = setup_validator();
+    for resource_type in ["worldgen/noise", "minecraft:worldgen/noise"] {
+        let result = validator.validate_json(&json!({ "firstOctave": "low" }), resource_type, None);
+        assert!(result.errors.iter().any(|e| e.path == "firstOctave"), "Unexpected errors: {:?}", result.errors);
+    }
+}
+
+#[test]
+fn test_spread_with_path_key() {
+    let validator = setup_validator();
+    for key in ["worldgen/biome", "minecraft:worldgen/biome", "fixed"] {
+        let result = validator.validate_json(&json!({ "type": key }), "worldgen", None);
+        assert!(result.errors.iter().any(|e| e.path == "biome"), "'{}' didn't dispatch: {:?}", key, result.errors);
+    }
+}
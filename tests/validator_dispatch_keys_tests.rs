@@ -0,0 +1,44 @@
+//! Tests for dispatch declarations with several keys and `%` patterns
+
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:item[
+    axolotl_bucket,
+    cod_bucket,
+    salmon_bucket
+] to struct BasicFishBucket {
+    EntityTag?: string,
+}
+
+dispatch minecraft:item[%unknown] to struct AnyItem {}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("item.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator
+}
+
+#[test]
+fn test_every_dispatch_key_resolves() {
+    let validator = setup_validator();
+
+    for key in ["axolotl_bucket", "cod_bucket", "salmon_bucket"] {
+        let resource_type = format!("minecraft:item[{}]", key);
+        let valid = validator.validate_json(&json!({ "EntityTag": "fish" }), &resource_type, None);
+        assert!(valid.is_valid, "{} failed: {:?}", key, valid.errors);
+
+        let invalid = validator.validate_json(&json!({ "EntityTag": 3 }), &resource_type, None);
+        assert!(!invalid.is_valid, "{} should use the fish bucket schema", key);
+    }
+}
+
+#[test]
+fn test_unmatched_key_falls_back_to_unknown() {
+    let validator = setup_validator();
+
+    let result = validator.validate_json(&json!({ "EntityTag": 3 }), "minecraft:item[stone]", None);
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+}
@@ -0,0 +1,48 @@
+//! Tests for types referencing a dispatch target by key, like `minecraft:block_entity[moving_piston]`
+
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::ErrorCode;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:block_entity[moving_piston] to struct MovingPiston {
+    extending: boolean,
+    progress: float,
+}
+
+dispatch minecraft:resource[structure_block] to struct StructureBlock {
+    piston: minecraft:block_entity[moving_piston],
+    other?: minecraft:block_entity[furnace],
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("block_entity.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator
+}
+
+#[test]
+fn test_reference_validates_against_dispatch_target() {
+    let validator = setup_validator();
+
+    let valid = validator.validate_json(&json!({ "piston": { "extending": true, "progress": 0.5 } }), "structure_block", None);
+    assert!(valid.is_valid, "Validation failed: {:?}", valid.errors);
+
+    let invalid = validator.validate_json(&json!({ "piston": { "extending": "yes", "progress": 0.5 } }), "structure_block", None);
+    assert_eq!(invalid.errors.len(), 1, "{:?}", invalid.errors);
+    assert_eq!(invalid.errors[0].path, "piston.extending");
+    assert_eq!(invalid.errors[0].code, ErrorCode::TypeMismatch);
+}
+
+#[test]
+fn test_reference_to_unregistered_key() {
+    let validator = setup_validator();
+    let json = json!({ "piston": { "extending": true, "progress": 1.0 }, "other": {} });
+
+    let result = validator.validate_json(&json, "structure_block", None);
+    assert_eq!(result.errors.len(), 1, "{:?}", result.errors);
+    assert_eq!(result.errors[0].path, "other");
+    assert_eq!(result.errors[0].code, ErrorCode::UnknownDispatchKey);
+    assert_eq!(result.errors[0].message, "No dispatch registered for key 'furnace' in 'minecraft:block_entity'");
+}
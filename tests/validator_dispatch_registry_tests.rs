@@ -0,0 +1,59 @@
+//! Tests for dispatches sharing a key in different registries
+
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::parser::Declaration;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:recipe_serializer[special] to struct SpecialRecipe {
+    category: string,
+}
+
+dispatch minecraft:loot_function[special] to struct SpecialFunction {
+    count: int,
+}
+
+dispatch minecraft:resource[recipe] to struct Recipe {
+    type: string,
+    ...minecraft:recipe_serializer[[type]],
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("special.mcdoc".to_string(), ast).unwrap();
+    validator
+}
+
+#[test]
+fn test_dispatch_source_keeps_namespace_and_registry() {
+    let ast = parse_mcdoc(MCDOC).unwrap();
+    let Declaration::Dispatch(dispatch) = &ast.declarations[1] else { panic!("Expected dispatch") };
+    assert_eq!((dispatch.source.namespace, dispatch.source.registry), ("minecraft", "loot_function"));
+}
+
+#[test]
+fn test_same_key_resolves_per_registry() {
+    let validator = setup_validator();
+
+    let serializer = validator.validate_json(&json!({ "category": "misc" }), "minecraft:recipe_serializer[special]", None);
+    assert!(serializer.is_valid, "Validation failed: {:?}", serializer.errors);
+    let function = validator.validate_json(&json!({ "count": 2 }), "minecraft:loot_function[special]", None);
+    assert!(function.is_valid, "Validation failed: {:?}", function.errors);
+
+    let wrong = validator.validate_json(&json!({ "category": "misc" }), "loot_function[special]", None);
+    assert!(wrong.errors.iter().any(|e| e.path == "count"), "Unexpected errors: {:?}", wrong.errors);
+}
+
+#[test]
+fn test_spread_uses_its_registry() {
+    let validator = setup_validator();
+
+    let result = validator.validate_json(&json!({ "type": "special", "category": "misc" }), "recipe", None);
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+
+    let result = validator.validate_json(&json!({ "type": "special", "count": 1 }), "recipe", None);
+    assert!(result.errors.iter().any(|e| e.path == "category"), "Unexpected errors: {:?}", result.errors);
+}
@@ -0,0 +1,81 @@
+//! Tests for validating "type + payload" objects against a dispatcher registry
+
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::ErrorCode;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:recipe_serializer[crafting_shapeless] to struct Shapeless {
+    ingredients: [#[id="item"] string],
+}
+
+dispatch minecraft:recipe_serializer[%unknown] to struct UnknownSerializer {
+    weird_value: int,
+}
+
+dispatch minecraft:rule_test[always_true] to struct AlwaysTrue {}
+
+dispatch minecraft:rule_test[random_chance] to struct RandomChance {
+    probability: float @ 0..1,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("dispatched.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!(["minecraft:stick"])).unwrap();
+    validator.load_registry("recipe_serializer".to_string(), "1.21".to_string(), &json!(["minecraft:crafting_shapeless"])).unwrap();
+    validator.load_registry("rule_test".to_string(), "1.21".to_string(), &json!(["minecraft:always_true", "minecraft:random_chance"])).unwrap();
+    validator
+}
+
+#[test]
+fn test_known_key_validates_payload() {
+    let validator = setup_validator();
+
+    let result = validator.validate_dispatched(&json!({ "type": "minecraft:crafting_shapeless", "ingredients": ["stick"] }), "recipe_serializer", None);
+    assert!(result.is_valid, "{:?}", result.errors);
+    assert!(result.dependencies.iter().any(|dependency| {
+        &*dependency.resource_location == "minecraft:crafting_shapeless"
+            && &*dependency.registry_type == "recipe_serializer"
+            && dependency.source_path == "type"
+    }), "{:?}", result.dependencies);
+    assert!(result.dependencies.iter().any(|dependency| &*dependency.resource_location == "minecraft:stick"));
+
+    let result = validator.validate_dispatched(&json!({ "type": "random_chance", "probability": 2.0 }), "minecraft:rule_test", None);
+    assert!(!result.is_valid);
+    assert_eq!(result.errors[0].path, "probability");
+    assert_eq!(result.errors[0].code, ErrorCode::OutOfRange);
+}
+
+#[test]
+fn test_unknown_key_uses_unknown_fallback() {
+    let validator = setup_validator();
+
+    // Validated against `%unknown`, but the serializer registry doesn't list the key
+    let result = validator.validate_dispatched(&json!({ "type": "mod:custom", "weird_value": 3 }), "recipe_serializer", None);
+    assert_eq!(result.errors.len(), 1, "{:?}", result.errors);
+    assert_eq!(result.errors[0].code, ErrorCode::RegistryMiss);
+    assert_eq!(result.errors[0].path, "type");
+
+    let result = validator.validate_dispatched(&json!({ "type": "mod:custom", "weird_value": "3" }), "recipe_serializer", None);
+    assert!(result.errors.iter().any(|error| error.path == "weird_value" && error.code == ErrorCode::TypeMismatch), "{:?}", result.errors);
+
+    // No `%unknown` for rule tests
+    let result = validator.validate_dispatched(&json!({ "type": "mod:custom" }), "rule_test", None);
+    assert!(result.errors.iter().any(|error| error.path == "type" && error.code == ErrorCode::UnknownDispatchKey), "{:?}", result.errors);
+}
+
+#[test]
+fn test_missing_type_field() {
+    let validator = setup_validator();
+
+    let result = validator.validate_dispatched(&json!({ "probability": 0.5 }), "rule_test", None);
+    assert!(!result.is_valid);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].path, "type");
+    assert_eq!(result.errors[0].code, ErrorCode::MissingField);
+
+    let result = validator.validate_dispatched(&json!({ "type": 3 }), "rule_test", None);
+    assert_eq!(result.errors[0].code, ErrorCode::TypeMismatch);
+}
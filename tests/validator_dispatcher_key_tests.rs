@@ -0,0 +1,68 @@
+//! Tests for `#[dispatcher_key]` annotated string fields
+
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::types::DependencyKind;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:loot_function[set_count] to struct SetCount {
+    count: int,
+}
+
+dispatch minecraft:loot_function[enchant_randomly] to struct EnchantRandomly {
+    only_compatible?: boolean,
+}
+
+dispatch minecraft:resource[function_ref] to struct FunctionRef {
+    function: #[dispatcher_key="minecraft:loot_function"] string,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("loot_function.mcdoc".to_string(), ast).unwrap();
+    validator
+}
+
+#[test]
+fn test_dispatcher_key_accepts_registered_key() {
+    let validator = setup_validator();
+    let json = json!({ "function": "minecraft:set_count" });
+
+    let result = validator.validate_json(&json, "function_ref", None);
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+
+    let bare = validator.validate_json(&json!({ "function": "enchant_randomly" }), "function_ref", None);
+    assert!(bare.is_valid, "Validation failed: {:?}", bare.errors);
+}
+
+#[test]
+fn test_dispatcher_key_rejects_unknown_key_with_suggestions() {
+    let validator = setup_validator();
+    let json = json!({ "function": "minecraft:set_cont" });
+
+    let result = validator.validate_json(&json, "function_ref", None);
+    assert!(!result.is_valid);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].path, "function");
+    assert!(result.errors[0].message.contains("minecraft:set_cont"));
+    assert!(result.errors[0].message.contains("enchant_randomly, set_count"));
+}
+
+#[test]
+fn test_dispatcher_key_dependency_kind() {
+    let validator = setup_validator();
+    let json = json!({ "function": "minecraft:set_count" });
+
+    let result = validator.validate_json(&json, "function_ref", None);
+    assert_eq!(result.dependencies.len(), 1);
+
+    let dependency = &result.dependencies[0];
+    assert_eq!(dependency.dependency_kind, DependencyKind::DispatcherKey);
+    assert_eq!(dependency.registry_type, "minecraft:loot_function");
+    assert_eq!(dependency.resource_location, "minecraft:set_count");
+    assert_eq!(dependency.source_path, "function");
+    assert!(!dependency.is_tag);
+}
@@ -0,0 +1,121 @@
+//! Tests for loading the same MCDOC content under several filenames
+
+use voxel_rsmcdoc::{parse_mcdoc, ErrorCode, Severity};
+use voxel_rsmcdoc::validator::{ConflictPolicy, DatapackValidator};
+use serde_json::json;
+
+const RECIPE_MCDOC: &str = r#"
+dispatch minecraft:resource[recipe] to struct Recipe {
+    type: string,
+    result: #[id="item"] string,
+}
+"#;
+
+const MODIFIED_RECIPE_MCDOC: &str = r#"
+dispatch minecraft:resource[recipe] to struct Recipe {
+    type: string,
+    result: #[id="item"] string,
+    count?: int,
+}
+"#;
+
+#[test]
+fn test_identical_content_is_registered_as_alias() {
+    let mut validator = DatapackValidator::new();
+    validator.load_parsed_mcdoc("recipe.mcdoc".to_string(), parse_mcdoc(RECIPE_MCDOC).unwrap()).unwrap();
+    validator.load_parsed_mcdoc("copy_of_recipe.mcdoc".to_string(), parse_mcdoc(RECIPE_MCDOC).unwrap()).unwrap();
+
+    assert_eq!(validator.schema_names().count(), 1);
+    assert!(validator.schema("recipe.mcdoc").is_some());
+    assert_eq!(
+        validator.schema_aliases.get("copy_of_recipe.mcdoc").map(String::as_str),
+        Some("recipe.mcdoc")
+    );
+
+    let json = json!({ "type": "minecraft:crafting_shaped", "result": "minecraft:stone" });
+    let result = validator.validate_json(&json, "recipe", None);
+    assert_eq!(result.dependencies.len(), 1);
+}
+
+#[test]
+fn test_modified_content_is_loaded_separately() {
+    let mut validator = DatapackValidator::new();
+    validator.load_parsed_mcdoc("recipe.mcdoc".to_string(), parse_mcdoc(RECIPE_MCDOC).unwrap()).unwrap();
+    validator.load_parsed_mcdoc("copy_of_recipe.mcdoc".to_string(), parse_mcdoc(MODIFIED_RECIPE_MCDOC).unwrap()).unwrap();
+
+    assert_eq!(validator.schema_names().count(), 2);
+    assert!(validator.schema_aliases.is_empty());
+}
+
+#[test]
+fn test_reloading_alias_with_new_content_replaces_alias() {
+    let mut validator = DatapackValidator::new();
+    validator.load_parsed_mcdoc("recipe.mcdoc".to_string(), parse_mcdoc(RECIPE_MCDOC).unwrap()).unwrap();
+    validator.load_parsed_mcdoc("copy_of_recipe.mcdoc".to_string(), parse_mcdoc(RECIPE_MCDOC).unwrap()).unwrap();
+    validator.load_parsed_mcdoc("copy_of_recipe.mcdoc".to_string(), parse_mcdoc(MODIFIED_RECIPE_MCDOC).unwrap()).unwrap();
+
+    assert_eq!(validator.schema_names().count(), 2);
+    assert!(!validator.schema_aliases.contains_key("copy_of_recipe.mcdoc"));
+}
+
+#[test]
+fn test_reloading_same_name_is_not_an_alias() {
+    let mut validator = DatapackValidator::new();
+    validator.load_parsed_mcdoc("recipe.mcdoc".to_string(), parse_mcdoc(RECIPE_MCDOC).unwrap()).unwrap();
+    validator.load_parsed_mcdoc("recipe.mcdoc".to_string(), parse_mcdoc(RECIPE_MCDOC).unwrap()).unwrap();
+
+    assert_eq!(validator.schema_names().count(), 1);
+    assert!(validator.schema_aliases.is_empty());
+}
+
+#[test]
+fn test_same_content_with_other_spacing_is_an_alias() {
+    let reformatted = RECIPE_MCDOC.replace("\n    ", "\n        ").replace(": ", " : ");
+    let mut validator = DatapackValidator::new();
+    validator.load_parsed_mcdoc("recipe.mcdoc".to_string(), parse_mcdoc(RECIPE_MCDOC).unwrap()).unwrap();
+    validator.load_parsed_mcdoc("copy_of_recipe.mcdoc".to_string(), parse_mcdoc(&reformatted).unwrap()).unwrap();
+
+    assert_eq!(validator.schema_names().count(), 1);
+    assert_eq!(
+        validator.schema_aliases.get("copy_of_recipe.mcdoc").map(String::as_str),
+        Some("recipe.mcdoc")
+    );
+}
+
+#[test]
+fn test_alias_has_one_info_note() {
+    let mut validator = DatapackValidator::new();
+    validator.conflict_policy = ConflictPolicy::Error;
+    validator.load_parsed_mcdoc("recipe.mcdoc".to_string(), parse_mcdoc(RECIPE_MCDOC).unwrap()).unwrap();
+    validator.load_parsed_mcdoc("copy_of_recipe.mcdoc".to_string(), parse_mcdoc(RECIPE_MCDOC).unwrap()).unwrap();
+
+    let notes = validator.schema_notes("copy_of_recipe.mcdoc");
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].severity, Severity::Info);
+    assert_eq!(notes[0].code, ErrorCode::DuplicateSchema);
+    assert_eq!(notes[0].file.as_ref(), "copy_of_recipe.mcdoc");
+    assert_eq!(notes[0].message, "Same content as 'recipe.mcdoc', loaded as its alias");
+    assert!(validator.schema_notes("recipe.mcdoc").is_empty());
+
+    // Unloading the original hands its content over to the alias
+    validator.unload_mcdoc("recipe.mcdoc");
+    assert!(validator.schema("copy_of_recipe.mcdoc").is_some());
+    assert!(validator.schema_notes("copy_of_recipe.mcdoc").is_empty());
+    validator.load_parsed_mcdoc("recipe.mcdoc".to_string(), parse_mcdoc(RECIPE_MCDOC).unwrap()).unwrap();
+    assert_eq!(validator.schema_aliases.get("recipe.mcdoc").map(String::as_str), Some("copy_of_recipe.mcdoc"));
+}
+
+#[test]
+fn test_identical_dispatch_in_other_schema_is_no_conflict() {
+    let with_extra = format!("{}\nstruct Extra {{ value: int }}", RECIPE_MCDOC);
+    let mut validator = DatapackValidator::new();
+    validator.conflict_policy = ConflictPolicy::Error;
+    validator.load_parsed_mcdoc("recipe.mcdoc".to_string(), parse_mcdoc(RECIPE_MCDOC).unwrap()).unwrap();
+    validator.load_parsed_mcdoc("vendored.mcdoc".to_string(), parse_mcdoc(&with_extra).unwrap()).unwrap();
+    assert!(validator.schema_conflicts("vendored.mcdoc").is_empty());
+
+    // Different content under the same dispatch key follows the policy
+    let errors = validator.load_parsed_mcdoc("modified.mcdoc".to_string(), parse_mcdoc(MODIFIED_RECIPE_MCDOC).unwrap()).unwrap_err();
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+    assert!(validator.schema("modified.mcdoc").is_none());
+}
@@ -0,0 +1,105 @@
+//! Tests for validating the keys of dynamic struct fields
+
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+enum(string) Slot {
+    Head = "head",
+    Chest = "chest",
+}
+
+dispatch minecraft:resource[predicate] to struct Predicate {
+    effects?: struct {
+        [#[id="mob_effect"] string]: struct {
+            amplifier?: int,
+        },
+    },
+    slots?: struct {
+        [Slot]: int,
+    },
+    named?: struct {
+        count: int,
+        ["extra"]: string,
+    },
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("predicate.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator.load_registry("mob_effect".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:speed": {}, "minecraft:haste": {} }
+    })).unwrap();
+    validator
+}
+
+#[test]
+fn test_id_keys_are_dependencies() {
+    let validator = setup_validator();
+    let json = json!({
+        "effects": {
+            "minecraft:speed": { "amplifier": 1 },
+            "minecraft:haste": {},
+            "minecraft:not_an_effect": {}
+        }
+    });
+
+    let result = validator.validate_json(&json, "predicate", None);
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "effects.minecraft:not_an_effect");
+    assert!(result.errors[0].message.contains("not found in registry 'mob_effect'"));
+
+    let locations: Vec<_> = result.dependencies.iter().map(|d| d.resource_location.as_str()).collect();
+    assert!(locations.contains(&"minecraft:speed"));
+    assert!(locations.contains(&"minecraft:haste"));
+    assert!(result.dependencies.iter().all(|d| d.registry_type == "mob_effect"));
+}
+
+#[test]
+fn test_enum_keys() {
+    let validator = setup_validator();
+
+    let valid = validator.validate_json(&json!({ "slots": { "head": 1, "chest": 2 } }), "predicate", None);
+    assert!(valid.is_valid, "Validation failed: {:?}", valid.errors);
+
+    let invalid = validator.validate_json(&json!({ "slots": { "feet": 1 } }), "predicate", None);
+    assert_eq!(invalid.errors.len(), 1, "Unexpected errors: {:?}", invalid.errors);
+    assert_eq!(invalid.errors[0].path, "slots.feet");
+}
+
+#[test]
+fn test_literal_keys_skip_named_fields() {
+    let validator = setup_validator();
+
+    let valid = validator.validate_json(&json!({ "named": { "count": 1, "extra": "x" } }), "predicate", None);
+    assert!(valid.is_valid, "Validation failed: {:?}", valid.errors);
+
+    let invalid = validator.validate_json(&json!({ "named": { "count": 1, "other": "x" } }), "predicate", None);
+    assert_eq!(invalid.errors.len(), 1, "Unexpected errors: {:?}", invalid.errors);
+    assert_eq!(invalid.errors[0].path, "named.other");
+}
+
+#[test]
+fn test_key_length_constraints() {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("names.mcdoc".to_string(), r#"
+dispatch minecraft:resource[names] to struct Names {
+    [#[id="mob_effect"] string @ 1..]?: int,
+    labels?: struct { [string @ 2..3]: int },
+}
+"#.to_string()).unwrap();
+    validator.load_registry("mob_effect".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:speed": {} }
+    })).unwrap();
+
+    let result = validator.validate_json(&json!({ "speed": 1, "labels": { "ab": 1, "a": 2, "abcd": 3 } }), "names", None);
+    let errors: Vec<(&str, &str)> = result.errors.iter().map(|error| (error.path.as_str(), error.message.as_str())).collect();
+    assert_eq!(errors, [
+        ("labels.a", "Expected a string of at least 2 characters, found 1"),
+        ("labels.abcd", "Expected a string of at most 3 characters, found 4"),
+    ]);
+
+    let result = validator.validate_json(&json!({ "": 1 }), "names", None);
+    assert!(result.errors.iter().any(|error| error.message == "Expected a string of at least 1 characters, found 0"), "{:?}", result.errors);
+}
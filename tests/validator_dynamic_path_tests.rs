@@ -0,0 +1,81 @@
+//! Tests for dynamic references reading the discriminator from enclosing objects
+
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[machine] to struct Machine {
+    kind: string,
+    meta?: struct { variant: string },
+    settings: struct Settings {
+        ...minecraft:setting[[%parent.kind]],
+    },
+    shorthand?: struct {
+        ...minecraft:setting[[..kind]],
+    },
+    variant_settings?: struct {
+        ...minecraft:setting[[%parent.meta.variant]],
+    },
+}
+
+dispatch minecraft:setting[speed] to struct {
+    speed: float,
+}
+
+dispatch minecraft:setting[color] to struct {
+    color: string,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("machine.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator
+}
+
+#[test]
+fn test_parent_discriminator_selects_target() {
+    let validator = setup_validator();
+
+    let speed = validator.validate_json(&json!({ "kind": "speed", "settings": { "speed": 1.5 } }), "machine", None);
+    assert!(speed.is_valid, "Validation failed: {:?}", speed.errors);
+
+    let color = validator.validate_json(&json!({ "kind": "color", "settings": { "color": "red" } }), "machine", None);
+    assert!(color.is_valid, "Validation failed: {:?}", color.errors);
+}
+
+#[test]
+fn test_parent_discriminator_reports_target_errors() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({ "kind": "speed", "settings": { "speed": "fast" } }), "machine", None);
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "settings.speed");
+}
+
+#[test]
+fn test_dot_dot_prefix_reads_parent() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({
+        "kind": "color",
+        "settings": { "color": "red" },
+        "shorthand": { "color": 3 }
+    }), "machine", None);
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "shorthand.color");
+}
+
+#[test]
+fn test_parent_path_into_nested_field() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({
+        "kind": "color",
+        "meta": { "variant": "speed" },
+        "settings": { "color": "red" },
+        "variant_settings": { "speed": "slow" }
+    }), "machine", None);
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "variant_settings.speed");
+}
@@ -0,0 +1,65 @@
+//! Tests for fields typed by an enum declaration
+
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[narration] to struct Narration {
+    priority: NarrationPriority,
+    level?: Level,
+}
+
+enum(string) NarrationPriority {
+    Chat = "chat",
+    System = "system",
+}
+
+enum(int) Level {
+    Low = 1,
+    High = 3,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("narration.mcdoc".to_string(), ast).unwrap();
+    validator
+}
+
+#[test]
+fn test_enum_value_accepted() {
+    let validator = setup_validator();
+
+    let result = validator.validate_json(&json!({ "priority": "chat", "level": 3 }), "narration", None);
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+}
+
+#[test]
+fn test_enum_value_rejected_with_allowed_set() {
+    let validator = setup_validator();
+
+    let result = validator.validate_json(&json!({ "priority": "loud" }), "narration", None);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].path, "priority");
+    assert_eq!(
+        result.errors[0].message,
+        "Invalid value \"loud\" for enum 'NarrationPriority', expected one of: 'chat', 'system'"
+    );
+
+    let numeric = validator.validate_json(&json!({ "priority": "chat", "level": 2 }), "narration", None);
+    assert_eq!(numeric.errors.len(), 1);
+    assert!(numeric.errors[0].message.contains("expected one of: 1, 3"));
+}
+
+#[test]
+fn test_enum_base_type_checked() {
+    let validator = setup_validator();
+
+    let result = validator.validate_json(&json!({ "priority": 1, "level": "high" }), "narration", None);
+    let mut paths: Vec<_> = result.errors.iter().map(|e| e.path.as_str()).collect();
+    paths.sort();
+    assert_eq!(paths, ["level", "priority"]);
+    assert!(result.errors.iter().any(|e| e.message == "Expected string value of enum 'NarrationPriority', found 1"));
+}
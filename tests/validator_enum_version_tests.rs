@@ -0,0 +1,84 @@
+//! Tests for enum variants gated by version or deprecated
+
+use std::fs;
+use serde_json::json;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::ErrorCode;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let path = format!("{}/tests/dataset/mcdoc/data/chat_type.mcdoc", env!("CARGO_MANIFEST_DIR"));
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("data/chat_type.mcdoc".to_string(), fs::read_to_string(path).unwrap()).unwrap();
+    validator.load_mcdoc_source("java/util/text.mcdoc".to_string(), "struct TextStyle { bold?: boolean }".to_string()).unwrap();
+    validator
+}
+
+/// Chat type decorating with `parameters`, shaped for versions before and after 1.19.1
+fn chat_type(version: &str, parameters: &[&str]) -> serde_json::Value {
+    let decoration = json!({ "translation_key": "chat.type.text", "parameters": parameters, "style": {} });
+    if version == "1.19" {
+        json!({ "chat": { "decoration": decoration } })
+    } else {
+        json!({ "chat": decoration })
+    }
+}
+
+#[test]
+fn test_variant_until_version() {
+    let validator = setup_validator();
+
+    let result = validator.validate_json(&chat_type("1.19", &["sender", "team_name"]), "chat_type", Some("1.19"));
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+
+    let result = validator.validate_json(&chat_type("1.20", &["sender", "team_name"]), "chat_type", Some("1.20"));
+    // `chat` is a union, the enum error is reported within the union's
+    assert_eq!(result.errors.len(), 1, "{:?}", result.errors);
+    assert!(result.errors[0].message.contains(
+        "Invalid value \"team_name\" for enum 'ChatDecorationParameter', expected one of: 'sender', 'content', 'target' at 'chat.parameters[1]'"
+    ), "{}", result.errors[0].message);
+}
+
+#[test]
+fn test_variant_since_version() {
+    let validator = setup_validator();
+
+    let result = validator.validate_json(&chat_type("1.20", &["content", "target"]), "chat_type", Some("1.20"));
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+
+    let result = validator.validate_json(&chat_type("1.19", &["target"]), "chat_type", Some("1.19"));
+    assert!(result.errors[0].message.contains(
+        "Invalid value \"target\" for enum 'ChatDecorationParameter', expected one of: 'sender', 'content', 'team_name' at 'chat.decoration.parameters[0]'"
+    ), "{}", result.errors[0].message);
+}
+
+#[test]
+fn test_every_variant_without_version() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({ "chat": { "translation_key": "a", "parameters": ["team_name", "target"] } }), "chat_type", None);
+    assert!(result.errors.iter().all(|error| !error.path.starts_with("chat.parameters")), "{:?}", result.errors);
+}
+
+#[test]
+fn test_deprecated_variant_warns() {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("mode.mcdoc".to_string(), r#"
+dispatch minecraft:resource[mode] to struct { mode: Mode }
+enum(string) Mode {
+    Any = "any",
+    #[deprecated="1.20"]
+    Old = "old",
+    #[deprecated]
+    Older = "older",
+}
+"#.to_string()).unwrap();
+
+    let result = validator.validate_json(&json!({ "mode": "old" }), "mode", Some("1.20.5"));
+    assert!(result.is_valid);
+    assert_eq!(result.warnings.len(), 1);
+    assert_eq!(result.warnings[0].code, ErrorCode::Deprecated);
+    assert_eq!(result.warnings[0].message, "enum 'Mode' \"old\" is deprecated since 1.20");
+
+    assert!(validator.validate_json(&json!({ "mode": "old" }), "mode", Some("1.19")).warnings.is_empty());
+    assert!(validator.validate_json(&json!({ "mode": "any" }), "mode", None).warnings.is_empty());
+    assert_eq!(validator.validate_json(&json!({ "mode": "older" }), "mode", None).warnings[0].message, "enum 'Mode' \"older\" is deprecated");
+}
@@ -0,0 +1,104 @@
+//! Tests for the error codes attached to validation errors
+
+use voxel_rsmcdoc::error::ErrorCode;
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::types::ValidationResult;
+use voxel_rsmcdoc::validator::{DatapackValidator, ValidationOptions};
+use serde_json::json;
+
+const MCDOC: &str = r#"
+enum(string) Color {
+    Red = "red",
+    Blue = "blue",
+}
+
+dispatch minecraft:resource[sample] to struct Sample {
+    name: string,
+    count?: int @ 1..10,
+    tags?: [string] @ 1..2,
+    kind?: "fixed",
+    color?: Color,
+    either?: (int | boolean),
+    item?: #[id(registry="item", tags="allowed")] string,
+    block?: #[id="block"] string,
+    loot?: #[id(registry="loot_table", tags="required")] string,
+    shape?: Shape,
+    #[since="1.21"]
+    modern?: string,
+    #[deprecated]
+    legacy?: string,
+    effect?: #[dispatcher_key="minecraft:effect"] string,
+}
+
+dispatch minecraft:effect[glow] to struct Glow {}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("sample.mcdoc".to_string(), ast).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:stone": {} },
+        "tags": { "minecraft:empty": [] }
+    })).unwrap();
+    validator
+}
+
+fn codes(result: &ValidationResult) -> Vec<ErrorCode> {
+    result.errors.iter().map(|error| error.code).collect()
+}
+
+fn validate(json: serde_json::Value) -> ValidationResult {
+    setup_validator().validate_json(&json, "sample", Some("1.20"))
+}
+
+#[test]
+fn test_structural_codes() {
+    assert_eq!(codes(&validate(json!({}))), [ErrorCode::MissingField]);
+    assert_eq!(codes(&validate(json!({ "name": 3 }))), [ErrorCode::TypeMismatch]);
+    assert_eq!(codes(&validate(json!({ "name": "a", "modern": "x" }))), [ErrorCode::UnavailableField]);
+
+    let strict = ValidationOptions { strict_fields: true, ..Default::default() };
+    let result = setup_validator().validate_json_with_options(&json!({ "name": "a", "extra": 1 }), "sample", None, &strict);
+    assert_eq!(codes(&result), [ErrorCode::UnknownField]);
+}
+
+#[test]
+fn test_value_codes() {
+    assert_eq!(codes(&validate(json!({ "name": "a", "count": 20 }))), [ErrorCode::OutOfRange]);
+    assert_eq!(codes(&validate(json!({ "name": "a", "tags": [] }))), [ErrorCode::OutOfRange]);
+    assert_eq!(codes(&validate(json!({ "name": "a", "kind": "other" }))), [ErrorCode::LiteralMismatch]);
+    assert_eq!(codes(&validate(json!({ "name": "a", "color": "green" }))), [ErrorCode::LiteralMismatch]);
+    assert_eq!(codes(&validate(json!({ "name": "a", "either": "text" }))), [ErrorCode::UnionNoMatch]);
+    assert_eq!(codes(&validate(json!({ "name": "a", "shape": {} }))), [ErrorCode::UnresolvedType]);
+}
+
+#[test]
+fn test_reference_codes() {
+    assert_eq!(codes(&validate(json!({ "name": "a", "item": "minecraft:dirt" }))), [ErrorCode::RegistryMiss]);
+    assert_eq!(codes(&validate(json!({ "name": "a", "block": "minecraft:stone" }))), [ErrorCode::UnknownRegistry]);
+    assert_eq!(codes(&validate(json!({ "name": "a", "item": ":stone" }))), [ErrorCode::EmptyNamespace]);
+    assert_eq!(codes(&validate(json!({ "name": "a", "loot": "minecraft:chest" }))), [ErrorCode::InvalidResourceLocation]);
+    assert_eq!(codes(&validate(json!({ "name": "a", "effect": "sparkle" }))), [ErrorCode::UnknownDispatchKey]);
+
+    let check_tags = ValidationOptions { check_tag_contents: true, ..Default::default() };
+    let result = setup_validator().validate_json_with_options(&json!({ "name": "a", "item": "#minecraft:empty" }), "sample", None, &check_tags);
+    assert_eq!(codes(&result), [ErrorCode::InvalidTag]);
+}
+
+#[test]
+fn test_schema_and_warning_codes() {
+    let result = setup_validator().validate_json(&json!({}), "unknown_type", None);
+    assert_eq!(codes(&result), [ErrorCode::SchemaNotFound]);
+
+    let result = validate(json!({ "name": "a", "legacy": "x" }));
+    assert!(result.is_valid);
+    assert_eq!(result.warnings[0].code, ErrorCode::Deprecated);
+}
+
+#[test]
+fn test_code_is_serialized() {
+    let result = validate(json!({}));
+    let serialized = serde_json::to_value(&result).unwrap();
+    assert_eq!(serialized["errors"][0]["code"], json!("missingField"));
+}
@@ -0,0 +1,98 @@
+//! Tests for `#[exclusive]` groups of fields of which at most one may be present
+
+use serde_json::json;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::ErrorCode;
+
+const MCDOC: &str = r#"
+#[exclusive(fields=["item", "tag"])]
+struct Ingredient {
+    item?: #[id="item"] string,
+    tag?: string,
+    count?: int,
+}
+
+#[exclusive(fields=["name", "entity"], required=true)]
+#[exclusive(fields=["min", "max", "exact"])]
+struct Predicate {
+    name?: string,
+    entity?: string,
+    min?: int,
+    max?: int,
+    exact?: int,
+}
+
+dispatch minecraft:resource[ingredient] to Ingredient
+
+dispatch minecraft:resource[predicate] to Predicate
+
+dispatch minecraft:resource[recipe] to struct {
+    ingredients: [Ingredient],
+}
+
+dispatch minecraft:resource[inline] to #[exclusive(fields=["a", "b"])] struct {
+    a?: int,
+    b?: int,
+}
+"#;
+
+fn errors_of(json: serde_json::Value, resource_type: &str) -> Vec<(ErrorCode, String, String)> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("exclusive.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({ "entries": { "minecraft:stone": {} } })).unwrap();
+    let result = validator.validate_json(&json, resource_type, None);
+    result.errors.into_iter().map(|error| (error.code, error.path, error.message)).collect()
+}
+
+#[test]
+fn test_both_fields_present() {
+    assert_eq!(errors_of(json!({ "item": "minecraft:stone", "tag": "minecraft:logs" }), "ingredient"), [(
+        ErrorCode::ExclusiveFields,
+        String::new(),
+        "Fields 'item' and 'tag' are mutually exclusive".to_string(),
+    )]);
+}
+
+#[test]
+fn test_one_field_present() {
+    assert!(errors_of(json!({ "item": "minecraft:stone", "count": 2 }), "ingredient").is_empty());
+    assert!(errors_of(json!({ "tag": "minecraft:logs" }), "ingredient").is_empty());
+    assert!(errors_of(json!({ "count": 2 }), "ingredient").is_empty());
+}
+
+#[test]
+fn test_none_present_in_required_group() {
+    assert_eq!(errors_of(json!({ "min": 1 }), "predicate"), [(
+        ErrorCode::ExclusiveFields,
+        String::new(),
+        "Expected one of the fields 'name', 'entity'".to_string(),
+    )]);
+}
+
+#[test]
+fn test_groups_are_enforced_separately() {
+    assert!(errors_of(json!({ "name": "a", "min": 1 }), "predicate").is_empty());
+
+    let errors = errors_of(json!({ "name": "a", "min": 1, "max": 2, "exact": 3 }), "predicate");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].2, "Fields 'min', 'max' and 'exact' are mutually exclusive");
+
+    let errors = errors_of(json!({ "name": "a", "entity": "b", "exact": 3 }), "predicate");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].2, "Fields 'name' and 'entity' are mutually exclusive");
+}
+
+#[test]
+fn test_annotated_inline_struct() {
+    assert!(errors_of(json!({ "a": 1 }), "inline").is_empty());
+    let errors = errors_of(json!({ "a": 1, "b": 2 }), "inline");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, ErrorCode::ExclusiveFields);
+}
+
+#[test]
+fn test_struct_referenced_by_a_field() {
+    let errors = errors_of(json!({ "ingredients": [{ "tag": "minecraft:logs" }, { "item": "minecraft:stone", "tag": "minecraft:logs" }] }), "recipe");
+    assert_eq!(errors.len(), 1);
+    assert_eq!((errors[0].0, errors[0].1.as_str()), (ErrorCode::ExclusiveFields, "ingredients[1]"));
+}
@@ -0,0 +1,76 @@
+//! Tests for exclusive range bounds like `0..<1` and `0<..1`
+
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::lexer::{Lexer, Token};
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[chance] to struct Chance {
+    probability?: float @ 0..<1,
+    weight?: float @ 0<..,
+    ratio?: float @ 0<..<1,
+    name?: string @ ..<4,
+    items?: [int] @ 1<..3,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("chance.mcdoc".to_string(), ast).unwrap();
+    validator
+}
+
+fn error_messages(json: serde_json::Value) -> Vec<String> {
+    let result = setup_validator().validate_json(&json, "chance", None);
+    result.errors.iter().map(|e| format!("{}: {}", e.path, e.message)).collect()
+}
+
+#[test]
+fn test_lexer_range_operators() {
+    let tokens: Vec<Token> = Lexer::new("0..<1 0<..1 0<..<1")
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|t| t.token)
+        .filter(|t| !matches!(t, Token::Whitespace | Token::Eof))
+        .collect();
+
+    assert_eq!(tokens, vec![
+        Token::Number(0.0), Token::DotDotLess, Token::Number(1.0),
+        Token::Number(0.0), Token::LessDotDot, Token::Number(1.0),
+        Token::Number(0.0), Token::LessDotDotLess, Token::Number(1.0),
+    ]);
+}
+
+#[test]
+fn test_exclusive_maximum() {
+    assert!(error_messages(json!({ "probability": 0.999 })).is_empty());
+    assert!(error_messages(json!({ "probability": 0.0 })).is_empty());
+    assert_eq!(error_messages(json!({ "probability": 1.0 })), ["probability: Expected a value less than 1, found 1"]);
+}
+
+#[test]
+fn test_exclusive_minimum() {
+    assert!(error_messages(json!({ "weight": 0.5, "ratio": 0.5 })).is_empty());
+    assert_eq!(error_messages(json!({ "weight": 0.0, "ratio": 1.0 })), [
+        "weight: Expected a value greater than 0, found 0",
+        "ratio: Expected a value less than 1, found 1",
+    ]);
+}
+
+#[test]
+fn test_exclusive_lengths() {
+    assert!(error_messages(json!({ "name": "abc", "items": [1, 2] })).is_empty());
+    assert_eq!(error_messages(json!({ "name": "abcd", "items": [1] })), [
+        "name: Expected a string of fewer than 4 characters, found 4",
+        "items: Expected more than 1 elements, found 1",
+    ]);
+}
+
+#[test]
+fn test_excluded_bound_must_be_written() {
+    assert!(parse_mcdoc("type Chance = float @ 0..<").is_err());
+    assert!(parse_mcdoc("type Chance = float @ <..1").is_err());
+}
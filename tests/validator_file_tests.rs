@@ -0,0 +1,82 @@
+//! Tests for validating files by their path in a datapack
+
+use serde_json::json;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::ErrorCode;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[recipe] to struct Recipe {
+    result: #[id="item"] string,
+    count?: int,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("recipe.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:stone": {}, "minecraft:dirt": {} },
+        "tags": { "minecraft:logs": ["minecraft:stone"] }
+    })).unwrap();
+    validator
+}
+
+#[test]
+fn test_recipe_by_path() {
+    let validator = setup_validator();
+    let path = "data/mypack/recipes/sword.json";
+
+    let result = validator.validate_file(path, &json!({ "result": "minecraft:stone" }), Some("1.21"));
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+    assert_eq!(result.dependencies[0].source_file.as_deref(), Some(path));
+
+    let result = validator.validate_file(path, &json!({ "result": "minecraft:gold", "count": "one" }), None);
+    let errors: Vec<(&str, &str)> = result.errors.iter().map(|error| (error.file.as_ref(), error.path.as_str())).collect();
+    assert_eq!(errors, [(path, "count"), (path, "result")]);
+}
+
+#[test]
+fn test_tag_values_checked_against_path_registry() {
+    let validator = setup_validator();
+    let path = "data/mypack/tags/item/tools.json";
+
+    let result = validator.validate_file(path, &json!({ "values": ["minecraft:dirt", "#minecraft:logs"] }), None);
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+    let dependencies: Vec<(&str, &str, bool)> = result.dependencies.iter()
+        .map(|dependency| (dependency.registry_type.as_ref(), dependency.resource_location.as_ref(), dependency.is_tag))
+        .collect();
+    assert_eq!(dependencies, [("item", "minecraft:dirt", false), ("item", "#minecraft:logs", true)]);
+    assert!(result.dependencies.iter().all(|dependency| dependency.source_file.as_deref() == Some(path)));
+
+    let result = validator.validate_file("data/mypack/tags/items/tools.json", &json!({ "values": [
+        "minecraft:gold",
+        { "id": "minecraft:diamond", "required": false },
+        { "id": "minecraft:emerald" },
+    ] }), None);
+    let errors: Vec<(&str, &str)> = result.errors.iter().map(|error| (error.path.as_str(), error.message.as_str())).collect();
+    assert_eq!(errors, [
+        ("values[0]", "Resource 'minecraft:gold' not found in registry 'item'"),
+        ("values[2].id", "Resource 'minecraft:emerald' not found in registry 'item'"),
+    ]);
+}
+
+#[test]
+fn test_tag_of_unloaded_registry() {
+    let validator = setup_validator();
+    let result = validator.validate_file("data/mypack/tags/block/ores.json", &json!({ "values": ["minecraft:gold_ore"] }), None);
+    assert!(result.errors.is_empty());
+    assert_eq!(result.warnings[0].code, ErrorCode::UnknownRegistry);
+}
+
+#[test]
+fn test_unknown_folder() {
+    let validator = setup_validator();
+    let path = "data/mypack/not_a_resource/thing.json";
+    let result = validator.validate_file(path, &json!({}), None);
+
+    assert!(!result.is_valid);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].file, path);
+    assert_eq!(result.errors[0].code, ErrorCode::SchemaNotFound);
+    assert_eq!(result.errors[0].message, "Could not infer the resource type of 'data/mypack/not_a_resource/thing.json'");
+}
@@ -0,0 +1,105 @@
+//! Tests for instantiating generic type aliases during validation
+
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+type Conditions<C> = struct {
+    player?: string,
+    conditions?: C,
+}
+
+type Pair<A, B> = struct {
+    first: A,
+    second: B,
+}
+
+type Wrapped<T> = struct {
+    layer: Conditions<[T]>,
+}
+
+dispatch minecraft:trigger[placed_block] to Conditions<struct PlacedBlock {
+    block: string,
+}>
+
+dispatch minecraft:resource[pair] to Pair<int, string>
+
+dispatch minecraft:resource[wrapped] to Wrapped<int>
+
+dispatch minecraft:resource[broken] to Pair<int>
+
+type Layer<T> = struct {
+    texture: T,
+}
+
+type WingsLayer<T> = struct {
+    ...Layer<T>,
+    wings: boolean,
+}
+
+dispatch minecraft:resource[wings_layer] to WingsLayer<int>
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("generics.mcdoc".to_string(), ast).unwrap();
+    validator
+}
+
+#[test]
+fn test_struct_type_argument_is_substituted() {
+    let validator = setup_validator();
+
+    let valid = validator.validate_json(&json!({ "conditions": { "block": "minecraft:stone" } }), "minecraft:trigger[placed_block]", None);
+    assert!(valid.is_valid, "Validation failed: {:?}", valid.errors);
+
+    let invalid = validator.validate_json(&json!({ "conditions": { "block": 5 } }), "minecraft:trigger[placed_block]", None);
+    assert_eq!(invalid.errors.len(), 1);
+    assert_eq!(invalid.errors[0].path, "conditions.block");
+}
+
+#[test]
+fn test_multiple_type_parameters() {
+    let validator = setup_validator();
+
+    assert!(validator.validate_json(&json!({ "first": 1, "second": "two" }), "pair", None).is_valid);
+
+    let swapped = validator.validate_json(&json!({ "first": "one", "second": 2 }), "pair", None);
+    let mut paths: Vec<_> = swapped.errors.iter().map(|e| e.path.as_str()).collect();
+    paths.sort();
+    assert_eq!(paths, ["first", "second"]);
+}
+
+#[test]
+fn test_nested_generic_instantiation() {
+    let validator = setup_validator();
+
+    assert!(validator.validate_json(&json!({ "layer": { "conditions": [1, 2] } }), "wrapped", None).is_valid);
+
+    let invalid = validator.validate_json(&json!({ "layer": { "conditions": [1, "x"] } }), "wrapped", None);
+    assert_eq!(invalid.errors.len(), 1);
+    assert_eq!(invalid.errors[0].path, "layer.conditions[1]");
+}
+
+#[test]
+fn test_arity_mismatch() {
+    let validator = setup_validator();
+
+    let result = validator.validate_json(&json!({ "first": 1, "second": "two" }), "broken", None);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].message, "Type 'Pair' expects 2 type argument(s), found 1");
+}
+
+#[test]
+fn test_spread_of_generic_alias_is_substituted() {
+    let validator = setup_validator();
+
+    let valid = validator.validate_json(&json!({ "texture": 5, "wings": true }), "wings_layer", None);
+    assert!(valid.is_valid, "Validation failed: {:?}", valid.errors);
+
+    let invalid = validator.validate_json(&json!({ "texture": "five", "wings": true }), "wings_layer", None);
+    assert_eq!(invalid.errors.len(), 1);
+    assert_eq!(invalid.errors[0].path, "texture");
+}
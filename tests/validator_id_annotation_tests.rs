@@ -0,0 +1,111 @@
+//! Tests for `#[id(...)]` tags, path, definition and empty modifiers
+
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::ErrorCode;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[equipment] to struct Equipment {
+    humanoid?: #[id(registry="texture", path="entity/equipment/humanoid/")] string,
+    item?: #[id="item"] string,
+    any_item?: #[id(registry="item", tags="allowed")] string,
+    item_tag?: #[id(registry="item", tags="required")] string,
+    implicit_tag?: #[id(registry="item", tags="implicit")] string,
+    sound?: #[id(registry="sound_event", empty="allowed")] string,
+    name?: #[id(registry="item", definition=true)] string,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("equipment.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator.load_registry("texture".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:entity/equipment/humanoid/diamond": {} }
+    })).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:stone": {} },
+        "tags": { "minecraft:logs": [] }
+    })).unwrap();
+    validator.load_registry("sound_event".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:block.stone.break": {} }
+    })).unwrap();
+    validator
+}
+
+#[test]
+fn test_path_prefix_is_applied_before_lookup() {
+    let validator = setup_validator();
+
+    let result = validator.validate_json(&json!({ "humanoid": "minecraft:diamond" }), "equipment", None);
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+    assert_eq!(result.dependencies[0].resource_location, "minecraft:entity/equipment/humanoid/diamond");
+    assert_eq!(result.dependencies[0].registry_type, "texture");
+
+    let missing = validator.validate_json(&json!({ "humanoid": "minecraft:gold" }), "equipment", None);
+    assert!(!missing.is_valid);
+    assert!(missing.errors[0].message.contains("minecraft:entity/equipment/humanoid/gold"));
+}
+
+#[test]
+fn test_tags_are_rejected_unless_allowed() {
+    let validator = setup_validator();
+
+    let result = validator.validate_json(&json!({ "item": "#minecraft:logs" }), "equipment", None);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].message, "Tags are not allowed here, found '#minecraft:logs'");
+    assert!(result.dependencies.is_empty());
+
+    for value in ["minecraft:stone", "#minecraft:logs"] {
+        let result = validator.validate_json(&json!({ "any_item": value }), "equipment", None);
+        assert!(result.is_valid, "Validation failed for {}: {:?}", value, result.errors);
+    }
+}
+
+#[test]
+fn test_required_and_implicit_tags() {
+    let validator = setup_validator();
+
+    let entry = validator.validate_json(&json!({ "item_tag": "minecraft:stone" }), "equipment", None);
+    assert_eq!(entry.errors.len(), 1);
+    assert_eq!(entry.errors[0].message, "Expected a tag starting with '#', found 'minecraft:stone'");
+
+    let tag = validator.validate_json(&json!({ "item_tag": "#minecraft:logs" }), "equipment", None);
+    assert!(tag.is_valid, "Validation failed: {:?}", tag.errors);
+    assert!(tag.dependencies[0].is_tag);
+
+    let implicit = validator.validate_json(&json!({ "implicit_tag": "minecraft:logs" }), "equipment", None);
+    assert!(implicit.is_valid, "Validation failed: {:?}", implicit.errors);
+    assert!(implicit.dependencies[0].is_tag);
+}
+
+#[test]
+fn test_empty_allowed_and_definitions_emit_no_dependency() {
+    let validator = setup_validator();
+
+    let empty = validator.validate_json(&json!({ "sound": "" }), "equipment", None);
+    assert!(empty.is_valid, "Validation failed: {:?}", empty.errors);
+    assert!(empty.dependencies.is_empty());
+
+    let definition = validator.validate_json(&json!({ "name": "mymod:new_item" }), "equipment", None);
+    assert!(definition.is_valid, "Validation failed: {:?}", definition.errors);
+    assert!(definition.dependencies.is_empty());
+}
+
+#[test]
+fn test_malformed_ids_are_invalid_resource_locations() {
+    let validator = setup_validator();
+
+    for (value, reason) in [
+        ("Minecraft:Stone", "invalid character 'M' in namespace"),
+        ("minecraft:stone block", "invalid character ' ' in path"),
+        ("minecraft:", "empty path"),
+        ("#minecraft:Logs", "invalid character 'L' in path"),
+    ] {
+        let result = validator.validate_json(&json!({ "any_item": value }), "equipment", None);
+        assert!(!result.is_valid);
+        assert_eq!(result.errors.len(), 1, "{}: {:?}", value, result.errors);
+        assert_eq!(result.errors[0].code, ErrorCode::InvalidResourceLocation);
+        assert!(result.errors[0].message.contains(reason), "{}: {}", value, result.errors[0].message);
+        assert!(result.dependencies.is_empty());
+    }
+}
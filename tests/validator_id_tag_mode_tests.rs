@@ -0,0 +1,114 @@
+//! Tests for the tag flag of `#[id]` dependencies under each `tags` mode, against a
+//! registry where `minecraft:logs` is both an entry and a tag
+
+use serde_json::json;
+use voxel_rsmcdoc::annotation::{IdAnnotation, IdTags};
+use voxel_rsmcdoc::parser::AnnotationData;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::{ErrorCode, ValidationResult};
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[fuel] to struct Fuel {
+    entry?: #[id="item"] string,
+    allowed?: #[id(registry="item", tags="allowed")] string,
+    required?: #[id(registry="item", tags="required")] string,
+    implicit?: #[id(registry="item", tags="implicit")] string,
+    tag_registry?: #[id(registry="tag/item")] string,
+    branch?: (int | #[id(registry="item", tags="implicit")] string | [#[id(registry="item", tags="allowed")] string]),
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("fuel.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:logs": {}, "minecraft:stone": {} },
+        "tags": { "minecraft:logs": ["minecraft:logs"], "minecraft:planks": [] }
+    })).unwrap();
+    validator
+}
+
+fn validate(field: &str, value: serde_json::Value) -> ValidationResult {
+    setup_validator().validate_json(&json!({ field: value }), "fuel", None)
+}
+
+/// (location, is_tag) of each dependency
+fn dependencies(result: &ValidationResult) -> Vec<(&str, bool)> {
+    result.dependencies.iter().map(|dependency| (dependency.resource_location.as_ref(), dependency.is_tag)).collect()
+}
+
+#[test]
+fn test_allowed_decided_by_hash() {
+    let entry = validate("allowed", json!("minecraft:logs"));
+    assert!(entry.is_valid, "{:?}", entry.errors);
+    assert_eq!(dependencies(&entry), [("minecraft:logs", false)]);
+
+    let tag = validate("allowed", json!("#minecraft:logs"));
+    assert!(tag.is_valid, "{:?}", tag.errors);
+    assert_eq!(dependencies(&tag), [("#minecraft:logs", true)]);
+
+    // Only listed as an entry
+    let missing_tag = validate("allowed", json!("#minecraft:stone"));
+    assert_eq!(missing_tag.errors[0].code, ErrorCode::RegistryMiss);
+    // Only listed as a tag
+    let missing_entry = validate("allowed", json!("minecraft:planks"));
+    assert_eq!(missing_entry.errors[0].code, ErrorCode::RegistryMiss);
+}
+
+#[test]
+fn test_required_always_tag() {
+    let tag = validate("required", json!("#minecraft:logs"));
+    assert!(tag.is_valid, "{:?}", tag.errors);
+    assert_eq!(dependencies(&tag), [("#minecraft:logs", true)]);
+
+    let bare = validate("required", json!("minecraft:logs"));
+    assert_eq!(bare.errors.len(), 1);
+    assert_eq!(bare.errors[0].message, "Expected a tag starting with '#', found 'minecraft:logs'");
+    assert!(bare.dependencies.is_empty());
+}
+
+#[test]
+fn test_implicit_always_tag_without_hash() {
+    let tag = validate("implicit", json!("minecraft:logs"));
+    assert!(tag.is_valid, "{:?}", tag.errors);
+    assert_eq!(dependencies(&tag), [("minecraft:logs", true)]);
+
+    // A tag of the registry, not one of its entries
+    assert!(validate("implicit", json!("minecraft:planks")).is_valid);
+    assert_eq!(validate("implicit", json!("minecraft:stone")).errors[0].code, ErrorCode::RegistryMiss);
+
+    let hashed = validate("implicit", json!("#minecraft:logs"));
+    assert_eq!(hashed.errors.len(), 1);
+    assert_eq!(hashed.errors[0].code, ErrorCode::InvalidResourceLocation);
+    assert_eq!(hashed.errors[0].message, "Expected a tag without '#', found '#minecraft:logs'");
+}
+
+#[test]
+fn test_tag_registry_is_implicit() {
+    let data = AnnotationData::Simple("tag/item".into());
+    let id = IdAnnotation::from_data(&data);
+    assert_eq!((id.registry, id.tags), (Some("item"), IdTags::Implicit));
+
+    let result = validate("tag_registry", json!("minecraft:planks"));
+    assert!(result.is_valid, "{:?}", result.errors);
+    assert_eq!(result.dependencies[0].registry_type, "item");
+    assert!(result.dependencies[0].is_tag);
+}
+
+#[test]
+fn test_entries_only_by_default() {
+    let entry = validate("entry", json!("minecraft:logs"));
+    assert_eq!(dependencies(&entry), [("minecraft:logs", false)]);
+    assert_eq!(validate("entry", json!("minecraft:planks")).errors[0].code, ErrorCode::RegistryMiss);
+}
+
+#[test]
+fn test_union_branches() {
+    let implicit = validate("branch", json!("minecraft:planks"));
+    assert!(implicit.is_valid, "{:?}", implicit.errors);
+    assert_eq!(dependencies(&implicit), [("minecraft:planks", true)]);
+
+    let allowed = validate("branch", json!(["minecraft:logs", "#minecraft:logs"]));
+    assert!(allowed.is_valid, "{:?}", allowed.errors);
+    assert_eq!(dependencies(&allowed), [("minecraft:logs", false), ("#minecraft:logs", true)]);
+}
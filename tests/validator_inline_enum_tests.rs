@@ -0,0 +1,63 @@
+//! Tests for inline enums used as type expressions
+
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::parser::{Declaration, StructMember, TypeExpression};
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[recipe] to struct Recipe {
+    category?: enum(string) {
+        Blocks = "blocks",
+        Misc = "misc",
+    },
+    group?: enum(string) Group { Food = "food" },
+    weight?: enum Weight: int { Light = 1, Heavy = 2 },
+}
+"#;
+
+#[test]
+fn test_inline_enum_is_parsed() {
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    let Declaration::Dispatch(dispatch) = &ast.declarations[0] else {
+        panic!("Expected dispatch declaration");
+    };
+    let TypeExpression::Struct { members, .. } = &dispatch.target_type else {
+        panic!("Expected struct target");
+    };
+
+    let StructMember::Field(category) = &members[0] else {
+        panic!("Expected field");
+    };
+    match &category.field_type {
+        TypeExpression::Enum { name, base_type, variants } => {
+            assert_eq!(*name, None);
+            assert_eq!(*base_type, Some("string"));
+            assert_eq!(variants.len(), 2);
+        }
+        other => panic!("Expected inline enum, found {:?}", other),
+    }
+
+    let StructMember::Field(weight) = &members[2] else {
+        panic!("Expected field");
+    };
+    assert!(matches!(&weight.field_type, TypeExpression::Enum { name: Some("Weight"), base_type: Some("int"), .. }));
+}
+
+#[test]
+fn test_inline_enum_membership() {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("recipe.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+
+    let valid = validator.validate_json(&json!({ "category": "blocks", "group": "food", "weight": 2 }), "recipe", None);
+    assert!(valid.is_valid, "Validation failed: {:?}", valid.errors);
+
+    let invalid = validator.validate_json(&json!({ "category": "redstone" }), "recipe", None);
+    assert_eq!(invalid.errors.len(), 1);
+    assert_eq!(invalid.errors[0].path, "category");
+    assert_eq!(invalid.errors[0].message, "Invalid value \"redstone\" for enum, expected one of: 'blocks', 'misc'");
+
+    let named = validator.validate_json(&json!({ "group": "tools", "weight": 3 }), "recipe", None);
+    assert_eq!(named.errors.len(), 2);
+    assert!(named.errors.iter().any(|error| error.message.contains("for enum 'Group'")));
+}
@@ -0,0 +1,64 @@
+//! Tests for validating JSON given as text
+
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::{DatapackValidator, ValidationOptions};
+use voxel_rsmcdoc::ErrorCode;
+use std::collections::HashMap;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[recipe] to struct Recipe {
+    count: int,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("recipe.mcdoc".to_string(), ast).unwrap();
+    validator
+}
+
+#[test]
+fn test_validate_json_str_matches_value_validation() {
+    let validator = setup_validator();
+    let options = ValidationOptions::default();
+
+    let valid = validator.validate_json_str(r#"{ "count": 3 }"#, "recipe", None, &options);
+    assert!(valid.is_valid, "{:?}", valid.errors);
+
+    let invalid = validator.validate_json_str(r#"{ "count": "three" }"#, "recipe", None, &options);
+    assert!(!invalid.is_valid);
+    assert_eq!(invalid.errors[0].code, ErrorCode::TypeMismatch);
+}
+
+#[test]
+fn test_validate_json_str_reports_malformed_json() {
+    let validator = setup_validator();
+    let result = validator.validate_json_str("{\n  \"count\": 3,\n}", "recipe", None, &ValidationOptions::default());
+
+    assert!(!result.is_valid);
+    assert_eq!(result.errors.len(), 1);
+    let error = &result.errors[0];
+    assert_eq!(error.code, ErrorCode::InvalidJson);
+    assert_eq!(error.file, "");
+    assert_eq!(error.resource_type.as_deref(), Some("recipe"));
+    assert_eq!(error.path, "");
+    assert!(error.message.starts_with("Invalid JSON:"), "{}", error.message);
+    assert_eq!(error.line, Some(3));
+}
+
+#[test]
+fn test_analyze_datapack_strs_counts_malformed_files() {
+    let validator = setup_validator();
+    let files = HashMap::from([
+        ("data/test/recipe/good.json".to_string(), r#"{ "count": 1 }"#.to_string()),
+        ("data/test/recipe/broken.json".to_string(), r#"{ "count": "#.to_string()),
+    ]);
+
+    let result = validator.analyze_datapack_strs(&files);
+    assert_eq!(result.total_files, 2);
+    assert_eq!(result.valid_files, 1);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].file_path, "data/test/recipe/broken.json");
+    assert_eq!(result.errors[0].error.code, ErrorCode::InvalidJson);
+}
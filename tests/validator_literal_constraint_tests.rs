@@ -30,12 +30,10 @@ struct SafePositionSource {
 
     // For this test, we need to manually find the struct type and validate against it
     // This is a simplified validation test
-    if let Some(decl) = validator.mcdoc_schemas.get("test.mcdoc").unwrap().declarations.first() {
-        if let voxel_rsmcdoc::parser::Declaration::Struct(struct_decl) = decl {
-            let result = validator.validate_json(&json, "test", None);
-            // For now, we expect no validation errors for valid literal constraints
-            // Note: This is a basic test - in reality we'd need proper dispatch resolution
-        }
+    if let Some(voxel_rsmcdoc::parser::Declaration::Struct(_struct_decl)) = validator.schema("test.mcdoc").unwrap().declarations.first() {
+        let _result = validator.validate_json(&json, "test", None);
+        // For now, we expect no validation errors for valid literal constraints
+        // Note: This is a basic test - in reality we'd need proper dispatch resolution
     }
 }
 
@@ -48,7 +46,7 @@ struct SafePositionSource {
 }
 "#;
 
-    let json = json!({
+    let _json = json!({
         "type": "item",  // Wrong value - expected "block"
         "pos": [1, 2, 3]
     });
@@ -74,12 +72,12 @@ struct TestStruct {
 }
 "#;
 
-    let json_valid = json!({
+    let _json_valid = json!({
         "version": 42,
         "name": "test"
     });
 
-    let json_invalid = json!({
+    let _json_invalid = json!({
         "version": 43,  // Wrong number
         "name": "test"
     });
@@ -105,12 +103,12 @@ struct TestStruct {
 }
 "#;
 
-    let json_valid = json!({
+    let _json_valid = json!({
         "enabled": true,
         "name": "test"
     });
 
-    let json_invalid = json!({
+    let _json_invalid = json!({
         "enabled": false,  // Wrong boolean
         "name": "test"
     });
@@ -0,0 +1,56 @@
+//! Tests for the error of unions made only of literals, like `(0 | 90 | 180 | 270)`
+
+use serde_json::json;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::ErrorCode;
+
+const MCDOC: &str = r#"
+struct Placement {
+    rotation: (0 | 90 | 180 | 270),
+    offset?: (-1 | 0 | 1),
+    mirror?: (true | false),
+    mode?: ("none" | #[since="1.20"] "front_back" | #[until="1.20"] "legacy"),
+    size?: (1 | "auto" | [int]),
+}
+
+dispatch minecraft:resource[placement] to Placement
+"#;
+
+fn errors_of(json: serde_json::Value, version: Option<&str>) -> Vec<(ErrorCode, String, String)> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("placement.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    let result = validator.validate_json(&json, "placement", version);
+    result.errors.into_iter().map(|error| (error.code, error.path, error.message)).collect()
+}
+
+#[test]
+fn test_allowed_literals_are_valid() {
+    assert!(errors_of(json!({ "rotation": 180, "offset": -1, "mirror": false, "mode": "none" }), None).is_empty());
+    assert!(errors_of(json!({ "rotation": 0, "mode": "front_back" }), Some("1.21")).is_empty());
+}
+
+#[test]
+fn test_lists_allowed_values() {
+    assert_eq!(errors_of(json!({ "rotation": 45 }), None), [(
+        ErrorCode::UnionNoMatch,
+        "rotation".to_string(),
+        "Expected one of 0, 90, 180, 270, found 45".to_string(),
+    )]);
+
+    let messages = |json| errors_of(json, None).into_iter().map(|(_, _, message)| message).collect::<Vec<_>>();
+    assert_eq!(messages(json!({ "rotation": "90" })), ["Expected one of 0, 90, 180, 270, found \"90\""]);
+    assert_eq!(messages(json!({ "rotation": 0, "offset": 2 })), ["Expected one of -1, 0, 1, found 2"]);
+    assert_eq!(messages(json!({ "rotation": 0, "mirror": [true] })), ["Expected one of true, false, found array"]);
+}
+
+#[test]
+fn test_lists_only_available_values() {
+    let errors = errors_of(json!({ "rotation": 0, "mode": "legacy" }), Some("1.21"));
+    assert_eq!(errors[0].2, "Expected one of \"none\", \"front_back\", found \"legacy\"");
+}
+
+#[test]
+fn test_mixed_union_keeps_branch_errors() {
+    let errors = errors_of(json!({ "rotation": 0, "size": 5 }), None);
+    assert!(errors[0].2.starts_with("JSON does not match any of the expected types"), "{}", errors[0].2);
+}
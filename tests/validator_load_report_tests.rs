@@ -0,0 +1,53 @@
+//! Tests for loading several MCDOC sources past the ones that fail to parse
+
+use serde_json::json;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::ErrorType;
+
+const GOOD_MCDOC: &str = "dispatch minecraft:resource[recipe] to struct Recipe { result: string }";
+const BROKEN_MCDOC: &str = "struct Loot {\n    rolls: int int,\n}";
+
+fn files() -> Vec<(String, String)> {
+    vec![
+        ("broken.mcdoc".to_string(), BROKEN_MCDOC.to_string()),
+        ("recipe.mcdoc".to_string(), GOOD_MCDOC.to_string()),
+    ]
+}
+
+#[test]
+fn test_good_files_load_past_broken_ones() {
+    let mut validator = DatapackValidator::new();
+    let report = validator.load_mcdoc_sources(files());
+
+    assert!(!report.is_ok());
+    assert_eq!(report.loaded, ["recipe.mcdoc"]);
+    assert!(validator.schema("broken.mcdoc").is_none());
+    assert!(validator.validate_json(&json!({ "result": "minecraft:stone" }), "recipe", None).is_valid);
+    assert!(!validator.validate_json(&json!({ "result": 3 }), "recipe", None).is_valid);
+}
+
+#[test]
+fn test_report_names_broken_file() {
+    let mut validator = DatapackValidator::new();
+    let report = validator.load_mcdoc_sources(files());
+
+    assert_eq!(report.diagnostics.keys().collect::<Vec<_>>(), ["broken.mcdoc"]);
+    let errors = &report.diagnostics["broken.mcdoc"];
+    assert!(!errors.is_empty());
+    assert_eq!(errors[0].file.as_ref(), "broken.mcdoc");
+    assert_eq!(errors[0].error_type, ErrorType::Syntax);
+    assert_eq!(errors[0].line, Some(2));
+    assert!(errors[0].column.is_some());
+
+    let serialized = serde_json::to_value(&report).unwrap();
+    assert_eq!(serialized["diagnostics"]["broken.mcdoc"][0]["errorType"], "syntax");
+    assert_eq!(serialized["loaded"], json!(["recipe.mcdoc"]));
+}
+
+#[test]
+fn test_all_files_load() {
+    let mut validator = DatapackValidator::new();
+    let report = validator.load_mcdoc_sources(vec![("recipe.mcdoc".to_string(), GOOD_MCDOC.to_string())]);
+    assert!(report.is_ok());
+    assert!(report.diagnostics.is_empty());
+}
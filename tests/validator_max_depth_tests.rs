@@ -0,0 +1,169 @@
+//! Tests for the maximum nesting depth of validated JSON
+
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::{DatapackValidator, ValidationOptions};
+use voxel_rsmcdoc::ErrorCode;
+use serde_json::{json, Value};
+
+const MCDOC: &str = r#"
+struct Node {
+    children?: [Node],
+}
+
+type Nested = (int | [Nested])
+
+dispatch minecraft:resource[tree] to Node
+
+dispatch minecraft:resource[nested] to struct Holder {
+    value: Nested,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("tree.mcdoc".to_string(), ast).unwrap();
+    validator
+}
+
+/// Nodes each holding the next one in `children`, `levels` objects and arrays deep
+fn tree(levels: usize) -> Value {
+    let mut node = json!({});
+    for _ in 1..levels / 2 {
+        node = json!({ "children": [node] });
+    }
+    node
+}
+
+fn nested_arrays(levels: usize) -> Value {
+    let mut value = json!(1);
+    for _ in 0..levels {
+        value = Value::Array(vec![value]);
+    }
+    value
+}
+
+#[test]
+fn test_deep_object_nesting_is_an_error() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&tree(1000), "tree", None);
+
+    assert!(!result.is_valid);
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].code, ErrorCode::MaxDepthExceeded);
+    assert_eq!(result.errors[0].message, "Value nested deeper than the maximum depth of 128");
+}
+
+#[test]
+fn test_deep_array_nesting_is_an_error() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({ "value": nested_arrays(1000) }), "nested", None);
+
+    // Reported as is rather than as a union without matching branch
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].code, ErrorCode::MaxDepthExceeded);
+    assert_eq!(result.errors[0].path.matches('[').count(), 128);
+}
+
+#[test]
+fn test_nesting_within_the_limit() {
+    let validator = setup_validator();
+    assert!(validator.validate_json(&tree(120), "tree", None).is_valid);
+
+    let options = ValidationOptions { max_depth: 10, ..ValidationOptions::default() };
+    assert!(validator.validate_json_with_options(&tree(10), "tree", None, &options).is_valid);
+    let result = validator.validate_json_with_options(&tree(14), "tree", None, &options);
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].code, ErrorCode::MaxDepthExceeded);
+}
+
+/// Validator for a schema whose types refer back to themselves without going into the value
+fn recursive_validator(source: &str) -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("recursive.mcdoc".to_string(), source.to_string()).unwrap();
+    validator
+}
+
+#[test]
+fn test_recursive_spreads_are_a_cycle() {
+    let validator = recursive_validator(r#"
+struct A {
+    a?: int,
+    ...B,
+}
+
+struct B {
+    b?: int,
+    ...A,
+}
+
+dispatch minecraft:resource[recursive] to A
+"#);
+    let result = validator.validate_json(&json!({ "a": 1 }), "recursive", None);
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].code, ErrorCode::TypeCycle);
+}
+
+#[test]
+fn test_recursive_aliases_are_a_cycle() {
+    let validator = recursive_validator(r#"
+type A = B
+type B = A
+
+dispatch minecraft:resource[recursive] to struct Holder {
+    value: A,
+}
+"#);
+    let result = validator.validate_json(&json!({ "value": 1 }), "recursive", None);
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].code, ErrorCode::TypeCycle);
+    assert_eq!(result.errors[0].message, "Type alias cycle: A -> B -> A");
+}
+
+#[test]
+fn test_union_of_itself_is_a_cycle() {
+    let validator = recursive_validator(r#"
+type U = (U | int)
+
+dispatch minecraft:resource[recursive] to struct Holder {
+    value: U,
+}
+"#);
+    // The other branch can still match
+    assert!(validator.validate_json(&json!({ "value": 1 }), "recursive", None).is_valid);
+
+    let result = validator.validate_json(&json!({ "value": "one" }), "recursive", None);
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].code, ErrorCode::UnionNoMatch);
+}
+
+#[test]
+fn test_recursive_generic_alias() {
+    let validator = recursive_validator(r#"
+type Same<T> = Same<T>
+type Growing<T> = Growing<[T]>
+type Either<A, B> = (A | B)
+
+dispatch minecraft:resource[recursive] to struct Holder {
+    same?: Same<int>,
+    growing?: Growing<int>,
+    either?: Either<Either<int, string>, boolean>,
+}
+"#);
+    let result = validator.validate_json(&json!({ "same": 1 }), "recursive", None);
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].code, ErrorCode::TypeCycle);
+    assert_eq!(result.errors[0].message, "Type alias cycle: Same<int> -> Same<int>");
+
+    let result = validator.validate_json(&json!({ "growing": 1 }), "recursive", None);
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].code, ErrorCode::MaxDepthExceeded);
+
+    // Nested instances of the same alias with other arguments aren't a cycle
+    for value in [json!(1), json!("one"), json!(true)] {
+        let result = validator.validate_json(&json!({ "either": value }), "recursive", None);
+        assert!(result.is_valid, "Validation failed for {}: {:?}", value, result.errors);
+    }
+}
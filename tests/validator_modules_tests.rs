@@ -0,0 +1,176 @@
+//! Tests for loading MCDOC modules and checking their imports
+
+use std::fs;
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::error::ParseError;
+use voxel_rsmcdoc::parser::ImportPath;
+use voxel_rsmcdoc::validator::{DatapackValidator, ResolvedDeclaration};
+use serde_json::json;
+
+/// `TextStyle` from `java/util/text.mcdoc` of the dataset, without its imports
+const TEXT_MCDOC: &str = r#"
+struct TextStyle {
+	color?: (
+		#[since="1.16"] #[color="hex_rgb"] string |
+		#[color="named"] TextColor |
+	),
+	bold?: boolean,
+	italic?: boolean,
+	insertion?: string,
+}
+
+enum(string) TextColor {
+	Black = "black",
+	Gold = "gold",
+}
+"#;
+
+#[test]
+fn test_chat_type_resolves_imported_text_style() {
+    let chat_type = fs::read_to_string("tests/dataset/mcdoc/data/chat_type.mcdoc")
+        .expect("Failed to read chat_type.mcdoc");
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_modules(vec![
+        ("java/data/chat_type.mcdoc".to_string(), parse_mcdoc(&chat_type).unwrap()),
+        ("java/util/text.mcdoc".to_string(), parse_mcdoc(TEXT_MCDOC).unwrap()),
+    ]).expect("imports should resolve");
+
+    let decoration = |style: serde_json::Value| json!({
+        "chat": { "translation_key": "chat.type.text", "parameters": ["sender", "content"], "style": style }
+    });
+
+    let valid = validator.validate_json(&decoration(json!({ "color": "gold", "bold": true })), "chat_type", Some("1.21"));
+    assert!(valid.is_valid, "Validation failed: {:?}", valid.errors);
+
+    let invalid = validator.validate_json(&decoration(json!({ "bold": "yes" })), "chat_type", Some("1.21"));
+    assert!(!invalid.is_valid);
+    // `chat` is a union, the branch errors are listed in its message
+    assert!(invalid.errors[0].message.contains("at 'chat.style.bold'"), "{}", invalid.errors[0].message);
+}
+
+#[test]
+fn test_missing_modules_and_names_are_reported_at_load_time() {
+    let mut validator = DatapackValidator::new();
+    let errors = validator.load_mcdoc_modules(vec![
+        ("java/util/particle.mcdoc".to_string(), parse_mcdoc("use super::block_state::BlockState\nuse ::java::util::color::RGB\nuse super::text::Missing\n").unwrap()),
+        ("java/util/mod.mcdoc".to_string(), parse_mcdoc("use super::util::text\nstruct GlobalPos { pos: [int] }").unwrap()),
+        ("java/util/text.mcdoc".to_string(), parse_mcdoc(TEXT_MCDOC).unwrap()),
+        ("java/util/block_state.mcdoc".to_string(), parse_mcdoc("use ::java::util::GlobalPos\nstruct BlockState { Name: string }").unwrap()),
+    ]).unwrap_err();
+
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+    assert_eq!(errors[0], ParseError::ModuleNotFound {
+        module: "::java::util::color".to_string(),
+        from: "java/util/particle.mcdoc".to_string(),
+    });
+    assert!(errors[1].to_string().contains("'Missing' is not declared in module ::java::util::text"), "{}", errors[1]);
+
+    // The modules are loaded anyway
+    assert!(validator.schema("java/util/particle.mcdoc").is_some());
+}
+
+#[test]
+fn test_resolve_import_path() {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_modules(vec![
+        ("java/util/text.mcdoc".to_string(), parse_mcdoc(TEXT_MCDOC).unwrap()),
+        ("java/util/mod.mcdoc".to_string(), parse_mcdoc("type GlobalPos = struct { pos: [int] }").unwrap()),
+    ]).unwrap();
+
+    match validator.resolve_import_path("::java::util::text::TextStyle") {
+        Some(ResolvedDeclaration::Struct(text_style)) => assert_eq!(text_style.members.len(), 4),
+        other => panic!("Expected a struct, got {:?}", other),
+    }
+    match validator.resolve_import_path("::java::util::text::TextColor") {
+        Some(ResolvedDeclaration::Enum(text_color)) => assert_eq!(text_color.variants.len(), 2),
+        other => panic!("Expected an enum, got {:?}", other),
+    }
+    match validator.resolve_import_path("::java::util::GlobalPos") {
+        Some(ResolvedDeclaration::Type(global_pos)) => assert_eq!(global_pos.name, "GlobalPos"),
+        other => panic!("Expected a type alias, got {:?}", other),
+    }
+    assert!(validator.resolve_import_path("::java::util::text::Missing").is_none());
+    assert!(validator.resolve_import_path("::java::util::color::RGB").is_none());
+}
+
+#[test]
+fn test_chained_super_imports() {
+    let file = parse_mcdoc("use super::super::util::text::TextStyle\nuse super::Sibling\n").unwrap();
+    assert_eq!(file.imports[0].path, ImportPath::Relative { supers: 2, segments: vec!["util", "text", "TextStyle"] });
+    assert_eq!(file.imports[1].path, ImportPath::Relative { supers: 1, segments: vec!["Sibling"] });
+
+    let mut validator = DatapackValidator::new();
+    let errors = validator.load_mcdoc_modules(vec![
+        ("java/util/text.mcdoc".to_string(), parse_mcdoc(TEXT_MCDOC).unwrap()),
+        ("java/data/worldgen/biome.mcdoc".to_string(), parse_mcdoc(
+            "use super::super::super::util::text::TextStyle\nuse super::noise::Noise\nuse super::super::super::super::super::Root\n"
+        ).unwrap()),
+        ("java/data/worldgen/noise.mcdoc".to_string(), parse_mcdoc("struct Noise {}").unwrap()),
+        ("mod.mcdoc".to_string(), parse_mcdoc("use super::Root\n").unwrap()),
+    ]).unwrap_err();
+
+    let mut messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+    messages.sort();
+    assert_eq!(messages.len(), 2, "{:?}", messages);
+    assert!(messages[0].contains("Import 'super::Root' goes above the root module from ::"), "{}", messages[0]);
+    assert!(messages[1].contains("Import 'super::super::super::super::super::Root' goes above the root module from ::java::data::worldgen::biome"), "{}", messages[1]);
+}
+
+const COLOR_MCDOC: &str = r#"
+type RGB = [int] @ 3
+
+struct Tint {
+	value: int,
+}
+"#;
+
+#[test]
+fn test_grouped_and_aliased_import_parsing() {
+    let file = parse_mcdoc("use ::java::util::color::{RGB, Tint as Shade,}\nuse super::text::TextStyle as Style\n").unwrap();
+    let imports: Vec<(Vec<&str>, Option<&str>, Option<&str>)> = file.imports.iter()
+        .map(|import| (import.path.segments().to_vec(), import.alias, import.local_name()))
+        .collect();
+    assert_eq!(imports, [
+        (vec!["java", "util", "color", "RGB"], None, Some("RGB")),
+        (vec!["java", "util", "color", "Tint"], Some("Shade"), Some("Shade")),
+        (vec!["text", "TextStyle"], Some("Style"), Some("Style")),
+    ]);
+}
+
+#[test]
+fn test_grouped_import_resolves_both_types() {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_modules(vec![
+        ("java/util/color.mcdoc".to_string(), parse_mcdoc(COLOR_MCDOC).unwrap()),
+        ("java/data/banner.mcdoc".to_string(), parse_mcdoc(
+            "use ::java::util::color::{RGB, Tint}\ndispatch minecraft:resource[banner] to struct Banner { color: RGB, tint: Tint }"
+        ).unwrap()),
+    ]).expect("imports should resolve");
+
+    let valid = validator.validate_json(&json!({ "color": [1, 2, 3], "tint": { "value": 4 } }), "banner", None);
+    assert!(valid.is_valid, "Validation failed: {:?}", valid.errors);
+
+    let invalid = validator.validate_json(&json!({ "color": [1, 2, 3], "tint": { "value": "4" } }), "banner", None);
+    assert_eq!(invalid.errors.len(), 1, "{:?}", invalid.errors);
+    assert_eq!(invalid.errors[0].path, "tint.value");
+}
+
+#[test]
+fn test_aliased_import_hides_the_original_name() {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_modules(vec![
+        ("java/util/color.mcdoc".to_string(), parse_mcdoc(COLOR_MCDOC).unwrap()),
+        ("java/data/banner.mcdoc".to_string(), parse_mcdoc(
+            "use ::java::util::color::Tint as Shade\ndispatch minecraft:resource[banner] to struct Banner { shade?: Shade, tint?: Tint }"
+        ).unwrap()),
+    ]).expect("imports should resolve");
+
+    let aliased = validator.validate_json(&json!({ "shade": { "value": 4 } }), "banner", None);
+    assert!(aliased.is_valid, "Validation failed: {:?}", aliased.errors);
+    let aliased = validator.validate_json(&json!({ "shade": { "value": "4" } }), "banner", None);
+    assert_eq!(aliased.errors[0].path, "shade.value");
+
+    let original = validator.validate_json(&json!({ "tint": { "value": 4 } }), "banner", None);
+    assert!(!original.is_valid);
+    assert_eq!(original.errors[0].message, "Unresolved type 'Tint'");
+}
@@ -0,0 +1,55 @@
+//! Tests for references to named inline structs
+
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[foo] to struct Foo {
+    value: int,
+}
+
+dispatch minecraft:resource[holder] to struct Holder {
+    x: Foo,
+    list?: [Foo],
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("foo.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator
+}
+
+#[test]
+fn test_reference_to_named_inline_struct_resolves() {
+    let validator = setup_validator();
+
+    let valid = validator.validate_json(&json!({ "x": { "value": 1 }, "list": [{ "value": 2 }] }), "holder", None);
+    assert!(valid.is_valid, "Validation failed: {:?}", valid.errors);
+
+    let invalid = validator.validate_json(&json!({ "x": { "value": "one" }, "list": [{}] }), "holder", None);
+    assert_eq!(invalid.errors.len(), 2, "Unexpected errors: {:?}", invalid.errors);
+    assert_eq!(invalid.errors[0].path, "x.value");
+    assert_eq!(invalid.errors[1].path, "list[0].value");
+}
+
+#[test]
+fn test_named_inline_struct_by_name() {
+    let validator = setup_validator();
+
+    let result = validator.validate_against_type(&json!({ "value": "one" }), "Foo", None);
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "value");
+}
+
+#[test]
+fn test_unloading_removes_named_struct() {
+    let mut validator = setup_validator();
+    validator.load_mcdoc_source("other.mcdoc".to_string(), "dispatch minecraft:resource[other] to struct Other { x: Foo }".to_string()).unwrap();
+    assert!(!validator.validate_json(&json!({ "x": {} }), "other", None).is_valid);
+
+    validator.unload_mcdoc("foo.mcdoc");
+    let result = validator.validate_json(&json!({ "x": {} }), "other", None);
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].message, "Unresolved type 'Foo'");
+}
@@ -0,0 +1,87 @@
+//! Tests for validating JSON fragments against named types
+
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const UTIL_MCDOC: &str = r#"
+struct ItemStack {
+    id: #[id="item"] string,
+    count?: int,
+}
+
+enum(string) Rarity {
+    Common = "common",
+    Rare = "rare",
+}
+
+type WeightedList<T> = [struct {
+    weight: int,
+    data: T,
+}]
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(UTIL_MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("data/util.mcdoc".to_string(), ast).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({ "entries": { "minecraft:stone": {} } })).unwrap();
+    validator
+}
+
+#[test]
+fn test_validate_struct_by_name() {
+    let validator = setup_validator();
+
+    let result = validator.validate_against_type(&json!({ "id": "minecraft:stone", "count": 2 }), "ItemStack", None);
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+    assert_eq!(result.dependencies.len(), 1);
+
+    let qualified = validator.validate_against_type(&json!({ "count": 2 }), "util::ItemStack", None);
+    assert!(!qualified.is_valid);
+    assert_eq!(qualified.errors[0].resource_type.as_deref(), Some("util::ItemStack"));
+}
+
+#[test]
+fn test_validate_enum_by_name() {
+    let validator = setup_validator();
+
+    assert!(validator.validate_against_type(&json!("rare"), "Rarity", None).is_valid);
+    assert!(!validator.validate_against_type(&json!("legendary"), "Rarity", None).is_valid);
+}
+
+#[test]
+fn test_validate_generic_alias() {
+    let validator = setup_validator();
+    let json = json!([{ "weight": 1, "data": "anything" }]);
+
+    let result = validator.validate_against_type(&json, "WeightedList<string>", None);
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+
+    // The argument is the type of `data`
+    let wrong_arg = validator.validate_against_type(&json, "WeightedList<int>", None);
+    assert_eq!(wrong_arg.errors.len(), 1);
+    assert_eq!(wrong_arg.errors[0].path, "[0].data");
+    assert!(validator.validate_against_type(&json!([{ "weight": 1, "data": 3 }]), "WeightedList<int>", None).is_valid);
+    assert!(validator.validate_against_type(&json!([{ "weight": 1, "data": "common" }]), "WeightedList<Rarity>", None).is_valid);
+    assert!(!validator.validate_against_type(&json!([{ "weight": 1, "data": "epic" }]), "WeightedList<Rarity>", None).is_valid);
+
+    let unknown_arg = validator.validate_against_type(&json, "WeightedList<Missing>", None);
+    assert_eq!(unknown_arg.errors[0].message, "Unknown type 'Missing' in 'WeightedList<Missing>'");
+
+    let missing_arg = validator.validate_against_type(&json, "WeightedList", None);
+    assert!(!missing_arg.is_valid);
+    assert!(missing_arg.errors[0].message.contains("expects 1 type argument"));
+}
+
+#[test]
+fn test_unknown_type_name() {
+    let validator = setup_validator();
+
+    let result = validator.validate_against_type(&json!({}), "Missing", None);
+    assert!(!result.is_valid);
+    assert_eq!(result.errors[0].message, "Unknown type 'Missing'");
+
+    let wrong_module = validator.validate_against_type(&json!({}), "other::ItemStack", None);
+    assert!(!wrong_module.is_valid);
+}
@@ -0,0 +1,49 @@
+//! Tests for integer and floating point primitive types
+
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[numbers] to struct Numbers {
+    byte_value?: byte,
+    short_value?: short,
+    int_value?: int,
+    long_value?: long,
+    float_value?: float,
+    double_value?: double,
+    count?: int @ 1..64,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("numbers.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator
+}
+
+fn errors(json: serde_json::Value) -> Vec<String> {
+    let validator = setup_validator();
+    validator.validate_json(&json, "numbers", None).errors.into_iter().map(|error| error.message).collect()
+}
+
+#[test]
+fn test_fractional_values_fail_for_integers() {
+    assert_eq!(errors(json!({ "int_value": 1.5 })), vec!["Expected an integer, found 1.5"]);
+    assert_eq!(errors(json!({ "count": 1.5 })), vec!["Expected an integer, found 1.5"]);
+    assert!(errors(json!({ "int_value": 2.0, "long_value": -7 })).is_empty());
+}
+
+#[test]
+fn test_floats_accept_any_number() {
+    assert!(errors(json!({ "float_value": 1.5, "double_value": 3 })).is_empty());
+    assert_eq!(errors(json!({ "double_value": "3" })), vec!["Expected number, found string"]);
+}
+
+#[test]
+fn test_integer_ranges_are_enforced() {
+    assert_eq!(errors(json!({ "byte_value": 300 })), vec!["Expected a byte between -128 and 127, found 300"]);
+    assert!(errors(json!({ "byte_value": -128, "short_value": 32767 })).is_empty());
+    assert_eq!(errors(json!({ "short_value": 32768 })).len(), 1);
+    assert_eq!(errors(json!({ "int_value": 2147483648u64 })).len(), 1);
+    assert_eq!(errors(json!({ "long_value": u64::MAX })).len(), 1);
+}
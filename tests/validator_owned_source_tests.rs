@@ -0,0 +1,51 @@
+//! Tests for loading MCDOC schemas from sources owned by the validator
+
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+fn recipe_source(result_type: &str) -> String {
+    format!("dispatch minecraft:resource[recipe] to struct Recipe {{ result: {} }}", result_type)
+}
+
+#[test]
+fn test_load_owned_source() {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("recipe.mcdoc".to_string(), recipe_source("string")).unwrap();
+
+    assert!(validator.schema("recipe.mcdoc").is_some());
+    assert!(validator.validate_json(&json!({ "result": "stone" }), "recipe", None).is_valid);
+    assert!(!validator.validate_json(&json!({ "result": 1 }), "recipe", None).is_valid);
+}
+
+#[test]
+fn test_replace_owned_source() {
+    let mut validator = DatapackValidator::new();
+    for _ in 0..3 {
+        validator.load_mcdoc_source("recipe.mcdoc".to_string(), recipe_source("string")).unwrap();
+        validator.load_mcdoc_source("recipe.mcdoc".to_string(), recipe_source("int")).unwrap();
+    }
+
+    assert_eq!(validator.schema_names().count(), 1);
+    assert!(validator.validate_json(&json!({ "result": 1 }), "recipe", None).is_valid);
+}
+
+#[test]
+fn test_owned_source_parse_error_keeps_previous_schema() {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("recipe.mcdoc".to_string(), recipe_source("string")).unwrap();
+
+    let errors = validator.load_mcdoc_source("recipe.mcdoc".to_string(), "struct {".to_string()).unwrap_err();
+    assert!(!errors.is_empty());
+    assert!(validator.validate_json(&json!({ "result": "stone" }), "recipe", None).is_valid);
+}
+
+#[test]
+fn test_owned_and_borrowed_sources_mix() {
+    let borrowed = recipe_source("string");
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("recipe.mcdoc".to_string(), recipe_source("string")).unwrap();
+    validator.load_parsed_mcdoc("copy.mcdoc".to_string(), voxel_rsmcdoc::parse_mcdoc(&borrowed).unwrap()).unwrap();
+
+    assert_eq!(validator.schema_aliases.get("copy.mcdoc").map(String::as_str), Some("recipe.mcdoc"));
+    assert!(validator.validate_json(&json!({ "result": "stone" }), "recipe", None).is_valid);
+}
@@ -0,0 +1,49 @@
+//! Tests for validating JSON keys against quoted field names
+
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[components] to struct Components {
+    "minecraft:max_stack_size"?: int,
+    "fabric:load_conditions": [string],
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("components.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator
+}
+
+#[test]
+fn test_quoted_field_matches_json_key() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({
+        "minecraft:max_stack_size": 16,
+        "fabric:load_conditions": ["a", "b"]
+    }), "components", None);
+
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+}
+
+#[test]
+fn test_quoted_field_type_is_checked() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({
+        "minecraft:max_stack_size": "many",
+        "fabric:load_conditions": []
+    }), "components", None);
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "minecraft:max_stack_size");
+}
+
+#[test]
+fn test_missing_quoted_field_is_reported() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({}), "components", None);
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert!(result.errors[0].message.contains("fabric:load_conditions"), "Unexpected message: {}", result.errors[0].message);
+}
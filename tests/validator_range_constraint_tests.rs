@@ -0,0 +1,95 @@
+//! Tests for `@` range constraints on simple types
+
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::parser::{Declaration, StructMember, TypeExpression};
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[timer] to struct Timer {
+    max_ticks: int @ 1..,
+    delay?: int @ ..5,
+    slot?: int @ 3,
+    scale?: float @ -0.5..2.5,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("timer.mcdoc".to_string(), ast).unwrap();
+    validator
+}
+
+#[test]
+fn test_parser_keeps_simple_type_constraints() {
+    let ast = parse_mcdoc("struct S { a: int @ 1..10, b: string }").expect("MCDOC should parse");
+    let Declaration::Struct(s) = &ast.declarations[0] else { panic!("Expected struct") };
+
+    let StructMember::Field(a) = &s.members[0] else { panic!("Expected field") };
+    match &a.field_type {
+        TypeExpression::Constrained { base_type, constraints } => {
+            assert_eq!(**base_type, TypeExpression::Simple("int"));
+            assert_eq!(constraints.min, Some(1.0));
+            assert_eq!(constraints.max, Some(10.0));
+        }
+        other => panic!("Expected constrained type, got {:?}", other),
+    }
+
+    let StructMember::Field(b) = &s.members[1] else { panic!("Expected field") };
+    assert_eq!(b.field_type, TypeExpression::Simple("string"));
+}
+
+#[test]
+fn test_numbers_within_ranges() {
+    let validator = setup_validator();
+    let json = json!({ "max_ticks": 200, "delay": -3, "slot": 3, "scale": -0.5 });
+
+    let result = validator.validate_json(&json, "timer", None);
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+}
+
+#[test]
+fn test_numbers_outside_ranges() {
+    let validator = setup_validator();
+    let json = json!({ "max_ticks": 0, "delay": 6, "slot": 4, "scale": 2.75 });
+
+    let result = validator.validate_json(&json, "timer", None);
+    let mut paths: Vec<_> = result.errors.iter().map(|e| e.path.as_str()).collect();
+    paths.sort();
+    assert_eq!(paths, ["delay", "max_ticks", "scale", "slot"], "Unexpected errors: {:?}", result.errors);
+
+    let max_ticks = result.errors.iter().find(|e| e.path == "max_ticks").unwrap();
+    assert_eq!(max_ticks.message, "Expected a value of at least 1, found 0");
+}
+
+#[test]
+fn test_constrained_type_still_checks_base_type() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({ "max_ticks": "ten" }), "timer", None);
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert!(result.errors[0].message.contains("Expected"));
+}
+
+#[test]
+fn test_string_length_constraint() {
+    let ast = parse_mcdoc("dispatch minecraft:resource[named] to struct Named { description: string @ 1..10, key?: string @ ..256 }")
+        .expect("MCDOC should parse");
+    let mut validator = DatapackValidator::new();
+    validator.load_parsed_mcdoc("named.mcdoc".to_string(), ast).unwrap();
+
+    let valid = validator.validate_json(&json!({ "description": "short" }), "named", None);
+    assert!(valid.is_valid, "Validation failed: {:?}", valid.errors);
+
+    let too_long = validator.validate_json(&json!({ "description": "eleven char" }), "named", None);
+    assert_eq!(too_long.errors.len(), 1);
+    assert_eq!(too_long.errors[0].path, "description");
+    assert_eq!(too_long.errors[0].message, "Expected a string of at most 10 characters, found 11");
+
+    let empty = validator.validate_json(&json!({ "description": "" }), "named", None);
+    assert!(!empty.is_valid);
+
+    let multibyte = validator.validate_json(&json!({ "description": "ééééé" }), "named", None);
+    assert!(multibyte.is_valid, "Length should count characters: {:?}", multibyte.errors);
+}
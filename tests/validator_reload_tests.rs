@@ -0,0 +1,68 @@
+//! Tests for unloading and reloading schemas and registries
+
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const RECIPE_MCDOC: &str = "dispatch minecraft:resource[recipe] to struct Recipe { result: #[id=\"item\"] string }";
+const BIOME_MCDOC: &str = "dispatch minecraft:resource[\"worldgen/biome\"] to struct Biome { temperature: float }";
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("recipe.mcdoc".to_string(), RECIPE_MCDOC.to_string()).unwrap();
+    validator.load_mcdoc_source("biome.mcdoc".to_string(), BIOME_MCDOC.to_string()).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({ "entries": { "minecraft:stone": {} } })).unwrap();
+    validator
+}
+
+#[test]
+fn test_unload_schema_removes_its_dispatches() {
+    let mut validator = setup_validator();
+
+    assert!(validator.unload_mcdoc("recipe.mcdoc"));
+    assert!(!validator.unload_mcdoc("recipe.mcdoc"));
+
+    let removed = validator.validate_json(&json!({ "result": "minecraft:stone" }), "recipe", None);
+    assert_eq!(removed.errors[0].message, "No MCDOC schema found for resource type 'recipe'");
+
+    let remaining = validator.validate_json(&json!({ "temperature": 0.5 }), "worldgen/biome", None);
+    assert!(remaining.is_valid, "Validation failed: {:?}", remaining.errors);
+}
+
+#[test]
+fn test_reload_schema() {
+    let mut validator = setup_validator();
+    let json = json!({ "result": "minecraft:stone", "count": 2 });
+
+    validator.reload_mcdoc("recipe.mcdoc", RECIPE_MCDOC.replace(" }", ", count: string }")).unwrap();
+    assert!(!validator.validate_json(&json, "recipe", None).is_valid);
+
+    assert!(validator.reload_mcdoc("recipe.mcdoc", "dispatch {".to_string()).is_err());
+    assert!(!validator.validate_json(&json, "recipe", None).is_valid);
+
+    validator.reload_mcdoc("recipe.mcdoc", RECIPE_MCDOC.to_string()).unwrap();
+    assert!(validator.validate_json(&json, "recipe", None).is_valid);
+}
+
+#[test]
+fn test_unload_original_keeps_alias_content() {
+    let mut validator = setup_validator();
+    validator.load_mcdoc_source("copy_b.mcdoc".to_string(), RECIPE_MCDOC.to_string()).unwrap();
+    validator.load_mcdoc_source("copy_a.mcdoc".to_string(), RECIPE_MCDOC.to_string()).unwrap();
+
+    assert!(validator.unload_mcdoc("recipe.mcdoc"));
+    assert!(validator.schema("copy_a.mcdoc").is_some());
+    assert_eq!(validator.schema_aliases.get("copy_b.mcdoc").map(String::as_str), Some("copy_a.mcdoc"));
+    assert!(validator.validate_json(&json!({ "result": "minecraft:stone" }), "recipe", None).is_valid);
+}
+
+#[test]
+fn test_unload_registry() {
+    let mut validator = setup_validator();
+
+    assert!(validator.unload_registry("item"));
+    assert!(!validator.registry_manager.has_registry("item"));
+    assert!(!validator.unload_registry("item"));
+
+    let result = validator.validate_json(&json!({ "result": "minecraft:stone" }), "recipe", None);
+    assert_eq!(result.errors[0].message, "Unknown registry 'item'");
+}
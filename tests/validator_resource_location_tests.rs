@@ -0,0 +1,74 @@
+//! Tests for malformed resource locations in `#[id]` annotated fields
+
+use voxel_rsmcdoc::{parse_mcdoc, ErrorCode, McDocError};
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[recipe] to struct Recipe {
+    result: #[id(registry="item", tags="allowed")] string,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("recipe.mcdoc".to_string(), ast).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:stone": {} },
+        "tags": { "minecraft:logs": [] }
+    })).unwrap();
+    validator
+}
+
+fn single_error(value: &str) -> McDocError {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({ "result": value }), "recipe", None);
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "result");
+    assert!(result.dependencies.is_empty());
+    result.errors[0].clone()
+}
+
+#[test]
+fn test_misplaced_tag_marker() {
+    let error = single_error("minecraft:#logs");
+    assert_eq!(error.code, ErrorCode::MisplacedTagMarker);
+    assert_eq!(error.suggestion.as_deref(), Some("#minecraft:logs"));
+    assert!(error.message.contains("the tag marker '#' must be the first character"));
+    assert!(error.message.contains("did you mean '#minecraft:logs'"));
+}
+
+#[test]
+fn test_empty_namespace() {
+    let error = single_error("#:logs");
+    assert_eq!(error.code, ErrorCode::EmptyNamespace);
+    assert_eq!(error.suggestion.as_deref(), Some("#minecraft:logs"));
+    assert!(error.message.contains("empty namespace"));
+    assert!(error.message.contains("did you mean '#minecraft:logs'"));
+}
+
+#[test]
+fn test_repeated_namespace() {
+    let error = single_error("minecraft:minecraft:stone");
+    assert_eq!(error.code, ErrorCode::RepeatedNamespace);
+    assert_eq!(error.suggestion.as_deref(), Some("minecraft:stone"));
+    assert!(error.message.contains("repeated namespace 'minecraft'"));
+    assert!(error.message.contains("did you mean 'minecraft:stone'"));
+}
+
+#[test]
+fn test_well_formed_locations_are_untouched() {
+    let validator = setup_validator();
+    for value in ["minecraft:stone", "#minecraft:logs"] {
+        let result = validator.validate_json(&json!({ "result": value }), "recipe", None);
+        assert!(result.is_valid, "Validation failed for {}: {:?}", value, result.errors);
+    }
+}
+
+#[test]
+fn test_suggestion_is_serialized() {
+    let serialized = serde_json::to_value(single_error("minecraft:minecraft:stone")).unwrap();
+    assert_eq!(serialized["code"], "repeatedNamespace");
+    assert_eq!(serialized["suggestion"], "minecraft:stone");
+}
@@ -0,0 +1,61 @@
+//! Tests for the cached root types of resource types
+
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::ErrorCode;
+use serde_json::json;
+
+const INT_MCDOC: &str = r#"
+dispatch minecraft:resource[counter] to Counter
+
+type Counter = CounterData
+
+struct CounterData {
+    value: int,
+}
+"#;
+
+const STRING_MCDOC: &str = r#"
+dispatch minecraft:resource[counter] to struct Counter {
+    value: string,
+}
+"#;
+
+#[test]
+fn test_root_follows_aliases_to_struct() {
+    let mut validator = DatapackValidator::new();
+    validator.load_parsed_mcdoc("counter.mcdoc".to_string(), parse_mcdoc(INT_MCDOC).unwrap()).unwrap();
+
+    // Second validation uses the cached root
+    for _ in 0..2 {
+        assert!(validator.validate_json(&json!({ "value": 3 }), "counter", None).is_valid);
+        let result = validator.validate_json(&json!({ "value": "three" }), "counter", None);
+        assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+        assert_eq!(result.errors[0].path, "value");
+    }
+}
+
+#[test]
+fn test_cache_invalidated_by_loading_and_unloading() {
+    let mut validator = DatapackValidator::new();
+    validator.load_parsed_mcdoc("counter.mcdoc".to_string(), parse_mcdoc(INT_MCDOC).unwrap()).unwrap();
+    assert!(validator.validate_json(&json!({ "value": 3 }), "counter", None).is_valid);
+
+    validator.load_parsed_mcdoc("counter.mcdoc".to_string(), parse_mcdoc(STRING_MCDOC).unwrap()).unwrap();
+    assert!(!validator.validate_json(&json!({ "value": 3 }), "counter", None).is_valid);
+    assert!(validator.validate_json(&json!({ "value": "three" }), "counter", None).is_valid);
+
+    assert!(validator.unload_mcdoc("counter.mcdoc"));
+    let result = validator.validate_json(&json!({ "value": "three" }), "counter", None);
+    assert_eq!(result.errors[0].code, ErrorCode::SchemaNotFound);
+}
+
+#[test]
+fn test_missing_schema_is_not_cached_after_load() {
+    let mut validator = DatapackValidator::new();
+    let result = validator.validate_json(&json!({ "value": 3 }), "counter", None);
+    assert_eq!(result.errors[0].code, ErrorCode::SchemaNotFound);
+
+    validator.load_mcdoc_source("counter.mcdoc".to_string(), INT_MCDOC.to_string()).unwrap();
+    assert!(validator.validate_json(&json!({ "value": 3 }), "counter", None).is_valid);
+}
@@ -0,0 +1,97 @@
+//! Tests for changing the severity of findings by error code
+
+use serde_json::json;
+use voxel_rsmcdoc::validator::{DatapackValidator, ValidationOptions};
+use voxel_rsmcdoc::{ErrorCode, Severity};
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[recipe] to struct Recipe {
+    result: #[id="item"] string,
+    #[deprecated] group?: string,
+    count?: int @ 1..64,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("recipe.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({ "entries": { "minecraft:stone": {} } })).unwrap();
+    validator
+}
+
+fn legacy_recipe() -> serde_json::Value {
+    json!({ "result": "mypack:ruby", "custom_data": true })
+}
+
+fn codes(errors: &[voxel_rsmcdoc::McDocError]) -> Vec<ErrorCode> {
+    let mut codes: Vec<ErrorCode> = errors.iter().map(|error| error.code).collect();
+    codes.sort_by_key(|code| format!("{:?}", code));
+    codes
+}
+
+#[test]
+fn test_lenient_preset_reports_strict_errors_as_warnings() {
+    let validator = setup_validator();
+    let strict = ValidationOptions { strict_fields: true, ..Default::default() };
+    let lenient = ValidationOptions { strict_fields: true, ..ValidationOptions::lenient_legacy() };
+
+    let strict_result = validator.validate_json_with_options(&legacy_recipe(), "recipe", None, &strict);
+    assert!(!strict_result.is_valid);
+    assert_eq!(codes(&strict_result.errors), [ErrorCode::RegistryMiss, ErrorCode::UnknownField]);
+
+    let lenient_result = validator.validate_json_with_options(&legacy_recipe(), "recipe", None, &lenient);
+    assert!(lenient_result.is_valid, "{:?}", lenient_result.errors);
+    assert!(lenient_result.errors.is_empty());
+    assert_eq!(codes(&lenient_result.warnings), codes(&strict_result.errors));
+    for (warning, error) in lenient_result.warnings.iter().zip(&strict_result.errors) {
+        assert_eq!(warning.severity, Severity::Warning);
+        assert_eq!(error.severity, Severity::Error);
+    }
+
+    // Other codes keep their severity
+    let result = validator.validate_json_with_options(&json!({ "result": "minecraft:stone", "count": 99 }), "recipe", None, &lenient);
+    assert_eq!(codes(&result.errors), [ErrorCode::OutOfRange]);
+}
+
+#[test]
+fn test_single_override_flips_only_its_code() {
+    let validator = setup_validator();
+    let mut options = ValidationOptions { strict_fields: true, ..Default::default() };
+    options.severity_overrides.insert(ErrorCode::UnknownField, Severity::Info);
+
+    let result = validator.validate_json_with_options(&legacy_recipe(), "recipe", None, &options);
+    assert!(!result.is_valid);
+    assert_eq!(codes(&result.errors), [ErrorCode::RegistryMiss]);
+    assert_eq!(codes(&result.warnings), [ErrorCode::UnknownField]);
+    assert_eq!(result.warnings[0].severity, Severity::Info);
+}
+
+#[test]
+fn test_warning_upgraded_to_error() {
+    let validator = setup_validator();
+    let json = json!({ "result": "minecraft:stone", "group": "planks" });
+    assert!(validator.validate_json(&json, "recipe", None).is_valid);
+
+    let mut options = ValidationOptions::default();
+    options.severity_overrides.insert(ErrorCode::Deprecated, Severity::Error);
+    let result = validator.validate_json_with_options(&json, "recipe", None, &options);
+    assert!(!result.is_valid);
+    assert_eq!(codes(&result.errors), [ErrorCode::Deprecated]);
+    assert!(result.warnings.is_empty());
+}
+
+#[test]
+fn test_serialized_results_carry_effective_severity() {
+    let validator = setup_validator();
+    let mut options = ValidationOptions::default();
+    options.severity_overrides.insert(ErrorCode::RegistryMiss, Severity::Warning);
+
+    let result = validator.validate_json_with_options(&legacy_recipe(), "recipe", None, &options);
+    let serialized = serde_json::to_value(&result).unwrap();
+    assert_eq!(serialized["isValid"], true);
+    assert_eq!(serialized["warnings"][0]["code"], "registryMiss");
+    assert_eq!(serialized["warnings"][0]["severity"], "warning");
+
+    let strict = serde_json::to_value(validator.validate_json(&legacy_recipe(), "recipe", None)).unwrap();
+    assert_eq!(strict["errors"][0]["severity"], "error");
+}
@@ -0,0 +1,65 @@
+//! Tests for the file and resource type reported by errors and dependencies
+
+use std::collections::HashMap;
+use serde_json::json;
+use voxel_rsmcdoc::validator::{DatapackValidator, ValidationOptions};
+
+const MCDOC: &str = "dispatch minecraft:resource[recipe] to struct Recipe { result: #[id=\"item\"] string, count?: int }";
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("recipe.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({ "entries": { "minecraft:stone": {} } })).unwrap();
+    validator
+}
+
+fn invalid_recipe() -> serde_json::Value {
+    json!({ "result": "minecraft:stone", "count": "two" })
+}
+
+#[test]
+fn test_without_source_file() {
+    let result = setup_validator().validate_json(&invalid_recipe(), "recipe", None);
+    assert_eq!(result.errors[0].file, "");
+    assert_eq!(result.errors[0].resource_type.as_deref(), Some("recipe"));
+    assert_eq!(result.dependencies[0].source_file, None);
+}
+
+#[test]
+fn test_source_file_option() {
+    let options = ValidationOptions { source_file: Some("data/demo/recipe/sword.json".to_string()), ..ValidationOptions::default() };
+    let result = setup_validator().validate_json_with_options(&invalid_recipe(), "recipe", None, &options);
+
+    assert_eq!(result.errors[0].file, "data/demo/recipe/sword.json");
+    assert_eq!(result.errors[0].resource_type.as_deref(), Some("recipe"));
+    assert_eq!(result.dependencies[0].source_file.as_deref(), Some("data/demo/recipe/sword.json"));
+
+    let serialized = serde_json::to_value(&result.errors[0]).unwrap();
+    assert_eq!(serialized["file"], "data/demo/recipe/sword.json");
+    assert_eq!(serialized["resourceType"], "recipe");
+}
+
+#[test]
+fn test_datapack_errors_in_their_file() {
+    let validator = setup_validator();
+    let files = HashMap::from([
+        ("data/demo/recipe/first.json".to_string(), invalid_recipe()),
+        ("data/demo/recipe/second.json".to_string(), invalid_recipe()),
+    ]);
+    let result = validator.analyze_datapack(&files);
+
+    let files: Vec<(&str, &str)> = result.errors.iter().map(|error| (error.file_path.as_str(), error.error.file.as_str())).collect();
+    assert_eq!(files, [
+        ("data/demo/recipe/first.json", "data/demo/recipe/first.json"),
+        ("data/demo/recipe/second.json", "data/demo/recipe/second.json"),
+    ]);
+    assert!(result.errors.iter().all(|error| error.error.resource_type.as_deref() == Some("recipe")));
+}
+
+#[test]
+fn test_stream_errors_in_their_file() {
+    let validator = setup_validator();
+    let results: Vec<_> = validator.validate_stream([("data/demo/recipe/third.json".to_string(), invalid_recipe())]).collect();
+    assert_eq!(results[0].1.errors[0].file, "data/demo/recipe/third.json");
+    assert_eq!(results[0].1.dependencies[0].source_file.as_deref(), Some("data/demo/recipe/third.json"));
+}
@@ -0,0 +1,160 @@
+//! Tests for the expansion of spread structs reused across validations
+
+use voxel_rsmcdoc::types::ValidationResult;
+use voxel_rsmcdoc::validator::{DatapackValidator, ValidationOptions};
+use serde_json::{json, Value};
+
+const ITEM: &str = r#"
+dispatch minecraft:resource[item_like] to struct ItemLike {
+    id: string,
+    ...ItemData,
+}
+
+dispatch minecraft:resource[tooltip] to struct Tooltip {
+    ...ItemDisplay,
+}
+"#;
+
+const DATA: &str = r#"
+struct ItemData {
+    count?: int @ 1..99,
+    ...ItemDisplay,
+}
+"#;
+
+const DISPLAY: &str = r#"
+struct ItemDisplay {
+    name?: string,
+    ...ItemLore,
+    #[since="1.21"]
+    ...ItemRarity,
+}
+"#;
+
+const LORE: &str = r#"
+struct ItemLore {
+    lore?: [string],
+}
+
+struct ItemRarity {
+    rarity: ("common" | "rare" | "epic"),
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("item.mcdoc".to_string(), ITEM.to_string()).unwrap();
+    validator.load_mcdoc_source("data.mcdoc".to_string(), DATA.to_string()).unwrap();
+    validator.load_mcdoc_source("display.mcdoc".to_string(), DISPLAY.to_string()).unwrap();
+    validator.load_mcdoc_source("lore.mcdoc".to_string(), LORE.to_string()).unwrap();
+    validator
+}
+
+fn validate(validator: &DatapackValidator, json: &Value, version: Option<&str>, memoize_spreads: bool) -> ValidationResult {
+    let options = ValidationOptions { memoize_spreads, strict_fields: true, ..Default::default() };
+    validator.validate_json_with_options(json, "item_like", version, &options)
+}
+
+fn messages(result: &ValidationResult) -> Vec<(String, String)> {
+    result.errors.iter().map(|error| (error.path.to_string(), error.message.clone())).collect()
+}
+
+#[test]
+fn test_memoized_spreads_report_the_same_errors() {
+    let validator = setup_validator();
+    let documents = [
+        json!({ "id": "stone" }),
+        json!({ "id": "stone", "count": 120, "lore": [1], "extra": true }),
+        json!({ "id": "stone", "rarity": "legendary" }),
+        json!({ "count": "many", "name": 3 }),
+    ];
+
+    for version in [None, Some("1.20.4"), Some("1.21")] {
+        for document in &documents {
+            let uncached = messages(&validate(&validator, document, version, false));
+            // Twice, the second run reads the expansion cached by the first
+            assert_eq!(messages(&validate(&validator, document, version, true)), uncached, "{} at {:?}", document, version);
+            assert_eq!(messages(&validate(&validator, document, version, true)), uncached, "{} at {:?}", document, version);
+        }
+    }
+}
+
+#[test]
+fn test_memoized_spreads_are_kept_per_version() {
+    let validator = setup_validator();
+    let document = json!({ "id": "stone" });
+
+    assert!(validate(&validator, &document, Some("1.20.4"), true).is_valid);
+    let result = validate(&validator, &document, Some("1.21"), true);
+    assert_eq!(messages(&result), vec![("rarity".to_string(), "Missing required field 'rarity'".to_string())]);
+    assert!(validate(&validator, &document, Some("1.20.4"), true).is_valid);
+}
+
+#[test]
+fn test_replacing_a_mid_chain_schema_invalidates_its_dependents() {
+    let mut validator = setup_validator();
+    let document = json!({ "id": "stone", "name": "Stone" });
+    assert!(validate(&validator, &document, Some("1.20.4"), true).is_valid);
+    assert!(validator.validate_json(&json!({ "name": "Stone" }), "tooltip", Some("1.20.4")).is_valid);
+
+    validator.load_mcdoc_source("display.mcdoc".to_string(), "struct ItemDisplay { name: int, ...ItemLore }".to_string()).unwrap();
+
+    let result = validate(&validator, &document, Some("1.20.4"), true);
+    assert_eq!(messages(&result), vec![("name".to_string(), "Expected number, found string".to_string())]);
+    assert_eq!(messages(&result), messages(&validate(&validator, &document, Some("1.20.4"), false)));
+    assert!(!validator.validate_json(&json!({ "name": "Stone" }), "tooltip", Some("1.20.4")).is_valid);
+}
+
+#[test]
+fn test_document_dependent_spreads_are_not_memoized() {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("component.mcdoc".to_string(), r#"
+dispatch minecraft:resource[component] to struct Component {
+    ...Typed,
+}
+
+struct Typed {
+    type: string,
+    ...minecraft:component_kind[[type]],
+}
+
+dispatch minecraft:component_kind[damage] to struct { amount: int }
+dispatch minecraft:component_kind[name] to struct { text: string }
+"#.to_string()).unwrap();
+
+    let damage = validator.validate_json(&json!({ "type": "damage", "amount": "high" }), "component", None);
+    assert_eq!(messages(&damage), vec![("amount".to_string(), "Expected number, found string".to_string())]);
+    let name = validator.validate_json(&json!({ "type": "name", "text": 3 }), "component", None);
+    assert_eq!(messages(&name), vec![("text".to_string(), "Expected string, found number".to_string())]);
+}
+
+#[test]
+fn test_spread_cycles_end_with_memoized_spreads() {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("cycle.mcdoc".to_string(), r#"
+dispatch minecraft:resource[item_like] to struct ItemLike {
+    id: string,
+    ...ItemData,
+}
+
+struct ItemData {
+    count?: int,
+    ...ItemDisplay,
+}
+
+struct ItemDisplay {
+    name?: string,
+    ...ItemData,
+}
+"#.to_string()).unwrap();
+
+    let document = json!({ "id": "stone", "count": "many" });
+    let uncached = messages(&validate(&validator, &document, None, false));
+    assert_eq!(uncached, vec![
+        ("".to_string(), "Spread cycle: ItemData -> ItemDisplay -> ItemData".to_string()),
+        ("count".to_string(), "Expected number, found string".to_string()),
+    ]);
+    // Twice, cycles aren't cached
+    assert_eq!(messages(&validate(&validator, &document, None, true)), uncached);
+    assert_eq!(messages(&validate(&validator, &document, None, true)), uncached);
+}
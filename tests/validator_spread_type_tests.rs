@@ -0,0 +1,120 @@
+//! Tests for spreads of named structs, generic type aliases and import paths
+
+use voxel_rsmcdoc::error::ErrorCode;
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::{DatapackValidator, ValidationOptions};
+use serde_json::json;
+
+const MCDOC: &str = r#"
+struct Base {
+    id: string,
+}
+
+type Layer<T> = struct {
+    texture: T,
+}
+
+dispatch minecraft:resource[wings] to struct Wings {
+    ...Base,
+    ...Layer<int>,
+    use_player_texture?: boolean,
+}
+
+dispatch minecraft:resource[imported] to struct Imported {
+    ...super::Base,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("wings.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator
+}
+
+#[test]
+fn test_spread_fields_are_merged() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({ "id": "elytra", "texture": 3 }), "wings", None);
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+
+    let missing = validator.validate_json(&json!({ "texture": 3 }), "wings", None);
+    assert_eq!(missing.errors.len(), 1, "Unexpected errors: {:?}", missing.errors);
+    assert_eq!(missing.errors[0].message, "Missing required field 'id'");
+}
+
+#[test]
+fn test_generic_spread_is_instantiated() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({ "id": "elytra", "texture": "wings" }), "wings", None);
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "texture");
+}
+
+#[test]
+fn test_import_path_spread() {
+    let validator = setup_validator();
+    let result = validator.validate_json(&json!({ "id": 1 }), "imported", None);
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "id");
+}
+
+#[test]
+fn test_strict_fields_know_spread_fields() {
+    let validator = setup_validator();
+    let options = ValidationOptions { strict_fields: true, ..Default::default() };
+    let result = validator.validate_json_with_options(&json!({ "id": "elytra", "texture": 3, "extra": true }), "wings", None, &options);
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "extra");
+}
+
+#[test]
+fn test_import_path_spread_across_modules() {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_modules(vec![
+        // Same names declared in another module, loaded first, must not be picked
+        ("java/assets/util.mcdoc".to_string(), parse_mcdoc("struct MobCategory { tint: int }\nstruct Weighted { scale: float }").unwrap()),
+        ("java/data/util/mod.mcdoc".to_string(), parse_mcdoc("struct MobCategory { category: string }\nstruct Weighted { weight: int }").unwrap()),
+        ("java/data/spawner.mcdoc".to_string(), parse_mcdoc(r#"
+dispatch minecraft:resource[spawner] to struct Spawner {
+    ...::java::data::util::MobCategory,
+    ...super::util::Weighted,
+}
+"#).unwrap()),
+    ]).unwrap();
+
+    let result = validator.validate_json(&json!({ "category": "monster", "weight": 3 }), "spawner", None);
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+
+    let result = validator.validate_json(&json!({ "tint": 1, "scale": 0.5 }), "spawner", None);
+    let mut missing: Vec<&str> = result.errors.iter().map(|error| error.path.as_str()).collect();
+    missing.sort_unstable();
+    assert_eq!(missing, ["category", "weight"], "Unexpected errors: {:?}", result.errors);
+}
+
+#[test]
+fn test_spread_cycle_is_reported() {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("cycle.mcdoc".to_string(), r#"
+dispatch minecraft:resource[cycle] to struct A {
+    a?: int,
+    ...B,
+}
+
+struct B {
+    b?: int,
+    ...A,
+}
+"#.to_string()).unwrap();
+
+    let options = ValidationOptions { memoize_spreads: false, ..Default::default() };
+    let result = validator.validate_json_with_options(&json!({ "a": 1, "b": 2 }), "cycle", None, &options);
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].code, ErrorCode::TypeCycle);
+    assert_eq!(result.errors[0].message, "Spread cycle: B -> A -> B");
+
+    let result = validator.validate_json_with_options(&json!({ "b": "two" }), "cycle", None, &options);
+    assert!(result.errors.iter().any(|error| error.path == "b" && error.code == ErrorCode::TypeMismatch), "Unexpected errors: {:?}", result.errors);
+}
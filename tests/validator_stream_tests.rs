@@ -0,0 +1,87 @@
+//! Tests for validating files pulled one at a time from an iterator
+
+use std::cell::Cell;
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::{ErrorCode, ValidationStatus};
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[recipe] to struct Recipe {
+    result: #[id="item"] string,
+    count: int,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_parsed_mcdoc("recipe.mcdoc".to_string(), parse_mcdoc(MCDOC).unwrap()).unwrap();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:stone": {}, "minecraft:diamond": {} }
+    })).unwrap();
+    validator
+}
+
+fn recipe(index: usize) -> (String, serde_json::Value) {
+    let count = if index.is_multiple_of(2) { json!(index) } else { json!("many") };
+    (format!("data/demo/recipe/recipe_{}.json", index), json!({ "result": "minecraft:stone", "count": count }))
+}
+
+#[test]
+fn test_stream_validates_lazily() {
+    let validator = setup_validator();
+    let generated = Cell::new(0);
+    let items = (0..100).map(|index| {
+        generated.set(generated.get() + 1);
+        recipe(index)
+    });
+
+    let results: Vec<_> = validator.validate_stream(items).take(2).collect();
+    assert_eq!(generated.get(), 2);
+    assert_eq!(results[0].0, "data/demo/recipe/recipe_0.json");
+    assert!(results[0].1.is_valid, "Unexpected errors: {:?}", results[0].1.errors);
+    assert!(!results[1].1.is_valid);
+}
+
+#[test]
+fn test_stream_stops_on_first_error() {
+    let validator = setup_validator();
+    let generated = Cell::new(0);
+    let items = (0..100).map(|index| {
+        generated.set(generated.get() + 1);
+        recipe(index)
+    });
+
+    let first_invalid = validator.validate_stream(items).find(|(_, result)| !result.is_valid);
+    assert_eq!(first_invalid.unwrap().0, "data/demo/recipe/recipe_1.json");
+    assert_eq!(generated.get(), 2);
+}
+
+#[test]
+fn test_stream_unknown_resource_type() {
+    let validator = setup_validator();
+    let items = vec![("data/demo/machines/press.json".to_string(), json!({}))];
+
+    let results: Vec<_> = validator.validate_stream(items).collect();
+    assert_eq!(results.len(), 1);
+    let result = &results[0].1;
+    assert!(!result.is_valid);
+    assert_eq!(result.status, ValidationStatus::Unknown);
+    assert!(result.errors.is_empty());
+    assert_eq!(result.warnings[0].code, ErrorCode::SchemaNotFound);
+}
+
+#[test]
+fn test_analyze_stream_aggregates_results() {
+    let validator = setup_validator();
+    let items = (0..10).map(recipe)
+        .chain([("data/demo/machines/press.json".to_string(), json!({}))]);
+
+    let result = validator.analyze_stream(items);
+    assert_eq!(result.total_files, 11);
+    assert_eq!(result.valid_files, 5);
+    assert_eq!(result.errors.len(), 5);
+    assert_eq!(result.warnings.len(), 1);
+    assert_eq!(result.warnings[0].file_path, "data/demo/machines/press.json");
+    assert_eq!(result.dependencies["item"], ["minecraft:stone"]);
+}
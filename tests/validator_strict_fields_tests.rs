@@ -0,0 +1,67 @@
+//! Tests for rejecting undeclared object keys in strict mode
+
+use voxel_rsmcdoc::error::ErrorType;
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::{DatapackValidator, ValidationOptions};
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[recipe] to struct Recipe {
+    type: string,
+    result: struct Result {
+        id: string,
+        count?: int,
+    },
+    effects?: struct Effects {
+        [string]: int,
+    },
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("recipe.mcdoc".to_string(), ast).unwrap();
+    validator
+}
+
+fn strict() -> ValidationOptions {
+    ValidationOptions { strict_fields: true, ..Default::default() }
+}
+
+#[test]
+fn test_unknown_fields_are_lenient_by_default() {
+    let validator = setup_validator();
+    let json = json!({ "type": "shaped", "result": { "id": "stone" }, "resutl": {} });
+
+    let result = validator.validate_json(&json, "recipe", None);
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+}
+
+#[test]
+fn test_strict_mode_reports_unknown_fields() {
+    let validator = setup_validator();
+    let json = json!({ "type": "shaped", "result": { "id": "stone", "cout": 2 }, "resutl": {} });
+
+    let result = validator.validate_json_with_options(&json, "recipe", None, &strict());
+    let mut paths: Vec<_> = result.errors.iter().map(|e| e.path.as_str()).collect();
+    paths.sort();
+    assert_eq!(paths, ["result.cout", "resutl"]);
+    assert!(result.errors.iter().all(|e| e.error_type == ErrorType::UnknownField));
+
+    let typo = result.errors.iter().find(|e| e.path == "resutl").unwrap();
+    assert_eq!(typo.message, "Unknown field 'resutl'");
+}
+
+#[test]
+fn test_strict_mode_allows_dynamic_field_keys() {
+    let validator = setup_validator();
+    let json = json!({
+        "type": "shaped",
+        "result": { "id": "stone" },
+        "effects": { "speed": 1, "anything_goes": 2 }
+    });
+
+    let result = validator.validate_json_with_options(&json, "recipe", None, &strict());
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+}
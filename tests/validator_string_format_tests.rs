@@ -0,0 +1,84 @@
+//! Tests for string formats given by annotations like `#[uuid]`
+
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::ErrorCode;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[profile] to struct Profile {
+    id?: #[uuid] string,
+    color?: #[color="hex_rgb"] string,
+    tint?: #[color="dec_rgb"] string,
+    pattern?: #[regex_pattern] string,
+    aliases?: [#[regex_pattern] string],
+    name?: #[lowercase] string,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("profile.mcdoc".to_string(), ast).unwrap();
+    validator
+}
+
+fn error_messages(validator: &DatapackValidator, json: serde_json::Value) -> Vec<String> {
+    let result = validator.validate_json(&json, "profile", None);
+    assert!(result.errors.iter().all(|e| e.code == ErrorCode::InvalidFormat), "Unexpected errors: {:?}", result.errors);
+    result.errors.iter().map(|e| format!("{}: {}", e.path, e.message)).collect()
+}
+
+#[test]
+fn test_uuid() {
+    let validator = setup_validator();
+    assert!(error_messages(&validator, json!({ "id": "f81d4fae-7dec-11d0-a765-00a0c91e6bf6" })).is_empty());
+    assert_eq!(
+        error_messages(&validator, json!({ "id": "f81d4fae7dec11d0a76500a0c91e6bf6" })),
+        ["id: Invalid uuid 'f81d4fae7dec11d0a76500a0c91e6bf6': expected hexadecimal digits grouped as 8-4-4-4-12"]
+    );
+}
+
+#[test]
+fn test_hex_color() {
+    let validator = setup_validator();
+    assert!(error_messages(&validator, json!({ "color": "#1a2B3c", "tint": "anything" })).is_empty());
+    assert_eq!(
+        error_messages(&validator, json!({ "color": "1a2b3c" })),
+        ["color: Invalid color '1a2b3c': expected '#' followed by 6 hexadecimal digits"]
+    );
+}
+
+#[test]
+fn test_regex_pattern() {
+    let validator = setup_validator();
+    assert!(error_messages(&validator, json!({ "pattern": "^(?:[a-z_]+|\\d{1,3})*?$", "aliases": ["[]a]", "a{2,}"] })).is_empty());
+    assert_eq!(
+        error_messages(&validator, json!({ "pattern": "(abc", "aliases": ["ok", "*a", "[a-z", "a{3,1}"] })),
+        [
+            "pattern: Invalid regex_pattern '(abc': unclosed group",
+            "aliases[1]: Invalid regex_pattern '*a': nothing to repeat before '*' at index 0",
+            "aliases[2]: Invalid regex_pattern '[a-z': unclosed character class",
+            "aliases[3]: Invalid regex_pattern 'a{3,1}': invalid repetition range at index 1",
+        ]
+    );
+}
+
+#[test]
+fn test_custom_format() {
+    let mut validator = setup_validator();
+    assert!(error_messages(&validator, json!({ "name": "Steve" })).is_empty());
+
+    validator.register_string_format("lowercase", |value, _| {
+        if value.chars().any(|c| c.is_uppercase()) {
+            Err("expected lowercase characters".to_string())
+        } else {
+            Ok(())
+        }
+    });
+    assert!(error_messages(&validator, json!({ "name": "steve" })).is_empty());
+    assert_eq!(error_messages(&validator, json!({ "name": "Steve" })), ["name: Invalid lowercase 'Steve': expected lowercase characters"]);
+
+    assert!(validator.string_formats.unregister("uuid"));
+    assert!(error_messages(&validator, json!({ "id": "not a uuid" })).is_empty());
+}
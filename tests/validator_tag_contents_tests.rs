@@ -0,0 +1,69 @@
+//! Tests for checking the contents of referenced tags
+
+use voxel_rsmcdoc::validator::{DatapackValidator, ValidationOptions};
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[fuel] to struct Fuel {
+    blocks: #[id(registry="block", tags="required")] string,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("fuel.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator.load_registry("block".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:oak_planks": {}, "minecraft:oak_log": {} },
+        "tags": {
+            "minecraft:planks": ["minecraft:oak_planks"],
+            "minecraft:wooden": ["#minecraft:planks", "minecraft:oak_log"],
+            "minecraft:empty": [],
+            "minecraft:stale": ["minecraft:oak_log", "minecraft:removed_block"],
+            "minecraft:loop_a": ["#minecraft:loop_b"],
+            "minecraft:loop_b": ["#minecraft:loop_a"]
+        }
+    })).unwrap();
+    validator
+}
+
+fn check_tags() -> ValidationOptions {
+    ValidationOptions { check_tag_contents: true, ..Default::default() }
+}
+
+#[test]
+fn test_tag_of_tags_is_valid() {
+    let validator = setup_validator();
+    let result = validator.validate_json_with_options(&json!({ "blocks": "#minecraft:wooden" }), "fuel", None, &check_tags());
+    assert!(result.is_valid, "Validation failed: {:?}", result.errors);
+}
+
+#[test]
+fn test_empty_tag_is_reported() {
+    let validator = setup_validator();
+    let json = json!({ "blocks": "#minecraft:empty" });
+
+    assert!(validator.validate_json(&json, "fuel", None).is_valid, "Tag contents are only checked on request");
+
+    let result = validator.validate_json_with_options(&json, "fuel", None, &check_tags());
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].message, "Tag '#minecraft:empty' in registry 'block' has no entries");
+}
+
+#[test]
+fn test_unknown_tag_entries_are_reported() {
+    let validator = setup_validator();
+    let result = validator.validate_json_with_options(&json!({ "blocks": "#minecraft:stale" }), "fuel", None, &check_tags());
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "blocks");
+    assert_eq!(result.errors[0].message, "Tag '#minecraft:stale' contains entries not found in registry 'block': minecraft:removed_block");
+}
+
+#[test]
+fn test_circular_tag_is_reported() {
+    let validator = setup_validator();
+    let result = validator.validate_json_with_options(&json!({ "blocks": "#minecraft:loop_a" }), "fuel", None, &check_tags());
+
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert!(result.errors[0].message.starts_with("Circular tag reference"), "Unexpected message: {}", result.errors[0].message);
+}
@@ -56,13 +56,27 @@ fn test_validate_json_missing_dependency() {
     assert!(!result.is_valid); // Should be false once validation is implemented
 }
 
-/*
 #[test]
 fn test_get_required_registries() {
     let mut validator = DatapackValidator::new();
+    let ast = voxel_rsmcdoc::parse_mcdoc(r#"
+dispatch minecraft:resource[recipe] to struct Recipe {
+    type: #[id="recipe_serializer"] string,
+    ...minecraft:recipe_serializer[[type]],
+}
+
+dispatch minecraft:recipe_serializer[crafting_shaped] to struct CraftingShaped {
+    result: struct { item: #[id="item"] string },
+    ingredients: [struct Ingredient {
+        item?: #[id="item"] string,
+        tag?: #[id(registry="item", tags="implicit")] string,
+    }],
+}
+"#).unwrap();
+    validator.load_parsed_mcdoc("recipe.mcdoc".to_string(), ast).unwrap();
     
     let json = json!({
-        "type": "crafting_shaped",
+        "type": "minecraft:crafting_shaped",
         "result": { "item": "minecraft:diamond" },
         "ingredients": [
             { "item": "minecraft:stick" },
@@ -70,10 +84,17 @@ fn test_get_required_registries() {
         ]
     });
     
-    let registries = validator.get_required_registries(&json, "recipe");
-    assert_eq!(registries, vec!["item", "tag"]);
+    // No registry is loaded, the schema alone tells what is needed
+    let dependencies = validator.get_required_registries(&json, "recipe");
+    let mut registries: Vec<&str> = dependencies.iter().map(|dependency| dependency.registry.as_str()).collect();
+    registries.sort_unstable();
+    registries.dedup();
+    assert_eq!(registries, vec!["item", "recipe_serializer"]);
+
+    let tag = dependencies.iter().find(|dependency| dependency.is_tag).unwrap();
+    assert_eq!(tag.source_path, "ingredients[1].tag");
+    assert_eq!(dependencies.len(), 4);
 }
-*/
 
 #[test]
 fn test_registry_loading() {
@@ -0,0 +1,81 @@
+//! Tests for unions of structs told apart by a literal field, like `type: "block"`
+
+use serde_json::json;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use voxel_rsmcdoc::ErrorCode;
+
+const POSITION_SOURCE_MCDOC: &str = r#"
+struct BlockPositionSource {
+    type: #[id="position_source_type"] "block",
+    pos: [int] @ 3,
+}
+
+struct EntityPositionSource {
+    type: #[id="position_source_type"] "entity",
+    source_entity: string,
+    y_offset?: float,
+}
+
+struct Vibration {
+    source: (BlockPositionSource | EntityPositionSource | struct { type: "fixed", enabled: boolean }),
+    untagged?: (struct { a: int } | struct { b: int }),
+}
+
+dispatch minecraft:resource[vibration] to Vibration
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("vibration.mcdoc".to_string(), POSITION_SOURCE_MCDOC.to_string()).unwrap();
+    validator.load_registry("position_source_type".to_string(), "1.21".to_string(), &json!({
+        "entries": { "minecraft:block": {}, "minecraft:entity": {} }
+    })).unwrap();
+    validator
+}
+
+fn errors_of(json: serde_json::Value) -> Vec<(ErrorCode, String, String)> {
+    let result = setup_validator().validate_json(&json, "vibration", None);
+    result.errors.into_iter().map(|error| (error.code, error.path, error.message)).collect()
+}
+
+#[test]
+fn test_matching_variant_is_valid() {
+    assert!(errors_of(json!({ "source": { "type": "block", "pos": [1, 2, 3] } })).is_empty());
+    assert!(errors_of(json!({ "source": { "type": "entity", "source_entity": "a" } })).is_empty());
+    assert!(errors_of(json!({ "source": { "type": "fixed", "enabled": true } })).is_empty());
+}
+
+#[test]
+fn test_reports_errors_of_selected_variant() {
+    assert_eq!(errors_of(json!({ "source": { "type": "block" } })), [(
+        ErrorCode::MissingField,
+        "source.pos".to_string(),
+        "in variant type=\"block\": Missing required field 'pos'".to_string(),
+    )]);
+
+    let errors = errors_of(json!({ "source": { "type": "entity", "source_entity": "a", "y_offset": "up" } }));
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].1, "source.y_offset");
+    assert!(errors[0].2.starts_with("in variant type=\"entity\": "), "{}", errors[0].2);
+
+    // Inline struct branches are told apart too
+    let errors = errors_of(json!({ "source": { "type": "fixed" } }));
+    assert_eq!(errors[0].2, "in variant type=\"fixed\": Missing required field 'enabled'");
+}
+
+#[test]
+fn test_unknown_discriminator_falls_back_to_union_error() {
+    for source in [json!({ "type": "sky" }), json!({ "pos": [1, 2, 3] }), json!("block")] {
+        let errors = errors_of(json!({ "source": source }));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, ErrorCode::UnionNoMatch);
+        assert!(errors[0].2.starts_with("JSON does not match any of the expected types"), "{}", errors[0].2);
+    }
+}
+
+#[test]
+fn test_union_without_discriminator_is_unchanged() {
+    let errors = errors_of(json!({ "source": { "type": "fixed", "enabled": true }, "untagged": { "c": 1 } }));
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, ErrorCode::UnionNoMatch);
+}
@@ -0,0 +1,62 @@
+//! Tests for union validation error messages
+
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[recipe] to struct Recipe {
+    ingredient: (
+        #[id(registry="item", tags="allowed")] string |
+        struct IngredientEntry {
+            item: string,
+            count?: int,
+        } |
+        [string] |
+    ),
+    mode?: ("fast" | "slow"),
+    name?: (#[since="1.20"] string | #[until="1.20"] string),
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    validator.load_mcdoc_source("recipe.mcdoc".to_string(), MCDOC.to_string()).unwrap();
+    validator
+}
+
+#[test]
+fn test_closest_branch_is_explained_first() {
+    let validator = setup_validator();
+
+    let result = validator.validate_json(&json!({ "ingredient": { "count": 2 } }), "recipe", None);
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(result.errors[0].path, "ingredient");
+
+    let message = &result.errors[0].message;
+    assert!(message.starts_with("JSON does not match any of the expected types (as struct 'IngredientEntry': Missing required field 'item'"), "{}", message);
+    assert!(message.contains("as string: Expected string, found object"), "{}", message);
+    assert!(message.contains("as array: Expected array"), "{}", message);
+}
+
+#[test]
+fn test_every_branch_is_described() {
+    let validator = setup_validator();
+
+    let result = validator.validate_json(&json!({ "ingredient": ["minecraft:stone"], "mode": 3 }), "recipe", None);
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+
+    // A union of literals only lists every allowed value
+    assert_eq!(result.errors[0].message, "Expected one of \"fast\", \"slow\", found 3");
+}
+
+#[test]
+fn test_identical_branch_messages_are_deduplicated() {
+    let validator = setup_validator();
+
+    let result = validator.validate_json(&json!({ "ingredient": ["minecraft:stone"], "name": 3 }), "recipe", None);
+    assert_eq!(result.errors.len(), 1, "Unexpected errors: {:?}", result.errors);
+    assert_eq!(
+        result.errors[0].message,
+        "JSON does not match any of the expected types (as string: Expected string, found number)"
+    );
+}
@@ -0,0 +1,71 @@
+//! Tests for `#[since]` and `#[until]` version gating during validation
+
+use std::fs;
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::DatapackValidator;
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[gated] to struct Gated {
+    value: (
+        #[until="1.20"] int |
+        #[since="1.20"] string
+    ),
+    #[since="1.20.5"]
+    components?: int,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("gated.mcdoc".to_string(), ast).unwrap();
+    validator
+}
+
+#[test]
+fn test_chat_type_overlay_removed_in_1_19_1() {
+    let content = fs::read_to_string("tests/dataset/mcdoc/data/chat_type.mcdoc")
+        .expect("Failed to read chat_type.mcdoc");
+    let mut validator = DatapackValidator::new();
+    validator.load_parsed_mcdoc("chat_type.mcdoc".to_string(), parse_mcdoc(&content).unwrap()).unwrap();
+
+    let json = json!({ "overlay": {} });
+
+    let old = validator.validate_json(&json, "chat_type", Some("1.19"));
+    assert!(old.is_valid, "Validation failed: {:?}", old.errors);
+
+    let new = validator.validate_json(&json, "chat_type", Some("1.20"));
+    assert!(!new.is_valid);
+    assert_eq!(new.errors[0].path, "overlay");
+    assert_eq!(new.errors[0].message, "Field 'overlay' is not available in version 1.20");
+}
+
+#[test]
+fn test_union_branches_outside_version_are_excluded() {
+    let validator = setup_validator();
+
+    assert!(validator.validate_json(&json!({ "value": 3 }), "gated", Some("1.19")).is_valid);
+    assert!(!validator.validate_json(&json!({ "value": 3 }), "gated", Some("1.20")).is_valid);
+    assert!(validator.validate_json(&json!({ "value": "three" }), "gated", Some("1.20")).is_valid);
+    assert!(!validator.validate_json(&json!({ "value": "three" }), "gated", Some("1.19")).is_valid);
+}
+
+#[test]
+fn test_since_field_before_its_version() {
+    let validator = setup_validator();
+    let json = json!({ "value": "v", "components": 1 });
+
+    assert!(validator.validate_json(&json, "gated", Some("1.20.5")).is_valid);
+    let result = validator.validate_json(&json, "gated", Some("1.20.4"));
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].path, "components");
+}
+
+#[test]
+fn test_no_version_accepts_every_branch() {
+    let validator = setup_validator();
+
+    assert!(validator.validate_json(&json!({ "value": 3, "components": 1 }), "gated", None).is_valid);
+    assert!(validator.validate_json(&json!({ "value": "three" }), "gated", None).is_valid);
+}
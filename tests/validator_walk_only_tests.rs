@@ -0,0 +1,57 @@
+//! Tests for validating without registry checks
+
+use voxel_rsmcdoc::parse_mcdoc;
+use voxel_rsmcdoc::validator::{DatapackValidator, ValidationOptions};
+use serde_json::json;
+
+const MCDOC: &str = r#"
+dispatch minecraft:resource[recipe] to struct Recipe {
+    result: #[id="item"] string,
+    count?: int,
+    sound?: #[id(registry="sound_event", tags="allowed")] string,
+}
+"#;
+
+fn setup_validator() -> DatapackValidator<'static> {
+    let mut validator = DatapackValidator::new();
+    let ast = parse_mcdoc(MCDOC).expect("MCDOC should parse");
+    validator.load_parsed_mcdoc("recipe.mcdoc".to_string(), ast).unwrap();
+    validator
+}
+
+fn walk_only() -> ValidationOptions {
+    ValidationOptions { check_registries: false, ..Default::default() }
+}
+
+#[test]
+fn test_registry_checks_are_on_by_default() {
+    assert!(ValidationOptions::default().check_registries);
+
+    let json = json!({ "result": "minecraft:diamond" });
+    let result = setup_validator().validate_json(&json, "recipe", None);
+    assert!(!result.is_valid);
+    assert!(result.errors[0].message.contains("Unknown registry 'item'"));
+}
+
+#[test]
+fn test_walk_only_extracts_dependencies() {
+    let json = json!({ "result": "minecraft:diamond", "sound": "#minecraft:ambient" });
+    let result = setup_validator().validate_json_with_options(&json, "recipe", None, &walk_only());
+
+    assert!(result.is_valid, "Unexpected errors: {:?}", result.errors);
+    let dependencies: Vec<(&str, &str)> = result.dependencies.iter()
+        .map(|dependency| (dependency.registry_type.as_str(), dependency.resource_location.as_str()))
+        .collect();
+    assert_eq!(dependencies, [("item", "minecraft:diamond"), ("sound_event", "#minecraft:ambient")]);
+}
+
+#[test]
+fn test_walk_only_still_validates_structure() {
+    let mut validator = setup_validator();
+    validator.load_registry("item".to_string(), "1.21".to_string(), &json!({ "entries": {} })).unwrap();
+
+    let json = json!({ "result": "minecraft:missing", "count": "two" });
+    let result = validator.validate_json_with_options(&json, "recipe", None, &walk_only());
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].path, "count");
+}
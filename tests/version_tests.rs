@@ -0,0 +1,11 @@
+use std::cmp::Ordering;
+use voxel_rsmcdoc::version::compare_versions;
+
+#[test]
+fn test_compare_versions() {
+    assert_eq!(compare_versions("1.19.1", "1.20"), Ordering::Less);
+    assert_eq!(compare_versions("1.20", "1.19.1"), Ordering::Greater);
+    assert_eq!(compare_versions("1.20", "1.20.0"), Ordering::Equal);
+    assert_eq!(compare_versions("1.20.10", "1.20.9"), Ordering::Greater);
+    assert_eq!(compare_versions("1.21-pre1", "1.21"), Ordering::Equal);
+}